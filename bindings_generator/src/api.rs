@@ -148,7 +148,19 @@ pub struct GodotArgument {
 
 impl GodotArgument {
     pub fn get_type(&self) -> Ty {
-        Ty::from_src(&self.ty)
+        match Ty::from_src(&self.ty) {
+            Ty::String if is_name_argument(&self.name) => Ty::Name,
+            ty => ty,
+        }
+    }
+}
+
+/// String arguments that hold the name of a method, signal or property, which the generated
+/// bindings accept as any `IntoName`.
+fn is_name_argument(name: &str) -> bool {
+    match name {
+        "method" | "signal" | "property" | "signal_name" | "callback" | "receiver_func" => true,
+        _ => false,
     }
 }
 
@@ -156,6 +168,7 @@ impl GodotArgument {
 pub enum Ty {
     Void,
     String,
+    Name,
     F64,
     I64,
     Bool,
@@ -236,6 +249,7 @@ impl Ty {
         match self {
             &Ty::Void => Some(String::from("()")),
             &Ty::String => Some(String::from("GodotString")),
+            &Ty::Name => Some(String::from("impl IntoName")),
             &Ty::F64 => Some(String::from("f64")),
             &Ty::I64 => Some(String::from("i64")),
             &Ty::Bool => Some(String::from("bool")),
@@ -271,7 +285,7 @@ impl Ty {
     pub fn to_sys(&self) -> Option<String> {
         match self {
             &Ty::Void => None,
-            &Ty::String => Some(String::from("sys::godot_string")),
+            &Ty::String | &Ty::Name => Some(String::from("sys::godot_string")),
             &Ty::F64 => Some(String::from("sys::godot_real")),
             &Ty::I64 => Some(String::from("sys::godot_int")),
             &Ty::Bool => Some(String::from("sys::godot_bool")),
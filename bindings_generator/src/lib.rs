@@ -144,6 +144,10 @@ fn generate_class_bindings(
 
         generate_dynamic_cast(output_types_impls, class)?;
 
+        if class.name == "Node" {
+            generate_node_lookup(output_types_impls)?;
+        }
+
         writeln!(output_types_impls, "}}")?;
     }
 
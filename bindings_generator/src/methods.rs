@@ -184,6 +184,17 @@ pub unsafe fn {cname}_{name}(obj_ptr: *mut sys::godot_object{params}) -> {rust_r
         rust_ret_type = rust_ret_type,
         params = params,
     )?;
+
+    for argument in &method.arguments {
+        if let Ty::Name = argument.get_type() {
+            writeln!(
+                output,
+                r#"    let {name} = {name}.into_name();"#,
+                name = rust_safe_name(&argument.name)
+            )?;
+        }
+    }
+
     if method.has_varargs {
         writeln!(
             output,
@@ -193,6 +204,10 @@ pub unsafe fn {cname}_{name}(obj_ptr: *mut sys::godot_object{params}) -> {rust_r
 
         for argument in &method.arguments {
             let ty = argument.get_type().to_rust().unwrap();
+            let is_string = match argument.get_type() {
+                Ty::String | Ty::Name => true,
+                _ => false,
+            };
             if ty.starts_with("Option") {
                 writeln!(
                     output,
@@ -201,7 +216,7 @@ pub unsafe fn {cname}_{name}(obj_ptr: *mut sys::godot_object{params}) -> {rust_r
        }} else {{ Variant::new() }};"#,
                     name = rust_safe_name(&argument.name)
                 )?;
-            } else if ty == "GodotString" {
+            } else if is_string {
                 writeln!(
                     output,
                     r#"    let {name}: Variant = Variant::from_godot_string(&{name});"#,
@@ -421,6 +436,7 @@ fn generate_argument_pre(w: &mut impl Write, ty: &Ty, name: &str) -> GeneratorRe
         }
         &Ty::Variant
         | &Ty::String
+        | &Ty::Name
         | &Ty::Rid
         | &Ty::NodePath
         | &Ty::VariantArray
@@ -476,6 +492,7 @@ fn generate_return_pre(w: &mut impl Write, ty: &Ty) -> GeneratorResult {
             )?;
         },
         &Ty::String
+        | &Ty::Name
         | &Ty::Vector2
         | &Ty::Vector3
         | &Ty::Transform
@@ -561,6 +578,13 @@ fn generate_return_post(w: &mut impl Write, ty: &Ty) -> GeneratorResult {
         &Ty::Rid => {
             writeln!(w, r#"    Rid::from_sys(ret)"#)?;
         }
+        &Ty::Name => {
+            writeln!(
+                w,
+                r#"
+    GodotString::from_sys(ret)"#
+            )?;
+        }
         &Ty::String
         | &Ty::NodePath
         | &Ty::VariantArray
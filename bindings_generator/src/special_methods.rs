@@ -210,6 +210,34 @@ pub fn generate_dynamic_cast(output: &mut impl Write, class: &GodotClass) -> Gen
     Ok(())
 }
 
+pub fn generate_node_lookup(output: &mut impl Write) -> GeneratorResult {
+    writeln!(
+        output,
+        r#"
+    /// Gets the node at `path` with `get_node_or_null` and casts it to `T`.
+    ///
+    /// Returns `None` if there is no node at `path` or if it is not a `T`. Unlike `get_node`,
+    /// no error is logged when the node is missing.
+    #[inline]
+    pub unsafe fn get_node_as<T: GodotObject>(&self, path: impl Into<NodePath>) -> Option<T> {{
+        self.get_node_or_null(path.into())?.cast::<T>()
+    }}
+
+    /// Gets the node at `path` with `get_node_or_null` and returns the instance of the
+    /// `NativeClass` `T` attached to it.
+    ///
+    /// Returns `None` if there is no node at `path`, if it is not a `T::Base` or if its script
+    /// is not `T`.
+    #[inline]
+    pub unsafe fn get_instance_as<T: NativeClass>(&self, path: impl Into<NodePath>) -> Option<Instance<T>> {{
+        let base = self.get_node_as::<T::Base>(path)?;
+        Instance::try_from_unsafe_base(base)
+    }}"#
+    )?;
+
+    Ok(())
+}
+
 pub fn generate_upcast(
     output: &mut impl Write,
     api: &Api,
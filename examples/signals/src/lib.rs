@@ -1,7 +1,21 @@
+use gdnative::signal::Signal;
 use gdnative::*;
 
+// Signals can be declared as types. The fields are the arguments of the signal.
+#[derive(Signal)]
+struct Tick;
+
+#[derive(Signal)]
+struct TickWithData {
+    // The default value is used by the editor for GUI and generation of GDScript handlers.
+    #[signal(default = 100)]
+    data: i64,
+}
+
 #[derive(NativeClass)]
 #[inherit(Node)]
+// signals attribute registers signals declared as types
+#[signals(Tick)]
 // register_with attribute can be used to specify custom register function for node signals and properties
 #[register_with(Self::register_signals)]
 struct SignalEmitter {
@@ -12,21 +26,9 @@ struct SignalEmitter {
 #[methods]
 impl SignalEmitter {
     fn register_signals(builder: &init::ClassBuilder<Self>) {
-        builder.add_signal(init::Signal {
-            name: "tick",
-            args: &[],
-        });
-
-        builder.add_signal(init::Signal {
-            name: "tick_with_data",
-            // Argument list used by the editor for GUI and generation of GDScript handlers. It can be omitted if the signal is only used from code.
-            args: &[init::SignalArgument {
-                name: "data",
-                default: Variant::from_i64(100),
-                hint: init::PropertyHint::None,
-                usage: init::PropertyUsage::DEFAULT,
-            }],
-        });
+        // Registering a signal type by hand is equivalent to listing it in `#[signals(...)]`,
+        // and is shown here for classes that already need a custom register function.
+        builder.register_signal::<TickWithData>();
     }
 
     fn _init(_owner: gdnative::Node) -> Self {
@@ -46,12 +48,9 @@ impl SignalEmitter {
         self.data += 1;
         unsafe {
             if self.data % 2 == 0 {
-                owner.emit_signal(GodotString::from_str("tick"), &[]);
+                Tick.emit(&mut owner);
             } else {
-                owner.emit_signal(
-                    GodotString::from_str("tick_with_data"),
-                    &[Variant::from_i64(self.data)],
-                );
+                TickWithData { data: self.data }.emit(&mut owner);
             }
         }
     }
@@ -71,28 +70,12 @@ impl SignalSubscriber {
 
     #[export]
     unsafe fn _ready(&mut self, owner: Label) {
-        let emitter = &mut owner
+        let mut emitter = owner
             .get_node(NodePath::from_str("../SignalEmitter"))
             .unwrap();
-        let object = &owner.to_object();
-        emitter
-            .connect(
-                GodotString::from_str("tick"),
-                Some(*object),
-                GodotString::from_str("notify"),
-                VariantArray::new(),
-                0,
-            )
-            .unwrap();
-        emitter
-            .connect(
-                GodotString::from_str("tick_with_data"),
-                Some(*object),
-                GodotString::from_str("notify_with_data"),
-                VariantArray::new(),
-                0,
-            )
-            .unwrap();
+        // The signature of the receiving method is checked against the signal.
+        godot_connect!(emitter, Tick => owner, SignalSubscriber::notify).unwrap();
+        godot_connect!(emitter, TickWithData => owner, SignalSubscriber::notify_with_data).unwrap();
     }
 
     #[export]
@@ -106,11 +89,8 @@ impl SignalSubscriber {
     }
 
     #[export]
-    fn notify_with_data(&mut self, mut owner: Label, data: Variant) {
-        let msg = format!(
-            "Received signal \"tick_with_data\" with data {}",
-            data.try_to_u64().unwrap()
-        );
+    fn notify_with_data(&mut self, mut owner: Label, data: i64) {
+        let msg = format!("Received signal \"tick_with_data\" with data {}", data);
 
         unsafe {
             owner.set_text(GodotString::from_str(msg.as_str()));
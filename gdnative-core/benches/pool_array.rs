@@ -0,0 +1,68 @@
+//! Compares bulk conversion between slices and pool arrays with per-element `push` and `get`.
+//!
+//! The benchmarks run against the mock API, where `push` appends to a Rust `Vec` behind a
+//! function pointer. They show the overhead of crossing the API boundary once per element,
+//! but not the cost of the engine's own `push`, which is higher:
+//!
+//! ```text
+//! cargo bench -p gdnative-core --features mock_api
+//! ```
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gdnative_core::*;
+
+const LEN: usize = 1_000_000;
+
+fn bench_array<T, F>(c: &mut Criterion, name: &str, make: F)
+where
+    T: PoolElement,
+    F: Fn(usize) -> T,
+{
+    gdnative_core::mock::install();
+
+    let src = (0..LEN).map(make).collect::<Vec<T>>();
+    let array = PoolArray::from_slice(&src);
+
+    let mut group = c.benchmark_group(name);
+    group.sample_size(10);
+
+    group.bench_function("push", |b| {
+        b.iter(|| {
+            let mut array = PoolArray::new();
+            for val in &src {
+                array.push(val);
+            }
+            array
+        })
+    });
+    group.bench_function("from_slice", |b| {
+        b.iter(|| PoolArray::from_slice(black_box(&src)))
+    });
+    group.bench_function("from_vec", |b| {
+        b.iter_with_setup(|| src.clone(), PoolArray::from_vec)
+    });
+
+    group.bench_function("get", |b| {
+        b.iter(|| (0..array.len()).map(|i| array.get(i)).collect::<Vec<T>>())
+    });
+    group.bench_function("to_vec", |b| b.iter(|| black_box(&array).to_vec()));
+
+    group.finish();
+}
+
+fn byte_array(c: &mut Criterion) {
+    bench_array(c, "ByteArray", |i| i as u8);
+}
+
+fn float32_array(c: &mut Criterion) {
+    bench_array(c, "Float32Array", |i| i as f32);
+}
+
+fn vector3_array(c: &mut Criterion) {
+    bench_array(c, "Vector3Array", |i| {
+        Vector3::new(i as f32, 0.0, -(i as f32))
+    });
+}
+
+criterion_group!(benches, byte_array, float32_array, vector3_array);
+criterion_main!(benches);
@@ -1,10 +1,13 @@
 use crate::get_api;
 use crate::sys;
+use crate::FromVariant;
+use crate::FromVariantError;
 use crate::GodotString;
 use crate::ToVariant;
 use crate::Variant;
 use crate::VariantArray;
 use std::fmt;
+use std::ops::Index;
 
 /// A reference-counted `Dictionary` of `Variant` key-value pairs.
 pub struct Dictionary(pub(crate) sys::godot_dictionary);
@@ -45,18 +48,30 @@ impl Dictionary {
         unsafe { (get_api().godot_dictionary_erase)(&mut self.0, &key.0) }
     }
 
-    /// Returns a copy of the value corresponding to the key.
+    /// Returns a copy of the value corresponding to the key, or nil if there is none.
     pub fn get(&self, key: &Variant) -> Variant {
         unsafe { Variant((get_api().godot_dictionary_get)(&self.0, &key.0)) }
     }
 
+    /// Returns the value corresponding to the key converted to `T`.
+    ///
+    /// A missing key is converted like a nil value, so that `T` can be an `Option`.
+    pub fn get_as<T: FromVariant>(&self, key: &Variant) -> Result<T, FromVariantError> {
+        T::from_variant(&self.get(key))
+    }
+
     /// Sets a value to the element corresponding to the key.
     pub fn set(&mut self, key: &Variant, val: &Variant) {
         unsafe { (get_api().godot_dictionary_set)(&mut self.0, &key.0, &val.0) }
     }
 
     /// Returns a reference to the value corresponding to the key.
+    ///
+    /// # Panics
+    ///
+    /// Panics if the key is not in the dictionary.
     pub fn get_ref(&self, key: &Variant) -> &Variant {
+        assert!(self.contains(key), "key not found in dictionary: {:?}", key);
         unsafe {
             Variant::cast_ref((get_api().godot_dictionary_operator_index_const)(
                 &self.0, &key.0,
@@ -64,7 +79,8 @@ impl Dictionary {
         }
     }
 
-    /// Returns a mutable reference to the value corresponding to the key.
+    /// Returns a mutable reference to the value corresponding to the key, inserting nil if the
+    /// key is not in the dictionary.
     pub fn get_mut_ref(&mut self, key: &Variant) -> &mut Variant {
         unsafe {
             Variant::cast_mut_ref((get_api().godot_dictionary_operator_index)(
@@ -89,8 +105,40 @@ impl Dictionary {
         unsafe { VariantArray((get_api().godot_dictionary_values)(&self.0)) }
     }
 
-    pub fn get_next(&self, key: &Variant) -> &Variant {
-        unsafe { Variant::cast_ref((get_api().godot_dictionary_next)(&self.0, &key.0)) }
+    /// Returns the key following `key` in the dictionary, or the first key if `key` is `None`.
+    /// Returns `None` after the last key, or if `key` is not in the dictionary.
+    pub fn get_next(&self, key: Option<&Variant>) -> Option<&Variant> {
+        let key = key.map_or(std::ptr::null(), |key| &key.0 as *const _);
+        unsafe {
+            let next = (get_api().godot_dictionary_next)(&self.0, key);
+            if next.is_null() {
+                None
+            } else {
+                Some(Variant::cast_ref(next))
+            }
+        }
+    }
+
+    /// Gets the entry for `key`, for in-place manipulation.
+    pub fn entry(&mut self, key: &Variant) -> Entry<'_> {
+        let key = key.clone();
+        if self.contains(&key) {
+            Entry::Occupied(OccupiedEntry { dict: self, key })
+        } else {
+            Entry::Vacant(VacantEntry { dict: self, key })
+        }
+    }
+
+    /// Returns an iterator over copies of the keys and values of the dictionary.
+    ///
+    /// The keys are copied when the iterator is created.
+    pub fn iter(&self) -> DictionaryIter<'_> {
+        let keys = self.keys();
+        DictionaryIter {
+            dict: self,
+            range: 0..keys.len(),
+            keys,
+        }
     }
 
     /// Return a hashed i32 value representing the dictionary's contents.
@@ -128,6 +176,182 @@ impl ToVariant for Dictionary {
     }
 }
 
+impl<'a> Index<&'a Variant> for Dictionary {
+    type Output = Variant;
+
+    fn index(&self, key: &Variant) -> &Variant {
+        self.get_ref(key)
+    }
+}
+
+impl<'a> IntoIterator for &'a Dictionary {
+    type Item = (Variant, Variant);
+    type IntoIter = DictionaryIter<'a>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl IntoIterator for Dictionary {
+    type Item = (Variant, Variant);
+    type IntoIter = DictionaryIntoIter;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let keys = self.keys();
+        DictionaryIntoIter {
+            dict: self,
+            range: 0..keys.len(),
+            keys,
+        }
+    }
+}
+
+/// Iterator over the entries of a `Dictionary`.
+pub struct DictionaryIter<'a> {
+    dict: &'a Dictionary,
+    keys: VariantArray,
+    range: std::ops::Range<i32>,
+}
+
+impl<'a> Iterator for DictionaryIter<'a> {
+    type Item = (Variant, Variant);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.get_val(self.range.next()?);
+        let value = self.dict.get(&key);
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+/// Owning iterator over the entries of a `Dictionary`.
+pub struct DictionaryIntoIter {
+    dict: Dictionary,
+    keys: VariantArray,
+    range: std::ops::Range<i32>,
+}
+
+impl Iterator for DictionaryIntoIter {
+    type Item = (Variant, Variant);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let key = self.keys.get_val(self.range.next()?);
+        let value = self.dict.get(&key);
+        Some((key, value))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+/// A view into a single entry of a `Dictionary`, returned by
+/// [`Dictionary::entry`](struct.Dictionary.html#method.entry).
+pub enum Entry<'a> {
+    Occupied(OccupiedEntry<'a>),
+    Vacant(VacantEntry<'a>),
+}
+
+impl<'a> Entry<'a> {
+    /// Returns the key of the entry.
+    pub fn key(&self) -> &Variant {
+        match self {
+            Entry::Occupied(entry) => entry.key(),
+            Entry::Vacant(entry) => entry.key(),
+        }
+    }
+
+    /// Inserts `default` if the entry is vacant, and returns a mutable reference to the value.
+    pub fn or_insert(self, default: &Variant) -> &'a mut Variant {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(default),
+        }
+    }
+
+    /// Inserts the result of `default` if the entry is vacant, and returns a mutable reference to
+    /// the value.
+    pub fn or_insert_with<F: FnOnce() -> Variant>(self, default: F) -> &'a mut Variant {
+        match self {
+            Entry::Occupied(entry) => entry.into_mut(),
+            Entry::Vacant(entry) => entry.insert(&default()),
+        }
+    }
+
+    /// Calls `f` with the value if the entry is occupied.
+    pub fn and_modify<F: FnOnce(&mut Variant)>(self, f: F) -> Self {
+        match self {
+            Entry::Occupied(mut entry) => {
+                f(entry.get_mut());
+                Entry::Occupied(entry)
+            }
+            Entry::Vacant(entry) => Entry::Vacant(entry),
+        }
+    }
+}
+
+/// An entry of a `Dictionary` whose key is present.
+pub struct OccupiedEntry<'a> {
+    dict: &'a mut Dictionary,
+    key: Variant,
+}
+
+impl<'a> OccupiedEntry<'a> {
+    pub fn key(&self) -> &Variant {
+        &self.key
+    }
+
+    pub fn get(&self) -> &Variant {
+        self.dict.get_ref(&self.key)
+    }
+
+    pub fn get_mut(&mut self) -> &mut Variant {
+        self.dict.get_mut_ref(&self.key)
+    }
+
+    /// Converts the entry into a mutable reference to the value, bound to the dictionary.
+    pub fn into_mut(self) -> &'a mut Variant {
+        let OccupiedEntry { dict, key } = self;
+        dict.get_mut_ref(&key)
+    }
+
+    /// Sets the value of the entry and returns the old value.
+    pub fn insert(&mut self, value: &Variant) -> Variant {
+        std::mem::replace(self.get_mut(), value.clone())
+    }
+
+    /// Removes the entry from the dictionary and returns its value.
+    pub fn remove(self) -> Variant {
+        let value = self.dict.get(&self.key);
+        self.dict.erase(&self.key);
+        value
+    }
+}
+
+/// An entry of a `Dictionary` whose key is missing.
+pub struct VacantEntry<'a> {
+    dict: &'a mut Dictionary,
+    key: Variant,
+}
+
+impl<'a> VacantEntry<'a> {
+    pub fn key(&self) -> &Variant {
+        &self.key
+    }
+
+    /// Inserts `value` for the key of the entry, and returns a mutable reference to it.
+    pub fn insert(self, value: &Variant) -> &'a mut Variant {
+        let VacantEntry { dict, key } = self;
+        let slot = dict.get_mut_ref(&key);
+        *slot = value.clone();
+        slot
+    }
+}
+
 impl fmt::Debug for Dictionary {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         self.to_json().to_string().fmt(f)
@@ -176,6 +400,34 @@ godot_test!(test_dictionary {
     } else {
         panic!("variant should be a Dictionary");
     }
+
+    assert_eq!(dict.get_as::<i64>(&foo), Ok(42));
+    assert_eq!(dict.get_as::<Option<i64>>(&nope), Ok(None));
+    assert!(dict.get_as::<GodotString>(&bar).is_err());
+    assert_eq!(dict[&bar], y);
+
+    let mut entries: Vec<(Variant, Variant)> = dict.iter().collect();
+    entries.sort_by_key(|(_, value)| value.try_to_i64());
+    assert_eq!(entries, vec![(foo.clone(), x.clone()), (bar.clone(), y.clone())]);
+
+    let first = dict.get_next(None).expect("dictionary should not be empty").clone();
+    let second = dict.get_next(Some(&first)).expect("dictionary has two keys").clone();
+    assert!(dict.get_next(Some(&second)).is_none());
+    assert!(dict.get_next(Some(&nope)).is_none());
+
+    *dict.entry(&foo).or_insert(&y) = Variant::from_i64(1);
+    dict.entry(&nope).or_insert(&y);
+    dict.entry(&bar).and_modify(|v| *v = Variant::from_i64(2));
+    assert_eq!(dict.get(&foo), Variant::from_i64(1));
+    assert_eq!(dict.get(&nope), y);
+    assert_eq!(dict.get(&bar), Variant::from_i64(2));
+
+    if let Entry::Occupied(entry) = dict.entry(&nope) {
+        assert_eq!(entry.remove(), y);
+    } else {
+        panic!("entry should be occupied");
+    }
+    assert!(!dict.contains(&nope));
 });
 
 // TODO: clear dictionaries without affecting clones
@@ -1,10 +1,13 @@
-use crate::Vector3;
+use crate::{Plane, Vector3};
 
 /// Axis-aligned bounding box.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Aabb {
+    #[cfg_attr(feature = "serde", serde(with = "crate::marshal::serialize::vector3"))]
     pub position: Vector3,
+    #[cfg_attr(feature = "serde", serde(with = "crate::marshal::serialize::vector3"))]
     pub size: Vector3,
 }
 
@@ -13,4 +16,308 @@ impl Aabb {
     pub fn from_sys(c: sys::godot_aabb) -> Self {
         unsafe { std::mem::transmute::<sys::godot_aabb, Self>(c) }
     }
+
+    #[inline]
+    pub fn new(position: Vector3, size: Vector3) -> Self {
+        Aabb { position, size }
+    }
+
+    /// Returns the corner opposite to `position`.
+    #[inline]
+    pub fn end(&self) -> Vector3 {
+        self.position + self.size
+    }
+
+    /// Returns the volume of the box. This is `get_area` in GDScript.
+    #[inline]
+    pub fn volume(&self) -> f32 {
+        self.size.x * self.size.y * self.size.z
+    }
+
+    /// Returns `true` if the box is flat or empty.
+    #[inline]
+    pub fn has_no_area(&self) -> bool {
+        self.size.x <= 0.0 || self.size.y <= 0.0 || self.size.z <= 0.0
+    }
+
+    /// Returns `true` if the box is empty.
+    #[inline]
+    pub fn has_no_surface(&self) -> bool {
+        self.size.x <= 0.0 && self.size.y <= 0.0 && self.size.z <= 0.0
+    }
+
+    /// Returns `true` if `point` is inside the box or on its faces.
+    #[inline]
+    pub fn has_point(&self, point: Vector3) -> bool {
+        let end = self.end();
+        point.x >= self.position.x
+            && point.y >= self.position.y
+            && point.z >= self.position.z
+            && point.x <= end.x
+            && point.y <= end.y
+            && point.z <= end.z
+    }
+
+    /// Returns `true` if the boxes overlap. Boxes that only touch do not intersect.
+    #[inline]
+    pub fn intersects(&self, other: &Aabb) -> bool {
+        let (end, other_end) = (self.end(), other.end());
+        self.position.x < other_end.x
+            && self.position.y < other_end.y
+            && self.position.z < other_end.z
+            && end.x > other.position.x
+            && end.y > other.position.y
+            && end.z > other.position.z
+    }
+
+    /// Returns `true` if the boxes overlap or touch.
+    #[inline]
+    pub fn intersects_inclusive(&self, other: &Aabb) -> bool {
+        let (end, other_end) = (self.end(), other.end());
+        self.position.x <= other_end.x
+            && self.position.y <= other_end.y
+            && self.position.z <= other_end.z
+            && end.x >= other.position.x
+            && end.y >= other.position.y
+            && end.z >= other.position.z
+    }
+
+    /// Returns `true` if the box completely encloses `other`. Like in the engine, the far faces
+    /// of the boxes must not touch.
+    #[inline]
+    pub fn encloses(&self, other: &Aabb) -> bool {
+        let (end, other_end) = (self.end(), other.end());
+        self.position.x <= other.position.x
+            && self.position.y <= other.position.y
+            && self.position.z <= other.position.z
+            && end.x > other_end.x
+            && end.y > other_end.y
+            && end.z > other_end.z
+    }
+
+    /// Returns the smallest box enclosing both boxes.
+    #[inline]
+    pub fn merge(&self, other: &Aabb) -> Self {
+        let position = self.position.min(other.position);
+        Aabb::new(position, self.end().max(other.end()) - position)
+    }
+
+    /// Returns the intersection of the boxes, or an empty box at the origin if they don't
+    /// intersect.
+    pub fn intersection(&self, other: &Aabb) -> Self {
+        let (end, other_end) = (self.end(), other.end());
+        if self.position.x > other_end.x
+            || self.position.y > other_end.y
+            || self.position.z > other_end.z
+            || end.x < other.position.x
+            || end.y < other.position.y
+            || end.z < other.position.z
+        {
+            return Aabb::new(Vector3::zero(), Vector3::zero());
+        }
+
+        let position = self.position.max(other.position);
+        Aabb::new(position, end.min(other_end) - position)
+    }
+
+    /// Returns the box expanded to include `point`.
+    #[inline]
+    pub fn expand(&self, point: Vector3) -> Self {
+        let position = self.position.min(point);
+        Aabb::new(position, self.end().max(point) - position)
+    }
+
+    /// Returns the box grown by `by` on every side.
+    #[inline]
+    pub fn grow(&self, by: f32) -> Self {
+        let by = Vector3::new(by, by, by);
+        Aabb::new(self.position - by, self.size + by * 2.0)
+    }
+
+    /// Returns one of the 8 corners of the box, in the same order as the engine.
+    ///
+    /// # Panics
+    ///
+    /// If `index` is not in `0..8`.
+    pub fn endpoint(&self, index: usize) -> Vector3 {
+        assert!(index < 8, "endpoint index out of range: {}", index);
+
+        let pick = |bit: usize, start: f32, size: f32| {
+            if index & bit == 0 {
+                start
+            } else {
+                start + size
+            }
+        };
+
+        Vector3::new(
+            pick(4, self.position.x, self.size.x),
+            pick(2, self.position.y, self.size.y),
+            pick(1, self.position.z, self.size.z),
+        )
+    }
+
+    /// Returns `true` if the box has corners on both sides of `plane`.
+    pub fn intersects_plane(&self, plane: &Plane) -> bool {
+        let mut over = false;
+        let mut under = false;
+
+        for index in 0..8 {
+            if plane.distance_to(self.endpoint(index)) > 0.0 {
+                over = true;
+            } else {
+                under = true;
+            }
+        }
+
+        over && under
+    }
+
+    /// Returns the point where the segment from `from` to `to` enters the box, or `None` if it
+    /// does not intersect the box. The point is `from` itself if it is inside the box.
+    pub fn intersects_segment(&self, from: Vector3, to: Vector3) -> Option<Vector3> {
+        let from_a = from.to_array();
+        let to_a = to.to_array();
+        let begin_a = self.position.to_array();
+        let end_a = self.end().to_array();
+
+        let mut min = 0.0;
+        let mut max = 1.0;
+
+        for i in 0..3 {
+            let (seg_from, seg_to) = (from_a[i], to_a[i]);
+            let (box_begin, box_end) = (begin_a[i], end_a[i]);
+            let length = seg_to - seg_from;
+
+            let (cmin, cmax) = if seg_from < seg_to {
+                if seg_from > box_end || seg_to < box_begin {
+                    return None;
+                }
+                (
+                    if seg_from < box_begin {
+                        (box_begin - seg_from) / length
+                    } else {
+                        0.0
+                    },
+                    if seg_to > box_end {
+                        (box_end - seg_from) / length
+                    } else {
+                        1.0
+                    },
+                )
+            } else {
+                if seg_to > box_end || seg_from < box_begin {
+                    return None;
+                }
+                (
+                    if seg_from > box_end {
+                        (box_end - seg_from) / length
+                    } else {
+                        0.0
+                    },
+                    if seg_to < box_begin {
+                        (box_begin - seg_from) / length
+                    } else {
+                        1.0
+                    },
+                )
+            };
+
+            if cmin > min {
+                min = cmin;
+            }
+            if cmax < max {
+                max = cmax;
+            }
+            if max < min {
+                return None;
+            }
+        }
+
+        Some(from + (to - from) * min)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn unit() -> Aabb {
+        Aabb::new(Vector3::new(0.0, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    fn shifted(x: f32) -> Aabb {
+        Aabb::new(Vector3::new(x, 0.0, 0.0), Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    #[test]
+    fn edge_rules() {
+        let a = unit();
+        assert!(a.has_point(Vector3::new(1.0, 1.0, 0.0)));
+        assert!(!a.has_point(Vector3::new(1.0, 1.0, -0.1)));
+
+        assert!(a.intersects(&shifted(0.5)));
+        assert!(!a.intersects(&shifted(1.0)));
+        assert!(a.intersects_inclusive(&shifted(1.0)));
+        assert!(!a.intersects_inclusive(&shifted(1.5)));
+
+        let big = a.grow(1.0);
+        assert!(big.encloses(&a));
+        assert!(!a.encloses(&a));
+    }
+
+    #[test]
+    fn merge_and_intersection() {
+        let a = unit();
+        let b = shifted(0.5);
+        assert_eq!(
+            Aabb::new(Vector3::zero(), Vector3::new(1.5, 1.0, 1.0)),
+            a.merge(&b)
+        );
+        assert_eq!(
+            Aabb::new(Vector3::new(0.5, 0.0, 0.0), Vector3::new(0.5, 1.0, 1.0)),
+            a.intersection(&b)
+        );
+        assert_eq!(
+            Aabb::new(Vector3::zero(), Vector3::zero()),
+            a.intersection(&shifted(2.0))
+        );
+        assert_eq!(
+            Aabb::new(Vector3::new(0.0, -1.0, 0.0), Vector3::new(1.0, 2.0, 3.0)),
+            a.expand(Vector3::new(0.5, -1.0, 3.0))
+        );
+        assert_eq!(0.5, a.intersection(&b).volume());
+    }
+
+    #[test]
+    fn endpoints() {
+        let a = Aabb::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(1.0, 1.0, 1.0));
+        assert_eq!(Vector3::new(1.0, 2.0, 3.0), a.endpoint(0));
+        assert_eq!(Vector3::new(1.0, 2.0, 4.0), a.endpoint(1));
+        assert_eq!(Vector3::new(1.0, 3.0, 3.0), a.endpoint(2));
+        assert_eq!(Vector3::new(2.0, 2.0, 3.0), a.endpoint(4));
+        assert_eq!(Vector3::new(2.0, 3.0, 4.0), a.endpoint(7));
+    }
+
+    #[test]
+    fn planes_and_segments() {
+        let a = unit();
+        let through = Plane::new(Vector3::new(1.0, 0.0, 0.0), 0.5);
+        let outside = Plane::new(Vector3::new(1.0, 0.0, 0.0), 2.0);
+        assert!(a.intersects_plane(&through));
+        assert!(!a.intersects_plane(&outside));
+
+        assert_eq!(
+            Some(Vector3::new(0.0, 0.5, 0.5)),
+            a.intersects_segment(Vector3::new(-1.0, 0.5, 0.5), Vector3::new(2.0, 0.5, 0.5))
+        );
+        assert_eq!(
+            Some(Vector3::new(1.0, 0.5, 0.5)),
+            a.intersects_segment(Vector3::new(2.0, 0.5, 0.5), Vector3::new(-1.0, 0.5, 0.5))
+        );
+        assert_eq!(
+            None,
+            a.intersects_segment(Vector3::new(-1.0, 2.0, 0.5), Vector3::new(2.0, 2.0, 0.5))
+        );
+    }
 }
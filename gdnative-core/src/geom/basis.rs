@@ -1,9 +1,17 @@
-use crate::Vector3;
+use super::{normalized_or_zero, CMP_EPSILON};
+use crate::{Quat, Vector3};
+use std::ops::Mul;
 
 /// A 3x3 matrix.
+///
+/// Like in the engine, `elements` holds the rows of the matrix. The axes of the coordinate
+/// system, `basis.x`, `basis.y` and `basis.z` in GDScript, are its columns and can be accessed
+/// with [`x`](#method.x), [`y`](#method.y) and [`z`](#method.z).
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Basis {
+    #[cfg_attr(feature = "serde", serde(with = "crate::marshal::serialize::rows"))]
     pub elements: [Vector3; 3],
 }
 
@@ -12,4 +20,458 @@ impl Basis {
     pub fn from_sys(c: sys::godot_basis) -> Self {
         unsafe { std::mem::transmute::<sys::godot_basis, Self>(c) }
     }
+
+    /// The identity basis.
+    #[inline]
+    pub fn identity() -> Self {
+        Basis::from_diagonal(Vector3::new(1.0, 1.0, 1.0))
+    }
+
+    /// Creates a basis from its rows.
+    #[inline]
+    pub fn from_elements(elements: [Vector3; 3]) -> Self {
+        Basis { elements }
+    }
+
+    /// Creates a basis from its axes, which are the columns of the matrix.
+    #[inline]
+    pub fn from_axes(x: Vector3, y: Vector3, z: Vector3) -> Self {
+        Basis::from_elements([
+            Vector3::new(x.x, y.x, z.x),
+            Vector3::new(x.y, y.y, z.y),
+            Vector3::new(x.z, y.z, z.z),
+        ])
+    }
+
+    /// Creates a diagonal matrix, which scales by `scale`.
+    #[inline]
+    pub fn from_diagonal(scale: Vector3) -> Self {
+        Basis::from_elements([
+            Vector3::new(scale.x, 0.0, 0.0),
+            Vector3::new(0.0, scale.y, 0.0),
+            Vector3::new(0.0, 0.0, scale.z),
+        ])
+    }
+
+    /// Creates a rotation of `phi` radians around `axis`, which must be normalized.
+    pub fn from_axis_angle(axis: Vector3, phi: f32) -> Self {
+        let axis_sq = Vector3::new(axis.x * axis.x, axis.y * axis.y, axis.z * axis.z);
+        let cosine = phi.cos();
+        let sine = phi.sin();
+        let t = 1.0 - cosine;
+
+        let xy = axis.x * axis.y * t;
+        let zx = axis.z * axis.x * t;
+        let yz = axis.y * axis.z * t;
+
+        Basis::from_elements([
+            Vector3::new(
+                axis_sq.x + cosine * (1.0 - axis_sq.x),
+                xy - axis.z * sine,
+                zx + axis.y * sine,
+            ),
+            Vector3::new(
+                xy + axis.z * sine,
+                axis_sq.y + cosine * (1.0 - axis_sq.y),
+                yz - axis.x * sine,
+            ),
+            Vector3::new(
+                zx - axis.y * sine,
+                yz + axis.x * sine,
+                axis_sq.z + cosine * (1.0 - axis_sq.z),
+            ),
+        ])
+    }
+
+    /// Creates a rotation from Euler angles in the YXZ convention: when decomposing, the
+    /// rotation around Z is applied first, then X and finally Y.
+    pub fn from_euler(euler: Vector3) -> Self {
+        let (s, c) = euler.x.sin_cos();
+        let xmat = Basis::from_elements([
+            Vector3::new(1.0, 0.0, 0.0),
+            Vector3::new(0.0, c, -s),
+            Vector3::new(0.0, s, c),
+        ]);
+
+        let (s, c) = euler.y.sin_cos();
+        let ymat = Basis::from_elements([
+            Vector3::new(c, 0.0, s),
+            Vector3::new(0.0, 1.0, 0.0),
+            Vector3::new(-s, 0.0, c),
+        ]);
+
+        let (s, c) = euler.z.sin_cos();
+        let zmat = Basis::from_elements([
+            Vector3::new(c, -s, 0.0),
+            Vector3::new(s, c, 0.0),
+            Vector3::new(0.0, 0.0, 1.0),
+        ]);
+
+        ymat * xmat * zmat
+    }
+
+    /// Creates a rotation from a quaternion, which does not need to be normalized.
+    pub fn from_quat(quat: Quat) -> Self {
+        let d = quat.i * quat.i + quat.j * quat.j + quat.k * quat.k + quat.r * quat.r;
+        let s = 2.0 / d;
+        let (xs, ys, zs) = (quat.i * s, quat.j * s, quat.k * s);
+        let (wx, wy, wz) = (quat.r * xs, quat.r * ys, quat.r * zs);
+        let (xx, xy, xz) = (quat.i * xs, quat.i * ys, quat.i * zs);
+        let (yy, yz, zz) = (quat.j * ys, quat.j * zs, quat.k * zs);
+
+        Basis::from_elements([
+            Vector3::new(1.0 - (yy + zz), xy - wz, xz + wy),
+            Vector3::new(xy + wz, 1.0 - (xx + zz), yz - wx),
+            Vector3::new(xz - wy, yz + wx, 1.0 - (xx + yy)),
+        ])
+    }
+
+    /// The X axis, which is the first column of the matrix.
+    #[inline]
+    pub fn x(&self) -> Vector3 {
+        self.column(0)
+    }
+
+    /// The Y axis, which is the second column of the matrix.
+    #[inline]
+    pub fn y(&self) -> Vector3 {
+        self.column(1)
+    }
+
+    /// The Z axis, which is the third column of the matrix.
+    #[inline]
+    pub fn z(&self) -> Vector3 {
+        self.column(2)
+    }
+
+    #[inline]
+    fn column(&self, index: usize) -> Vector3 {
+        let e = &self.elements;
+        match index {
+            0 => Vector3::new(e[0].x, e[1].x, e[2].x),
+            1 => Vector3::new(e[0].y, e[1].y, e[2].y),
+            _ => Vector3::new(e[0].z, e[1].z, e[2].z),
+        }
+    }
+
+    /// Returns the determinant of the matrix.
+    #[inline]
+    pub fn determinant(&self) -> f32 {
+        let e = &self.elements;
+        e[0].x * (e[1].y * e[2].z - e[2].y * e[1].z) - e[1].x * (e[0].y * e[2].z - e[2].y * e[0].z)
+            + e[2].x * (e[0].y * e[1].z - e[1].y * e[0].z)
+    }
+
+    /// Returns the transposed matrix.
+    #[inline]
+    pub fn transposed(&self) -> Self {
+        Basis::from_elements([self.x(), self.y(), self.z()])
+    }
+
+    /// Returns the inverse of the matrix. Like in the engine, a matrix that cannot be inverted
+    /// is returned unchanged.
+    pub fn inverse(&self) -> Self {
+        let e = &self.elements;
+        let cofac = |r1: usize, c1: usize, r2: usize, c2: usize| {
+            let get = |r: usize, c: usize| match c {
+                0 => e[r].x,
+                1 => e[r].y,
+                _ => e[r].z,
+            };
+            get(r1, c1) * get(r2, c2) - get(r1, c2) * get(r2, c1)
+        };
+
+        let co = [cofac(1, 1, 2, 2), cofac(1, 2, 2, 0), cofac(1, 0, 2, 1)];
+        let det = e[0].x * co[0] + e[0].y * co[1] + e[0].z * co[2];
+        if det == 0.0 {
+            return *self;
+        }
+
+        let s = 1.0 / det;
+        Basis::from_elements([
+            Vector3::new(co[0] * s, cofac(0, 2, 2, 1) * s, cofac(0, 1, 1, 2) * s),
+            Vector3::new(co[1] * s, cofac(0, 0, 2, 2) * s, cofac(0, 2, 1, 0) * s),
+            Vector3::new(co[2] * s, cofac(0, 1, 2, 0) * s, cofac(0, 0, 1, 1) * s),
+        ])
+    }
+
+    /// Returns the matrix with its axes orthogonal and normalized, using the Gram-Schmidt
+    /// process starting from the X axis.
+    pub fn orthonormalized(&self) -> Self {
+        let x = normalized_or_zero(self.x());
+        let y = self.y();
+        let y = normalized_or_zero(y - x * x.dot(y));
+        let z = self.z();
+        let z = normalized_or_zero(z - x * x.dot(z) - y * y.dot(z));
+        Basis::from_axes(x, y, z)
+    }
+
+    /// Returns the length of each axis. The lengths are negative if the determinant is.
+    pub fn scale(&self) -> Vector3 {
+        let sign = if self.determinant() < 0.0 { -1.0 } else { 1.0 };
+        Vector3::new(self.x().length(), self.y().length(), self.z().length()) * sign
+    }
+
+    /// Returns the matrix scaled by `scale` in global coordinates.
+    #[inline]
+    pub fn scaled(&self, scale: Vector3) -> Self {
+        let e = &self.elements;
+        Basis::from_elements([e[0] * scale.x, e[1] * scale.y, e[2] * scale.z])
+    }
+
+    /// Returns the matrix rotated by `phi` radians around `axis` in global coordinates.
+    #[inline]
+    pub fn rotated(&self, axis: Vector3, phi: f32) -> Self {
+        Basis::from_axis_angle(axis, phi) * *self
+    }
+
+    /// Returns the Euler angles of the rotation in the YXZ convention, as accepted by
+    /// [`from_euler`](#method.from_euler). The matrix must be orthonormal.
+    pub fn to_euler(&self) -> Vector3 {
+        let e = &self.elements;
+        let m12 = e[1].z;
+
+        if m12 < 1.0 {
+            if m12 > -1.0 {
+                // A pure X rotation is returned in its simplest form.
+                if e[1].x == 0.0 && e[0].y == 0.0 && e[0].z == 0.0 && e[2].x == 0.0 && e[0].x == 1.0
+                {
+                    Vector3::new((-m12).atan2(e[1].y), 0.0, 0.0)
+                } else {
+                    Vector3::new((-m12).asin(), e[0].z.atan2(e[2].z), e[1].x.atan2(e[1].y))
+                }
+            } else {
+                Vector3::new(std::f32::consts::FRAC_PI_2, -(-e[0].y).atan2(e[0].x), 0.0)
+            }
+        } else {
+            Vector3::new(-std::f32::consts::FRAC_PI_2, -(-e[0].y).atan2(e[0].x), 0.0)
+        }
+    }
+
+    /// Returns the rotation as a quaternion. The matrix must be orthonormal.
+    pub fn to_quat(&self) -> Quat {
+        let e = &self.elements;
+        let m = |r: usize, c: usize| match c {
+            0 => e[r].x,
+            1 => e[r].y,
+            _ => e[r].z,
+        };
+
+        let trace = m(0, 0) + m(1, 1) + m(2, 2);
+        let mut temp = [0.0; 4];
+
+        if trace > 0.0 {
+            let mut s = (trace + 1.0).sqrt();
+            temp[3] = s * 0.5;
+            s = 0.5 / s;
+            temp[0] = (m(2, 1) - m(1, 2)) * s;
+            temp[1] = (m(0, 2) - m(2, 0)) * s;
+            temp[2] = (m(1, 0) - m(0, 1)) * s;
+        } else {
+            let i = if m(0, 0) < m(1, 1) {
+                if m(1, 1) < m(2, 2) {
+                    2
+                } else {
+                    1
+                }
+            } else if m(0, 0) < m(2, 2) {
+                2
+            } else {
+                0
+            };
+            let j = (i + 1) % 3;
+            let k = (i + 2) % 3;
+
+            let mut s = (m(i, i) - m(j, j) - m(k, k) + 1.0).sqrt();
+            temp[i] = s * 0.5;
+            s = 0.5 / s;
+            temp[3] = (m(k, j) - m(j, k)) * s;
+            temp[j] = (m(j, i) + m(i, j)) * s;
+            temp[k] = (m(k, i) + m(i, k)) * s;
+        }
+
+        Quat::quaternion(temp[0], temp[1], temp[2], temp[3])
+    }
+
+    /// Returns the rotation part of the matrix, without scale, as a quaternion.
+    pub fn to_rotation_quat(&self) -> Quat {
+        let mut m = self.orthonormalized();
+        if m.determinant() < 0.0 {
+            m = m.scaled(Vector3::new(-1.0, -1.0, -1.0));
+        }
+        m.to_quat()
+    }
+
+    /// Transforms `v` by the matrix.
+    #[inline]
+    pub fn xform(&self, v: Vector3) -> Vector3 {
+        let e = &self.elements;
+        Vector3::new(e[0].dot(v), e[1].dot(v), e[2].dot(v))
+    }
+
+    /// Transforms `v` by the transposed matrix, which is the inverse transformation if the
+    /// matrix is orthonormal.
+    #[inline]
+    pub fn xform_inv(&self, v: Vector3) -> Vector3 {
+        Vector3::new(self.x().dot(v), self.y().dot(v), self.z().dot(v))
+    }
+
+    /// Spherically interpolates the rotation towards `other`, and linearly interpolates the
+    /// length of the rows.
+    pub fn slerp(&self, other: &Basis, t: f32) -> Self {
+        let from = self.to_rotation_quat();
+        let to = other.to_rotation_quat();
+        let mut b = Basis::from_quat(super::quat::slerp(from, to, t));
+        for (row, (a, c)) in b
+            .elements
+            .iter_mut()
+            .zip(self.elements.iter().zip(other.elements.iter()))
+        {
+            *row *= a.length() + (c.length() - a.length()) * t;
+        }
+        b
+    }
+
+    /// Returns `true` if all elements are approximately equal to those of `other`.
+    pub fn is_equal_approx(&self, other: &Basis) -> bool {
+        self.elements
+            .iter()
+            .zip(other.elements.iter())
+            .all(|(a, b)| (*a - *b).abs().to_array().iter().all(|d| *d < CMP_EPSILON))
+    }
+}
+
+impl Default for Basis {
+    #[inline]
+    fn default() -> Self {
+        Basis::identity()
+    }
+}
+
+impl Mul<Basis> for Basis {
+    type Output = Basis;
+
+    #[inline]
+    fn mul(self, rhs: Basis) -> Basis {
+        let (x, y, z) = (rhs.x(), rhs.y(), rhs.z());
+        let row = |r: Vector3| Vector3::new(x.dot(r), y.dot(r), z.dot(r));
+        Basis::from_elements([
+            row(self.elements[0]),
+            row(self.elements[1]),
+            row(self.elements[2]),
+        ])
+    }
+}
+
+impl Mul<Vector3> for Basis {
+    type Output = Vector3;
+
+    #[inline]
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.xform(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_PI_2, FRAC_PI_4};
+
+    fn approx(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    fn rotation() -> Basis {
+        Basis::from_euler(Vector3::new(0.1, 0.2, 0.3))
+    }
+
+    #[test]
+    fn euler_matches_engine() {
+        // Basis(Vector3(0.1, 0.2, 0.3)) in Godot 3.2.
+        let expected = Basis::from_elements([
+            Vector3::new(0.942155, -0.270681, 0.197677),
+            Vector3::new(0.294044, 0.950564, -0.099833),
+            Vector3::new(-0.160881, 0.152184, 0.97517),
+        ]);
+        let b = rotation();
+        for (row, expected) in b.elements.iter().zip(expected.elements.iter()) {
+            assert!(approx(*row, *expected), "{:?}", b);
+        }
+        assert!(approx(Vector3::new(0.1, 0.2, 0.3), b.to_euler()));
+    }
+
+    #[test]
+    fn euler_pure_x_rotation() {
+        let b = Basis::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), 2.5);
+        assert!(approx(Vector3::new(2.5, 0.0, 0.0), b.to_euler()));
+    }
+
+    #[test]
+    fn axis_angle() {
+        let b = Basis::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), FRAC_PI_2);
+        assert!(approx(
+            Vector3::new(0.0, 0.0, -1.0),
+            b.xform(Vector3::new(1.0, 0.0, 0.0))
+        ));
+        assert!(approx(Vector3::new(0.0, FRAC_PI_2, 0.0), b.to_euler()));
+        assert!(approx(
+            Vector3::new(1.0, 0.0, 0.0),
+            b.xform_inv(Vector3::new(0.0, 0.0, -1.0))
+        ));
+    }
+
+    #[test]
+    fn multiplication_and_inverse() {
+        let b = rotation().scaled(Vector3::new(2.0, 3.0, 4.0));
+        assert!((b * b.inverse()).is_equal_approx(&Basis::identity()));
+        assert!((b.inverse() * b).is_equal_approx(&Basis::identity()));
+
+        let r = rotation();
+        assert!(r.inverse().is_equal_approx(&r.transposed()));
+        assert!((r.determinant() - 1.0).abs() < 1e-5);
+
+        let v = Vector3::new(1.0, -2.0, 3.0);
+        assert!(approx((r * b) * v, r * (b * v)));
+    }
+
+    #[test]
+    fn singular_inverse_is_unchanged() {
+        let b = Basis::from_diagonal(Vector3::new(1.0, 0.0, 1.0));
+        assert_eq!(b, b.inverse());
+    }
+
+    #[test]
+    fn scale_and_orthonormalization() {
+        let b = rotation().scaled(Vector3::new(2.0, 2.0, 2.0));
+        assert!(approx(Vector3::new(2.0, 2.0, 2.0), b.scale()));
+        assert!(b.orthonormalized().is_equal_approx(&rotation()));
+
+        let mirrored = Basis::from_diagonal(Vector3::new(-1.0, 1.0, 1.0));
+        assert!(approx(Vector3::new(-1.0, -1.0, -1.0), mirrored.scale()));
+    }
+
+    #[test]
+    fn quat_round_trip() {
+        let q = Basis::from_axis_angle(Vector3::new(1.0, 0.0, 0.0), FRAC_PI_2).to_quat();
+        assert!((q.i - FRAC_PI_4.sin()).abs() < 1e-6);
+        assert!((q.r - FRAC_PI_4.cos()).abs() < 1e-6);
+
+        let q = rotation().to_quat();
+        let q2 = rotation()
+            .scaled(Vector3::new(3.0, 3.0, 3.0))
+            .to_rotation_quat();
+        assert!(Basis::from_quat(q).is_equal_approx(&rotation()));
+        assert!(Basis::from_quat(q2).is_equal_approx(&rotation()));
+    }
+
+    #[test]
+    fn slerp() {
+        let from = Basis::identity();
+        let to = Basis::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), FRAC_PI_2)
+            .scaled(Vector3::new(3.0, 3.0, 3.0));
+        let half = from.slerp(&to, 0.5);
+        let expected = Basis::from_axis_angle(Vector3::new(0.0, 0.0, 1.0), FRAC_PI_4)
+            .scaled(Vector3::new(2.0, 2.0, 2.0));
+        assert!(half.is_equal_approx(&expected), "{:?}", half);
+    }
 }
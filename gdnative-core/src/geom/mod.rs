@@ -3,6 +3,7 @@
 mod aabb;
 mod basis;
 mod plane;
+mod quat;
 mod transform;
 
 pub type Vector3 = euclid::default::Vector3D<f32>;
@@ -19,4 +20,19 @@ pub type Rotation3D = euclid::default::Rotation3D<f32>;
 pub use self::aabb::Aabb;
 pub use self::basis::Basis;
 pub use self::plane::Plane;
+pub use self::quat::QuatGodot;
 pub use self::transform::Transform;
+
+/// Tolerance used by the engine for approximate comparisons.
+pub(crate) const CMP_EPSILON: f32 = 0.00001;
+
+/// Normalizes `v` like the engine does, returning a zero vector instead of NaNs if `v` is zero.
+#[inline]
+pub(crate) fn normalized_or_zero(v: Vector3) -> Vector3 {
+    let length = v.length();
+    if length == 0.0 {
+        Vector3::zero()
+    } else {
+        v / length
+    }
+}
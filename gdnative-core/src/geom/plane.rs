@@ -1,9 +1,12 @@
+use super::CMP_EPSILON;
 use crate::Vector3;
 
 /// Plane in hessian form.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Plane {
+    #[cfg_attr(feature = "serde", serde(with = "crate::marshal::serialize::vector3"))]
     pub normal: Vector3,
     pub d: f32,
 }
@@ -13,4 +16,183 @@ impl Plane {
     pub fn from_sys(c: sys::godot_plane) -> Self {
         unsafe { std::mem::transmute::<sys::godot_plane, Self>(c) }
     }
+
+    #[inline]
+    pub fn new(normal: Vector3, d: f32) -> Self {
+        Plane { normal, d }
+    }
+
+    /// Creates the plane going through `p1`, `p2` and `p3`, in clockwise order.
+    #[inline]
+    pub fn from_points(p1: Vector3, p2: Vector3, p3: Vector3) -> Self {
+        let normal = (p1 - p3).cross(p1 - p2).normalize();
+        Plane::new(normal, normal.dot(p1))
+    }
+
+    /// Creates the plane going through `point` with the given normal.
+    #[inline]
+    pub fn from_point_normal(point: Vector3, normal: Vector3) -> Self {
+        Plane::new(normal, normal.dot(point))
+    }
+
+    /// Returns the signed distance from the plane to `point`, which is positive if the point
+    /// is above the plane.
+    #[inline]
+    pub fn distance_to(&self, point: Vector3) -> f32 {
+        self.normal.dot(point) - self.d
+    }
+
+    /// Returns `true` if `point` is strictly above the plane.
+    #[inline]
+    pub fn is_point_over(&self, point: Vector3) -> bool {
+        self.normal.dot(point) > self.d
+    }
+
+    /// Returns `true` if `point` is inside the plane, within `epsilon`.
+    #[inline]
+    pub fn has_point(&self, point: Vector3, epsilon: f32) -> bool {
+        self.distance_to(point).abs() <= epsilon
+    }
+
+    /// Returns the orthogonal projection of `point` onto the plane.
+    #[inline]
+    pub fn project(&self, point: Vector3) -> Vector3 {
+        point - self.normal * self.distance_to(point)
+    }
+
+    /// Returns the point of the plane closest to the origin.
+    #[inline]
+    pub fn center(&self) -> Vector3 {
+        self.normal * self.d
+    }
+
+    /// Returns the plane with a normalized normal. A plane with a zero normal becomes the
+    /// zero plane.
+    #[inline]
+    pub fn normalized(&self) -> Self {
+        let length = self.normal.length();
+        if length == 0.0 {
+            return Plane::new(Vector3::zero(), 0.0);
+        }
+        Plane::new(self.normal / length, self.d / length)
+    }
+
+    /// Returns the intersection point of this plane and the planes `b` and `c`, or `None` if
+    /// there is no single intersection point.
+    pub fn intersect_3(&self, b: &Plane, c: &Plane) -> Option<Vector3> {
+        let (n0, n1, n2) = (self.normal, b.normal, c.normal);
+
+        let denom = n0.cross(n1).dot(n2);
+        if denom.abs() <= CMP_EPSILON {
+            return None;
+        }
+
+        Some((n1.cross(n2) * self.d + n2.cross(n0) * b.d + n0.cross(n1) * c.d) / denom)
+    }
+
+    /// Returns the point where the ray starting at `from` in direction `dir` crosses the plane,
+    /// or `None` if it doesn't.
+    pub fn intersects_ray(&self, from: Vector3, dir: Vector3) -> Option<Vector3> {
+        let den = self.normal.dot(dir);
+        if den.abs() < CMP_EPSILON {
+            return None;
+        }
+
+        let dist = (self.normal.dot(from) - self.d) / den;
+        if dist > CMP_EPSILON {
+            // The ray points away from the plane.
+            return None;
+        }
+
+        Some(from - dir * dist)
+    }
+
+    /// Returns the point where the segment from `begin` to `end` crosses the plane, or `None`
+    /// if it doesn't.
+    pub fn intersects_segment(&self, begin: Vector3, end: Vector3) -> Option<Vector3> {
+        let segment = begin - end;
+        let den = self.normal.dot(segment);
+        if den.abs() < CMP_EPSILON {
+            return None;
+        }
+
+        let dist = (self.normal.dot(begin) - self.d) / den;
+        if !(-CMP_EPSILON..=1.0 + CMP_EPSILON).contains(&dist) {
+            return None;
+        }
+
+        Some(begin - segment * dist)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn approx(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    fn plane() -> Plane {
+        Plane::new(Vector3::new(0.0, 1.0, 0.0), 2.0)
+    }
+
+    #[test]
+    fn from_points_is_clockwise() {
+        let p = Plane::from_points(
+            Vector3::new(0.0, 2.0, 0.0),
+            Vector3::new(1.0, 2.0, 0.0),
+            Vector3::new(0.0, 2.0, 1.0),
+        );
+        assert!(approx(Vector3::new(0.0, 1.0, 0.0), p.normal));
+        assert!((p.d - 2.0).abs() < 1e-5);
+    }
+
+    #[test]
+    fn distances() {
+        let p = plane();
+        assert_eq!(3.0, p.distance_to(Vector3::new(1.0, 5.0, 1.0)));
+        assert!(p.is_point_over(Vector3::new(0.0, 2.5, 0.0)));
+        assert!(!p.is_point_over(Vector3::new(0.0, 2.0, 0.0)));
+        assert!(p.has_point(Vector3::new(7.0, 2.0, -3.0), CMP_EPSILON));
+        assert!(approx(
+            Vector3::new(7.0, 2.0, -3.0),
+            p.project(Vector3::new(7.0, -8.0, -3.0))
+        ));
+        assert!(approx(Vector3::new(0.0, 2.0, 0.0), p.center()));
+
+        let p = Plane::new(Vector3::new(0.0, 2.0, 0.0), 4.0).normalized();
+        assert_eq!(plane(), p);
+    }
+
+    #[test]
+    fn intersections() {
+        let p = plane();
+        let x = Plane::new(Vector3::new(1.0, 0.0, 0.0), 1.0);
+        let z = Plane::new(Vector3::new(0.0, 0.0, 1.0), 3.0);
+        assert!(approx(
+            Vector3::new(1.0, 2.0, 3.0),
+            p.intersect_3(&x, &z).unwrap()
+        ));
+        assert_eq!(None, p.intersect_3(&p, &z));
+
+        let from = Vector3::new(1.0, 5.0, 1.0);
+        assert!(approx(
+            Vector3::new(1.0, 2.0, 1.0),
+            p.intersects_ray(from, Vector3::new(0.0, -1.0, 0.0))
+                .unwrap()
+        ));
+        assert_eq!(None, p.intersects_ray(from, Vector3::new(0.0, 1.0, 0.0)));
+        assert_eq!(None, p.intersects_ray(from, Vector3::new(1.0, 0.0, 0.0)));
+
+        assert!(approx(
+            Vector3::new(2.0, 2.0, 1.0),
+            p.intersects_segment(from, Vector3::new(3.0, -1.0, 1.0))
+                .unwrap()
+        ));
+        assert_eq!(
+            None,
+            p.intersects_segment(from, Vector3::new(1.0, 3.0, 1.0))
+        );
+    }
 }
@@ -0,0 +1,170 @@
+use super::CMP_EPSILON;
+use crate::{Basis, Quat, Vector3};
+
+/// Helper methods for `Quat`.
+///
+/// Trait used to provide additional methods that are equivalent to Godot's methods.
+/// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.1/classes/class_quat.html).
+///
+/// Methods that `Quat` already has, like `slerp` or `inverse`, are not repeated here.
+pub trait QuatGodot {
+    /// Creates a rotation from Euler angles in the YXZ convention, like
+    /// [`Basis::from_euler`](struct.Basis.html#method.from_euler).
+    fn from_euler(euler: Vector3) -> Self;
+    /// Returns the Euler angles of the rotation in the YXZ convention.
+    fn to_euler(self) -> Vector3;
+    /// Returns `v` rotated by this quaternion.
+    fn xform(self, v: Vector3) -> Vector3;
+    /// Spherically interpolates towards `to` without checking for the shortest path. Returns
+    /// `self` if the quaternions are almost equal.
+    fn slerpni(self, to: Self, t: f32) -> Self;
+    /// Cubicly interpolates between this quaternion and `b` using `pre_a` and `post_b` as
+    /// handles, and returns the result at position `t`.
+    fn cubic_slerp(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self;
+}
+
+impl QuatGodot for Quat {
+    fn from_euler(euler: Vector3) -> Self {
+        let (sin1, cos1) = (euler.y * 0.5).sin_cos();
+        let (sin2, cos2) = (euler.x * 0.5).sin_cos();
+        let (sin3, cos3) = (euler.z * 0.5).sin_cos();
+
+        Quat::quaternion(
+            sin1 * cos2 * sin3 + cos1 * sin2 * cos3,
+            sin1 * cos2 * cos3 - cos1 * sin2 * sin3,
+            -sin1 * sin2 * cos3 + cos1 * cos2 * sin3,
+            sin1 * sin2 * sin3 + cos1 * cos2 * cos3,
+        )
+    }
+
+    #[inline]
+    fn to_euler(self) -> Vector3 {
+        Basis::from_quat(self).to_euler()
+    }
+
+    #[inline]
+    fn xform(self, v: Vector3) -> Vector3 {
+        let u = self.vector_part();
+        let w = self.r;
+        v * (w * w - u.dot(u)) + u * (2.0 * u.dot(v)) + u.cross(v) * (2.0 * w)
+    }
+
+    fn slerpni(self, to: Self, t: f32) -> Self {
+        let dot = dot(self, to);
+        if dot.abs() > 0.9999 {
+            return self;
+        }
+
+        let theta = dot.acos();
+        let sin_t = 1.0 / theta.sin();
+        let new_factor = (t * theta).sin() * sin_t;
+        let inv_factor = ((1.0 - t) * theta).sin() * sin_t;
+
+        add(scale(self, inv_factor), scale(to, new_factor))
+    }
+
+    fn cubic_slerp(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self {
+        let t2 = (1.0 - t) * t * 2.0;
+        let sp = slerp(self, b, t);
+        let sq = pre_a.slerpni(post_b, t);
+        sp.slerpni(sq, t2)
+    }
+}
+
+/// Spherical interpolation as done by the engine, which falls back to linear interpolation
+/// without normalizing for very close rotations.
+pub(crate) fn slerp(from: Quat, to: Quat, t: f32) -> Quat {
+    let mut cosom = dot(from, to);
+    let to = if cosom < 0.0 {
+        cosom = -cosom;
+        scale(to, -1.0)
+    } else {
+        to
+    };
+
+    let (scale0, scale1) = if 1.0 - cosom > CMP_EPSILON {
+        let omega = cosom.acos();
+        let sinom = omega.sin();
+        (((1.0 - t) * omega).sin() / sinom, (t * omega).sin() / sinom)
+    } else {
+        (1.0 - t, t)
+    };
+
+    add(scale(from, scale0), scale(to, scale1))
+}
+
+#[inline]
+fn dot(a: Quat, b: Quat) -> f32 {
+    a.i * b.i + a.j * b.j + a.k * b.k + a.r * b.r
+}
+
+#[inline]
+fn scale(q: Quat, s: f32) -> Quat {
+    Quat::quaternion(q.i * s, q.j * s, q.k * s, q.r * s)
+}
+
+#[inline]
+fn add(a: Quat, b: Quat) -> Quat {
+    Quat::quaternion(a.i + b.i, a.j + b.j, a.k + b.k, a.r + b.r)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::{FRAC_1_SQRT_2, FRAC_PI_2, FRAC_PI_4};
+
+    fn approx(a: Quat, b: Quat) -> bool {
+        (a.i - b.i).abs() < 1e-5
+            && (a.j - b.j).abs() < 1e-5
+            && (a.k - b.k).abs() < 1e-5
+            && (a.r - b.r).abs() < 1e-5
+    }
+
+    #[test]
+    fn euler_matches_basis() {
+        let euler = Vector3::new(0.1, 0.2, 0.3);
+        let q = Quat::from_euler(euler);
+        assert!(approx(q, Basis::from_euler(euler).to_quat()));
+        assert!((q.to_euler() - euler).length() < 1e-5);
+
+        // Quat(Vector3(PI / 2, 0, 0)) in Godot 3.2.
+        let q = Quat::from_euler(Vector3::new(FRAC_PI_2, 0.0, 0.0));
+        assert!(approx(
+            Quat::quaternion(FRAC_1_SQRT_2, 0.0, 0.0, FRAC_1_SQRT_2),
+            q
+        ));
+    }
+
+    #[test]
+    fn xform_matches_basis() {
+        let q = Quat::from_euler(Vector3::new(0.4, -1.2, 2.0));
+        let v = Vector3::new(1.0, 2.0, -3.0);
+        assert!((q.xform(v) - Basis::from_quat(q).xform(v)).length() < 1e-5);
+
+        let q = Quat::from_euler(Vector3::new(0.0, FRAC_PI_2, 0.0));
+        let v = q.xform(Vector3::new(1.0, 0.0, 0.0));
+        assert!((v - Vector3::new(0.0, 0.0, -1.0)).length() < 1e-5);
+    }
+
+    #[test]
+    fn slerp_takes_the_shortest_path() {
+        let from = Quat::identity();
+        let to = Quat::from_euler(Vector3::new(0.0, 0.0, FRAC_PI_2));
+        let expected = Quat::from_euler(Vector3::new(0.0, 0.0, FRAC_PI_4));
+
+        assert!(approx(expected, slerp(from, to, 0.5)));
+        assert!(approx(expected, slerp(from, scale(to, -1.0), 0.5)));
+        assert!(approx(expected, from.slerpni(to, 0.5)));
+    }
+
+    #[test]
+    fn cubic_slerp_hits_end_points() {
+        let a = Quat::from_euler(Vector3::new(0.1, 0.2, 0.3));
+        let b = Quat::from_euler(Vector3::new(-0.5, 1.0, 0.2));
+        let pre_a = Quat::identity();
+        let post_b = Quat::from_euler(Vector3::new(0.0, 1.5, 0.0));
+
+        assert!(approx(a, a.cubic_slerp(b, pre_a, post_b, 0.0)));
+        assert!(approx(b, a.cubic_slerp(b, pre_a, post_b, 1.0)));
+    }
+}
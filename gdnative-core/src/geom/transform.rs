@@ -1,14 +1,17 @@
 use crate::{Basis, Vector3};
+use std::ops::Mul;
 
 /// 3D Transformation (3x4 matrix) Using basis + origin representation.
 #[repr(C)]
 #[derive(Copy, Clone, Debug, PartialEq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Transform {
     /// The basis is a matrix containing 3 Vector3 as its columns: X axis, Y axis, and Z axis.
     /// These vectors can be interpreted as the basis vectors of local coordinate system
     /// traveling with the object.
     pub basis: Basis,
     /// The translation offset of the transform.
+    #[cfg_attr(feature = "serde", serde(with = "crate::marshal::serialize::vector3"))]
     pub origin: Vector3,
 }
 
@@ -17,4 +20,190 @@ impl Transform {
     pub fn from_sys(c: sys::godot_transform) -> Self {
         unsafe { std::mem::transmute::<sys::godot_transform, Self>(c) }
     }
+
+    /// The identity transform.
+    #[inline]
+    pub fn identity() -> Self {
+        Transform::new(Basis::identity(), Vector3::zero())
+    }
+
+    #[inline]
+    pub fn new(basis: Basis, origin: Vector3) -> Self {
+        Transform { basis, origin }
+    }
+
+    /// Transforms `v` by this transform.
+    #[inline]
+    pub fn xform(&self, v: Vector3) -> Vector3 {
+        self.basis.xform(v) + self.origin
+    }
+
+    /// Inverse-transforms `v` by this transform, assuming that the basis is orthonormal.
+    #[inline]
+    pub fn xform_inv(&self, v: Vector3) -> Vector3 {
+        self.basis.xform_inv(v - self.origin)
+    }
+
+    /// Returns the inverse of the transform, assuming that the basis is orthonormal, i.e.
+    /// that the transform only consists of rotation and translation.
+    #[inline]
+    pub fn inverse(&self) -> Self {
+        let basis = self.basis.transposed();
+        Transform::new(basis, basis.xform(-self.origin))
+    }
+
+    /// Returns the inverse of the transform, which may also contain scaling.
+    #[inline]
+    pub fn affine_inverse(&self) -> Self {
+        let basis = self.basis.inverse();
+        Transform::new(basis, basis.xform(-self.origin))
+    }
+
+    /// Returns the transform with an orthonormalized basis.
+    #[inline]
+    pub fn orthonormalized(&self) -> Self {
+        Transform::new(self.basis.orthonormalized(), self.origin)
+    }
+
+    /// Returns the transform rotated by `phi` radians around `axis`, relative to the parent.
+    #[inline]
+    pub fn rotated(&self, axis: Vector3, phi: f32) -> Self {
+        Transform::new(Basis::from_axis_angle(axis, phi), Vector3::zero()) * *self
+    }
+
+    /// Returns the transform scaled by `scale`, relative to the parent.
+    #[inline]
+    pub fn scaled(&self, scale: Vector3) -> Self {
+        Transform::new(
+            self.basis.scaled(scale),
+            Vector3::new(
+                self.origin.x * scale.x,
+                self.origin.y * scale.y,
+                self.origin.z * scale.z,
+            ),
+        )
+    }
+
+    /// Returns the transform translated by `offset`, relative to the transform itself.
+    #[inline]
+    pub fn translated(&self, offset: Vector3) -> Self {
+        Transform::new(self.basis, self.origin + self.basis.xform(offset))
+    }
+
+    /// Returns the transform rotated so that its -Z axis points towards `target`, with its Y
+    /// axis as close to `up` as possible.
+    pub fn looking_at(&self, target: Vector3, up: Vector3) -> Self {
+        let v_z = (self.origin - target).normalize();
+        let v_x = up.cross(v_z);
+        let v_y = v_z.cross(v_x);
+
+        Transform::new(
+            Basis::from_axes(v_x.normalize(), v_y.normalize(), v_z),
+            self.origin,
+        )
+    }
+
+    /// Interpolates the rotation, scale and origin of the transform towards `other`.
+    pub fn interpolate_with(&self, other: &Transform, t: f32) -> Self {
+        let src_scale = self.basis.scale();
+        let src_rot = self.basis.to_rotation_quat();
+
+        let dst_scale = other.basis.scale();
+        let dst_rot = other.basis.to_rotation_quat();
+
+        let rot = super::quat::slerp(src_rot, dst_rot, t).normalize();
+        Transform::new(
+            Basis::from_quat(rot).scaled(src_scale.lerp(dst_scale, t)),
+            self.origin.lerp(other.origin, t),
+        )
+    }
+}
+
+impl Default for Transform {
+    #[inline]
+    fn default() -> Self {
+        Transform::identity()
+    }
+}
+
+impl Mul<Transform> for Transform {
+    type Output = Transform;
+
+    #[inline]
+    fn mul(self, rhs: Transform) -> Transform {
+        Transform::new(self.basis * rhs.basis, self.xform(rhs.origin))
+    }
+}
+
+impl Mul<Vector3> for Transform {
+    type Output = Vector3;
+
+    #[inline]
+    fn mul(self, rhs: Vector3) -> Vector3 {
+        self.xform(rhs)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn approx(a: Vector3, b: Vector3) -> bool {
+        (a - b).length() < 1e-5
+    }
+
+    fn transform() -> Transform {
+        Transform::new(
+            Basis::from_euler(Vector3::new(0.1, 0.2, 0.3)),
+            Vector3::new(1.0, 2.0, 3.0),
+        )
+    }
+
+    #[test]
+    fn xform_round_trip() {
+        let t = transform();
+        let v = Vector3::new(-4.0, 5.0, 0.5);
+        assert!(approx(v, t.xform_inv(t.xform(v))));
+        assert!(approx(v, t.inverse().xform(t.xform(v))));
+
+        let scaled = t.scaled(Vector3::new(2.0, 3.0, 0.5));
+        assert!(approx(v, scaled.affine_inverse().xform(scaled.xform(v))));
+        assert!(approx(v, (scaled.affine_inverse() * scaled).xform(v)));
+    }
+
+    #[test]
+    fn rotated_and_translated() {
+        let t = Transform::new(Basis::identity(), Vector3::new(1.0, 0.0, 0.0))
+            .rotated(Vector3::new(0.0, 1.0, 0.0), FRAC_PI_2);
+        assert!(approx(Vector3::new(0.0, 0.0, -1.0), t.origin));
+
+        let t = t.translated(Vector3::new(1.0, 0.0, 0.0));
+        assert!(approx(Vector3::new(0.0, 0.0, -2.0), t.origin));
+    }
+
+    #[test]
+    fn looking_at() {
+        let t = Transform::identity()
+            .looking_at(Vector3::new(1.0, 0.0, 0.0), Vector3::new(0.0, 1.0, 0.0));
+        assert!(approx(Vector3::new(1.0, 0.0, 0.0), -t.basis.z()));
+        assert!(approx(Vector3::new(0.0, 1.0, 0.0), t.basis.y()));
+        assert!(approx(Vector3::new(0.0, 0.0, 1.0), t.basis.x()));
+    }
+
+    #[test]
+    fn interpolate_with() {
+        let from = Transform::identity();
+        let to = Transform::new(
+            Basis::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), FRAC_PI_2)
+                .scaled(Vector3::new(3.0, 3.0, 3.0)),
+            Vector3::new(2.0, 4.0, 6.0),
+        );
+
+        let half = from.interpolate_with(&to, 0.5);
+        let expected = Basis::from_axis_angle(Vector3::new(0.0, 1.0, 0.0), FRAC_PI_2 / 2.0)
+            .scaled(Vector3::new(2.0, 2.0, 2.0));
+        assert!(half.basis.is_equal_approx(&expected));
+        assert!(approx(Vector3::new(1.0, 2.0, 3.0), half.origin));
+    }
 }
@@ -35,6 +35,7 @@ use std::marker::PhantomData;
 use std::mem;
 use std::ops::Range;
 use std::ptr;
+use std::sync::atomic::{AtomicPtr, Ordering};
 
 /// A handle that can register new classes to the engine during initialization.
 ///
@@ -200,6 +201,49 @@ impl InitHandle {
     }
 }
 
+/// Returns `true` when the library is loaded by the editor, like `Engine.editor_hint` in
+/// GDScript. This can be used to register members of tool classes only in the editor.
+pub fn is_editor_hint() -> bool {
+    // The singleton and the method bind live as long as the engine, so they are looked up once.
+    static ENGINE: AtomicPtr<sys::godot_object> = AtomicPtr::new(ptr::null_mut());
+    static METHOD_BIND: AtomicPtr<sys::godot_method_bind> = AtomicPtr::new(ptr::null_mut());
+
+    unsafe {
+        let api = get_api();
+        let engine = cached(&ENGINE, || {
+            (api.godot_global_get_singleton)(b"Engine\0".as_ptr() as *mut _)
+        });
+        let method_bind = cached(&METHOD_BIND, || {
+            (api.godot_method_bind_get_method)(
+                b"_Engine\0".as_ptr() as *const _,
+                b"is_editor_hint\0".as_ptr() as *const _,
+            )
+        });
+
+        let mut argument_buffer: [*const libc::c_void; 0] = [];
+        let mut editor_hint = false;
+        (api.godot_method_bind_ptrcall)(
+            method_bind,
+            engine,
+            argument_buffer.as_mut_ptr() as *mut _,
+            &mut editor_hint as *mut bool as *mut _,
+        );
+
+        editor_hint
+    }
+}
+
+/// Returns the pointer stored in `cell`, storing the result of `init` first if it is null.
+/// Racing threads may both call `init`, which must return the same pointer every time.
+fn cached<T>(cell: &AtomicPtr<T>, init: impl FnOnce() -> *mut T) -> *mut T {
+    let mut ptr = cell.load(Ordering::Acquire);
+    if ptr.is_null() {
+        ptr = init();
+        cell.store(ptr, Ordering::Release);
+    }
+    ptr
+}
+
 pub type ScriptMethodFn = unsafe extern "C" fn(
     *mut sys::godot_object,
     *mut libc::c_void,
@@ -214,12 +258,45 @@ pub type ScriptConstructorFn =
 pub type ScriptDestructorFn =
     unsafe extern "C" fn(*mut sys::godot_object, *mut libc::c_void, *mut libc::c_void) -> ();
 
+/// Network mode of a method or a property, used by the high-level multiplayer API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum RpcMode {
+    /// Not callable over the network.
     Disabled,
+    /// Callable from remote peers.
     Remote,
-    Sync,
-    Mater,
-    Slave,
+    /// Callable from remote peers, and also called locally.
+    RemoteSync,
+    /// Callable from remote peers on the network master only.
+    Master,
+    /// Callable on the network master only, and also called locally.
+    MasterSync,
+    /// Callable from the network master on puppets only.
+    Puppet,
+    /// Callable on puppets only, and also called locally.
+    PuppetSync,
+}
+
+#[allow(non_upper_case_globals)]
+impl RpcMode {
+    #[deprecated(note = "renamed to `RemoteSync`")]
+    pub const Sync: RpcMode = RpcMode::RemoteSync;
+    #[deprecated(note = "renamed to `Master`")]
+    pub const Mater: RpcMode = RpcMode::Master;
+    #[deprecated(note = "renamed to `Puppet`")]
+    pub const Slave: RpcMode = RpcMode::Puppet;
+
+    pub fn to_sys(self) -> sys::godot_method_rpc_mode {
+        match self {
+            RpcMode::Disabled => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED,
+            RpcMode::Remote => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_REMOTE,
+            RpcMode::RemoteSync => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_REMOTESYNC,
+            RpcMode::Master => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_MASTER,
+            RpcMode::MasterSync => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_MASTERSYNC,
+            RpcMode::Puppet => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_PUPPET,
+            RpcMode::PuppetSync => sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_PUPPETSYNC,
+        }
+    }
 }
 
 pub struct ScriptMethodAttributes {
@@ -253,7 +330,7 @@ impl<C: NativeClass> ClassBuilder<C> {
     pub fn add_method_advanced(&self, method: ScriptMethod) {
         let method_name = CString::new(method.name).unwrap();
         let attr = sys::godot_method_attributes {
-            rpc_type: sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED,
+            rpc_type: method.attributes.rpc_mode.to_sys(),
         };
 
         let method_desc = sys::godot_instance_method {
@@ -274,52 +351,44 @@ impl<C: NativeClass> ClassBuilder<C> {
     }
 
     pub fn add_method(&self, name: &str, method: ScriptMethodFn) {
+        self.add_method_with_rpc_mode(name, method, RpcMode::Disabled);
+    }
+
+    /// Registers a method that can be called over the network according to `rpc_mode`.
+    pub fn add_method_with_rpc_mode(&self, name: &str, method: ScriptMethodFn, rpc_mode: RpcMode) {
         self.add_method_advanced(ScriptMethod {
             name: name,
             method_ptr: Some(method),
-            attributes: ScriptMethodAttributes {
-                rpc_mode: RpcMode::Disabled,
-            },
+            attributes: ScriptMethodAttributes { rpc_mode },
             method_data: ptr::null_mut(),
             free_func: None,
         });
     }
 
     pub fn add_property<T, S, G>(&self, property: Property<T, S, G>)
+    where
+        T: ToVariant + FromVariant,
+        S: PropertySetter<C, T>,
+        G: PropertyGetter<C, T>,
+    {
+        self.add_property_with_rset(property, RpcMode::Disabled);
+    }
+
+    /// Registers a property that can be set over the network according to `rset_mode`.
+    pub fn add_property_with_rset<T, S, G>(&self, property: Property<T, S, G>, rset_mode: RpcMode)
     where
         T: ToVariant + FromVariant,
         S: PropertySetter<C, T>,
         G: PropertyGetter<C, T>,
     {
         unsafe {
-            let hint_text = match property.hint {
-                PropertyHint::Range {
-                    ref range,
-                    step,
-                    slider,
-                } => {
-                    if slider {
-                        Some(format!("{},{},{},slider", range.start, range.end, step))
-                    } else {
-                        Some(format!("{},{},{}", range.start, range.end, step))
-                    }
-                }
-                PropertyHint::Enum { values } | PropertyHint::Flags { values } => {
-                    Some(values.join(","))
-                }
-                PropertyHint::NodePathToEditedNode | PropertyHint::None => None,
-            };
-            let hint_string = if let Some(text) = hint_text {
-                GodotString::from_str(text)
-            } else {
-                GodotString::default()
-            };
+            let hint_string = GodotString::from_str(property.hint.to_hint_string());
 
             let default: Variant = property.default.to_variant();
             let ty = default.get_type();
 
             let mut attr = sys::godot_property_attributes {
-                rset_type: sys::godot_method_rpc_mode_GODOT_METHOD_RPC_MODE_DISABLED, // TODO:
+                rset_type: rset_mode.to_sys(),
                 type_: mem::transmute(ty),
                 hint: property.hint.to_sys(),
                 hint_string: hint_string.to_sys(),
@@ -351,7 +420,7 @@ impl<C: NativeClass> ClassBuilder<C> {
                 .iter()
                 .map(|arg| {
                     let arg_name = GodotString::from_str(arg.name);
-                    let hint_string = GodotString::new();
+                    let hint_string = GodotString::from_str(arg.hint.to_hint_string());
                     (arg, arg_name, hint_string)
                 })
                 .collect::<Vec<_>>();
@@ -379,51 +448,126 @@ impl<C: NativeClass> ClassBuilder<C> {
             );
         }
     }
+
+    /// Registers a signal declared as a type. See the [`signal`](../signal/index.html) module.
+    pub fn register_signal<S: crate::signal::Signal>(&self) {
+        self.add_signal(Signal {
+            name: S::NAME,
+            args: &S::arguments(),
+        });
+    }
 }
 
-// TODO: missing property hints.
+/// Editor hint of a property or a signal argument.
+///
+/// Each hint is registered together with a hint string, which is generated from the data of
+/// the variant.
 pub enum PropertyHint<'l> {
     None,
+    /// Numeric range, edited with a spin box or optionally a slider.
     Range {
         range: Range<f64>,
         step: f64,
         slider: bool,
     },
-    // ExpRange,
+    /// Numeric range, edited with an exponential slider.
+    ExpRange {
+        range: Range<f64>,
+        step: f64,
+    },
+    /// Integer or string chosen from a list of values.
     Enum {
         values: &'l [&'l str],
     },
-    // ExpEasing,
-    // Length,
-    // SpriteFrame,
-    // KeyAccel,
+    /// Real number edited as the curve of an easing function.
+    ExpEasing {
+        /// Flip the curve horizontally.
+        attenuation: bool,
+        /// Also show in-out and out-in presets.
+        in_out: bool,
+    },
+    Length {
+        length: i64,
+    },
+    SpriteFrame,
+    KeyAccel,
+    /// Integer edited as a set of named bit flags.
     Flags {
         values: &'l [&'l str],
     },
-    // Layers2DRender,
-    // Layers2DPhysics,
-    // Layers3DRender,
-    // Layers3DPhysics,
-    // File,
-    // Dir,
-    // GlobalFile,
-    // GlobalDir,
-    // ResourceType,
-    // MultilineText,
-    // ColorNoAlpha,
-    // ImageCompressLossy,
-    // IMageCompressLossless,
-    // ObjectID,
-    // TypeString,
+    /// Integer edited as a mask of the 2D render layers.
+    Layers2DRender,
+    /// Integer edited as a mask of the 2D physics layers.
+    Layers2DPhysics,
+    /// Integer edited as a mask of the 3D render layers.
+    Layers3DRender,
+    /// Integer edited as a mask of the 3D physics layers.
+    Layers3DPhysics,
+    /// String edited as a path to a file inside the project, optionally filtered by patterns
+    /// like `"*.png"`.
+    File {
+        filters: &'l [&'l str],
+    },
+    /// String edited as a path to a directory inside the project.
+    Dir,
+    /// String edited as an absolute path to a file, optionally filtered by patterns.
+    GlobalFile {
+        filters: &'l [&'l str],
+    },
+    /// String edited as an absolute path to a directory.
+    GlobalDir,
+    /// Resource property that only accepts the given resource classes, like `"Texture"`.
+    ResourceType {
+        types: &'l [&'l str],
+    },
+    /// String edited in a multiline text box.
+    MultilineText,
+    /// String edited in a text box showing `text` while it is empty.
+    PlaceholderText {
+        text: &'l str,
+    },
+    /// Color edited without its alpha component.
+    ColorNoAlpha,
+    ImageCompressLossy,
+    ImageCompressLossless,
+    ObjectId,
+    /// Type string whose choices derive from `base_type`.
+    TypeString {
+        base_type: &'l str,
+    },
     NodePathToEditedNode,
-    // MethodOfVariantType,
-    // MethodOfBaseType,
-    // MethodOfInstance,
-    // MethodOfScript,
-    // PropertyOfVariantType,
-    // PropertyOfBaseType,
-    // PropertyOfInstance,
-    // PropertyOfScript,
+    /// Method name of a built-in type.
+    MethodOfVariantType {
+        variant_type: VariantType,
+    },
+    /// Method name of an engine class.
+    MethodOfBaseType {
+        base_type: &'l str,
+    },
+    /// Method name of the object with the given instance ID.
+    MethodOfInstance {
+        instance_id: i64,
+    },
+    /// Method name of the script with the given instance ID.
+    MethodOfScript {
+        instance_id: i64,
+    },
+    /// Property name of a built-in type.
+    PropertyOfVariantType {
+        variant_type: VariantType,
+    },
+    /// Property name of an engine class.
+    PropertyOfBaseType {
+        base_type: &'l str,
+    },
+    /// Property name of the object with the given instance ID.
+    PropertyOfInstance {
+        instance_id: i64,
+    },
+    /// Property name of the script with the given instance ID.
+    PropertyOfScript {
+        instance_id: i64,
+    },
 }
 
 impl<'l> PropertyHint<'l> {
@@ -431,15 +575,185 @@ impl<'l> PropertyHint<'l> {
         match *self {
             PropertyHint::None => sys::godot_property_hint_GODOT_PROPERTY_HINT_NONE,
             PropertyHint::Range { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_RANGE,
+            PropertyHint::ExpRange { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_EXP_RANGE,
             PropertyHint::Enum { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_ENUM,
+            PropertyHint::ExpEasing { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_EXP_EASING
+            }
+            PropertyHint::Length { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_LENGTH,
+            PropertyHint::SpriteFrame => sys::godot_property_hint_GODOT_PROPERTY_HINT_SPRITE_FRAME,
+            PropertyHint::KeyAccel => sys::godot_property_hint_GODOT_PROPERTY_HINT_KEY_ACCEL,
             PropertyHint::Flags { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_FLAGS,
+            PropertyHint::Layers2DRender => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_2D_RENDER
+            }
+            PropertyHint::Layers2DPhysics => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_2D_PHYSICS
+            }
+            PropertyHint::Layers3DRender => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_3D_RENDER
+            }
+            PropertyHint::Layers3DPhysics => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_LAYERS_3D_PHYSICS
+            }
+            PropertyHint::File { .. } => sys::godot_property_hint_GODOT_PROPERTY_HINT_FILE,
+            PropertyHint::Dir => sys::godot_property_hint_GODOT_PROPERTY_HINT_DIR,
+            PropertyHint::GlobalFile { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_GLOBAL_FILE
+            }
+            PropertyHint::GlobalDir => sys::godot_property_hint_GODOT_PROPERTY_HINT_GLOBAL_DIR,
+            PropertyHint::ResourceType { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_RESOURCE_TYPE
+            }
+            PropertyHint::MultilineText => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_MULTILINE_TEXT
+            }
+            PropertyHint::PlaceholderText { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PLACEHOLDER_TEXT
+            }
+            PropertyHint::ColorNoAlpha => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_COLOR_NO_ALPHA
+            }
+            PropertyHint::ImageCompressLossy => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_IMAGE_COMPRESS_LOSSY
+            }
+            PropertyHint::ImageCompressLossless => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_IMAGE_COMPRESS_LOSSLESS
+            }
+            PropertyHint::ObjectId => sys::godot_property_hint_GODOT_PROPERTY_HINT_OBJECT_ID,
+            PropertyHint::TypeString { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_TYPE_STRING
+            }
             PropertyHint::NodePathToEditedNode => {
                 sys::godot_property_hint_GODOT_PROPERTY_HINT_NODE_PATH_TO_EDITED_NODE
             }
+            PropertyHint::MethodOfVariantType { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_VARIANT_TYPE
+            }
+            PropertyHint::MethodOfBaseType { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_BASE_TYPE
+            }
+            PropertyHint::MethodOfInstance { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_INSTANCE
+            }
+            PropertyHint::MethodOfScript { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_METHOD_OF_SCRIPT
+            }
+            PropertyHint::PropertyOfVariantType { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_VARIANT_TYPE
+            }
+            PropertyHint::PropertyOfBaseType { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_BASE_TYPE
+            }
+            PropertyHint::PropertyOfInstance { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_INSTANCE
+            }
+            PropertyHint::PropertyOfScript { .. } => {
+                sys::godot_property_hint_GODOT_PROPERTY_HINT_PROPERTY_OF_SCRIPT
+            }
+        }
+    }
+
+    /// Returns the hint string registered together with the hint.
+    pub fn to_hint_string(&self) -> String {
+        match *self {
+            PropertyHint::Range {
+                ref range,
+                step,
+                slider,
+            } => {
+                if slider {
+                    format!("{},{},{},slider", range.start, range.end, step)
+                } else {
+                    format!("{},{},{}", range.start, range.end, step)
+                }
+            }
+            PropertyHint::ExpRange { ref range, step } => {
+                format!("{},{},{}", range.start, range.end, step)
+            }
+            PropertyHint::Enum { values }
+            | PropertyHint::Flags { values }
+            | PropertyHint::File { filters: values }
+            | PropertyHint::GlobalFile { filters: values }
+            | PropertyHint::ResourceType { types: values } => values.join(","),
+            PropertyHint::ExpEasing {
+                attenuation,
+                in_out,
+            } => {
+                let mut flags = Vec::new();
+                if attenuation {
+                    flags.push("attenuation");
+                }
+                if in_out {
+                    flags.push("inout");
+                }
+                flags.join(",")
+            }
+            PropertyHint::Length { length } => length.to_string(),
+            PropertyHint::PlaceholderText { text } => text.to_string(),
+            PropertyHint::TypeString { base_type }
+            | PropertyHint::MethodOfBaseType { base_type }
+            | PropertyHint::PropertyOfBaseType { base_type } => base_type.to_string(),
+            PropertyHint::MethodOfVariantType { variant_type }
+            | PropertyHint::PropertyOfVariantType { variant_type } => {
+                variant_type_name(variant_type).to_string()
+            }
+            PropertyHint::MethodOfInstance { instance_id }
+            | PropertyHint::MethodOfScript { instance_id }
+            | PropertyHint::PropertyOfInstance { instance_id }
+            | PropertyHint::PropertyOfScript { instance_id } => instance_id.to_string(),
+            PropertyHint::None
+            | PropertyHint::SpriteFrame
+            | PropertyHint::KeyAccel
+            | PropertyHint::Layers2DRender
+            | PropertyHint::Layers2DPhysics
+            | PropertyHint::Layers3DRender
+            | PropertyHint::Layers3DPhysics
+            | PropertyHint::Dir
+            | PropertyHint::GlobalDir
+            | PropertyHint::MultilineText
+            | PropertyHint::ColorNoAlpha
+            | PropertyHint::ImageCompressLossy
+            | PropertyHint::ImageCompressLossless
+            | PropertyHint::ObjectId
+            | PropertyHint::NodePathToEditedNode => String::new(),
         }
     }
 }
 
+/// The type names the editor uses in hint strings.
+fn variant_type_name(variant_type: VariantType) -> &'static str {
+    match variant_type {
+        VariantType::Nil => "Nil",
+        VariantType::Bool => "bool",
+        VariantType::I64 => "int",
+        VariantType::F64 => "float",
+        VariantType::GodotString => "String",
+        VariantType::Vector2 => "Vector2",
+        VariantType::Rect2 => "Rect2",
+        VariantType::Vector3 => "Vector3",
+        VariantType::Transform2D => "Transform2D",
+        VariantType::Plane => "Plane",
+        VariantType::Quat => "Quat",
+        VariantType::Aabb => "AABB",
+        VariantType::Basis => "Basis",
+        VariantType::Transform => "Transform",
+        VariantType::Color => "Color",
+        VariantType::NodePath => "NodePath",
+        VariantType::Rid => "RID",
+        VariantType::Object => "Object",
+        VariantType::Dictionary => "Dictionary",
+        VariantType::VariantArray => "Array",
+        VariantType::ByteArray => "PoolByteArray",
+        VariantType::Int32Array => "PoolIntArray",
+        VariantType::Float32Array => "PoolRealArray",
+        VariantType::StringArray => "PoolStringArray",
+        VariantType::Vector2Array => "PoolVector2Array",
+        VariantType::Vector3Array => "PoolVector3Array",
+        VariantType::ColorArray => "PoolColorArray",
+    }
+}
+
 bitflags! {
     pub struct PropertyUsage: u32 {
         const STORAGE = sys::godot_property_usage_flags_GODOT_PROPERTY_USAGE_STORAGE as u32;
@@ -636,3 +950,102 @@ where
         get
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::mock;
+    use crate::user_data::MutexData;
+
+    #[test]
+    fn hint_strings() {
+        let hint = PropertyHint::Range {
+            range: 0.0..10.0,
+            step: 0.5,
+            slider: true,
+        };
+        assert_eq!("0,10,0.5,slider", hint.to_hint_string());
+
+        let hint = PropertyHint::File {
+            filters: &["*.png", "*.jpg"],
+        };
+        assert_eq!("*.png,*.jpg", hint.to_hint_string());
+
+        let hint = PropertyHint::ExpEasing {
+            attenuation: true,
+            in_out: true,
+        };
+        assert_eq!("attenuation,inout", hint.to_hint_string());
+
+        let hint = PropertyHint::MethodOfVariantType {
+            variant_type: VariantType::Aabb,
+        };
+        assert_eq!("AABB", hint.to_hint_string());
+        assert_eq!("", PropertyHint::Layers2DPhysics.to_hint_string());
+    }
+
+    struct Hinted {
+        texture: Variant,
+    }
+
+    impl NativeClass for Hinted {
+        type Base = crate::Reference;
+        type UserData = MutexData<Hinted>;
+
+        fn class_name() -> &'static str {
+            "Hinted"
+        }
+
+        fn init(_owner: crate::Reference) -> Self {
+            Hinted {
+                texture: Variant::new(),
+            }
+        }
+
+        fn register_properties(builder: &ClassBuilder<Self>) {
+            builder.add_property(Property {
+                name: "texture",
+                default: Variant::new(),
+                hint: PropertyHint::ResourceType {
+                    types: &["Texture"],
+                },
+                getter: |this: &Hinted| this.texture.clone(),
+                setter: |this: &mut Hinted, v| this.texture = v,
+                usage: PropertyUsage::DEFAULT,
+            });
+
+            builder.add_signal(Signal {
+                name: "loaded",
+                args: &[SignalArgument {
+                    name: "path",
+                    default: Variant::from_str(""),
+                    hint: PropertyHint::File {
+                        filters: &["*.tres"],
+                    },
+                    usage: PropertyUsage::DEFAULT,
+                }],
+            });
+        }
+    }
+
+    impl crate::NativeClassMethods for Hinted {
+        fn register(_builder: &ClassBuilder<Self>) {}
+    }
+
+    #[test]
+    fn register_hints() {
+        mock::init_nativescript(|handle| handle.add_class::<Hinted>());
+        let info = mock::class_info("Hinted").unwrap();
+
+        let texture = info.property("texture").unwrap();
+        assert_eq!(
+            sys::godot_property_hint_GODOT_PROPERTY_HINT_RESOURCE_TYPE,
+            texture.hint
+        );
+        assert_eq!("Texture", texture.hint_string);
+
+        let path = &info.signal("loaded").unwrap().args[0];
+        assert_eq!(sys::godot_property_hint_GODOT_PROPERTY_HINT_FILE, path.hint);
+        assert_eq!("*.tres", path.hint_string);
+    }
+}
@@ -30,6 +30,8 @@ pub extern crate gdnative_sys as sys;
 pub extern crate libc;
 #[macro_use]
 extern crate bitflags;
+#[macro_use]
+extern crate lazy_static;
 extern crate parking_lot;
 
 pub mod geom;
@@ -38,57 +40,61 @@ mod macros;
 #[macro_use]
 mod class;
 pub mod access;
-mod byte_array;
 mod color;
-mod color_array;
 mod dictionary;
-mod float32_array;
 mod free_on_drop;
 mod generated;
 pub mod init;
-mod int32_array;
+pub mod marshal;
+#[doc(hidden)]
+pub mod method_return;
+#[cfg(any(test, feature = "mock_api"))]
+pub mod mock;
 mod node_path;
 #[doc(hidden)]
 pub mod object;
 mod point2;
+mod pool_array;
+mod rect2;
 mod rid;
+pub mod signal;
 mod string;
-mod string_array;
+mod transform2d;
 mod type_tag;
+mod typed_array;
+mod typed_dictionary;
+#[doc(hidden)]
+pub mod unwind;
 pub mod user_data;
 mod variant;
 mod variant_array;
 mod vector2;
-mod vector2_array;
 mod vector3;
-mod vector3_array;
 
-pub use crate::byte_array::*;
 pub use crate::class::*;
 pub use crate::color::*;
-pub use crate::color_array::*;
 pub use crate::dictionary::*;
-pub use crate::float32_array::*;
 pub use crate::free_on_drop::*;
 pub use crate::generated::*;
 pub use crate::geom::*;
-pub use crate::int32_array::*;
 pub use crate::node_path::*;
 pub use crate::object::GodotObject;
 pub use crate::object::Instanciable;
 pub use crate::point2::*;
+pub use crate::pool_array::*;
+pub use crate::rect2::*;
 pub use crate::rid::*;
 pub use crate::string::*;
-pub use crate::string_array::*;
+pub use crate::transform2d::*;
+pub use crate::typed_array::*;
+pub use crate::typed_dictionary::*;
 pub use crate::user_data::Map;
 pub use crate::user_data::MapMut;
 pub use crate::user_data::UserData;
 pub use crate::variant::*;
 pub use crate::variant_array::*;
 pub use crate::vector2::*;
-pub use crate::vector2_array::*;
 pub use crate::vector3::*;
-pub use crate::vector3_array::*;
 
 pub use sys::GodotApi;
 
@@ -185,6 +185,59 @@ macro_rules! godot_error {
     })
 }
 
+/// Connect a typed signal to an exported method of a NativeScript class.
+///
+/// The method must take the owner of its class followed by the arguments of the signal,
+/// otherwise the invocation does not compile. Evaluates to a `GodotResult`. The connection
+/// flags can be passed as an optional last argument.
+///
+/// The method is connected under its Rust name. Methods exported under another name with
+/// `#[export(name = "...")]` must be connected with a `name = "..."` argument, since the
+/// engine only reports a missing method when the signal is emitted.
+///
+/// ```ignore
+/// godot_connect!(emitter, TickWithData => owner, SignalSubscriber::notify_with_data)?;
+/// godot_connect!(emitter, Tick => owner, SignalSubscriber::notify, name = "onTick")?;
+/// ```
+#[macro_export]
+macro_rules! godot_connect {
+    ($emitter:expr, $signal:ty => $target:expr, $class:ident :: $method:ident) => {
+        $crate::godot_connect!($emitter, $signal => $target, $class::$method, name = stringify!($method), 0)
+    };
+    ($emitter:expr, $signal:ty => $target:expr, $class:ident :: $method:ident, name = $name:expr) => {
+        $crate::godot_connect!($emitter, $signal => $target, $class::$method, name = $name, 0)
+    };
+    ($emitter:expr, $signal:ty => $target:expr, $class:ident :: $method:ident, name = $name:expr, $flags:expr) => {
+        $crate::signal::connect::<$signal, _, $class, _, _>(
+            &mut $emitter,
+            &$target,
+            $name,
+            $flags,
+            $class::$method,
+        )
+    };
+    ($emitter:expr, $signal:ty => $target:expr, $class:ident :: $method:ident, $flags:expr) => {
+        $crate::godot_connect!($emitter, $signal => $target, $class::$method, name = stringify!($method), $flags)
+    };
+}
+
+/// Disconnect a connection made with `godot_connect!`. Methods connected with a `name = "..."`
+/// argument must be disconnected with the same argument.
+#[macro_export]
+macro_rules! godot_disconnect {
+    ($emitter:expr, $signal:ty => $target:expr, $class:ident :: $method:ident) => {
+        $crate::godot_disconnect!($emitter, $signal => $target, $class::$method, name = stringify!($method))
+    };
+    ($emitter:expr, $signal:ty => $target:expr, $class:ident :: $method:ident, name = $name:expr) => {
+        $crate::signal::disconnect::<$signal, _, $class, _, _>(
+            &mut $emitter,
+            &$target,
+            $name,
+            $class::$method,
+        )
+    };
+}
+
 macro_rules! impl_basic_trait {
     (
         Drop for $Type:ident as $GdType:ident : $gd_method:ident
@@ -292,102 +345,6 @@ macro_rules! impl_common_methods {
     )
 }
 
-macro_rules! impl_guard_common_trait {
-    (
-        Drop for $Type:ident { $access:ident, $len:ident, } : $gd_method:ident
-    ) => {
-        impl<'a> Drop for $Type<'a> {
-            fn drop(&mut self) {
-                unsafe {
-                    ($crate::get_api().$gd_method)(self.$access);
-                }
-            }
-        }
-    };
-
-    (
-        Clone for $Type:ident { $access:ident, $len:ident, } : $gd_method:ident
-    ) => {
-        impl<'a> Clone for $Type<'a> {
-            fn clone(&self) -> Self {
-                let $access = unsafe { ($crate::get_api().$gd_method)(self.$access) };
-
-                $Type {
-                    $access,
-                    $len: self.$len,
-                    _marker: std::marker::PhantomData,
-                }
-            }
-        }
-    };
-}
-
-macro_rules! define_access_guard {
-    (
-        pub struct $Type:ident<'a>: $AccessPtr:ty {
-            $access:ident = $access_gd_method:ident($BasePtr:ty),
-            $len:ident = $len_gd_method:ident,
-        }
-        Guard<Target=$Target:ident> => $guard_gd_method:ident -> $OrigPtr:ty;
-        $( $Trait:ident => $trait_gd_method:ident; )*
-    ) => {
-        pub struct $Type<'a> {
-            $access: *mut $AccessPtr,
-            $len: usize,
-            _marker: std::marker::PhantomData<&'a $Target>,
-        }
-
-        impl<'a> $Type<'a> {
-            unsafe fn new(arr: $BasePtr) -> Self {
-                let $len = ($crate::get_api().$len_gd_method)(arr) as usize;
-                let $access = ($crate::get_api().$access_gd_method)(arr);
-                $Type {
-                    $access,
-                    $len,
-                    _marker: std::marker::PhantomData,
-                }
-            }
-        }
-
-        unsafe impl<'a> $crate::access::Guard for $Type<'a> {
-            type Target = $Target;
-            fn len(&self) -> usize { self.$len }
-            fn read_ptr(&self) -> *const Self::Target {
-                unsafe {
-                    let orig_ptr: $OrigPtr = ($crate::get_api().$guard_gd_method)(self.$access);
-                    orig_ptr as *const Self::Target
-                }
-            }
-        }
-
-        $(
-            impl_guard_common_trait!(
-                $Trait for $Type { $access, $len, } : $trait_gd_method
-            );
-        )*
-    };
-
-    (
-        pub struct $Type:ident<'a>: $AccessPtr:ty {
-            $access:ident = $access_gd_method:ident($BasePtr:ty),
-            $len:ident = $len_gd_method:ident,
-        }
-        Guard<Target=$Target:ident> + WritePtr => $guard_gd_method:ident -> $OrigPtr:ty;
-        $( $Trait:ident => $trait_gd_method:ident; )*
-    ) => {
-        define_access_guard!(
-            pub struct $Type<'a>: $AccessPtr {
-                $access = $access_gd_method($BasePtr),
-                $len = $len_gd_method,
-            }
-            Guard<Target=$Target> => $guard_gd_method -> $OrigPtr;
-            $( $Trait => $trait_gd_method; )*
-        );
-
-        unsafe impl<'a> $crate::access::WritePtr for $Type<'a> { }
-    };
-}
-
 macro_rules! godot_test {
     ($($test_name:ident $body:block)*) => {
         $(
@@ -407,6 +364,18 @@ macro_rules! godot_test {
 
                 ok
             }
+
+            #[cfg(test)]
+            mod $test_name {
+                #[allow(unused_imports)]
+                use super::*;
+
+                #[test]
+                fn run() {
+                    $crate::mock::install();
+                    $body
+                }
+            }
         )*
     }
 }
@@ -462,6 +431,52 @@ macro_rules! godot_wrap_method_parameter_count {
     }
 }
 
+#[doc(hidden)]
+#[macro_export]
+macro_rules! godot_wrap_method_default {
+    () => {
+        Default::default()
+    };
+    ($default:expr) => {
+        $default
+    };
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! godot_wrap_method_argument {
+    ($args:ident, $offset:ident, $pname:ident : $pty:ty) => {{
+        let _variant: &$crate::Variant = ::std::mem::transmute(&mut **($args.offset($offset)));
+        match <$pty as $crate::FromVariant>::from_variant(_variant) {
+            Ok(val) => val,
+            Err(err) => {
+                godot_error!(
+                    "Cannot convert argument #{idx} ({name}: {ty}): {err}",
+                    idx = $offset + 1,
+                    name = stringify!($pname),
+                    ty = stringify!($pty),
+                    err = err,
+                );
+                return $crate::Variant::new().to_sys();
+            }
+        }
+    }};
+}
+
+#[doc(hidden)]
+#[macro_export]
+macro_rules! godot_wrap_method_return {
+    ($ret:ident, $type_name:ty, $method_name:ident) => {{
+        #[allow(unused_imports)]
+        use $crate::method_return::{MethodReturn, ReturnResult, ReturnToVariant};
+
+        (&&MethodReturn($ret)).to_return_variant(
+            <$type_name as $crate::NativeClass>::class_name(),
+            stringify!($method_name),
+        )
+    }};
+}
+
 #[doc(hidden)]
 #[macro_export]
 macro_rules! godot_wrap_method_inner {
@@ -472,7 +487,9 @@ macro_rules! godot_wrap_method_inner {
             $self:ident,
             $owner:ident : $owner_ty:ty
             $(,$pname:ident : $pty:ty)*
-        ) -> $retty:ty
+            $(, #[opt $(= $odefault:expr)?] $oname:ident : $oty:ty)*
+            $(, #[varargs] $rest:ident : $rest_ty:ty)?
+        ) -> $($retty:tt)+
     ) => {
         {
             #[allow(unused_unsafe, unused_variables, unused_assignments, unused_mut)]
@@ -490,41 +507,62 @@ macro_rules! godot_wrap_method_inner {
                 let __instance: Instance<$type_name> = Instance::from_raw(this, user_data);
 
                 let num_params = godot_wrap_method_parameter_count!($($pname,)*);
-                if num_args != num_params {
-                    godot_error!("Incorrect number of parameters: expected {} but got {}", num_params, num_args);
+                let num_optional = godot_wrap_method_parameter_count!($($oname,)*);
+                let has_varargs = godot_wrap_method_parameter_count!($($rest,)?) > 0;
+                if num_args < num_params || (!has_varargs && num_args > num_params + num_optional) {
+                    if has_varargs {
+                        godot_error!("Incorrect number of parameters: expected at least {} but got {}", num_params, num_args);
+                    } else if num_optional > 0 {
+                        godot_error!("Incorrect number of parameters: expected {} to {} but got {}", num_params, num_params + num_optional, num_args);
+                    } else {
+                        godot_error!("Incorrect number of parameters: expected {} but got {}", num_params, num_args);
+                    }
                     return $crate::Variant::new().to_sys();
                 }
 
                 let mut offset = 0;
                 $(
-                    let _variant: &$crate::Variant = ::std::mem::transmute(&mut **(args.offset(offset)));
-                    let $pname = match <$pty as $crate::FromVariant>::from_variant(_variant) {
-                        Ok(val) => val,
-                        Err(err) => {
-                            godot_error!(
-                                "Cannot convert argument #{idx} ({name}) to {ty}: {err} (non-primitive types may impose structural checks)",
-                                idx = offset + 1,
-                                name = stringify!($pname),
-                                ty = stringify!($pty),
-                                err = err,
-                            );
-                            return $crate::Variant::new().to_sys();
-                        },
-                    };
+                    let $pname = godot_wrap_method_argument!(args, offset, $pname : $pty);
+                    offset += 1;
+                )*
 
+                // Omitted trailing arguments take their default values.
+                $(
+                    let $oname: $oty = if offset < num_args as isize {
+                        godot_wrap_method_argument!(args, offset, $oname : $oty)
+                    } else {
+                        godot_wrap_method_default!($($odefault)?)
+                    };
                     offset += 1;
                 )*
 
+                $(
+                    let __rest = (offset..num_args as isize)
+                        .map(|idx| {
+                            let _variant: &$crate::Variant = ::std::mem::transmute(&mut **(args.offset(idx)));
+                            _variant.clone()
+                        })
+                        .collect::<Vec<_>>();
+                    let $rest: $rest_ty = &__rest[..];
+                )?
+
+                $crate::unwind::install_hook();
                 let rust_ret = match panic::catch_unwind(AssertUnwindSafe(move || {
                     let ret = __instance.$map_method(|__rust_val, $owner| {
-                        let ret = __rust_val.$method_name($owner, $($pname,)*);
-                        <$retty as $crate::ToVariant>::to_variant(&ret)
+                        let ret = __rust_val.$method_name($owner, $($pname,)* $($oname,)* $($rest,)?);
+                        godot_wrap_method_return!(ret, $type_name, $method_name)
                     });
                     std::mem::drop(__instance);
                     ret
                 })) {
                     Ok(val) => val,
                     Err(err) => {
+                        godot_error!(
+                            "{}::{} panicked: {}",
+                            <$type_name as $crate::NativeClass>::class_name(),
+                            stringify!($method_name),
+                            $crate::unwind::describe(&*err),
+                        );
                         return $crate::Variant::new().to_sys();
                     }
                 };
@@ -546,6 +584,22 @@ macro_rules! godot_wrap_method_inner {
 
 /// Convenience macro to wrap an object's method into a function pointer
 /// that can be passed to the engine when registering a class.
+///
+/// Trailing parameters marked with `#[opt]` may be omitted by the caller, in which case they
+/// are set to `Default::default()`, or to the given expression with `#[opt = expr]`. A final
+/// parameter of type `&[Variant]` marked with `#[varargs]` receives all remaining arguments.
+///
+/// Methods returning `Result<T, E>` where `E: Display`, including aliases like `io::Result<T>`,
+/// return `T` to the caller, or log the error with `godot_error!` and return nil. Other return
+/// types, including `Result`s whose error only implements `ToVariant`, are converted with
+/// `ToVariant`. Panics are caught and logged with their message and location.
+///
+/// ```ignore
+/// godot_wrap_method!(
+///     Foo,
+///     fn bar(&self, owner: Node, a: i64, #[opt] b: i64, #[opt = 10] c: i64, #[varargs] rest: &[Variant])
+/// )
+/// ```
 #[macro_export]
 macro_rules! godot_wrap_method {
     // mutable
@@ -555,8 +609,10 @@ macro_rules! godot_wrap_method {
             &mut $self:ident,
             $owner:ident : $owner_ty:ty
             $(,$pname:ident : $pty:ty)*
+            $(, #[opt $(= $odefault:expr)?] $oname:ident : $oty:ty)*
+            $(, #[varargs] $rest:ident : $rest_ty:ty)?
             $(,)?
-        ) -> $retty:ty
+        ) -> $($retty:tt)+
     ) => {
         godot_wrap_method_inner!(
             $type_name,
@@ -565,7 +621,9 @@ macro_rules! godot_wrap_method {
                 $self,
                 $owner: $owner_ty
                 $(,$pname : $pty)*
-            ) -> $retty
+                $(, #[opt $(= $odefault)?] $oname : $oty)*
+                $(, #[varargs] $rest : $rest_ty)?
+            ) -> $($retty)+
         )
     };
     // immutable
@@ -575,8 +633,10 @@ macro_rules! godot_wrap_method {
             & $self:ident,
             $owner:ident : $owner_ty:ty
             $(,$pname:ident : $pty:ty)*
+            $(, #[opt $(= $odefault:expr)?] $oname:ident : $oty:ty)*
+            $(, #[varargs] $rest:ident : $rest_ty:ty)?
             $(,)?
-        ) -> $retty:ty
+        ) -> $($retty:tt)+
     ) => {
         godot_wrap_method_inner!(
             $type_name,
@@ -585,7 +645,9 @@ macro_rules! godot_wrap_method {
                 $self,
                 $owner: $owner_ty
                 $(,$pname : $pty)*
-            ) -> $retty
+                $(, #[opt $(= $odefault)?] $oname : $oty)*
+                $(, #[varargs] $rest : $rest_ty)?
+            ) -> $($retty)+
         )
     };
     // mutable without return type
@@ -595,6 +657,8 @@ macro_rules! godot_wrap_method {
             &mut $self:ident,
             $owner:ident : $owner_ty:ty
             $(,$pname:ident : $pty:ty)*
+            $(, #[opt $(= $odefault:expr)?] $oname:ident : $oty:ty)*
+            $(, #[varargs] $rest:ident : $rest_ty:ty)?
             $(,)?
         )
     ) => {
@@ -604,6 +668,8 @@ macro_rules! godot_wrap_method {
                 &mut $self,
                 $owner: $owner_ty
                 $(,$pname : $pty)*
+                $(, #[opt $(= $odefault)?] $oname : $oty)*
+                $(, #[varargs] $rest : $rest_ty)?
             ) -> ()
         )
     };
@@ -614,6 +680,8 @@ macro_rules! godot_wrap_method {
             & $self:ident,
             $owner:ident : $owner_ty:ty
             $(,$pname:ident : $pty:ty)*
+            $(, #[opt $(= $odefault:expr)?] $oname:ident : $oty:ty)*
+            $(, #[varargs] $rest:ident : $rest_ty:ty)?
             $(,)?
         )
     ) => {
@@ -623,6 +691,8 @@ macro_rules! godot_wrap_method {
                 & $self,
                 $owner: $owner_ty
                 $(,$pname : $pty)*
+                $(, #[opt $(= $odefault)?] $oname : $oty)*
+                $(, #[varargs] $rest : $rest_ty)?
             ) -> ()
         )
     };
@@ -0,0 +1,760 @@
+//! The binary format of `var2bytes`, `bytes2var` and the network and file APIs of the engine.
+//!
+//! Every value starts with a 32-bit header holding the variant type in its lowest byte and flags
+//! in the upper bytes. Numbers are little-endian and strings are UTF-8, padded with zeros to a
+//! multiple of 4 bytes.
+
+use super::{ObjectData, ObjectValue, Value, MAX_DEPTH};
+use crate::{
+    Aabb, Basis, Color, Plane, Quat, Transform, Transform2D, VariantType, Vector2, Vector3,
+};
+use std::convert::TryFrom;
+use std::fmt;
+
+const HEADER_TYPE_MASK: u32 = 0xff;
+/// Set for integers and reals that need 64 bits.
+const HEADER_FLAG_64: u32 = 1 << 16;
+/// Set for objects encoded by their instance ID.
+const HEADER_FLAG_OBJECT_AS_ID: u32 = 1 << 16;
+
+/// Set in the name count of node paths, to distinguish them from the old format which was a
+/// plain string.
+const NODE_PATH_NEW_FORMAT: u32 = 0x8000_0000;
+const NODE_PATH_ABSOLUTE: u32 = 1;
+
+/// The engine may set this bit in the length of arrays and dictionaries.
+const COLLECTION_SHARED: u32 = 0x8000_0000;
+
+/// Error returned when decoding an invalid buffer.
+#[derive(Copy, Clone, Debug, PartialEq, Eq)]
+pub enum DecodeError {
+    /// The buffer ends in the middle of a value.
+    UnexpectedEof,
+    /// A header holds a type that is not a variant type.
+    InvalidType(u32),
+    /// A string is not valid UTF-8.
+    InvalidUtf8,
+    /// Arrays, dictionaries or objects are nested too deeply.
+    TooDeep,
+}
+
+impl fmt::Display for DecodeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            DecodeError::UnexpectedEof => write!(f, "unexpected end of buffer"),
+            DecodeError::InvalidType(ty) => write!(f, "invalid variant type: {}", ty),
+            DecodeError::InvalidUtf8 => write!(f, "string is not valid UTF-8"),
+            DecodeError::TooDeep => write!(f, "values are nested too deeply"),
+        }
+    }
+}
+
+impl std::error::Error for DecodeError {}
+
+/// Encodes a value like `var2bytes` does.
+///
+/// Objects are encoded by instance ID or with their properties, depending on the variant of
+/// [`ObjectValue`](enum.ObjectValue.html).
+pub fn var2bytes(value: &Value) -> Vec<u8> {
+    let mut buf = Vec::new();
+    encode_variant(value, &mut buf);
+    buf
+}
+
+/// Decodes a value encoded by `var2bytes`. Bytes after the value are ignored, like in the
+/// engine.
+pub fn bytes2var(bytes: &[u8]) -> Result<Value, DecodeError> {
+    decode_variant(bytes).map(|(value, _)| value)
+}
+
+/// Appends the encoding of `value` to `buf`.
+pub fn encode_variant(value: &Value, buf: &mut Vec<u8>) {
+    let ty = value.get_type() as u32;
+
+    match value {
+        Value::Nil => put_u32(buf, ty),
+        Value::Bool(b) => {
+            put_u32(buf, ty);
+            put_u32(buf, *b as u32);
+        }
+        Value::I64(i) => {
+            if let Ok(i) = i32::try_from(*i) {
+                put_u32(buf, ty);
+                buf.extend_from_slice(&i.to_le_bytes());
+            } else {
+                put_u32(buf, ty | HEADER_FLAG_64);
+                buf.extend_from_slice(&i.to_le_bytes());
+            }
+        }
+        Value::F64(f) => {
+            // NaN never compares equal, so it is always written with 64 bits like in the engine.
+            if f64::from(*f as f32) == *f {
+                put_u32(buf, ty);
+                put_f32(buf, *f as f32);
+            } else {
+                put_u32(buf, ty | HEADER_FLAG_64);
+                buf.extend_from_slice(&f.to_bits().to_le_bytes());
+            }
+        }
+        Value::GodotString(s) => {
+            put_u32(buf, ty);
+            put_string(buf, s);
+        }
+        Value::Vector2(v) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[v.x, v.y]);
+        }
+        Value::Rect2(r) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[r.origin.x, r.origin.y, r.size.width, r.size.height]);
+        }
+        Value::Vector3(v) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[v.x, v.y, v.z]);
+        }
+        Value::Transform2D(t) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[t.m11, t.m12, t.m21, t.m22, t.m31, t.m32]);
+        }
+        Value::Plane(p) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[p.normal.x, p.normal.y, p.normal.z, p.d]);
+        }
+        Value::Quat(q) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[q.i, q.j, q.k, q.r]);
+        }
+        Value::Aabb(a) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[a.position.x, a.position.y, a.position.z]);
+            put_reals(buf, &[a.size.x, a.size.y, a.size.z]);
+        }
+        Value::Basis(b) => {
+            put_u32(buf, ty);
+            put_basis(buf, b);
+        }
+        Value::Transform(t) => {
+            put_u32(buf, ty);
+            put_basis(buf, &t.basis);
+            put_reals(buf, &[t.origin.x, t.origin.y, t.origin.z]);
+        }
+        Value::Color(c) => {
+            put_u32(buf, ty);
+            put_reals(buf, &[c.r, c.g, c.b, c.a]);
+        }
+        Value::NodePath(path) => {
+            put_u32(buf, ty);
+
+            let absolute = path.starts_with('/');
+            let mut parts = path.splitn(2, ':');
+            let names = parts.next().unwrap_or("").split('/');
+            let subnames = parts.next().unwrap_or("").split(':');
+            let names: Vec<&str> = names.filter(|name| !name.is_empty()).collect();
+            let subnames: Vec<&str> = subnames.filter(|name| !name.is_empty()).collect();
+
+            put_u32(buf, names.len() as u32 | NODE_PATH_NEW_FORMAT);
+            put_u32(buf, subnames.len() as u32);
+            put_u32(buf, if absolute { NODE_PATH_ABSOLUTE } else { 0 });
+            for name in names.iter().chain(&subnames) {
+                put_string(buf, name);
+            }
+        }
+        Value::Rid => put_u32(buf, ty),
+        Value::Object(ObjectValue::Id(id)) => {
+            put_u32(buf, ty | HEADER_FLAG_OBJECT_AS_ID);
+            buf.extend_from_slice(&id.to_le_bytes());
+        }
+        Value::Object(ObjectValue::Full(None)) => {
+            put_u32(buf, ty);
+            put_string(buf, "");
+        }
+        Value::Object(ObjectValue::Full(Some(data))) => {
+            put_u32(buf, ty);
+            put_string(buf, &data.class);
+            put_u32(buf, data.properties.len() as u32);
+            for (name, value) in &data.properties {
+                put_string(buf, name);
+                encode_variant(value, buf);
+            }
+        }
+        Value::Dictionary(entries) => {
+            put_u32(buf, ty);
+            put_u32(buf, entries.len() as u32);
+            for (key, value) in entries {
+                encode_variant(key, buf);
+                encode_variant(value, buf);
+            }
+        }
+        Value::VariantArray(values) => {
+            put_u32(buf, ty);
+            put_u32(buf, values.len() as u32);
+            for value in values {
+                encode_variant(value, buf);
+            }
+        }
+        Value::ByteArray(bytes) => {
+            put_u32(buf, ty);
+            put_u32(buf, bytes.len() as u32);
+            buf.extend_from_slice(bytes);
+            pad(buf, bytes.len());
+        }
+        Value::Int32Array(ints) => {
+            put_u32(buf, ty);
+            put_u32(buf, ints.len() as u32);
+            for int in ints {
+                buf.extend_from_slice(&int.to_le_bytes());
+            }
+        }
+        Value::Float32Array(floats) => {
+            put_u32(buf, ty);
+            put_u32(buf, floats.len() as u32);
+            put_reals(buf, floats);
+        }
+        Value::StringArray(strings) => {
+            put_u32(buf, ty);
+            put_u32(buf, strings.len() as u32);
+            for s in strings {
+                // Unlike other strings, these include their null terminator.
+                put_u32(buf, s.len() as u32 + 1);
+                buf.extend_from_slice(s.as_bytes());
+                buf.push(0);
+                pad(buf, s.len() + 1);
+            }
+        }
+        Value::Vector2Array(vectors) => {
+            put_u32(buf, ty);
+            put_u32(buf, vectors.len() as u32);
+            for v in vectors {
+                put_reals(buf, &[v.x, v.y]);
+            }
+        }
+        Value::Vector3Array(vectors) => {
+            put_u32(buf, ty);
+            put_u32(buf, vectors.len() as u32);
+            for v in vectors {
+                put_reals(buf, &[v.x, v.y, v.z]);
+            }
+        }
+        Value::ColorArray(colors) => {
+            put_u32(buf, ty);
+            put_u32(buf, colors.len() as u32);
+            for c in colors {
+                put_reals(buf, &[c.r, c.g, c.b, c.a]);
+            }
+        }
+    }
+}
+
+/// Decodes the value at the start of `bytes`, returning it with the number of bytes it used.
+pub fn decode_variant(bytes: &[u8]) -> Result<(Value, usize), DecodeError> {
+    let mut reader = Reader {
+        bytes,
+        pos: 0,
+        depth: 0,
+    };
+    let value = reader.variant()?;
+    Ok((value, reader.pos))
+}
+
+fn put_u32(buf: &mut Vec<u8>, val: u32) {
+    buf.extend_from_slice(&val.to_le_bytes());
+}
+
+fn put_f32(buf: &mut Vec<u8>, val: f32) {
+    put_u32(buf, val.to_bits());
+}
+
+fn put_reals(buf: &mut Vec<u8>, reals: &[f32]) {
+    for real in reals {
+        put_f32(buf, *real);
+    }
+}
+
+fn put_basis(buf: &mut Vec<u8>, basis: &Basis) {
+    for row in &basis.elements {
+        put_reals(buf, &[row.x, row.y, row.z]);
+    }
+}
+
+fn put_string(buf: &mut Vec<u8>, s: &str) {
+    put_u32(buf, s.len() as u32);
+    buf.extend_from_slice(s.as_bytes());
+    pad(buf, s.len());
+}
+
+/// Pads data of `len` bytes to a multiple of 4.
+fn pad(buf: &mut Vec<u8>, len: usize) {
+    buf.resize(buf.len() + (4 - len % 4) % 4, 0);
+}
+
+struct Reader<'a> {
+    bytes: &'a [u8],
+    pos: usize,
+    depth: usize,
+}
+
+impl<'a> Reader<'a> {
+    fn variant(&mut self) -> Result<Value, DecodeError> {
+        let header = self.u32()?;
+        let ty = header & HEADER_TYPE_MASK;
+        if ty > VariantType::ColorArray as u32 {
+            return Err(DecodeError::InvalidType(ty));
+        }
+
+        let value = match VariantType::from_sys(ty as crate::sys::godot_variant_type) {
+            VariantType::Nil => Value::Nil,
+            VariantType::Bool => Value::Bool(self.u32()? != 0),
+            VariantType::I64 => {
+                if header & HEADER_FLAG_64 != 0 {
+                    Value::I64(self.u64()? as i64)
+                } else {
+                    Value::I64(i64::from(self.u32()? as i32))
+                }
+            }
+            VariantType::F64 => {
+                if header & HEADER_FLAG_64 != 0 {
+                    Value::F64(f64::from_bits(self.u64()?))
+                } else {
+                    Value::F64(f64::from(self.f32()?))
+                }
+            }
+            VariantType::GodotString => {
+                let len = self.u32()? as usize;
+                Value::GodotString(self.string(len)?)
+            }
+            VariantType::Vector2 => Value::Vector2(self.vector2()?),
+            VariantType::Rect2 => {
+                let [x, y, width, height] = self.reals()?;
+                Value::Rect2(euclid::rect(x, y, width, height))
+            }
+            VariantType::Vector3 => Value::Vector3(self.vector3()?),
+            VariantType::Transform2D => {
+                let [m11, m12, m21, m22, m31, m32] = self.reals()?;
+                Value::Transform2D(Transform2D::row_major(m11, m12, m21, m22, m31, m32))
+            }
+            VariantType::Plane => {
+                let [x, y, z, d] = self.reals()?;
+                Value::Plane(Plane::new(Vector3::new(x, y, z), d))
+            }
+            VariantType::Quat => {
+                let [x, y, z, w] = self.reals()?;
+                Value::Quat(Quat::quaternion(x, y, z, w))
+            }
+            VariantType::Aabb => Value::Aabb(Aabb::new(self.vector3()?, self.vector3()?)),
+            VariantType::Basis => Value::Basis(self.basis()?),
+            VariantType::Transform => {
+                Value::Transform(Transform::new(self.basis()?, self.vector3()?))
+            }
+            VariantType::Color => Value::Color(self.color()?),
+            VariantType::NodePath => Value::NodePath(self.node_path()?),
+            VariantType::Rid => Value::Rid,
+            VariantType::Object => {
+                if header & HEADER_FLAG_OBJECT_AS_ID != 0 {
+                    Value::Object(ObjectValue::Id(self.u64()?))
+                } else {
+                    Value::Object(ObjectValue::Full(self.object()?))
+                }
+            }
+            VariantType::Dictionary => {
+                let count = self.u32()? & !COLLECTION_SHARED;
+                let entries =
+                    self.nested(count, |reader| Ok((reader.variant()?, reader.variant()?)))?;
+                Value::Dictionary(entries)
+            }
+            VariantType::VariantArray => {
+                let count = self.u32()? & !COLLECTION_SHARED;
+                Value::VariantArray(self.nested(count, Reader::variant)?)
+            }
+            VariantType::ByteArray => {
+                let len = self.u32()? as usize;
+                let bytes = self.padded(len)?.to_vec();
+                Value::ByteArray(bytes)
+            }
+            VariantType::Int32Array => {
+                let count = self.count(4)?;
+                Value::Int32Array(self.repeat(count, |reader| Ok(reader.u32()? as i32))?)
+            }
+            VariantType::Float32Array => {
+                let count = self.count(4)?;
+                Value::Float32Array(self.repeat(count, Reader::f32)?)
+            }
+            VariantType::StringArray => {
+                let count = self.count(4)?;
+                Value::StringArray(self.repeat(count, |reader| {
+                    let len = reader.u32()? as usize;
+                    reader.string(len)
+                })?)
+            }
+            VariantType::Vector2Array => {
+                let count = self.count(8)?;
+                Value::Vector2Array(self.repeat(count, Reader::vector2)?)
+            }
+            VariantType::Vector3Array => {
+                let count = self.count(12)?;
+                Value::Vector3Array(self.repeat(count, Reader::vector3)?)
+            }
+            VariantType::ColorArray => {
+                let count = self.count(16)?;
+                Value::ColorArray(self.repeat(count, Reader::color)?)
+            }
+        };
+
+        Ok(value)
+    }
+
+    fn take(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        if self.bytes.len() - self.pos < len {
+            return Err(DecodeError::UnexpectedEof);
+        }
+
+        let bytes = &self.bytes[self.pos..self.pos + len];
+        self.pos += len;
+        Ok(bytes)
+    }
+
+    /// Takes `len` bytes followed by padding to a multiple of 4.
+    fn padded(&mut self, len: usize) -> Result<&'a [u8], DecodeError> {
+        let padding = (4 - len % 4) % 4;
+        let bytes = self.take(len.checked_add(padding).ok_or(DecodeError::UnexpectedEof)?)?;
+        Ok(&bytes[..len])
+    }
+
+    fn u32(&mut self) -> Result<u32, DecodeError> {
+        let mut bytes = [0; 4];
+        bytes.copy_from_slice(self.take(4)?);
+        Ok(u32::from_le_bytes(bytes))
+    }
+
+    fn u64(&mut self) -> Result<u64, DecodeError> {
+        let mut bytes = [0; 8];
+        bytes.copy_from_slice(self.take(8)?);
+        Ok(u64::from_le_bytes(bytes))
+    }
+
+    fn f32(&mut self) -> Result<f32, DecodeError> {
+        self.u32().map(f32::from_bits)
+    }
+
+    fn reals<A: Default + AsMut<[f32]>>(&mut self) -> Result<A, DecodeError> {
+        let mut reals = A::default();
+        for real in reals.as_mut() {
+            *real = self.f32()?;
+        }
+        Ok(reals)
+    }
+
+    fn vector2(&mut self) -> Result<Vector2, DecodeError> {
+        let [x, y] = self.reals()?;
+        Ok(Vector2::new(x, y))
+    }
+
+    fn vector3(&mut self) -> Result<Vector3, DecodeError> {
+        let [x, y, z] = self.reals()?;
+        Ok(Vector3::new(x, y, z))
+    }
+
+    fn basis(&mut self) -> Result<Basis, DecodeError> {
+        Ok(Basis {
+            elements: [self.vector3()?, self.vector3()?, self.vector3()?],
+        })
+    }
+
+    fn color(&mut self) -> Result<Color, DecodeError> {
+        let [r, g, b, a] = self.reals()?;
+        Ok(Color::rgba(r, g, b, a))
+    }
+
+    /// Reads a string of `len` bytes. Like the engine, the string ends at the first null byte.
+    fn string(&mut self, len: usize) -> Result<String, DecodeError> {
+        let bytes = self.padded(len)?;
+        let end = bytes.iter().position(|b| *b == 0).unwrap_or(len);
+        String::from_utf8(bytes[..end].to_vec()).map_err(|_| DecodeError::InvalidUtf8)
+    }
+
+    fn node_path(&mut self) -> Result<String, DecodeError> {
+        let name_count = self.u32()?;
+        if name_count & NODE_PATH_NEW_FORMAT == 0 {
+            return self.string(name_count as usize);
+        }
+
+        let name_count = name_count & !NODE_PATH_NEW_FORMAT;
+        let subname_count = self.u32()?;
+        let flags = self.u32()?;
+
+        let mut path = String::new();
+        if flags & NODE_PATH_ABSOLUTE != 0 {
+            path.push('/');
+        }
+
+        for i in 0..name_count {
+            if i > 0 {
+                path.push('/');
+            }
+            let len = self.u32()? as usize;
+            path += &self.string(len)?;
+        }
+
+        for _ in 0..subname_count {
+            path.push(':');
+            let len = self.u32()? as usize;
+            path += &self.string(len)?;
+        }
+
+        Ok(path)
+    }
+
+    fn object(&mut self) -> Result<Option<ObjectData>, DecodeError> {
+        let len = self.u32()? as usize;
+        let class = self.string(len)?;
+        if class.is_empty() {
+            return Ok(None);
+        }
+
+        let count = self.u32()?;
+        let properties = self.nested(count, |reader| {
+            let len = reader.u32()? as usize;
+            Ok((reader.string(len)?, reader.variant()?))
+        })?;
+
+        Ok(Some(ObjectData { class, properties }))
+    }
+
+    /// Reads an element count, checking that the buffer can hold that many elements of `size`
+    /// bytes before anything is allocated.
+    fn count(&mut self, size: usize) -> Result<usize, DecodeError> {
+        let count = self.u32()? as usize;
+        if count.saturating_mul(size) > self.bytes.len() - self.pos {
+            return Err(DecodeError::UnexpectedEof);
+        }
+        Ok(count)
+    }
+
+    fn repeat<T, F>(&mut self, count: usize, mut read: F) -> Result<Vec<T>, DecodeError>
+    where
+        F: FnMut(&mut Self) -> Result<T, DecodeError>,
+    {
+        let mut values = Vec::with_capacity(count);
+        for _ in 0..count {
+            values.push(read(self)?);
+        }
+        Ok(values)
+    }
+
+    /// Reads `count` elements that contain variants. Every variant takes at least 4 bytes, which
+    /// bounds the allocation.
+    fn nested<T, F>(&mut self, count: u32, read: F) -> Result<Vec<T>, DecodeError>
+    where
+        F: FnMut(&mut Self) -> Result<T, DecodeError>,
+    {
+        if self.depth == MAX_DEPTH {
+            return Err(DecodeError::TooDeep);
+        }
+
+        let count = count as usize;
+        if count.saturating_mul(4) > self.bytes.len() - self.pos {
+            return Err(DecodeError::UnexpectedEof);
+        }
+
+        self.depth += 1;
+        let values = self.repeat(count, read);
+        self.depth -= 1;
+        values
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(value: Value, bytes: &[u8]) {
+        assert_eq!(bytes, &var2bytes(&value)[..]);
+        assert_eq!(Ok((value, bytes.len())), decode_variant(bytes));
+    }
+
+    // The expected bytes are the output of `var2bytes` in Godot 3.2.
+
+    #[test]
+    fn scalars() {
+        check(Value::Nil, &[0, 0, 0, 0]);
+        check(Value::Bool(true), &[1, 0, 0, 0, 1, 0, 0, 0]);
+        check(Value::I64(42), &[2, 0, 0, 0, 42, 0, 0, 0]);
+        check(Value::I64(-2), &[2, 0, 0, 0, 254, 255, 255, 255]);
+        check(Value::I64(1 << 40), &[2, 0, 1, 0, 0, 0, 0, 0, 0, 1, 0, 0]);
+        check(Value::F64(1.5), &[3, 0, 0, 0, 0, 0, 192, 63]);
+        check(
+            Value::F64(0.1),
+            &[3, 0, 1, 0, 154, 153, 153, 153, 153, 153, 185, 63],
+        );
+        check(Value::Rid, &[16, 0, 0, 0]);
+    }
+
+    #[test]
+    fn strings() {
+        check(
+            Value::GodotString("abc".into()),
+            &[4, 0, 0, 0, 3, 0, 0, 0, 97, 98, 99, 0],
+        );
+        check(
+            Value::GodotString("éa".into()),
+            &[4, 0, 0, 0, 3, 0, 0, 0, 195, 169, 97, 0],
+        );
+        check(Value::GodotString("".into()), &[4, 0, 0, 0, 0, 0, 0, 0]);
+        check(
+            Value::StringArray(vec!["ab".into(), "".into()]),
+            &[
+                23, 0, 0, 0, 2, 0, 0, 0, 3, 0, 0, 0, 97, 98, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0,
+            ],
+        );
+    }
+
+    #[test]
+    fn math_types() {
+        check(
+            Value::Vector2(Vector2::new(1.0, 2.0)),
+            &[5, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 64],
+        );
+        check(
+            Value::Rect2(euclid::rect(1.0, 2.0, 0.0, -2.0)),
+            &[
+                6, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 64, 0, 0, 0, 0, 0, 0, 0, 192,
+            ],
+        );
+        check(
+            Value::Color(Color::rgba(1.0, 0.0, 0.5, 1.0)),
+            &[
+                14, 0, 0, 0, 0, 0, 128, 63, 0, 0, 0, 0, 0, 0, 0, 63, 0, 0, 128, 63,
+            ],
+        );
+
+        let mut expected = vec![13, 0, 0, 0];
+        for real in &[
+            1.0f32, 0.0, 0.0, 0.0, 1.0, 0.0, 0.0, 0.0, 1.0, 2.0, 3.0, 4.0,
+        ] {
+            expected.extend_from_slice(&real.to_le_bytes());
+        }
+        check(
+            Value::Transform(Transform::new(
+                Basis::identity(),
+                Vector3::new(2.0, 3.0, 4.0),
+            )),
+            &expected,
+        );
+    }
+
+    #[test]
+    fn node_paths() {
+        check(
+            Value::NodePath("/root/a:b".into()),
+            &[
+                15, 0, 0, 0, 2, 0, 0, 128, 1, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 114, 111, 111, 116,
+                1, 0, 0, 0, 97, 0, 0, 0, 1, 0, 0, 0, 98, 0, 0, 0,
+            ],
+        );
+        check(
+            Value::NodePath(":x".into()),
+            &[
+                15, 0, 0, 0, 0, 0, 0, 128, 1, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 120, 0, 0, 0,
+            ],
+        );
+
+        // Node paths used to be written as plain strings.
+        assert_eq!(
+            Ok(Value::NodePath("a/b".into())),
+            bytes2var(&[15, 0, 0, 0, 3, 0, 0, 0, 97, 47, 98, 0])
+        );
+    }
+
+    #[test]
+    fn objects() {
+        check(
+            Value::Object(ObjectValue::Id(1234)),
+            &[17, 0, 1, 0, 210, 4, 0, 0, 0, 0, 0, 0],
+        );
+        check(
+            Value::Object(ObjectValue::Full(None)),
+            &[17, 0, 0, 0, 0, 0, 0, 0],
+        );
+        check(
+            Value::Object(ObjectValue::Full(Some(ObjectData {
+                class: "Node".into(),
+                properties: vec![("a".into(), Value::I64(1))],
+            }))),
+            &[
+                17, 0, 0, 0, 4, 0, 0, 0, 78, 111, 100, 101, 1, 0, 0, 0, 1, 0, 0, 0, 97, 0, 0, 0, 2,
+                0, 0, 0, 1, 0, 0, 0,
+            ],
+        );
+    }
+
+    #[test]
+    fn collections() {
+        check(
+            Value::Dictionary(vec![(Value::GodotString("a".into()), Value::I64(1))]),
+            &[
+                18, 0, 0, 0, 1, 0, 0, 0, 4, 0, 0, 0, 1, 0, 0, 0, 97, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0,
+                0,
+            ],
+        );
+        check(
+            Value::VariantArray(vec![Value::Nil, Value::Bool(false)]),
+            &[19, 0, 0, 0, 2, 0, 0, 0, 0, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0],
+        );
+        check(
+            Value::ByteArray(vec![1, 2, 3]),
+            &[20, 0, 0, 0, 3, 0, 0, 0, 1, 2, 3, 0],
+        );
+        check(
+            Value::Int32Array(vec![1, -1]),
+            &[21, 0, 0, 0, 2, 0, 0, 0, 1, 0, 0, 0, 255, 255, 255, 255],
+        );
+        check(
+            Value::Vector2Array(vec![Vector2::new(0.0, 1.0)]),
+            &[24, 0, 0, 0, 1, 0, 0, 0, 0, 0, 0, 0, 0, 0, 128, 63],
+        );
+
+        // Shared arrays have the high bit of their size set.
+        assert_eq!(
+            Ok(Value::VariantArray(vec![Value::Nil])),
+            bytes2var(&[19, 0, 0, 0, 1, 0, 0, 128, 0, 0, 0, 0])
+        );
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(Err(DecodeError::UnexpectedEof), bytes2var(&[]));
+        assert_eq!(Err(DecodeError::UnexpectedEof), bytes2var(&[2, 0, 0, 0, 1]));
+        assert_eq!(
+            Err(DecodeError::UnexpectedEof),
+            bytes2var(&[4, 0, 0, 0, 3, 0, 0, 0, 97, 98, 99])
+        );
+        assert_eq!(
+            Err(DecodeError::UnexpectedEof),
+            bytes2var(&[21, 0, 0, 0, 255, 255, 255, 255])
+        );
+        assert_eq!(Err(DecodeError::InvalidType(27)), bytes2var(&[27, 0, 0, 0]));
+        assert_eq!(
+            Err(DecodeError::InvalidUtf8),
+            bytes2var(&[4, 0, 0, 0, 1, 0, 0, 0, 255, 0, 0, 0])
+        );
+
+        let mut nested = Vec::new();
+        for _ in 0..=MAX_DEPTH {
+            nested.extend_from_slice(&[19, 0, 0, 0, 1, 0, 0, 0]);
+        }
+        nested.extend_from_slice(&[0, 0, 0, 0]);
+        assert_eq!(Err(DecodeError::TooDeep), bytes2var(&nested));
+    }
+
+    #[test]
+    fn padding_does_not_depend_on_the_buffer() {
+        let mut buf = vec![42];
+        encode_variant(&Value::GodotString("a".into()), &mut buf);
+        assert_eq!(&[42, 4, 0, 0, 0, 1, 0, 0, 0, 97, 0, 0, 0], &buf[..]);
+    }
+
+    #[test]
+    fn trailing_bytes_are_ignored() {
+        let bytes = [1, 0, 0, 0, 1, 0, 0, 0, 42, 42];
+        assert_eq!(Ok((Value::Bool(true), 8)), decode_variant(&bytes));
+    }
+}
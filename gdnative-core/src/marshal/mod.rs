@@ -0,0 +1,336 @@
+//! Engine-independent variant values and their serialized forms.
+//!
+//! [`Value`](enum.Value.html) mirrors what a `Variant` can hold using plain Rust types, so that
+//! variants can be built, inspected and serialized in processes that never load the engine, like
+//! a game server talking to Godot clients. Values convert to and from `Variant` with
+//! `ToVariant` and `FromVariant` when the engine is available.
+//!
+//! [`var2bytes`](fn.var2bytes.html) and [`bytes2var`](fn.bytes2var.html) implement the binary
+//! format of the GDScript functions of the same name, which is also used by `PacketPeer`,
+//! `StreamPeer.put_var` and `File.store_var`. [`var2str`](fn.var2str.html) and
+//! [`str2var`](fn.str2var.html) implement the text format that appears in `.tscn`, `.tres` and
+//! `project.godot` files, e.g. `Vector2( 1, 2 )` or `{ "a": [ 1, 2 ] }`.
+//!
+//! ```ignore
+//! use gdnative::marshal::{bytes2var, var2bytes, Value};
+//!
+//! let value = Value::VariantArray(vec![Value::I64(42), Value::GodotString("foo".into())]);
+//! let bytes = var2bytes(&value);
+//! assert_eq!(Ok(value), bytes2var(&bytes));
+//! ```
+//!
+//! # Serde
+//!
+//! With the `serde` feature, `Variant` and `Value` serialize as an externally tagged enum named
+//! after the variant type, e.g. `{"I64":42}` or `{"Vector2":[1.0,2.0]}` in JSON, so that any value
+//! survives a round trip, including in formats that are not self-describing like bincode.
+//! Dictionaries serialize as maps with variant keys, which JSON can't represent: use a format
+//! with arbitrary map keys, like RON, MessagePack or bincode, for them. RIDs and objects are only
+//! meaningful within one engine instance and can't be serialized.
+//!
+//! The other types serialize as their contents: `GodotString` and `NodePath` as strings,
+//! `VariantArray` and the pool arrays as sequences, `Dictionary` as a map, and `Color`, `Plane`,
+//! `Aabb`, `Basis` and `Transform` as structs with their fields. The euclid types don't implement
+//! serde traits themselves. Inside variants, `Vector2`, `Vector3` and `Quat` are tuples of their
+//! components, `Rect2` is `(position, size)` and `Transform2D` is `(x, y, origin)`.
+//!
+//! In the other direction, `Value` implements `Deserializer`, which presents values in their
+//! natural shape instead: strings as strings, arrays as sequences, dictionaries as maps, and math
+//! types in the shape they serialize to. [`from_variant`](fn.from_variant.html) and
+//! [`Deserialized`](struct.Deserialized.html) use it to read any `Deserialize` type from a
+//! variant, such as an argument of an exported method.
+
+use crate::*;
+
+mod binary;
+#[cfg(feature = "serde")]
+pub(crate) mod serialize;
+mod text;
+
+pub use self::binary::{bytes2var, decode_variant, encode_variant, var2bytes, DecodeError};
+#[cfg(all(feature = "serde", feature = "gd_test"))]
+#[doc(hidden)]
+pub use self::serialize::test_marshal_from_variant;
+#[cfg(feature = "serde")]
+pub use self::serialize::{from_variant, DeserializeError, Deserialized};
+pub use self::text::{str2var, var2str, ParseError};
+
+/// Nesting depth after which decoding stops, so that malicious input cannot overflow the stack.
+const MAX_DEPTH: usize = 512;
+
+/// An engine-independent copy of a variant value.
+///
+/// Collections own their contents, unlike the reference-counted collections of the engine.
+/// Dictionaries keep their entries in order, as the engine does.
+#[derive(Clone, Debug, PartialEq)]
+pub enum Value {
+    Nil,
+    Bool(bool),
+    I64(i64),
+    F64(f64),
+    GodotString(String),
+    Vector2(Vector2),
+    Rect2(Rect2),
+    Vector3(Vector3),
+    Transform2D(Transform2D),
+    Plane(Plane),
+    Quat(Quat),
+    Aabb(Aabb),
+    Basis(Basis),
+    Transform(Transform),
+    Color(Color),
+    /// A node path in its textual form, e.g. `"/root/Node:position:x"`.
+    NodePath(String),
+    /// A resource ID. RIDs are only meaningful within one engine instance and carry no data.
+    Rid,
+    Object(ObjectValue),
+    Dictionary(Vec<(Value, Value)>),
+    VariantArray(Vec<Value>),
+    ByteArray(Vec<u8>),
+    Int32Array(Vec<i32>),
+    Float32Array(Vec<f32>),
+    StringArray(Vec<String>),
+    Vector2Array(Vec<Vector2>),
+    Vector3Array(Vec<Vector3>),
+    ColorArray(Vec<Color>),
+}
+
+/// An object held by a [`Value`](enum.Value.html).
+#[derive(Clone, Debug, PartialEq)]
+pub enum ObjectValue {
+    /// An object referred to by its instance ID, which is `0` for null objects. This is how
+    /// `var2bytes` encodes objects by default.
+    Id(u64),
+    /// An object encoded with its class and stored properties, as done by
+    /// `var2bytes(value, true)`. `None` is a null object.
+    Full(Option<ObjectData>),
+}
+
+/// The class and stored properties of an object, as encoded by `var2bytes(value, true)`.
+#[derive(Clone, Debug, PartialEq)]
+pub struct ObjectData {
+    pub class: String,
+    pub properties: Vec<(String, Value)>,
+}
+
+impl Value {
+    /// Returns the type of the variant this value corresponds to.
+    pub fn get_type(&self) -> VariantType {
+        match self {
+            Value::Nil => VariantType::Nil,
+            Value::Bool(_) => VariantType::Bool,
+            Value::I64(_) => VariantType::I64,
+            Value::F64(_) => VariantType::F64,
+            Value::GodotString(_) => VariantType::GodotString,
+            Value::Vector2(_) => VariantType::Vector2,
+            Value::Rect2(_) => VariantType::Rect2,
+            Value::Vector3(_) => VariantType::Vector3,
+            Value::Transform2D(_) => VariantType::Transform2D,
+            Value::Plane(_) => VariantType::Plane,
+            Value::Quat(_) => VariantType::Quat,
+            Value::Aabb(_) => VariantType::Aabb,
+            Value::Basis(_) => VariantType::Basis,
+            Value::Transform(_) => VariantType::Transform,
+            Value::Color(_) => VariantType::Color,
+            Value::NodePath(_) => VariantType::NodePath,
+            Value::Rid => VariantType::Rid,
+            Value::Object(_) => VariantType::Object,
+            Value::Dictionary(_) => VariantType::Dictionary,
+            Value::VariantArray(_) => VariantType::VariantArray,
+            Value::ByteArray(_) => VariantType::ByteArray,
+            Value::Int32Array(_) => VariantType::Int32Array,
+            Value::Float32Array(_) => VariantType::Float32Array,
+            Value::StringArray(_) => VariantType::StringArray,
+            Value::Vector2Array(_) => VariantType::Vector2Array,
+            Value::Vector3Array(_) => VariantType::Vector3Array,
+            Value::ColorArray(_) => VariantType::ColorArray,
+        }
+    }
+
+    /// Returns true if this is `Value::Nil`.
+    pub fn is_nil(&self) -> bool {
+        *self == Value::Nil
+    }
+}
+
+/// Objects can only be looked up by the engine, so `Value::Object` converts to a nil variant.
+impl ToVariant for Value {
+    fn to_variant(&self) -> Variant {
+        match self {
+            Value::Nil | Value::Object(_) => Variant::new(),
+            Value::Bool(b) => Variant::from_bool(*b),
+            Value::I64(i) => Variant::from_i64(*i),
+            Value::F64(f) => Variant::from_f64(*f),
+            Value::GodotString(s) => Variant::from_str(s),
+            Value::Vector2(v) => Variant::from_vector2(v),
+            Value::Rect2(r) => Variant::from_rect2(r),
+            Value::Vector3(v) => Variant::from_vector3(v),
+            Value::Transform2D(t) => Variant::from_transform2d(t),
+            Value::Plane(p) => Variant::from_plane(p),
+            Value::Quat(q) => Variant::from_quat(q),
+            Value::Aabb(a) => Variant::from_aabb(a),
+            Value::Basis(b) => Variant::from_basis(b),
+            Value::Transform(t) => Variant::from_transform(t),
+            Value::Color(c) => Variant::from_color(c),
+            Value::NodePath(p) => Variant::from_node_path(&NodePath::from_str(p)),
+            Value::Rid => Variant::from_rid(&Rid::new()),
+            Value::Dictionary(entries) => {
+                let mut dict = Dictionary::new();
+                for (key, value) in entries {
+                    dict.set(&key.to_variant(), &value.to_variant());
+                }
+                Variant::from_dictionary(&dict)
+            }
+            Value::VariantArray(values) => {
+                let mut array = VariantArray::new();
+                for value in values {
+                    array.push(&value.to_variant());
+                }
+                Variant::from_array(&array)
+            }
+            Value::ByteArray(bytes) => Variant::from_byte_array(&ByteArray::from_slice(bytes)),
+            Value::Int32Array(ints) => Variant::from_int32_array(&Int32Array::from_slice(ints)),
+            Value::Float32Array(floats) => {
+                Variant::from_float32_array(&Float32Array::from_slice(floats))
+            }
+            Value::StringArray(strings) => {
+                let array = strings.iter().map(GodotString::from_str).collect();
+                Variant::from_string_array(&array)
+            }
+            Value::Vector2Array(vectors) => {
+                Variant::from_vector2_array(&Vector2Array::from_slice(vectors))
+            }
+            Value::Vector3Array(vectors) => {
+                Variant::from_vector3_array(&Vector3Array::from_slice(vectors))
+            }
+            Value::ColorArray(colors) => Variant::from_color_array(&ColorArray::from_slice(colors)),
+        }
+    }
+}
+
+/// Objects are converted to `ObjectValue::Id` with their instance ID. This never fails.
+impl FromVariant for Value {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let value = match variant.get_type() {
+            VariantType::Nil => Value::Nil,
+            VariantType::Bool => Value::Bool(variant.to_bool()),
+            VariantType::I64 => Value::I64(variant.to_i64()),
+            VariantType::F64 => Value::F64(variant.to_f64()),
+            VariantType::GodotString => Value::GodotString(variant.to_string()),
+            VariantType::Vector2 => Value::Vector2(variant.to_vector2()),
+            VariantType::Rect2 => Value::Rect2(variant.to_rect2()),
+            VariantType::Vector3 => Value::Vector3(variant.to_vector3()),
+            VariantType::Transform2D => Value::Transform2D(variant.to_transform2d()),
+            VariantType::Plane => Value::Plane(variant.to_plane()),
+            VariantType::Quat => Value::Quat(variant.to_quat()),
+            VariantType::Aabb => Value::Aabb(variant.to_aabb()),
+            VariantType::Basis => Value::Basis(variant.to_basis()),
+            VariantType::Transform => Value::Transform(variant.to_transform()),
+            VariantType::Color => Value::Color(variant.to_color()),
+            VariantType::NodePath => Value::NodePath(variant.to_node_path().to_string()),
+            VariantType::Rid => Value::Rid,
+            VariantType::Object => Value::Object(ObjectValue::Id(instance_id(variant))),
+            VariantType::Dictionary => {
+                let dict = variant.to_dictionary();
+                let entries = dict
+                    .keys()
+                    .iter()
+                    .map(|key| {
+                        let key_value = Value::from_variant(key)?;
+                        Ok((key_value, Value::from_variant(dict.get_ref(key))?))
+                    })
+                    .collect::<Result<_, FromVariantError>>()?;
+                Value::Dictionary(entries)
+            }
+            VariantType::VariantArray => Value::VariantArray(
+                variant
+                    .to_array()
+                    .iter()
+                    .map(Value::from_variant)
+                    .collect::<Result<_, _>>()?,
+            ),
+            VariantType::ByteArray => Value::ByteArray(variant.to_byte_array().to_vec()),
+            VariantType::Int32Array => Value::Int32Array(variant.to_int32_array().to_vec()),
+            VariantType::Float32Array => Value::Float32Array(variant.to_float32_array().to_vec()),
+            VariantType::StringArray => Value::StringArray(
+                variant
+                    .to_string_array()
+                    .iter()
+                    .map(|s| s.to_string())
+                    .collect(),
+            ),
+            VariantType::Vector2Array => Value::Vector2Array(variant.to_vector2_array().to_vec()),
+            VariantType::Vector3Array => Value::Vector3Array(variant.to_vector3_array().to_vec()),
+            VariantType::ColorArray => Value::ColorArray(variant.to_color_array().to_vec()),
+        };
+
+        Ok(value)
+    }
+}
+
+fn instance_id(variant: &Variant) -> u64 {
+    unsafe {
+        let obj = (get_api().godot_variant_as_object)(&variant.0);
+        if obj.is_null() {
+            0
+        } else {
+            Object::from_sys(obj).get_instance_id() as u64
+        }
+    }
+}
+
+godot_test!(test_marshal_value_round_trip {
+    let values = vec![
+        Value::Nil,
+        Value::Bool(true),
+        Value::I64(-7),
+        Value::F64(0.5),
+        Value::GodotString("héllo".into()),
+        Value::Vector2(Vector2::new(1.0, 2.0)),
+        Value::Rect2(euclid::rect(1.0, 2.0, 3.0, 4.0)),
+        Value::Vector3(Vector3::new(1.0, 2.0, 3.0)),
+        Value::Transform2D(Transform2D::row_major(1.0, 2.0, 3.0, 4.0, 5.0, 6.0)),
+        Value::Plane(Plane::new(Vector3::new(0.0, 1.0, 0.0), 2.0)),
+        Value::Quat(Quat::quaternion(0.0, 0.0, 0.0, 1.0)),
+        Value::Aabb(Aabb::new(Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0))),
+        Value::Basis(Basis::from_diagonal(Vector3::new(1.0, 2.0, 3.0))),
+        Value::Transform(Transform::new(Basis::identity(), Vector3::new(1.0, 2.0, 3.0))),
+        Value::Color(Color::rgba(0.25, 0.5, 0.75, 1.0)),
+        Value::NodePath("/root/Node:position:x".into()),
+        Value::Rid,
+        Value::Dictionary(vec![
+            (Value::I64(2), Value::VariantArray(vec![Value::Nil, Value::Bool(false)])),
+            (Value::GodotString("a".into()), Value::I64(1)),
+        ]),
+        Value::ByteArray(vec![1, 2, 3]),
+        Value::Int32Array(vec![-1, 0, 1]),
+        Value::Float32Array(vec![0.5, 1.5]),
+        Value::StringArray(vec!["a".into(), "".into(), "bc".into()]),
+        Value::Vector2Array(vec![Vector2::new(1.0, 2.0)]),
+        Value::Vector3Array(vec![Vector3::new(1.0, 2.0, 3.0)]),
+        Value::ColorArray(vec![Color::rgb(1.0, 0.0, 0.0)]),
+    ];
+
+    for value in values {
+        let variant = value.to_variant();
+        assert_eq!(value.get_type(), variant.get_type());
+        assert_eq!(Ok(value.clone()), Value::from_variant(&variant));
+
+        let bytes = var2bytes(&value);
+        assert_eq!(Ok(value.clone()), bytes2var(&bytes));
+
+        let text = var2str(&value);
+        assert_eq!(Ok(value), str2var(&text));
+    }
+
+    let null_object = unsafe {
+        let mut dest = sys::godot_variant::default();
+        (get_api().godot_variant_new_object)(&mut dest, std::ptr::null_mut());
+        Variant::from_sys(dest)
+    };
+    assert_eq!(
+        Ok(Value::Object(ObjectValue::Id(0))),
+        Value::from_variant(&null_object)
+    );
+    assert!(Value::Object(ObjectValue::Id(0)).to_variant().is_nil());
+});
@@ -0,0 +1,983 @@
+//! Serde support, enabled by the `serde` feature. The data model is described in the
+//! documentation of the parent module.
+
+use super::Value;
+use crate::*;
+use serde::de::value::{MapDeserializer, SeqDeserializer};
+use serde::de::{self, DeserializeOwned, IntoDeserializer};
+use serde::ser;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::fmt;
+
+/// Name of the tagged enum that values serialize as.
+const ENUM_NAME: &str = "Variant";
+
+/// Variant names of the tagged enum, in the order of `VariantType`, so that formats encoding
+/// variants by index agree with `Serialize`.
+const TAGS: &[&str] = &[
+    "Nil",
+    "Bool",
+    "I64",
+    "F64",
+    "GodotString",
+    "Vector2",
+    "Rect2",
+    "Vector3",
+    "Transform2D",
+    "Plane",
+    "Quat",
+    "Aabb",
+    "Basis",
+    "Transform",
+    "Color",
+    "NodePath",
+    "Rid",
+    "Object",
+    "Dictionary",
+    "VariantArray",
+    "ByteArray",
+    "Int32Array",
+    "Float32Array",
+    "StringArray",
+    "Vector2Array",
+    "Vector3Array",
+    "ColorArray",
+];
+
+#[derive(Deserialize)]
+#[serde(variant_identifier)]
+enum Tag {
+    Nil,
+    Bool,
+    I64,
+    F64,
+    GodotString,
+    Vector2,
+    Rect2,
+    Vector3,
+    Transform2D,
+    Plane,
+    Quat,
+    Aabb,
+    Basis,
+    Transform,
+    Color,
+    NodePath,
+    Rid,
+    Object,
+    Dictionary,
+    VariantArray,
+    ByteArray,
+    Int32Array,
+    Float32Array,
+    StringArray,
+    Vector2Array,
+    Vector3Array,
+    ColorArray,
+}
+
+// Representations of the euclid types, which don't implement serde traits without the `serde`
+// feature of euclid.
+
+struct V2(Vector2);
+
+impl Serialize for V2 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0.x, self.0.y).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for V2 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (x, y) = Deserialize::deserialize(deserializer)?;
+        Ok(V2(Vector2::new(x, y)))
+    }
+}
+
+struct V3(Vector3);
+
+impl Serialize for V3 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0.x, self.0.y, self.0.z).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for V3 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (x, y, z) = Deserialize::deserialize(deserializer)?;
+        Ok(V3(Vector3::new(x, y, z)))
+    }
+}
+
+struct Q(Quat);
+
+impl Serialize for Q {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        (self.0.i, self.0.j, self.0.k, self.0.r).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Q {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (x, y, z, w) = Deserialize::deserialize(deserializer)?;
+        Ok(Q(Quat::quaternion(x, y, z, w)))
+    }
+}
+
+/// `Rect2` as `(position, size)`.
+struct R2(Rect2);
+
+impl Serialize for R2 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let rect = &self.0;
+        (V2(rect.origin.to_vector()), V2(rect.size.to_vector())).serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for R2 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (V2(position), V2(size)) = Deserialize::deserialize(deserializer)?;
+        Ok(R2(Rect2::new(position.to_point(), size.to_size())))
+    }
+}
+
+/// `Transform2D` as `(x, y, origin)`, the columns of the matrix in the engine.
+struct T2(Transform2D);
+
+impl Serialize for T2 {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let t = &self.0;
+        (
+            V2(Vector2::new(t.m11, t.m12)),
+            V2(Vector2::new(t.m21, t.m22)),
+            V2(Vector2::new(t.m31, t.m32)),
+        )
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for T2 {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let (V2(x), V2(y), V2(o)) = Deserialize::deserialize(deserializer)?;
+        Ok(T2(Transform2D::row_major(x.x, x.y, y.x, y.y, o.x, o.y)))
+    }
+}
+
+/// `#[serde(with)]` module for `Vector3` fields.
+pub(crate) mod vector3 {
+    use super::V3;
+    use crate::Vector3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(vector: &Vector3, serializer: S) -> Result<S::Ok, S::Error> {
+        V3(*vector).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Vector3, D::Error> {
+        V3::deserialize(deserializer).map(|v| v.0)
+    }
+}
+
+/// `#[serde(with)]` module for the rows of a `Basis`.
+pub(crate) mod rows {
+    use super::V3;
+    use crate::Vector3;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(rows: &[Vector3; 3], serializer: S) -> Result<S::Ok, S::Error> {
+        (V3(rows[0]), V3(rows[1]), V3(rows[2])).serialize(serializer)
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(
+        deserializer: D,
+    ) -> Result<[Vector3; 3], D::Error> {
+        let (V3(x), V3(y), V3(z)) = Deserialize::deserialize(deserializer)?;
+        Ok([x, y, z])
+    }
+}
+
+/// Serializes a slice as a sequence, converting each element first.
+struct SeqOf<'a, T, R>(&'a [T], fn(&T) -> R);
+
+impl<'a, T, R: Serialize> Serialize for SeqOf<'a, T, R> {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.0.iter().map(self.1))
+    }
+}
+
+/// Dictionary entries, which serialize as a map without losing their order.
+struct Entries(Vec<(Value, Value)>);
+
+impl<'de> Deserialize<'de> for Entries {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct EntriesVisitor;
+
+        impl<'de> de::Visitor<'de> for EntriesVisitor {
+            type Value = Entries;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Entries, A::Error> {
+                let mut entries = Vec::with_capacity(map.size_hint().unwrap_or(0));
+                while let Some(entry) = map.next_entry()? {
+                    entries.push(entry);
+                }
+                Ok(Entries(entries))
+            }
+        }
+
+        deserializer.deserialize_map(EntriesVisitor)
+    }
+}
+
+impl Serialize for Value {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        let index = self.get_type() as u32;
+        let tag = TAGS[index as usize];
+
+        macro_rules! newtype {
+            ($value:expr) => {
+                serializer.serialize_newtype_variant(ENUM_NAME, index, tag, $value)
+            };
+        }
+
+        match self {
+            Value::Nil => serializer.serialize_unit_variant(ENUM_NAME, index, tag),
+            Value::Bool(b) => newtype!(b),
+            Value::I64(i) => newtype!(i),
+            Value::F64(f) => newtype!(f),
+            Value::GodotString(s) => newtype!(s),
+            Value::Vector2(v) => newtype!(&V2(*v)),
+            Value::Rect2(r) => newtype!(&R2(*r)),
+            Value::Vector3(v) => newtype!(&V3(*v)),
+            Value::Transform2D(t) => newtype!(&T2(*t)),
+            Value::Plane(p) => newtype!(p),
+            Value::Quat(q) => newtype!(&Q(*q)),
+            Value::Aabb(a) => newtype!(a),
+            Value::Basis(b) => newtype!(b),
+            Value::Transform(t) => newtype!(t),
+            Value::Color(c) => newtype!(c),
+            Value::NodePath(p) => newtype!(p),
+            Value::Rid => Err(ser::Error::custom("RIDs cannot be serialized")),
+            Value::Object(_) => Err(ser::Error::custom("objects cannot be serialized")),
+            Value::Dictionary(entries) => {
+                struct EntriesRef<'a>(&'a [(Value, Value)]);
+
+                impl<'a> Serialize for EntriesRef<'a> {
+                    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                        serializer.collect_map(self.0.iter().map(|(key, value)| (key, value)))
+                    }
+                }
+
+                newtype!(&EntriesRef(entries))
+            }
+            Value::VariantArray(values) => newtype!(values),
+            Value::ByteArray(bytes) => newtype!(bytes),
+            Value::Int32Array(ints) => newtype!(ints),
+            Value::Float32Array(floats) => newtype!(floats),
+            Value::StringArray(strings) => newtype!(strings),
+            Value::Vector2Array(vectors) => newtype!(&SeqOf(vectors, |v| V2(*v))),
+            Value::Vector3Array(vectors) => newtype!(&SeqOf(vectors, |v| V3(*v))),
+            Value::ColorArray(colors) => newtype!(colors),
+        }
+    }
+}
+
+impl<'de> Deserialize<'de> for Value {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct ValueVisitor;
+
+        impl<'de> de::Visitor<'de> for ValueVisitor {
+            type Value = Value;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a variant")
+            }
+
+            fn visit_enum<A: de::EnumAccess<'de>>(self, data: A) -> Result<Value, A::Error> {
+                use serde::de::VariantAccess;
+
+                let (tag, access) = data.variant()?;
+                let value = match tag {
+                    Tag::Nil => {
+                        access.unit_variant()?;
+                        Value::Nil
+                    }
+                    Tag::Bool => Value::Bool(access.newtype_variant()?),
+                    Tag::I64 => Value::I64(access.newtype_variant()?),
+                    Tag::F64 => Value::F64(access.newtype_variant()?),
+                    Tag::GodotString => Value::GodotString(access.newtype_variant()?),
+                    Tag::Vector2 => Value::Vector2(access.newtype_variant::<V2>()?.0),
+                    Tag::Rect2 => Value::Rect2(access.newtype_variant::<R2>()?.0),
+                    Tag::Vector3 => Value::Vector3(access.newtype_variant::<V3>()?.0),
+                    Tag::Transform2D => Value::Transform2D(access.newtype_variant::<T2>()?.0),
+                    Tag::Plane => Value::Plane(access.newtype_variant()?),
+                    Tag::Quat => Value::Quat(access.newtype_variant::<Q>()?.0),
+                    Tag::Aabb => Value::Aabb(access.newtype_variant()?),
+                    Tag::Basis => Value::Basis(access.newtype_variant()?),
+                    Tag::Transform => Value::Transform(access.newtype_variant()?),
+                    Tag::Color => Value::Color(access.newtype_variant()?),
+                    Tag::NodePath => Value::NodePath(access.newtype_variant()?),
+                    Tag::Rid => return Err(de::Error::custom("RIDs cannot be deserialized")),
+                    Tag::Object => return Err(de::Error::custom("objects cannot be deserialized")),
+                    Tag::Dictionary => Value::Dictionary(access.newtype_variant::<Entries>()?.0),
+                    Tag::VariantArray => Value::VariantArray(access.newtype_variant()?),
+                    Tag::ByteArray => Value::ByteArray(access.newtype_variant()?),
+                    Tag::Int32Array => Value::Int32Array(access.newtype_variant()?),
+                    Tag::Float32Array => Value::Float32Array(access.newtype_variant()?),
+                    Tag::StringArray => Value::StringArray(access.newtype_variant()?),
+                    Tag::Vector2Array => Value::Vector2Array(
+                        access
+                            .newtype_variant::<Vec<V2>>()?
+                            .into_iter()
+                            .map(|v| v.0)
+                            .collect(),
+                    ),
+                    Tag::Vector3Array => Value::Vector3Array(
+                        access
+                            .newtype_variant::<Vec<V3>>()?
+                            .into_iter()
+                            .map(|v| v.0)
+                            .collect(),
+                    ),
+                    Tag::ColorArray => Value::ColorArray(access.newtype_variant()?),
+                };
+
+                Ok(value)
+            }
+        }
+
+        deserializer.deserialize_enum(ENUM_NAME, TAGS, ValueVisitor)
+    }
+}
+
+/// Error returned when a type can't be deserialized from a [`Value`](enum.Value.html).
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DeserializeError {
+    message: String,
+}
+
+impl fmt::Display for DeserializeError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(&self.message)
+    }
+}
+
+impl std::error::Error for DeserializeError {}
+
+impl de::Error for DeserializeError {
+    fn custom<T: fmt::Display>(msg: T) -> Self {
+        DeserializeError {
+            message: msg.to_string(),
+        }
+    }
+}
+
+fn reals(reals: &[f32]) -> Value {
+    Value::Float32Array(reals.to_vec())
+}
+
+fn fields(fields: Vec<(&str, Value)>) -> Value {
+    Value::Dictionary(
+        fields
+            .into_iter()
+            .map(|(name, value)| (Value::GodotString(name.into()), value))
+            .collect(),
+    )
+}
+
+impl Value {
+    /// Replaces math types with the arrays and dictionaries they serialize as.
+    fn into_content(self) -> Value {
+        let vector3 = |v: Vector3| reals(&[v.x, v.y, v.z]);
+        let basis = |b: Basis| {
+            let rows = b.elements.iter().map(|row| vector3(*row)).collect();
+            fields(vec![("elements", Value::VariantArray(rows))])
+        };
+
+        match self {
+            Value::Vector2(v) => reals(&[v.x, v.y]),
+            Value::Vector3(v) => vector3(v),
+            Value::Quat(q) => reals(&[q.i, q.j, q.k, q.r]),
+            Value::Rect2(r) => Value::VariantArray(vec![
+                reals(&[r.origin.x, r.origin.y]),
+                reals(&[r.size.width, r.size.height]),
+            ]),
+            Value::Transform2D(t) => Value::VariantArray(vec![
+                reals(&[t.m11, t.m12]),
+                reals(&[t.m21, t.m22]),
+                reals(&[t.m31, t.m32]),
+            ]),
+            Value::Plane(p) => fields(vec![
+                ("normal", vector3(p.normal)),
+                ("d", Value::F64(p.d.into())),
+            ]),
+            Value::Aabb(a) => fields(vec![
+                ("position", vector3(a.position)),
+                ("size", vector3(a.size)),
+            ]),
+            Value::Basis(b) => basis(b),
+            Value::Transform(t) => fields(vec![
+                ("basis", basis(t.basis)),
+                ("origin", vector3(t.origin)),
+            ]),
+            Value::Color(c) => fields(vec![
+                ("r", Value::F64(c.r.into())),
+                ("g", Value::F64(c.g.into())),
+                ("b", Value::F64(c.b.into())),
+                ("a", Value::F64(c.a.into())),
+            ]),
+            Value::Vector2Array(vectors) => {
+                Value::VariantArray(vectors.into_iter().map(Value::Vector2).collect())
+            }
+            Value::Vector3Array(vectors) => {
+                Value::VariantArray(vectors.into_iter().map(Value::Vector3).collect())
+            }
+            Value::ColorArray(colors) => {
+                Value::VariantArray(colors.into_iter().map(Value::Color).collect())
+            }
+            other => other,
+        }
+    }
+}
+
+fn visit_seq<'de, I, V>(items: I, visitor: V) -> Result<V::Value, DeserializeError>
+where
+    I: IntoIterator,
+    I::Item: IntoDeserializer<'de, DeserializeError>,
+    V: de::Visitor<'de>,
+{
+    let mut seq = SeqDeserializer::new(items.into_iter());
+    let value = visitor.visit_seq(&mut seq)?;
+    seq.end()?;
+    Ok(value)
+}
+
+impl<'de> Deserializer<'de> for Value {
+    type Error = DeserializeError;
+
+    fn deserialize_any<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::Nil => visitor.visit_unit(),
+            Value::Bool(b) => visitor.visit_bool(b),
+            Value::I64(i) => visitor.visit_i64(i),
+            Value::F64(f) => visitor.visit_f64(f),
+            Value::GodotString(s) | Value::NodePath(s) => visitor.visit_string(s),
+            Value::Rid => Err(de::Error::custom("RIDs cannot be deserialized")),
+            Value::Object(_) => Err(de::Error::custom("objects cannot be deserialized")),
+            Value::Dictionary(entries) => {
+                let mut map = MapDeserializer::new(entries.into_iter());
+                let value = visitor.visit_map(&mut map)?;
+                map.end()?;
+                Ok(value)
+            }
+            Value::VariantArray(values) => visit_seq(values, visitor),
+            Value::ByteArray(bytes) => visit_seq(bytes, visitor),
+            Value::Int32Array(ints) => visit_seq(ints, visitor),
+            Value::Float32Array(floats) => visit_seq(floats, visitor),
+            Value::StringArray(strings) => visit_seq(strings, visitor),
+            other => other.into_content().deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_option<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        if self.is_nil() {
+            visitor.visit_none()
+        } else {
+            visitor.visit_some(self)
+        }
+    }
+
+    fn deserialize_bytes<V: de::Visitor<'de>>(self, visitor: V) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::ByteArray(bytes) => visitor.visit_byte_buf(bytes),
+            other => other.deserialize_any(visitor),
+        }
+    }
+
+    fn deserialize_byte_buf<V: de::Visitor<'de>>(
+        self,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.deserialize_bytes(visitor)
+    }
+
+    fn deserialize_newtype_struct<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        visitor.visit_newtype_struct(self)
+    }
+
+    /// Enums are read from a string for unit variants, or from a dictionary with a single entry
+    /// that maps the variant name to its contents.
+    fn deserialize_enum<V: de::Visitor<'de>>(
+        self,
+        _name: &'static str,
+        _variants: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        match self {
+            Value::GodotString(variant) => visitor.visit_enum(variant.into_deserializer()),
+            Value::Dictionary(mut entries) if entries.len() == 1 => {
+                let (variant, value) = entries.pop().unwrap();
+                visitor.visit_enum(EnumDeserializer { variant, value })
+            }
+            other => Err(de::Error::custom(format!(
+                "expected a string or a dictionary with a single entry for an enum, found {:?}",
+                other.get_type()
+            ))),
+        }
+    }
+
+    serde::forward_to_deserialize_any! {
+        bool i8 i16 i32 i64 u8 u16 u32 u64 f32 f64 char str string unit unit_struct seq tuple
+        tuple_struct map struct identifier ignored_any
+    }
+}
+
+impl<'de> IntoDeserializer<'de, DeserializeError> for Value {
+    type Deserializer = Self;
+
+    fn into_deserializer(self) -> Self {
+        self
+    }
+}
+
+struct EnumDeserializer {
+    variant: Value,
+    value: Value,
+}
+
+impl<'de> de::EnumAccess<'de> for EnumDeserializer {
+    type Error = DeserializeError;
+    type Variant = VariantDeserializer;
+
+    fn variant_seed<T: de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<(T::Value, Self::Variant), Self::Error> {
+        let variant = seed.deserialize(self.variant)?;
+        Ok((variant, VariantDeserializer(self.value)))
+    }
+}
+
+struct VariantDeserializer(Value);
+
+impl<'de> de::VariantAccess<'de> for VariantDeserializer {
+    type Error = DeserializeError;
+
+    fn unit_variant(self) -> Result<(), Self::Error> {
+        Deserialize::deserialize(self.0)
+    }
+
+    fn newtype_variant_seed<T: de::DeserializeSeed<'de>>(
+        self,
+        seed: T,
+    ) -> Result<T::Value, Self::Error> {
+        seed.deserialize(self.0)
+    }
+
+    fn tuple_variant<V: de::Visitor<'de>>(
+        self,
+        _len: usize,
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_any(visitor)
+    }
+
+    fn struct_variant<V: de::Visitor<'de>>(
+        self,
+        _fields: &'static [&'static str],
+        visitor: V,
+    ) -> Result<V::Value, Self::Error> {
+        self.0.deserialize_any(visitor)
+    }
+}
+
+/// Deserializes a `T` from the contents of a variant.
+///
+/// See the [module documentation](index.html) for how variants are presented to `T`.
+pub fn from_variant<T: DeserializeOwned>(variant: &Variant) -> Result<T, DeserializeError> {
+    let value = Value::from_variant(variant).map_err(de::Error::custom)?;
+    T::deserialize(value)
+}
+
+/// A wrapper that converts from variants with serde, for `Deserialize` types that don't
+/// implement `FromVariant`, like arguments of exported methods:
+///
+/// ```ignore
+/// #[export]
+/// fn set_config(&mut self, _owner: Node, config: Deserialized<Config>) {
+///     self.config = config.0;
+/// }
+/// ```
+#[derive(Copy, Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct Deserialized<T>(pub T);
+
+impl<T: DeserializeOwned> FromVariant for Deserialized<T> {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        from_variant(variant)
+            .map(Deserialized)
+            .map_err(|err| FromVariantError::Custom(err.to_string()))
+    }
+}
+
+// Engine types, which serialize through `Value`.
+
+impl Serialize for Variant {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        Value::from_variant(self)
+            .map_err(ser::Error::custom)?
+            .serialize(serializer)
+    }
+}
+
+impl<'de> Deserialize<'de> for Variant {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        Value::deserialize(deserializer).map(|value| value.to_variant())
+    }
+}
+
+impl Serialize for Dictionary {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        use serde::ser::SerializeMap;
+
+        let keys = self.keys();
+        let mut map = serializer.serialize_map(Some(keys.len() as usize))?;
+        for key in keys.iter() {
+            map.serialize_entry(key, self.get_ref(key))?;
+        }
+        map.end()
+    }
+}
+
+impl<'de> Deserialize<'de> for Dictionary {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        struct DictionaryVisitor;
+
+        impl<'de> de::Visitor<'de> for DictionaryVisitor {
+            type Value = Dictionary;
+
+            fn expecting(&self, f: &mut fmt::Formatter) -> fmt::Result {
+                f.write_str("a map")
+            }
+
+            fn visit_map<A: de::MapAccess<'de>>(self, mut map: A) -> Result<Dictionary, A::Error> {
+                let mut dict = Dictionary::new();
+                while let Some((key, value)) = map.next_entry::<Variant, Variant>()? {
+                    dict.set(&key, &value);
+                }
+                Ok(dict)
+            }
+        }
+
+        deserializer.deserialize_map(DictionaryVisitor)
+    }
+}
+
+impl Serialize for VariantArray {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(self.iter())
+    }
+}
+
+impl<'de> Deserialize<'de> for VariantArray {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        let mut array = VariantArray::new();
+        for value in Vec::<Variant>::deserialize(deserializer)? {
+            array.push(&value);
+        }
+        Ok(array)
+    }
+}
+
+impl Serialize for GodotString {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for GodotString {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(GodotString::from_str)
+    }
+}
+
+impl Serialize for NodePath {
+    fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.serialize_str(&self.to_string())
+    }
+}
+
+impl<'de> Deserialize<'de> for NodePath {
+    fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+        String::deserialize(deserializer).map(|path| NodePath::from_str(&path))
+    }
+}
+
+macro_rules! impl_serde_for_pool_arrays {
+    ($($Array:ident<$T:ty, $Repr:ty>($to_repr:expr, $push:expr),)*) => {
+        $(
+            impl Serialize for $Array {
+                fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+                    let to_repr: fn(&$T) -> $Repr = $to_repr;
+                    serializer.collect_seq(self.read().iter().map(to_repr))
+                }
+            }
+
+            impl<'de> Deserialize<'de> for $Array {
+                fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+                    let push: fn(&mut $Array, $Repr) = $push;
+                    let mut array = $Array::new();
+                    for elem in Vec::<$Repr>::deserialize(deserializer)? {
+                        push(&mut array, elem);
+                    }
+                    Ok(array)
+                }
+            }
+        )*
+    };
+}
+
+impl_serde_for_pool_arrays! {
+    ByteArray<u8, u8>(|b| *b, |array, b| array.push(&b)),
+    Int32Array<i32, i32>(|i| *i, |array, i| array.push(&i)),
+    Float32Array<f32, f32>(|f| *f, |array, f| array.push(&f)),
+    StringArray<GodotString, String>(GodotString::to_string, |array, s| {
+        array.push(&GodotString::from_str(s))
+    }),
+    Vector2Array<Vector2, V2>(|v| V2(*v), |array, v| array.push(&v.0)),
+    Vector3Array<Vector3, V3>(|v| V3(*v), |array, v| array.push(&v.0)),
+    ColorArray<Color, Color>(|c| *c, |array, c| array.push(&c)),
+}
+
+godot_test!(test_marshal_from_variant {
+    #[derive(Debug, PartialEq, Deserialize)]
+    struct Config {
+        name: String,
+        speed: f32,
+        lives: Option<u8>,
+        spawn: (f32, f32),
+        tint: Color,
+        mode: Mode,
+    }
+
+    #[derive(Debug, PartialEq, Deserialize)]
+    enum Mode {
+        Easy,
+        Custom { waves: Vec<u16> },
+    }
+
+    let mut dict = Dictionary::new();
+    dict.set(&"name".into(), &"foo".into());
+    dict.set(&"speed".into(), &Variant::from_f64(1.5));
+    dict.set(&"lives".into(), &Variant::new());
+    dict.set(&"spawn".into(), &Variant::from_vector2(&Vector2::new(1.0, 2.0)));
+    dict.set(&"tint".into(), &Variant::from_color(&Color::rgb(1.0, 0.5, 0.0)));
+    dict.set(&"mode".into(), &"Easy".into());
+
+    let expected = Config {
+        name: "foo".into(),
+        speed: 1.5,
+        lives: None,
+        spawn: (1.0, 2.0),
+        tint: Color::rgb(1.0, 0.5, 0.0),
+        mode: Mode::Easy,
+    };
+    let variant = Variant::from_dictionary(&dict);
+    assert_eq!(Ok(expected), from_variant::<Config>(&variant));
+
+    let mut waves = Int32Array::new();
+    waves.push(&3);
+    waves.push(&5);
+    let mut custom = Dictionary::new();
+    custom.set(&"waves".into(), &Variant::from_int32_array(&waves));
+    let mut mode = Dictionary::new();
+    mode.set(&"Custom".into(), &Variant::from_dictionary(&custom));
+    dict.set(&"mode".into(), &Variant::from_dictionary(&mode));
+    dict.set(&"lives".into(), &Variant::from_i64(3));
+
+    let config = Deserialized::<Config>::from_variant(&Variant::from_dictionary(&dict))
+        .expect("should deserialize")
+        .0;
+    assert_eq!(Some(3), config.lives);
+    assert_eq!(Mode::Custom { waves: vec![3, 5] }, config.mode);
+
+    dict.set(&"lives".into(), &Variant::from_i64(300));
+    assert!(Deserialized::<Config>::from_variant(&Variant::from_dictionary(&dict)).is_err());
+});
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn json() {
+        let cases = vec![
+            (Value::Nil, r#""Nil""#),
+            (Value::I64(42), r#"{"I64":42}"#),
+            (Value::GodotString("foo".into()), r#"{"GodotString":"foo"}"#),
+            (
+                Value::Vector2(Vector2::new(1.0, 2.0)),
+                r#"{"Vector2":[1.0,2.0]}"#,
+            ),
+            (
+                Value::Rect2(euclid::rect(1.0, 2.0, 3.0, 4.0)),
+                r#"{"Rect2":[[1.0,2.0],[3.0,4.0]]}"#,
+            ),
+            (
+                Value::Plane(Plane::new(Vector3::new(0.0, 1.0, 0.0), 2.0)),
+                r#"{"Plane":{"normal":[0.0,1.0,0.0],"d":2.0}}"#,
+            ),
+            (
+                Value::Color(Color::rgba(0.25, 0.5, 0.75, 1.0)),
+                r#"{"Color":{"r":0.25,"g":0.5,"b":0.75,"a":1.0}}"#,
+            ),
+            (
+                Value::VariantArray(vec![Value::Bool(true), Value::F64(0.5)]),
+                r#"{"VariantArray":[{"Bool":true},{"F64":0.5}]}"#,
+            ),
+            (Value::ByteArray(vec![1, 2]), r#"{"ByteArray":[1,2]}"#),
+        ];
+
+        for (value, json) in cases {
+            assert_eq!(json, serde_json::to_string(&value).unwrap());
+            assert_eq!(value, serde_json::from_str(json).unwrap());
+        }
+
+        assert!(serde_json::to_string(&Value::Rid).is_err());
+        assert!(serde_json::from_str::<Value>(r#""Rid""#).is_err());
+    }
+
+    #[test]
+    fn bincode_round_trip() {
+        let value = Value::VariantArray(vec![
+            Value::Nil,
+            Value::F64(-0.5),
+            Value::Transform2D(Transform2D::row_major(1.0, 2.0, 3.0, 4.0, 5.0, 6.0)),
+            Value::Quat(Quat::quaternion(0.0, 0.0, 0.0, 1.0)),
+            Value::Transform(Transform::new(
+                Basis::identity(),
+                Vector3::new(1.0, 2.0, 3.0),
+            )),
+            Value::Aabb(Aabb::new(
+                Vector3::new(1.0, 2.0, 3.0),
+                Vector3::new(4.0, 5.0, 6.0),
+            )),
+            Value::NodePath("/root/Node:position:x".into()),
+            Value::Dictionary(vec![
+                (
+                    Value::I64(2),
+                    Value::StringArray(vec!["a".into(), "b".into()]),
+                ),
+                (
+                    Value::GodotString("a".into()),
+                    Value::Int32Array(vec![-1, 1]),
+                ),
+            ]),
+            Value::Vector2Array(vec![Vector2::new(1.0, 2.0)]),
+            Value::Vector3Array(vec![Vector3::new(1.0, 2.0, 3.0)]),
+            Value::ColorArray(vec![Color::rgb(1.0, 0.0, 0.0)]),
+        ]);
+
+        let bytes = bincode::serialize(&value).unwrap();
+        assert_eq!(value, bincode::deserialize(&bytes).unwrap());
+    }
+
+    #[test]
+    fn deserializer() {
+        #[derive(Debug, PartialEq, Deserialize)]
+        struct Player {
+            name: String,
+            transform: Transform,
+            bounds: (Vector2Tuple, Vector2Tuple),
+            data: Vec<u8>,
+        }
+
+        type Vector2Tuple = (f32, f32);
+
+        let transform = Transform::new(
+            Basis::from_diagonal(Vector3::new(1.0, 2.0, 3.0)),
+            Vector3::new(4.0, 5.0, 6.0),
+        );
+        let value = Value::Dictionary(vec![
+            (
+                Value::GodotString("name".into()),
+                Value::NodePath("foo".into()),
+            ),
+            (
+                Value::GodotString("transform".into()),
+                Value::Transform(transform),
+            ),
+            (
+                Value::GodotString("bounds".into()),
+                Value::Rect2(euclid::rect(1.0, 2.0, 3.0, 4.0)),
+            ),
+            (
+                Value::GodotString("data".into()),
+                Value::ByteArray(vec![1, 2]),
+            ),
+        ]);
+
+        let expected = Player {
+            name: "foo".into(),
+            transform,
+            bounds: ((1.0, 2.0), (3.0, 4.0)),
+            data: vec![1, 2],
+        };
+        assert_eq!(Ok(expected), Player::deserialize(value));
+
+        assert!(<(f32, f32)>::deserialize(Value::Vector3(Vector3::new(1.0, 2.0, 3.0))).is_err());
+        assert!(String::deserialize(Value::Rid).is_err());
+    }
+
+    #[test]
+    fn engine_types() {
+        crate::mock::install();
+
+        let mut dict = Dictionary::new();
+        dict.set(&"a".into(), &Variant::from_vector2(&Vector2::new(1.0, 2.0)));
+        dict.set(
+            &Variant::from_i64(1),
+            &Variant::from_node_path(&"/root".into()),
+        );
+        let bytes = bincode::serialize(&dict).unwrap();
+        let copy: Dictionary = bincode::deserialize(&bytes).unwrap();
+        assert_eq!(2, copy.len());
+        assert_eq!(Vector2::new(1.0, 2.0), copy.get(&"a".into()).to_vector2());
+        assert_eq!(
+            "/root",
+            copy.get(&Variant::from_i64(1)).to_node_path().to_string()
+        );
+
+        let mut array = VariantArray::new();
+        array.push(&Variant::from_i64(1));
+        array.push(&Variant::from_str("foo"));
+        assert_eq!(
+            r#"[{"I64":1},{"GodotString":"foo"}]"#,
+            serde_json::to_string(&array).unwrap()
+        );
+        let copy: VariantArray = serde_json::from_str(r#"[{"Bool":true},"Nil"]"#).unwrap();
+        assert_eq!(2, copy.len());
+        assert_eq!(Some(true), copy.get_ref(0).try_to_bool());
+        assert!(copy.get_ref(1).is_nil());
+
+        let mut vectors = Vector3Array::new();
+        vectors.push(&Vector3::new(1.0, 2.0, 3.0));
+        assert_eq!("[[1.0,2.0,3.0]]", serde_json::to_string(&vectors).unwrap());
+        let copy: Vector3Array = serde_json::from_str("[[1.0,2.0,3.0],[4,5,6]]").unwrap();
+        assert_eq!(Vector3::new(4.0, 5.0, 6.0), copy.get(1));
+
+        let string: GodotString = serde_json::from_str(r#""héllo""#).unwrap();
+        assert_eq!(r#""héllo""#, serde_json::to_string(&string).unwrap());
+        let path: NodePath = serde_json::from_str(r#""/root/Node""#).unwrap();
+        assert_eq!(r#""/root/Node""#, serde_json::to_string(&path).unwrap());
+
+        let variant = Variant::from_rid(&Rid::new());
+        assert!(serde_json::to_string(&variant).is_err());
+    }
+}
@@ -0,0 +1,941 @@
+//! The text format of `var2str`, `str2var` and the `.tscn`, `.tres` and `project.godot` files.
+//!
+//! Values are written the way the engine writes them, so that files edited by tools produce
+//! minimal diffs when saved again by the editor. Like in the engine, reals are written with 6
+//! significant digits.
+
+use super::{ObjectData, ObjectValue, Value, MAX_DEPTH};
+use crate::{Aabb, Basis, Color, Plane, Quat, Transform, Transform2D, Vector2, Vector3};
+use std::cmp::Ordering;
+use std::fmt;
+use std::iter::Peekable;
+use std::str::Chars;
+
+/// Error returned when parsing invalid text.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct ParseError {
+    /// The line of the error, starting at 1.
+    pub line: usize,
+    pub message: String,
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "line {}: {}", self.line, self.message)
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+/// Formats a value like `var2str` does.
+///
+/// Dictionaries are written with their keys sorted like in the engine. Objects encoded by
+/// instance ID cannot be represented and are written as `null`.
+pub fn var2str(value: &Value) -> String {
+    let mut out = String::new();
+    write_value(value, &mut out);
+    out
+}
+
+/// Parses a value written by `var2str` or found in resource files. Only whitespace and comments
+/// may follow the value.
+///
+/// References to other resources, like `ExtResource( 1 )`, are not supported.
+pub fn str2var(s: &str) -> Result<Value, ParseError> {
+    let mut parser = Parser {
+        lexer: Lexer {
+            chars: s.chars().peekable(),
+            line: 1,
+        },
+        depth: 0,
+    };
+
+    let value = parser.value()?;
+    match parser.lexer.next()? {
+        Token::Eof => Ok(value),
+        _ => Err(parser.lexer.error("unexpected data after the value")),
+    }
+}
+
+fn write_value(value: &Value, out: &mut String) {
+    match value {
+        Value::Nil | Value::Object(ObjectValue::Id(_)) | Value::Object(ObjectValue::Full(None)) => {
+            out.push_str("null")
+        }
+        Value::Bool(b) => out.push_str(if *b { "true" } else { "false" }),
+        Value::I64(i) => out.push_str(&i.to_string()),
+        Value::F64(f) => {
+            let s = real(*f);
+            out.push_str(&s);
+            if f.is_finite() && !s.contains('.') && !s.contains('e') {
+                out.push_str(".0");
+            }
+        }
+        Value::GodotString(s) => {
+            // Line breaks are kept as they are, which the parser accepts.
+            out.push('"');
+            for c in s.chars() {
+                match c {
+                    '"' => out.push_str("\\\""),
+                    '\\' => out.push_str("\\\\"),
+                    c => out.push(c),
+                }
+            }
+            out.push('"');
+        }
+        Value::Vector2(v) => construct(out, "Vector2", &[v.x, v.y]),
+        Value::Rect2(r) => construct(
+            out,
+            "Rect2",
+            &[r.origin.x, r.origin.y, r.size.width, r.size.height],
+        ),
+        Value::Vector3(v) => construct(out, "Vector3", &[v.x, v.y, v.z]),
+        Value::Transform2D(t) => construct(
+            out,
+            "Transform2D",
+            &[t.m11, t.m12, t.m21, t.m22, t.m31, t.m32],
+        ),
+        Value::Plane(p) => construct(out, "Plane", &[p.normal.x, p.normal.y, p.normal.z, p.d]),
+        Value::Quat(q) => construct(out, "Quat", &[q.i, q.j, q.k, q.r]),
+        Value::Aabb(a) => construct(
+            out,
+            "AABB",
+            &[
+                a.position.x,
+                a.position.y,
+                a.position.z,
+                a.size.x,
+                a.size.y,
+                a.size.z,
+            ],
+        ),
+        Value::Basis(b) => construct(out, "Basis", &basis_reals(b)),
+        Value::Transform(t) => {
+            let mut reals = basis_reals(&t.basis).to_vec();
+            reals.extend_from_slice(&[t.origin.x, t.origin.y, t.origin.z]);
+            construct(out, "Transform", &reals)
+        }
+        Value::Color(c) => construct(out, "Color", &[c.r, c.g, c.b, c.a]),
+        Value::NodePath(path) => {
+            out.push_str("NodePath(\"");
+            escape(path, out);
+            out.push_str("\")");
+        }
+        Value::Rid => out.push_str("RID()"),
+        Value::Object(ObjectValue::Full(Some(data))) => {
+            out.push_str("Object(");
+            out.push_str(&data.class);
+            out.push(',');
+            for (i, (name, value)) in data.properties.iter().enumerate() {
+                if i > 0 {
+                    out.push(',');
+                }
+                out.push('"');
+                out.push_str(name);
+                out.push_str("\":");
+                write_value(value, out);
+            }
+            out.push(')');
+        }
+        Value::Dictionary(entries) => {
+            let mut entries: Vec<_> = entries.iter().collect();
+            entries.sort_by(|(a, _), (b, _)| key_order(a, b));
+
+            out.push_str("{\n");
+            for (i, (key, value)) in entries.into_iter().enumerate() {
+                if i > 0 {
+                    out.push_str(",\n");
+                }
+                write_value(key, out);
+                out.push_str(": ");
+                write_value(value, out);
+            }
+            out.push_str("\n}");
+        }
+        Value::VariantArray(values) => {
+            out.push_str("[ ");
+            for (i, value) in values.iter().enumerate() {
+                if i > 0 {
+                    out.push_str(", ");
+                }
+                write_value(value, out);
+            }
+            out.push_str(" ]");
+        }
+        Value::ByteArray(bytes) => list(out, "PoolByteArray", bytes.iter().map(u8::to_string)),
+        Value::Int32Array(ints) => list(out, "PoolIntArray", ints.iter().map(i32::to_string)),
+        Value::Float32Array(floats) => list(
+            out,
+            "PoolRealArray",
+            floats.iter().map(|f| real(f64::from(*f))),
+        ),
+        Value::StringArray(strings) => list(
+            out,
+            "PoolStringArray",
+            strings.iter().map(|s| {
+                let mut quoted = String::from("\"");
+                escape(s, &mut quoted);
+                quoted.push('"');
+                quoted
+            }),
+        ),
+        Value::Vector2Array(vectors) => list(
+            out,
+            "PoolVector2Array",
+            vectors
+                .iter()
+                .flat_map(|v| vec![v.x, v.y])
+                .map(|r| real(f64::from(r))),
+        ),
+        Value::Vector3Array(vectors) => list(
+            out,
+            "PoolVector3Array",
+            vectors
+                .iter()
+                .flat_map(|v| vec![v.x, v.y, v.z])
+                .map(|r| real(f64::from(r))),
+        ),
+        Value::ColorArray(colors) => list(
+            out,
+            "PoolColorArray",
+            colors
+                .iter()
+                .flat_map(|c| vec![c.r, c.g, c.b, c.a])
+                .map(|r| real(f64::from(r))),
+        ),
+    }
+}
+
+fn basis_reals(basis: &Basis) -> [f32; 9] {
+    let [x, y, z] = basis.elements;
+    [x.x, x.y, x.z, y.x, y.y, y.z, z.x, z.y, z.z]
+}
+
+fn construct(out: &mut String, name: &str, reals: &[f32]) {
+    list(out, name, reals.iter().map(|r| real(f64::from(*r))))
+}
+
+fn list<I: Iterator<Item = String>>(out: &mut String, name: &str, items: I) {
+    out.push_str(name);
+    out.push_str("( ");
+    for (i, item) in items.enumerate() {
+        if i > 0 {
+            out.push_str(", ");
+        }
+        out.push_str(&item);
+    }
+    out.push_str(" )");
+}
+
+/// Formats a real like `printf("%g")`, which the engine uses.
+fn real(f: f64) -> String {
+    if f.is_nan() {
+        return "nan".into();
+    } else if f.is_infinite() {
+        return if f > 0.0 { "inf" } else { "-inf" }.into();
+    } else if f == 0.0 {
+        return "0".into();
+    }
+
+    fn trim_zeros(s: &str) -> &str {
+        if s.contains('.') {
+            s.trim_end_matches('0').trim_end_matches('.')
+        } else {
+            s
+        }
+    }
+
+    // The exponent is taken after rounding to 6 digits, like printf does.
+    let scientific = format!("{:.5e}", f);
+    let e = scientific.find('e').unwrap();
+    let exp: i32 = scientific[e + 1..].parse().unwrap();
+
+    if !(-4..6).contains(&exp) {
+        let sign = if exp < 0 { '-' } else { '+' };
+        format!("{}e{}{:02}", trim_zeros(&scientific[..e]), sign, exp.abs())
+    } else {
+        trim_zeros(&format!("{:.*}", (5 - exp) as usize, f)).into()
+    }
+}
+
+/// Escapes a string for the quoted arguments of constructors.
+fn escape(s: &str, out: &mut String) {
+    for c in s.chars() {
+        match c {
+            '"' => out.push_str("\\\""),
+            '\\' => out.push_str("\\\\"),
+            '\n' => out.push_str("\\n"),
+            '\t' => out.push_str("\\t"),
+            '\r' => out.push_str("\\r"),
+            '\u{8}' => out.push_str("\\b"),
+            '\u{c}' => out.push_str("\\f"),
+            c => out.push(c),
+        }
+    }
+}
+
+/// The order of dictionary keys in the output of the engine: by type, then by value for types
+/// that can be compared.
+fn key_order(a: &Value, b: &Value) -> Ordering {
+    fn reals(a: &[f32], b: &[f32]) -> Ordering {
+        a.iter()
+            .zip(b)
+            .map(|(a, b)| a.partial_cmp(b).unwrap_or(Ordering::Equal))
+            .find(|o| *o != Ordering::Equal)
+            .unwrap_or(Ordering::Equal)
+    }
+
+    match (a, b) {
+        (Value::Bool(a), Value::Bool(b)) => a.cmp(b),
+        (Value::I64(a), Value::I64(b)) => a.cmp(b),
+        (Value::F64(a), Value::F64(b)) => a.partial_cmp(b).unwrap_or(Ordering::Equal),
+        (Value::GodotString(a), Value::GodotString(b)) => a.cmp(b),
+        (Value::Vector2(a), Value::Vector2(b)) => reals(&[a.x, a.y], &[b.x, b.y]),
+        (Value::Vector3(a), Value::Vector3(b)) => reals(&[a.x, a.y, a.z], &[b.x, b.y, b.z]),
+        _ => (a.get_type() as u32).cmp(&(b.get_type() as u32)),
+    }
+}
+
+#[derive(Clone, Debug, PartialEq)]
+enum Token {
+    CurlyOpen,
+    CurlyClose,
+    BracketOpen,
+    BracketClose,
+    ParenOpen,
+    ParenClose,
+    Colon,
+    Comma,
+    Identifier(String),
+    Number(Value),
+    String(String),
+    Eof,
+}
+
+struct Lexer<'a> {
+    chars: Peekable<Chars<'a>>,
+    line: usize,
+}
+
+impl<'a> Lexer<'a> {
+    fn error<S: Into<String>>(&self, message: S) -> ParseError {
+        ParseError {
+            line: self.line,
+            message: message.into(),
+        }
+    }
+
+    fn next(&mut self) -> Result<Token, ParseError> {
+        loop {
+            let c = match self.chars.next() {
+                Some(c) => c,
+                None => return Ok(Token::Eof),
+            };
+
+            let token = match c {
+                '\n' => {
+                    self.line += 1;
+                    continue;
+                }
+                ';' => {
+                    while self.chars.peek().filter(|c| **c != '\n').is_some() {
+                        self.chars.next();
+                    }
+                    continue;
+                }
+                c if c.is_whitespace() => continue,
+                '{' => Token::CurlyOpen,
+                '}' => Token::CurlyClose,
+                '[' => Token::BracketOpen,
+                ']' => Token::BracketClose,
+                '(' => Token::ParenOpen,
+                ')' => Token::ParenClose,
+                ':' => Token::Colon,
+                ',' => Token::Comma,
+                '"' => Token::String(self.string()?),
+                '-' | '0'..='9' => Token::Number(self.number(c)?),
+                c if c.is_ascii_alphabetic() || c == '_' => Token::Identifier(self.identifier(c)),
+                c => return Err(self.error(format!("unexpected character '{}'", c))),
+            };
+
+            return Ok(token);
+        }
+    }
+
+    fn string(&mut self) -> Result<String, ParseError> {
+        let mut s = String::new();
+        loop {
+            let c = match self.chars.next() {
+                Some('"') => return Ok(s),
+                Some('\\') => match self.chars.next() {
+                    Some('b') => '\u{8}',
+                    Some('t') => '\t',
+                    Some('n') => '\n',
+                    Some('f') => '\u{c}',
+                    Some('r') => '\r',
+                    Some('u') => {
+                        let mut code = 0;
+                        for _ in 0..4 {
+                            let digit = self.chars.next().and_then(|c| c.to_digit(16));
+                            code = code * 16
+                                + digit.ok_or_else(|| self.error("invalid unicode escape"))?;
+                        }
+                        std::char::from_u32(code)
+                            .ok_or_else(|| self.error("invalid unicode escape"))?
+                    }
+                    // Other characters, including quotes and backslashes, stand for themselves.
+                    Some(c) => c,
+                    None => return Err(self.error("unterminated string")),
+                },
+                Some(c) => {
+                    if c == '\n' {
+                        self.line += 1;
+                    }
+                    c
+                }
+                None => return Err(self.error("unterminated string")),
+            };
+            s.push(c);
+        }
+    }
+
+    fn number(&mut self, first: char) -> Result<Value, ParseError> {
+        let alphabetic = self
+            .chars
+            .peek()
+            .filter(|c| c.is_ascii_alphabetic())
+            .cloned();
+        if let (Some(c), '-') = (alphabetic, first) {
+            self.chars.next();
+            return match self.identifier(c).as_str() {
+                "inf" => Ok(Value::F64(f64::NEG_INFINITY)),
+                _ => Err(self.error("invalid number")),
+            };
+        }
+
+        let mut text = first.to_string();
+        while let Some(&c) = self.chars.peek() {
+            if !(c.is_ascii_digit() || c == '.' || c == 'e' || c == 'E') {
+                break;
+            }
+            text.push(c);
+            self.chars.next();
+
+            if c == 'e' || c == 'E' {
+                if let Some(&sign) = self.chars.peek() {
+                    if sign == '+' || sign == '-' {
+                        text.push(sign);
+                        self.chars.next();
+                    }
+                }
+            }
+        }
+
+        if text.contains(&['.', 'e', 'E'][..]) {
+            text.parse().map(Value::F64).ok()
+        } else {
+            text.parse().map(Value::I64).ok()
+        }
+        .ok_or_else(|| self.error(format!("invalid number '{}'", text)))
+    }
+
+    fn identifier(&mut self, first: char) -> String {
+        let mut s = first.to_string();
+        while let Some(&c) = self.chars.peek() {
+            if !(c.is_ascii_alphanumeric() || c == '_') {
+                break;
+            }
+            s.push(c);
+            self.chars.next();
+        }
+        s
+    }
+}
+
+struct Parser<'a> {
+    lexer: Lexer<'a>,
+    depth: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn value(&mut self) -> Result<Value, ParseError> {
+        let token = self.lexer.next()?;
+        self.value_from(token)
+    }
+
+    fn value_from(&mut self, token: Token) -> Result<Value, ParseError> {
+        match token {
+            Token::CurlyOpen => self.nested(Parser::dictionary),
+            Token::BracketOpen => self.nested(Parser::array),
+            Token::Number(number) => Ok(number),
+            Token::String(s) => Ok(Value::GodotString(s)),
+            Token::Identifier(id) => self.identifier(&id),
+            Token::Eof => Err(self.lexer.error("expected a value, found the end of input")),
+            token => Err(self
+                .lexer
+                .error(format!("expected a value, found {:?}", token))),
+        }
+    }
+
+    fn expect(&mut self, expected: Token) -> Result<(), ParseError> {
+        let token = self.lexer.next()?;
+        if token == expected {
+            Ok(())
+        } else {
+            Err(self
+                .lexer
+                .error(format!("expected {:?}, found {:?}", expected, token)))
+        }
+    }
+
+    fn nested<F>(&mut self, parse: F) -> Result<Value, ParseError>
+    where
+        F: FnOnce(&mut Self) -> Result<Value, ParseError>,
+    {
+        if self.depth == MAX_DEPTH {
+            return Err(self.lexer.error("values are nested too deeply"));
+        }
+
+        self.depth += 1;
+        let value = parse(self);
+        self.depth -= 1;
+        value
+    }
+
+    fn dictionary(&mut self) -> Result<Value, ParseError> {
+        let mut entries = Vec::new();
+        loop {
+            let mut token = self.lexer.next()?;
+            if !entries.is_empty() && token != Token::CurlyClose {
+                if token != Token::Comma {
+                    return Err(self.lexer.error("expected ',' or '}'"));
+                }
+                token = self.lexer.next()?;
+            }
+            if token == Token::CurlyClose {
+                return Ok(Value::Dictionary(entries));
+            }
+
+            let key = self.value_from(token)?;
+            self.expect(Token::Colon)?;
+            entries.push((key, self.value()?));
+        }
+    }
+
+    fn array(&mut self) -> Result<Value, ParseError> {
+        let mut values = Vec::new();
+        loop {
+            let mut token = self.lexer.next()?;
+            if !values.is_empty() && token != Token::BracketClose {
+                if token != Token::Comma {
+                    return Err(self.lexer.error("expected ',' or ']'"));
+                }
+                token = self.lexer.next()?;
+            }
+            if token == Token::BracketClose {
+                return Ok(Value::VariantArray(values));
+            }
+
+            values.push(self.value_from(token)?);
+        }
+    }
+
+    fn identifier(&mut self, id: &str) -> Result<Value, ParseError> {
+        let value = match id {
+            "true" => Value::Bool(true),
+            "false" => Value::Bool(false),
+            "null" | "nil" => Value::Nil,
+            "inf" => Value::F64(f64::INFINITY),
+            "nan" => Value::F64(f64::NAN),
+            "Vector2" => Value::Vector2(self.reals_of::<[f32; 2]>(id).map(vector2)?),
+            "Rect2" => {
+                let [x, y, width, height] = self.reals_of(id)?;
+                Value::Rect2(euclid::rect(x, y, width, height))
+            }
+            "Vector3" => Value::Vector3(self.reals_of::<[f32; 3]>(id).map(vector3)?),
+            "Transform2D" | "Matrix32" => {
+                let [m11, m12, m21, m22, m31, m32] = self.reals_of(id)?;
+                Value::Transform2D(Transform2D::row_major(m11, m12, m21, m22, m31, m32))
+            }
+            "Plane" => {
+                let [x, y, z, d] = self.reals_of(id)?;
+                Value::Plane(Plane::new(Vector3::new(x, y, z), d))
+            }
+            "Quat" => {
+                let [x, y, z, w] = self.reals_of(id)?;
+                Value::Quat(Quat::quaternion(x, y, z, w))
+            }
+            "AABB" | "Rect3" => {
+                let [x, y, z, sx, sy, sz] = self.reals_of(id)?;
+                Value::Aabb(Aabb::new(Vector3::new(x, y, z), Vector3::new(sx, sy, sz)))
+            }
+            "Basis" | "Matrix3" => Value::Basis(self.reals_of::<[f32; 9]>(id).map(basis)?),
+            "Transform" => {
+                let r: [f32; 12] = self.reals_of(id)?;
+                let origin = Vector3::new(r[9], r[10], r[11]);
+                let mut basis_reals = [0.0; 9];
+                basis_reals.copy_from_slice(&r[..9]);
+                Value::Transform(Transform::new(basis(basis_reals), origin))
+            }
+            "Color" => Value::Color(self.reals_of::<[f32; 4]>(id).map(color)?),
+            "NodePath" => {
+                self.expect(Token::ParenOpen)?;
+                let path = match self.lexer.next()? {
+                    Token::String(path) => path,
+                    _ => return Err(self.lexer.error("expected a string in NodePath")),
+                };
+                self.expect(Token::ParenClose)?;
+                Value::NodePath(path)
+            }
+            "RID" => {
+                self.expect(Token::ParenOpen)?;
+                self.expect(Token::ParenClose)?;
+                Value::Rid
+            }
+            "Object" => self.nested(Parser::object)?,
+            "PoolByteArray" | "ByteArray" => Value::ByteArray(self.ints(id)?),
+            "PoolIntArray" | "IntArray" => Value::Int32Array(self.ints(id)?),
+            "PoolRealArray" | "RealArray" | "FloatArray" => {
+                Value::Float32Array(self.arguments(Parser::real)?)
+            }
+            "PoolStringArray" | "StringArray" => {
+                Value::StringArray(self.arguments(|parser, token| match token {
+                    Token::String(s) => Ok(s),
+                    _ => Err(parser.lexer.error("expected a string")),
+                })?)
+            }
+            "PoolVector2Array" | "Vector2Array" => Value::Vector2Array(self.chunks(id, vector2)?),
+            "PoolVector3Array" | "Vector3Array" => Value::Vector3Array(self.chunks(id, vector3)?),
+            "PoolColorArray" | "ColorArray" => Value::ColorArray(self.chunks(id, color)?),
+            "ExtResource" | "SubResource" | "Resource" => {
+                return Err(self
+                    .lexer
+                    .error(format!("resource references like {} are not supported", id)))
+            }
+            _ => return Err(self.lexer.error(format!("unknown identifier '{}'", id))),
+        };
+
+        Ok(value)
+    }
+
+    fn object(&mut self) -> Result<Value, ParseError> {
+        self.expect(Token::ParenOpen)?;
+        let class = match self.lexer.next()? {
+            Token::Identifier(class) => class,
+            _ => return Err(self.lexer.error("expected a class name in Object")),
+        };
+
+        let mut properties = Vec::new();
+        loop {
+            match self.lexer.next()? {
+                Token::ParenClose => break,
+                Token::Comma => {}
+                _ => return Err(self.lexer.error("expected ',' or ')'")),
+            }
+
+            let name = match self.lexer.next()? {
+                Token::String(name) => name,
+                // A comma may follow the class name even without properties.
+                Token::ParenClose if properties.is_empty() => break,
+                _ => return Err(self.lexer.error("expected a property name")),
+            };
+            self.expect(Token::Colon)?;
+            properties.push((name, self.value()?));
+        }
+
+        Ok(Value::Object(ObjectValue::Full(Some(ObjectData {
+            class,
+            properties,
+        }))))
+    }
+
+    /// Parses the parenthesized, comma-separated arguments of a constructor.
+    fn arguments<T, F>(&mut self, mut parse: F) -> Result<Vec<T>, ParseError>
+    where
+        F: FnMut(&mut Self, Token) -> Result<T, ParseError>,
+    {
+        self.expect(Token::ParenOpen)?;
+
+        let mut args = Vec::new();
+        let mut token = self.lexer.next()?;
+        if token == Token::ParenClose {
+            return Ok(args);
+        }
+
+        loop {
+            args.push(parse(self, token)?);
+            match self.lexer.next()? {
+                Token::Comma => token = self.lexer.next()?,
+                Token::ParenClose => return Ok(args),
+                _ => return Err(self.lexer.error("expected ',' or ')'")),
+            }
+        }
+    }
+
+    fn real(&mut self, token: Token) -> Result<f32, ParseError> {
+        match token {
+            Token::Number(Value::I64(i)) => Ok(i as f32),
+            Token::Number(Value::F64(f)) => Ok(f as f32),
+            Token::Identifier(ref id) if id == "inf" => Ok(f32::INFINITY),
+            Token::Identifier(ref id) if id == "nan" => Ok(f32::NAN),
+            _ => Err(self.lexer.error("expected a number")),
+        }
+    }
+
+    fn ints<T: std::convert::TryFrom<i64>>(&mut self, name: &str) -> Result<Vec<T>, ParseError> {
+        self.arguments(|parser, token| match token {
+            Token::Number(Value::I64(i)) => T::try_from(i).map_err(|_| {
+                parser
+                    .lexer
+                    .error(format!("{} is out of range in {}", i, name))
+            }),
+            _ => Err(parser.lexer.error("expected an integer")),
+        })
+    }
+
+    /// Parses the arguments of a constructor taking a fixed number of reals.
+    fn reals_of<A: Default + AsMut<[f32]>>(&mut self, name: &str) -> Result<A, ParseError> {
+        let args = self.arguments(Parser::real)?;
+
+        let mut reals = A::default();
+        if args.len() != reals.as_mut().len() {
+            return Err(self.lexer.error(format!(
+                "{} expects {} arguments, found {}",
+                name,
+                reals.as_mut().len(),
+                args.len()
+            )));
+        }
+
+        reals.as_mut().copy_from_slice(&args);
+        Ok(reals)
+    }
+
+    /// Parses the flattened components of a pool array of vectors or colors.
+    fn chunks<A, T, F>(&mut self, name: &str, make: F) -> Result<Vec<T>, ParseError>
+    where
+        A: Default + AsMut<[f32]>,
+        F: Fn(A) -> T,
+    {
+        let args = self.arguments(Parser::real)?;
+
+        let size = A::default().as_mut().len();
+        if args.len() % size != 0 {
+            return Err(self.lexer.error(format!(
+                "{} expects a multiple of {} arguments, found {}",
+                name,
+                size,
+                args.len()
+            )));
+        }
+
+        Ok(args
+            .chunks(size)
+            .map(|chunk| {
+                let mut reals = A::default();
+                reals.as_mut().copy_from_slice(chunk);
+                make(reals)
+            })
+            .collect())
+    }
+}
+
+fn vector2([x, y]: [f32; 2]) -> Vector2 {
+    Vector2::new(x, y)
+}
+
+fn vector3([x, y, z]: [f32; 3]) -> Vector3 {
+    Vector3::new(x, y, z)
+}
+
+fn color([r, g, b, a]: [f32; 4]) -> Color {
+    Color::rgba(r, g, b, a)
+}
+
+fn basis(r: [f32; 9]) -> Basis {
+    Basis {
+        elements: [
+            Vector3::new(r[0], r[1], r[2]),
+            Vector3::new(r[3], r[4], r[5]),
+            Vector3::new(r[6], r[7], r[8]),
+        ],
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn check(value: Value, text: &str) {
+        assert_eq!(text, var2str(&value));
+        assert_eq!(Ok(value), str2var(text));
+    }
+
+    fn error_line(text: &str) -> usize {
+        str2var(text).unwrap_err().line
+    }
+
+    // The expected text is the output of `var2str` in Godot 3.2.
+
+    #[test]
+    fn scalars() {
+        check(Value::Nil, "null");
+        check(Value::Bool(false), "false");
+        check(Value::I64(-42), "-42");
+        check(Value::F64(1.0), "1.0");
+        check(Value::F64(0.1), "0.1");
+        check(Value::F64(-2.5), "-2.5");
+        check(Value::F64(1e20), "1e+20");
+        check(Value::F64(0.0001), "0.0001");
+        check(Value::F64(0.00001), "1e-05");
+        check(Value::F64(f64::INFINITY), "inf");
+        check(Value::F64(f64::NEG_INFINITY), "-inf");
+        check(Value::Rid, "RID()");
+
+        assert_eq!("1.23457e+06", var2str(&Value::F64(1_234_567.0)));
+        assert_eq!("123457.0", var2str(&Value::F64(123_456.7)));
+        assert_eq!("nan", var2str(&Value::F64(f64::NAN)));
+        assert_eq!(Ok(Value::Nil), str2var("nil"));
+    }
+
+    #[test]
+    fn strings() {
+        check(
+            Value::GodotString("a\"b\\c\nd".into()),
+            "\"a\\\"b\\\\c\nd\"",
+        );
+        check(
+            Value::NodePath("/root/a:b\n".into()),
+            "NodePath(\"/root/a:b\\n\")",
+        );
+        check(
+            Value::StringArray(vec!["a".into(), "\"".into()]),
+            "PoolStringArray( \"a\", \"\\\"\" )",
+        );
+
+        assert_eq!(
+            Ok(Value::GodotString("é\t".into())),
+            str2var("\"\\u00e9\\t\"")
+        );
+    }
+
+    #[test]
+    fn math_types() {
+        check(Value::Vector2(Vector2::new(1.0, 2.5)), "Vector2( 1, 2.5 )");
+        check(
+            Value::Rect2(euclid::rect(0.0, -1.0, 64.0, 32.0)),
+            "Rect2( 0, -1, 64, 32 )",
+        );
+        check(
+            Value::Transform2D(Transform2D::row_major(1.0, 0.0, 0.0, 1.0, 10.0, 20.0)),
+            "Transform2D( 1, 0, 0, 1, 10, 20 )",
+        );
+        check(
+            Value::Transform(Transform::new(
+                Basis::identity(),
+                Vector3::new(1.0, 2.0, 3.0),
+            )),
+            "Transform( 1, 0, 0, 0, 1, 0, 0, 0, 1, 1, 2, 3 )",
+        );
+        check(
+            Value::Color(Color::rgba(1.0, 0.5, 0.25, 1.0)),
+            "Color( 1, 0.5, 0.25, 1 )",
+        );
+        check(
+            Value::Aabb(Aabb::new(
+                Vector3::new(0.0, 0.0, 0.0),
+                Vector3::new(1.0, 1.0, 1.0),
+            )),
+            "AABB( 0, 0, 0, 1, 1, 1 )",
+        );
+
+        assert_eq!(
+            Ok(Value::Basis(Basis::identity())),
+            str2var("Matrix3(1,0,0,0,1,0,0,0,1)")
+        );
+    }
+
+    #[test]
+    fn collections() {
+        check(
+            Value::Dictionary(vec![
+                (
+                    Value::GodotString("a".into()),
+                    Value::VariantArray(vec![Value::I64(1), Value::F64(2.0)]),
+                ),
+                (
+                    Value::GodotString("b".into()),
+                    Value::Int32Array(vec![1, 2]),
+                ),
+            ]),
+            "{\n\"a\": [ 1, 2.0 ],\n\"b\": PoolIntArray( 1, 2 )\n}",
+        );
+        check(Value::Dictionary(vec![]), "{\n\n}");
+        check(Value::VariantArray(vec![]), "[  ]");
+        check(Value::ByteArray(vec![]), "PoolByteArray(  )");
+        check(
+            Value::Vector2Array(vec![Vector2::new(0.0, 1.0), Vector2::new(2.0, 3.0)]),
+            "PoolVector2Array( 0, 1, 2, 3 )",
+        );
+        check(
+            Value::Object(ObjectValue::Full(Some(ObjectData {
+                class: "Node".into(),
+                properties: vec![("a".into(), Value::I64(1)), ("b".into(), Value::Nil)],
+            }))),
+            "Object(Node,\"a\":1,\"b\":null)",
+        );
+
+        // The engine sorts the keys of dictionaries by type first.
+        assert_eq!(
+            "{\n2: null,\n\"a\": null\n}",
+            var2str(&Value::Dictionary(vec![
+                (Value::GodotString("a".into()), Value::Nil),
+                (Value::I64(2), Value::Nil),
+            ]))
+        );
+    }
+
+    #[test]
+    fn resource_file_syntax() {
+        let text = r#"
+            {
+                "a": [ 1, 2, ], ; a comment
+                "b" : Vector3(1.5,-2,3e2),
+                "c": PoolColorArray( 1, 0, 0, 1 ),
+            }
+        "#;
+
+        let expected = Value::Dictionary(vec![
+            (
+                Value::GodotString("a".into()),
+                Value::VariantArray(vec![Value::I64(1), Value::I64(2)]),
+            ),
+            (
+                Value::GodotString("b".into()),
+                Value::Vector3(Vector3::new(1.5, -2.0, 300.0)),
+            ),
+            (
+                Value::GodotString("c".into()),
+                Value::ColorArray(vec![Color::rgba(1.0, 0.0, 0.0, 1.0)]),
+            ),
+        ]);
+
+        assert_eq!(Ok(expected), str2var(text));
+    }
+
+    #[test]
+    fn invalid_input() {
+        assert_eq!(1, error_line(""));
+        assert_eq!(1, error_line("[ 1 2 ]"));
+        assert_eq!(2, error_line("[ 1,\n 2 3 ]"));
+        assert_eq!(3, error_line("{\n\"a\": 1\n\"b\": 2 }"));
+        assert_eq!(1, error_line("Vector2( 1 )"));
+        assert_eq!(1, error_line("PoolVector2Array( 1, 2, 3 )"));
+        assert_eq!(1, error_line("PoolByteArray( 256 )"));
+        assert_eq!(1, error_line("ExtResource( 1 )"));
+        assert_eq!(1, error_line("Foo( 1 )"));
+        assert_eq!(1, error_line("\"abc"));
+        assert_eq!(1, error_line("1 2"));
+        assert_eq!(1, error_line(&"[".repeat(MAX_DEPTH + 1)));
+    }
+}
@@ -0,0 +1,41 @@
+//! Conversion of the values returned by exported methods.
+//!
+//! `godot_wrap_method_return!` picks between the two traits of this module by method
+//! resolution on `&&MethodReturn<R>`: `ReturnResult` applies to `Result<T, E>` with
+//! `E: Display`, and `ReturnToVariant` to every other `ToVariant` type. Because the choice is
+//! made on the type rather than on the tokens of the signature, aliases like `io::Result<T>`
+//! are handled like `Result<T, E>`.
+
+use crate::{ToVariant, Variant};
+use std::fmt::Display;
+
+/// A value returned by an exported method.
+pub struct MethodReturn<R>(pub R);
+
+/// Converts `Ok` values to variants, and logs `Err` values with `godot_error!` and returns nil.
+pub trait ReturnResult {
+    fn to_return_variant(&self, class_name: &str, method_name: &str) -> Variant;
+}
+
+impl<T: ToVariant, E: Display> ReturnResult for &MethodReturn<Result<T, E>> {
+    fn to_return_variant(&self, class_name: &str, method_name: &str) -> Variant {
+        match &self.0 {
+            Ok(val) => val.to_variant(),
+            Err(err) => {
+                godot_error!("{}::{} returned an error: {}", class_name, method_name, err);
+                Variant::new()
+            }
+        }
+    }
+}
+
+/// Converts the value with `ToVariant`.
+pub trait ReturnToVariant {
+    fn to_return_variant(&self, class_name: &str, method_name: &str) -> Variant;
+}
+
+impl<R: ToVariant> ReturnToVariant for MethodReturn<R> {
+    fn to_return_variant(&self, _class_name: &str, _method_name: &str) -> Variant {
+        self.0.to_variant()
+    }
+}
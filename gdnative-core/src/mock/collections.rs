@@ -0,0 +1,621 @@
+use super::value::{self, Array, Dictionary, DictionaryEntry, Value, VariantSlot};
+use super::{handle_init, handle_new, handle_ref, handle_take, string};
+use crate::sys;
+use std::cell::UnsafeCell;
+use std::ptr;
+use std::sync::Arc;
+
+// Arrays and dictionaries are reference types: copies of a handle share their contents, just
+// like in the engine.
+
+pub(super) unsafe fn array<'a>(arr: *const sys::godot_array) -> &'a Array {
+    handle_ref(arr)
+}
+
+pub(super) unsafe fn new_array(arr: Array) -> sys::godot_array {
+    handle_new(arr)
+}
+
+unsafe fn elements<'a>(arr: *const sys::godot_array) -> &'a mut Vec<VariantSlot> {
+    &mut *array(arr).get()
+}
+
+pub(super) unsafe fn dictionary<'a>(dict: *const sys::godot_dictionary) -> &'a Dictionary {
+    handle_ref(dict)
+}
+
+pub(super) unsafe fn new_dictionary(dict: Dictionary) -> sys::godot_dictionary {
+    handle_new(dict)
+}
+
+unsafe fn entries<'a>(dict: *const sys::godot_dictionary) -> &'a mut Vec<Box<DictionaryEntry>> {
+    &mut *dictionary(dict).get()
+}
+
+unsafe fn entry<'a>(
+    dict: *const sys::godot_dictionary,
+    key: &Value,
+) -> Option<&'a mut DictionaryEntry> {
+    entries(dict)
+        .iter_mut()
+        .find(|e| e.key.value().key_equals(key))
+        .map(|e| &mut **e)
+}
+
+/// Converts an index that may be negative, counting from the back, like the engine does for
+/// some array methods.
+fn wrap_index(len: usize, idx: sys::godot_int) -> usize {
+    if idx < 0 {
+        (len as i64 + i64::from(idx)).max(0) as usize
+    } else {
+        idx as usize
+    }
+}
+
+unsafe extern "C" fn godot_array_new(r_dest: *mut sys::godot_array) {
+    handle_init::<_, Array>(r_dest, Arc::new(UnsafeCell::new(Vec::new())));
+}
+
+unsafe extern "C" fn godot_array_new_copy(
+    r_dest: *mut sys::godot_array,
+    p_src: *const sys::godot_array,
+) {
+    handle_init(r_dest, array(p_src).clone());
+}
+
+unsafe extern "C" fn godot_array_destroy(p_self: *mut sys::godot_array) {
+    drop(handle_take::<_, Array>(p_self));
+}
+
+unsafe extern "C" fn godot_array_duplicate(
+    p_self: *const sys::godot_array,
+    p_deep: sys::godot_bool,
+) -> sys::godot_array {
+    fn duplicate(slot: &VariantSlot, deep: bool) -> VariantSlot {
+        match slot.value() {
+            Value::Array(arr) if deep => {
+                let elements = unsafe { &*arr.get() };
+                let copy = elements.iter().map(|e| duplicate(e, deep)).collect();
+                VariantSlot::new(Value::Array(Arc::new(UnsafeCell::new(copy))))
+            }
+            _ => slot.clone(),
+        }
+    }
+
+    let copy = elements(p_self)
+        .iter()
+        .map(|e| duplicate(e, p_deep))
+        .collect();
+    new_array(Arc::new(UnsafeCell::new(copy)))
+}
+
+unsafe extern "C" fn godot_array_size(p_self: *const sys::godot_array) -> sys::godot_int {
+    elements(p_self).len() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_empty(p_self: *const sys::godot_array) -> sys::godot_bool {
+    elements(p_self).is_empty()
+}
+
+unsafe extern "C" fn godot_array_hash(p_self: *const sys::godot_array) -> sys::godot_int {
+    Value::Array(array(p_self).clone()).hash() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_get(
+    p_self: *const sys::godot_array,
+    p_idx: sys::godot_int,
+) -> sys::godot_variant {
+    let value = value::get(godot_array_operator_index_const(p_self, p_idx));
+    value::new(value.clone())
+}
+
+unsafe extern "C" fn godot_array_set(
+    p_self: *mut sys::godot_array,
+    p_idx: sys::godot_int,
+    p_value: *const sys::godot_variant,
+) {
+    let elements = elements(p_self);
+    let idx = p_idx as usize;
+    assert!(idx < elements.len(), "array index out of bounds");
+    elements[idx] = VariantSlot::new(value::get(p_value).clone());
+}
+
+unsafe extern "C" fn godot_array_operator_index(
+    p_self: *mut sys::godot_array,
+    p_idx: sys::godot_int,
+) -> *mut sys::godot_variant {
+    let elements = elements(p_self);
+    let idx = p_idx as usize;
+    assert!(idx < elements.len(), "array index out of bounds");
+    &mut elements[idx].0
+}
+
+unsafe extern "C" fn godot_array_operator_index_const(
+    p_self: *const sys::godot_array,
+    p_idx: sys::godot_int,
+) -> *const sys::godot_variant {
+    let elements = elements(p_self);
+    let idx = p_idx as usize;
+    assert!(idx < elements.len(), "array index out of bounds");
+    &elements[idx].0
+}
+
+unsafe extern "C" fn godot_array_push_back(
+    p_self: *mut sys::godot_array,
+    p_value: *const sys::godot_variant,
+) {
+    elements(p_self).push(VariantSlot::new(value::get(p_value).clone()));
+}
+
+unsafe extern "C" fn godot_array_push_front(
+    p_self: *mut sys::godot_array,
+    p_value: *const sys::godot_variant,
+) {
+    elements(p_self).insert(0, VariantSlot::new(value::get(p_value).clone()));
+}
+
+unsafe extern "C" fn godot_array_pop_back(p_self: *mut sys::godot_array) -> sys::godot_variant {
+    elements(p_self).pop().map_or_else(
+        || value::new(Value::Nil),
+        |slot| value::new(slot.value().clone()),
+    )
+}
+
+unsafe extern "C" fn godot_array_pop_front(p_self: *mut sys::godot_array) -> sys::godot_variant {
+    let elements = elements(p_self);
+    if elements.is_empty() {
+        value::new(Value::Nil)
+    } else {
+        value::new(elements.remove(0).value().clone())
+    }
+}
+
+unsafe extern "C" fn godot_array_front(p_self: *const sys::godot_array) -> sys::godot_variant {
+    godot_array_get(p_self, 0)
+}
+
+unsafe extern "C" fn godot_array_back(p_self: *const sys::godot_array) -> sys::godot_variant {
+    godot_array_get(p_self, godot_array_size(p_self) - 1)
+}
+
+unsafe extern "C" fn godot_array_insert(
+    p_self: *mut sys::godot_array,
+    p_pos: sys::godot_int,
+    p_value: *const sys::godot_variant,
+) {
+    let elements = elements(p_self);
+    let pos = p_pos as usize;
+    assert!(pos <= elements.len(), "array index out of bounds");
+    elements.insert(pos, VariantSlot::new(value::get(p_value).clone()));
+}
+
+unsafe extern "C" fn godot_array_remove(p_self: *mut sys::godot_array, p_idx: sys::godot_int) {
+    let elements = elements(p_self);
+    let idx = p_idx as usize;
+    assert!(idx < elements.len(), "array index out of bounds");
+    elements.remove(idx);
+}
+
+unsafe extern "C" fn godot_array_erase(
+    p_self: *mut sys::godot_array,
+    p_value: *const sys::godot_variant,
+) {
+    let elements = elements(p_self);
+    let value = value::get(p_value);
+    if let Some(idx) = elements.iter().position(|e| e.value().equals(value)) {
+        elements.remove(idx);
+    }
+}
+
+unsafe extern "C" fn godot_array_clear(p_self: *mut sys::godot_array) {
+    elements(p_self).clear();
+}
+
+unsafe extern "C" fn godot_array_resize(p_self: *mut sys::godot_array, p_size: sys::godot_int) {
+    elements(p_self).resize_with(p_size.max(0) as usize, || VariantSlot::new(Value::Nil));
+}
+
+unsafe extern "C" fn godot_array_invert(p_self: *mut sys::godot_array) {
+    elements(p_self).reverse();
+}
+
+unsafe extern "C" fn godot_array_count(
+    p_self: *const sys::godot_array,
+    p_value: *const sys::godot_variant,
+) -> sys::godot_int {
+    let value = value::get(p_value);
+    elements(p_self)
+        .iter()
+        .filter(|e| e.value().equals(value))
+        .count() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_has(
+    p_self: *const sys::godot_array,
+    p_value: *const sys::godot_variant,
+) -> sys::godot_bool {
+    godot_array_find(p_self, p_value, 0) != -1
+}
+
+unsafe extern "C" fn godot_array_find(
+    p_self: *const sys::godot_array,
+    p_what: *const sys::godot_variant,
+    p_from: sys::godot_int,
+) -> sys::godot_int {
+    let elements = elements(p_self);
+    let what = value::get(p_what);
+    let from = wrap_index(elements.len(), p_from);
+    elements
+        .iter()
+        .enumerate()
+        .skip(from)
+        .find(|(_, e)| e.value().equals(what))
+        .map_or(-1, |(i, _)| i as sys::godot_int)
+}
+
+unsafe extern "C" fn godot_array_rfind(
+    p_self: *const sys::godot_array,
+    p_what: *const sys::godot_variant,
+    p_from: sys::godot_int,
+) -> sys::godot_int {
+    let elements = elements(p_self);
+    let what = value::get(p_what);
+    let len = elements.len();
+    if len == 0 {
+        return -1;
+    }
+    let from = wrap_index(len, p_from).min(len - 1);
+    elements[..=from]
+        .iter()
+        .rposition(|e| e.value().equals(what))
+        .map_or(-1, |i| i as sys::godot_int)
+}
+
+unsafe extern "C" fn godot_array_find_last(
+    p_self: *const sys::godot_array,
+    p_what: *const sys::godot_variant,
+) -> sys::godot_int {
+    godot_array_rfind(p_self, p_what, -1)
+}
+
+unsafe extern "C" fn godot_array_sort(p_self: *mut sys::godot_array) {
+    elements(p_self).sort_by(|a, b| {
+        if a.value().less(b.value()) {
+            std::cmp::Ordering::Less
+        } else if b.value().less(a.value()) {
+            std::cmp::Ordering::Greater
+        } else {
+            std::cmp::Ordering::Equal
+        }
+    });
+}
+
+unsafe extern "C" fn godot_array_bsearch(
+    p_self: *mut sys::godot_array,
+    p_value: *const sys::godot_variant,
+    p_before: sys::godot_bool,
+) -> sys::godot_int {
+    let value = value::get(p_value);
+    let elements = elements(p_self);
+    let (mut lo, mut hi) = (0, elements.len());
+    while lo < hi {
+        let mid = (lo + hi) / 2;
+        let element = elements[mid].value();
+        let go_right = if p_before {
+            element.less(value)
+        } else {
+            !value.less(element)
+        };
+        if go_right {
+            lo = mid + 1;
+        } else {
+            hi = mid;
+        }
+    }
+    lo as sys::godot_int
+}
+
+unsafe extern "C" fn godot_array_max(p_self: *const sys::godot_array) -> sys::godot_variant {
+    let max =
+        elements(p_self)
+            .iter()
+            .map(VariantSlot::value)
+            .fold(None, |max: Option<&Value>, v| match max {
+                Some(max) if !max.less(v) => Some(max),
+                _ => Some(v),
+            });
+    value::new(max.cloned().unwrap_or(Value::Nil))
+}
+
+unsafe extern "C" fn godot_array_min(p_self: *const sys::godot_array) -> sys::godot_variant {
+    let min =
+        elements(p_self)
+            .iter()
+            .map(VariantSlot::value)
+            .fold(None, |min: Option<&Value>, v| match min {
+                Some(min) if !v.less(min) => Some(min),
+                _ => Some(v),
+            });
+    value::new(min.cloned().unwrap_or(Value::Nil))
+}
+
+unsafe extern "C" fn godot_dictionary_new(r_dest: *mut sys::godot_dictionary) {
+    handle_init::<_, Dictionary>(r_dest, Arc::new(UnsafeCell::new(Vec::new())));
+}
+
+unsafe extern "C" fn godot_dictionary_new_copy(
+    r_dest: *mut sys::godot_dictionary,
+    p_src: *const sys::godot_dictionary,
+) {
+    handle_init(r_dest, dictionary(p_src).clone());
+}
+
+unsafe extern "C" fn godot_dictionary_destroy(p_self: *mut sys::godot_dictionary) {
+    drop(handle_take::<_, Dictionary>(p_self));
+}
+
+unsafe extern "C" fn godot_dictionary_size(p_self: *const sys::godot_dictionary) -> sys::godot_int {
+    entries(p_self).len() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_dictionary_empty(
+    p_self: *const sys::godot_dictionary,
+) -> sys::godot_bool {
+    entries(p_self).is_empty()
+}
+
+unsafe extern "C" fn godot_dictionary_clear(p_self: *mut sys::godot_dictionary) {
+    entries(p_self).clear();
+}
+
+unsafe extern "C" fn godot_dictionary_has(
+    p_self: *const sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+) -> sys::godot_bool {
+    entry(p_self, value::get(p_key)).is_some()
+}
+
+unsafe extern "C" fn godot_dictionary_has_all(
+    p_self: *const sys::godot_dictionary,
+    p_keys: *const sys::godot_array,
+) -> sys::godot_bool {
+    elements(p_keys)
+        .iter()
+        .all(|key| entry(p_self, key.value()).is_some())
+}
+
+unsafe extern "C" fn godot_dictionary_erase(
+    p_self: *mut sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+) {
+    godot_dictionary_erase_with_return(p_self, p_key);
+}
+
+unsafe extern "C" fn godot_dictionary_erase_with_return(
+    p_self: *mut sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+) -> bool {
+    let entries = entries(p_self);
+    let key = value::get(p_key);
+    match entries.iter().position(|e| e.key.value().key_equals(key)) {
+        Some(idx) => {
+            entries.remove(idx);
+            true
+        }
+        None => false,
+    }
+}
+
+unsafe extern "C" fn godot_dictionary_hash(p_self: *const sys::godot_dictionary) -> sys::godot_int {
+    Value::Dictionary(dictionary(p_self).clone()).hash() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_dictionary_keys(
+    p_self: *const sys::godot_dictionary,
+) -> sys::godot_array {
+    let keys = entries(p_self).iter().map(|e| e.key.clone()).collect();
+    new_array(Arc::new(UnsafeCell::new(keys)))
+}
+
+unsafe extern "C" fn godot_dictionary_values(
+    p_self: *const sys::godot_dictionary,
+) -> sys::godot_array {
+    let values = entries(p_self).iter().map(|e| e.value.clone()).collect();
+    new_array(Arc::new(UnsafeCell::new(values)))
+}
+
+unsafe extern "C" fn godot_dictionary_get(
+    p_self: *const sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+) -> sys::godot_variant {
+    let value = entry(p_self, value::get(p_key)).map_or(Value::Nil, |e| e.value.value().clone());
+    value::new(value)
+}
+
+unsafe extern "C" fn godot_dictionary_get_with_default(
+    p_self: *const sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+    p_default: *const sys::godot_variant,
+) -> sys::godot_variant {
+    let value = entry(p_self, value::get(p_key)).map_or_else(
+        || value::get(p_default).clone(),
+        |e| e.value.value().clone(),
+    );
+    value::new(value)
+}
+
+unsafe extern "C" fn godot_dictionary_set(
+    p_self: *mut sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+    p_value: *const sys::godot_variant,
+) {
+    let value = value::get(p_value).clone();
+    value::set(godot_dictionary_operator_index(p_self, p_key), value);
+}
+
+unsafe extern "C" fn godot_dictionary_operator_index(
+    p_self: *mut sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    let key = value::get(p_key);
+    if let Some(entry) = entry(p_self, key) {
+        return &mut entry.value.0;
+    }
+
+    let entries = entries(p_self);
+    entries.push(Box::new(DictionaryEntry {
+        key: VariantSlot::new(key.clone()),
+        value: VariantSlot::new(Value::Nil),
+    }));
+    &mut entries.last_mut().unwrap().value.0
+}
+
+// Like the engine, this inserts missing keys even though the dictionary is `const`.
+unsafe extern "C" fn godot_dictionary_operator_index_const(
+    p_self: *const sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+) -> *const sys::godot_variant {
+    godot_dictionary_operator_index(p_self as *mut _, p_key)
+}
+
+unsafe extern "C" fn godot_dictionary_next(
+    p_self: *const sys::godot_dictionary,
+    p_key: *const sys::godot_variant,
+) -> *mut sys::godot_variant {
+    let entries = entries(p_self);
+    let next = if p_key.is_null() {
+        0
+    } else {
+        let key = value::get(p_key);
+        match entries.iter().position(|e| e.key.value().key_equals(key)) {
+            Some(idx) => idx + 1,
+            None => return ptr::null_mut(),
+        }
+    };
+
+    entries
+        .get_mut(next)
+        .map_or(ptr::null_mut(), |e| &mut e.key.0 as *mut _)
+}
+
+unsafe extern "C" fn godot_dictionary_operator_equal(
+    p_self: *const sys::godot_dictionary,
+    p_b: *const sys::godot_dictionary,
+) -> sys::godot_bool {
+    Arc::ptr_eq(dictionary(p_self), dictionary(p_b))
+}
+
+fn to_json(value: &Value) -> String {
+    fn quote(s: &str) -> String {
+        let mut quoted = String::with_capacity(s.len() + 2);
+        quoted.push('"');
+        for c in s.chars() {
+            match c {
+                '"' => quoted.push_str("\\\""),
+                '\\' => quoted.push_str("\\\\"),
+                '\n' => quoted.push_str("\\n"),
+                '\r' => quoted.push_str("\\r"),
+                '\t' => quoted.push_str("\\t"),
+                c if (c as u32) < 0x20 => quoted.push_str(&format!("\\u{:04x}", c as u32)),
+                c => quoted.push(c),
+            }
+        }
+        quoted.push('"');
+        quoted
+    }
+
+    match value {
+        Value::Nil => "null".to_string(),
+        Value::Bool(b) => b.to_string(),
+        Value::Int(i) => i.to_string(),
+        Value::Real(r) => r.to_string(),
+        Value::String(s) => quote(s),
+        Value::Array(arr) => {
+            let elements = unsafe { &*arr.get() };
+            let elements = elements
+                .iter()
+                .map(|e| to_json(e.value()))
+                .collect::<Vec<_>>();
+            format!("[{}]", elements.join(","))
+        }
+        Value::Dictionary(dict) => {
+            let entries = unsafe { &*dict.get() };
+            let entries = entries
+                .iter()
+                .map(|e| {
+                    format!(
+                        "{}:{}",
+                        quote(&e.key.value().stringify()),
+                        to_json(e.value.value())
+                    )
+                })
+                .collect::<Vec<_>>();
+            format!("{{{}}}", entries.join(","))
+        }
+        other => quote(&other.stringify()),
+    }
+}
+
+unsafe extern "C" fn godot_dictionary_to_json(
+    p_self: *const sys::godot_dictionary,
+) -> sys::godot_string {
+    string::new(to_json(&Value::Dictionary(dictionary(p_self).clone())))
+}
+
+mock_lookup! {
+    pub(super) fn lookup {
+        godot_array_new,
+        godot_array_new_copy,
+        godot_array_destroy,
+        godot_array_duplicate,
+        godot_array_size,
+        godot_array_empty,
+        godot_array_hash,
+        godot_array_get,
+        godot_array_set,
+        godot_array_operator_index,
+        godot_array_operator_index_const,
+        godot_array_push_back,
+        godot_array_push_front,
+        godot_array_pop_back,
+        godot_array_pop_front,
+        godot_array_front,
+        godot_array_back,
+        godot_array_insert,
+        godot_array_remove,
+        godot_array_erase,
+        godot_array_clear,
+        godot_array_resize,
+        godot_array_invert,
+        godot_array_count,
+        godot_array_has,
+        godot_array_find,
+        godot_array_rfind,
+        godot_array_find_last,
+        godot_array_sort,
+        godot_array_bsearch,
+        godot_array_max,
+        godot_array_min,
+        godot_dictionary_new,
+        godot_dictionary_new_copy,
+        godot_dictionary_destroy,
+        godot_dictionary_size,
+        godot_dictionary_empty,
+        godot_dictionary_clear,
+        godot_dictionary_has,
+        godot_dictionary_has_all,
+        godot_dictionary_erase,
+        godot_dictionary_erase_with_return,
+        godot_dictionary_hash,
+        godot_dictionary_keys,
+        godot_dictionary_values,
+        godot_dictionary_get,
+        godot_dictionary_get_with_default,
+        godot_dictionary_set,
+        godot_dictionary_operator_index,
+        godot_dictionary_operator_index_const,
+        godot_dictionary_next,
+        godot_dictionary_operator_equal,
+        godot_dictionary_to_json,
+    }
+}
@@ -0,0 +1,340 @@
+//! A pure Rust stand-in for the engine API, for testing without Godot.
+//!
+//! The mock implements the part of `GodotApi` that gdnative-core itself relies on: strings,
+//! variants and their operators, arrays, dictionaries, pool arrays, node paths, reference
+//! counting and NativeScript class registration. API functions outside of that subset abort the
+//! process with the name of the missing function when called, so a test that strays from the
+//! supported surface fails loudly instead of reading garbage.
+//!
+//! Call [`install`](fn.install.html) before using any engine type. Installing is process-wide
+//! and idempotent, so every test can simply call it first:
+//!
+//! ```ignore
+//! #[test]
+//! fn it_works() {
+//!     gdnative::mock::install();
+//!
+//!     let mut dict = Dictionary::new();
+//!     dict.set(&"foo".into(), &Variant::from_i64(42));
+//!     assert_eq!(Some(42), dict.get(&"foo".into()).try_to_i64());
+//! }
+//! ```
+//!
+//! NativeScript classes can be registered with [`init_nativescript`](fn.init_nativescript.html),
+//! inspected with [`class_info`](fn.class_info.html), and instantiated with
+//! [`MockInstance`](struct.MockInstance.html). Engine classes are not implemented: objects
+//! created by the mock only support reference counting, `is_class` and `get_class`. The only
+//! singleton is `Engine`, whose editor hint is set with
+//! [`set_editor_hint`](fn.set_editor_hint.html).
+//!
+//! This module is available in the crate's own tests, and in other crates when the `mock_api`
+//! feature is enabled.
+
+// Containers are shared across threads exactly like the engine's, without locking.
+#![allow(clippy::arc_with_non_send_sync, clippy::vec_box)]
+
+use crate::sys;
+use libc;
+use std::cell::RefCell;
+use std::mem;
+use std::ptr;
+use std::sync::Once;
+
+macro_rules! mock_lookup {
+    ($vis:vis fn $lookup:ident { $($name:ident,)* }) => {
+        $vis fn $lookup(name: &str) -> Option<*const libc::c_void> {
+            match name {
+                $(stringify!($name) => Some($name as *const libc::c_void),)*
+                _ => None,
+            }
+        }
+    };
+}
+
+mod collections;
+mod object;
+mod operators;
+mod pool_arrays;
+mod string;
+mod value;
+mod variant;
+
+pub use self::object::{
+    class_info, init_nativescript, set_editor_hint, ClassInfo, MethodInfo, MockInstance,
+    PropertyInfo, SignalArgumentInfo, SignalInfo,
+};
+
+/// Installs the mock as the global API, if no API has been bound yet.
+///
+/// # Panics
+///
+/// Panics if a real API has already been bound by the engine.
+pub fn install() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| unsafe {
+        assert!(
+            crate::GODOT_API.is_none(),
+            "the mock API cannot replace an API bound by the engine"
+        );
+
+        crate::GODOT_API = Some(sys::GodotApi::from_lookup(lookup));
+
+        // Mirror `godot_gdnative_init`, which forces the initialization of this table.
+        crate::ReferenceMethodTable::get(crate::get_api());
+    });
+}
+
+fn lookup(name: &'static str) -> Option<*const libc::c_void> {
+    collections::lookup(name)
+        .or_else(|| object::lookup(name))
+        .or_else(|| operators::lookup(name))
+        .or_else(|| pool_arrays::lookup(name))
+        .or_else(|| string::lookup(name))
+        .or_else(|| variant::lookup(name))
+        .or_else(|| lookup_misc(name))
+}
+
+/// Severity of a message logged through the API.
+#[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
+pub enum MessageLevel {
+    Print,
+    Warning,
+    Error,
+}
+
+/// A message logged through `godot_print!`, `godot_warn!` or `godot_error!`.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Message {
+    pub level: MessageLevel,
+    pub text: String,
+}
+
+thread_local! {
+    static MESSAGES: RefCell<Vec<Message>> = RefCell::new(Vec::new());
+}
+
+/// Returns and clears the messages logged on the current thread.
+///
+/// Messages are still printed to the standard streams as they are logged.
+pub fn take_messages() -> Vec<Message> {
+    MESSAGES.with(|messages| mem::take(&mut *messages.borrow_mut()))
+}
+
+fn log(level: MessageLevel, text: String) {
+    match level {
+        MessageLevel::Print => println!("{}", text),
+        MessageLevel::Warning => eprintln!("WARNING: {}", text),
+        MessageLevel::Error => eprintln!("ERROR: {}", text),
+    }
+
+    MESSAGES.with(|messages| messages.borrow_mut().push(Message { level, text }));
+}
+
+// Opaque handles such as `godot_string` are at least pointer-sized. The mock keeps a boxed Rust
+// value behind each of them, with the pointer stored in the first bytes of the handle. Handles
+// are byte arrays without alignment guarantees, hence the unaligned accesses.
+
+unsafe fn handle_ptr<H, T>(handle: *const H) -> *mut T {
+    debug_assert!(mem::size_of::<H>() >= mem::size_of::<*mut T>());
+    ptr::read_unaligned(handle as *const *mut T)
+}
+
+unsafe fn handle_ref<'a, H, T>(handle: *const H) -> &'a mut T {
+    let ptr = handle_ptr::<H, T>(handle);
+    assert!(
+        !ptr.is_null(),
+        "use of an uninitialized or destroyed handle"
+    );
+    &mut *ptr
+}
+
+unsafe fn handle_init<H, T>(handle: *mut H, value: T) {
+    let ptr = Box::into_raw(Box::new(value));
+    ptr::write_unaligned(handle as *mut *mut T, ptr);
+}
+
+unsafe fn handle_take<H, T>(handle: *mut H) -> Option<T> {
+    let ptr = handle_ptr::<H, T>(handle);
+    ptr::write_unaligned(handle as *mut *mut T, ptr::null_mut());
+    if ptr.is_null() {
+        None
+    } else {
+        Some(*Box::from_raw(ptr))
+    }
+}
+
+unsafe fn handle_new<H, T>(value: T) -> H {
+    let mut handle = mem::zeroed();
+    handle_init(&mut handle, value);
+    handle
+}
+
+// Inline math types are plain `real_t` arrays on the engine side.
+
+unsafe fn real_at<T>(val: *const T, idx: usize) -> f32 {
+    assert!(idx < mem::size_of::<T>() / 4, "component out of range");
+    ptr::read_unaligned((val as *const f32).add(idx))
+}
+
+unsafe fn set_real_at<T>(val: *mut T, idx: usize, real: f32) {
+    assert!(idx < mem::size_of::<T>() / 4, "component out of range");
+    ptr::write_unaligned((val as *mut f32).add(idx), real);
+}
+
+unsafe fn c_str<'a>(s: *const libc::c_char) -> &'a str {
+    std::ffi::CStr::from_ptr(s)
+        .to_str()
+        .expect("API strings should be valid UTF-8")
+}
+
+unsafe extern "C" fn godot_print(p_message: *const sys::godot_string) {
+    log(MessageLevel::Print, string::get(p_message).clone());
+}
+
+unsafe extern "C" fn godot_print_warning(
+    p_description: *const libc::c_char,
+    p_function: *const libc::c_char,
+    p_file: *const libc::c_char,
+    p_line: libc::c_int,
+) {
+    let text = format!(
+        "{} (at {}:{} in {})",
+        c_str(p_description),
+        c_str(p_file),
+        p_line,
+        c_str(p_function),
+    );
+    log(MessageLevel::Warning, text);
+}
+
+unsafe extern "C" fn godot_print_error(
+    p_description: *const libc::c_char,
+    p_function: *const libc::c_char,
+    p_file: *const libc::c_char,
+    p_line: libc::c_int,
+) {
+    let text = format!(
+        "{} (at {}:{} in {})",
+        c_str(p_description),
+        c_str(p_file),
+        p_line,
+        c_str(p_function),
+    );
+    log(MessageLevel::Error, text);
+}
+
+unsafe extern "C" fn godot_vector2_get_x(p_self: *const sys::godot_vector2) -> sys::godot_real {
+    real_at(p_self, 0)
+}
+
+unsafe extern "C" fn godot_vector2_get_y(p_self: *const sys::godot_vector2) -> sys::godot_real {
+    real_at(p_self, 1)
+}
+
+unsafe extern "C" fn godot_vector2_set_x(p_self: *mut sys::godot_vector2, p_x: sys::godot_real) {
+    set_real_at(p_self, 0, p_x);
+}
+
+unsafe extern "C" fn godot_vector2_set_y(p_self: *mut sys::godot_vector2, p_y: sys::godot_real) {
+    set_real_at(p_self, 1, p_y);
+}
+
+unsafe extern "C" fn godot_vector3_get_axis(
+    p_self: *const sys::godot_vector3,
+    p_axis: sys::godot_vector3_axis,
+) -> sys::godot_real {
+    real_at(p_self, p_axis as usize)
+}
+
+unsafe extern "C" fn godot_vector3_set_axis(
+    p_self: *mut sys::godot_vector3,
+    p_axis: sys::godot_vector3_axis,
+    p_val: sys::godot_real,
+) {
+    set_real_at(p_self, p_axis as usize, p_val);
+}
+
+unsafe fn hsv(color: *const sys::godot_color) -> (f32, f32, f32) {
+    let (r, g, b) = (real_at(color, 0), real_at(color, 1), real_at(color, 2));
+    let max = r.max(g).max(b);
+    let min = r.min(g).min(b);
+    let delta = max - min;
+
+    let h = if delta == 0.0 {
+        0.0
+    } else {
+        let h = if r == max {
+            (g - b) / delta
+        } else if g == max {
+            2.0 + (b - r) / delta
+        } else {
+            4.0 + (r - g) / delta
+        } / 6.0;
+
+        if h < 0.0 {
+            h + 1.0
+        } else {
+            h
+        }
+    };
+
+    let s = if max == 0.0 { 0.0 } else { delta / max };
+
+    (h, s, max)
+}
+
+unsafe extern "C" fn godot_color_get_h(p_self: *const sys::godot_color) -> sys::godot_real {
+    hsv(p_self).0
+}
+
+unsafe extern "C" fn godot_color_get_s(p_self: *const sys::godot_color) -> sys::godot_real {
+    hsv(p_self).1
+}
+
+unsafe extern "C" fn godot_color_get_v(p_self: *const sys::godot_color) -> sys::godot_real {
+    hsv(p_self).2
+}
+
+unsafe extern "C" fn godot_rid_new(r_dest: *mut sys::godot_rid) {
+    ptr::write_unaligned(r_dest as *mut usize, 0);
+}
+
+unsafe extern "C" fn godot_rid_get_id(p_self: *const sys::godot_rid) -> sys::godot_int {
+    ptr::read_unaligned(p_self as *const usize) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_rid_operator_equal(
+    p_self: *const sys::godot_rid,
+    p_b: *const sys::godot_rid,
+) -> sys::godot_bool {
+    godot_rid_get_id(p_self) == godot_rid_get_id(p_b)
+}
+
+unsafe extern "C" fn godot_rid_operator_less(
+    p_self: *const sys::godot_rid,
+    p_b: *const sys::godot_rid,
+) -> sys::godot_bool {
+    godot_rid_get_id(p_self) < godot_rid_get_id(p_b)
+}
+
+mock_lookup! {
+    fn lookup_misc {
+        godot_print,
+        godot_print_warning,
+        godot_print_error,
+        godot_vector2_get_x,
+        godot_vector2_get_y,
+        godot_vector2_set_x,
+        godot_vector2_set_y,
+        godot_vector3_get_axis,
+        godot_vector3_set_axis,
+        godot_color_get_h,
+        godot_color_get_s,
+        godot_color_get_v,
+        godot_rid_new,
+        godot_rid_get_id,
+        godot_rid_operator_equal,
+        godot_rid_operator_less,
+    }
+}
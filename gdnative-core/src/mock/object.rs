@@ -0,0 +1,1018 @@
+use super::collections;
+use super::value::{self, Value, VariantSlot};
+use super::{c_str, log, string, MessageLevel};
+use crate::init::{InitHandle, PropertyUsage};
+use crate::sys;
+use crate::{Variant, VariantType};
+use libc;
+use std::cell::{Cell, RefCell};
+use std::collections::HashMap;
+use std::ptr;
+
+// Engine classes known to the mock, with their base classes. Objects of these classes can be
+// created with `godot_get_class_constructor`, and can serve as the base of NativeScript
+// classes. Classes deriving from `Reference` are reference-counted.
+macro_rules! builtin_classes {
+    ($($name:ident : $base:expr,)*) => {
+        #[allow(non_snake_case)]
+        mod constructors {
+            use crate::sys;
+
+            $(
+                pub(super) unsafe extern "C" fn $name() -> *mut sys::godot_object {
+                    super::MockObject::create(stringify!($name))
+                }
+            )*
+        }
+
+        static CLASSES: &[(&str, &str, unsafe extern "C" fn() -> *mut sys::godot_object)] = &[
+            $((stringify!($name), $base, constructors::$name),)*
+        ];
+    };
+}
+
+builtin_classes! {
+    Object: "",
+    Reference: "Object",
+    Resource: "Reference",
+    Node: "Object",
+    CanvasItem: "Node",
+    Node2D: "CanvasItem",
+    Control: "CanvasItem",
+    Spatial: "Node",
+    MainLoop: "Object",
+    SceneTree: "MainLoop",
+    Viewport: "Node",
+    Timer: "Node",
+    AnimationPlayer: "Node",
+    CollisionObject2D: "Node2D",
+    PhysicsBody2D: "CollisionObject2D",
+    KinematicBody2D: "PhysicsBody2D",
+    RigidBody2D: "PhysicsBody2D",
+    StaticBody2D: "PhysicsBody2D",
+    Area2D: "CollisionObject2D",
+    Sprite: "Node2D",
+    Camera2D: "Node2D",
+    CollisionObject: "Spatial",
+    PhysicsBody: "CollisionObject",
+    KinematicBody: "PhysicsBody",
+    RigidBody: "PhysicsBody",
+    StaticBody: "PhysicsBody",
+    Area: "CollisionObject",
+    VisualInstance: "Spatial",
+    GeometryInstance: "VisualInstance",
+    MeshInstance: "GeometryInstance",
+    Camera: "Spatial",
+    Container: "Control",
+    Label: "Control",
+    BaseButton: "Control",
+    Button: "BaseButton",
+    Panel: "Control",
+}
+
+fn base_class(class: &str) -> Option<&'static str> {
+    CLASSES
+        .iter()
+        .find(|(name, _, _)| *name == class)
+        .map(|(_, base, _)| *base)
+        .filter(|base| !base.is_empty())
+}
+
+fn inherits(class: &str, ancestor: &str) -> bool {
+    let mut class = Some(class);
+    while let Some(name) = class {
+        if name == ancestor {
+            return true;
+        }
+        class = base_class(name);
+    }
+    ancestor == "Object"
+}
+
+/// The NativeScript instance attached to an object.
+#[derive(Copy, Clone)]
+struct ScriptInstance {
+    class: &'static str,
+    user_data: *mut libc::c_void,
+    type_tag: *const libc::c_void,
+    destroy: sys::godot_instance_destroy_func,
+}
+
+/// The object behind a `godot_object` pointer.
+struct MockObject {
+    class: String,
+    refcounted: bool,
+    refcount: usize,
+    refcount_init: usize,
+    script: Option<ScriptInstance>,
+    connections: Vec<Connection>,
+}
+
+/// A signal connection made with `Object::connect`.
+#[derive(Clone)]
+struct Connection {
+    signal: String,
+    target: *mut sys::godot_object,
+    method: String,
+    binds: Vec<VariantSlot>,
+}
+
+impl MockObject {
+    fn create(class: &str) -> *mut sys::godot_object {
+        let object = MockObject {
+            class: class.to_string(),
+            refcounted: inherits(class, "Reference"),
+            refcount: 1,
+            refcount_init: 1,
+            script: None,
+            connections: Vec::new(),
+        };
+        Box::into_raw(Box::new(object)) as *mut sys::godot_object
+    }
+
+    unsafe fn get<'a>(obj: *const sys::godot_object) -> &'a mut MockObject {
+        assert!(!obj.is_null(), "use of a null object");
+        &mut *(obj as *mut MockObject)
+    }
+
+    fn is_class(&self, class: &str) -> bool {
+        inherits(&self.class, class)
+    }
+
+    fn reference(&mut self) -> bool {
+        if self.refcount == 0 {
+            return false;
+        }
+        self.refcount += 1;
+        true
+    }
+
+    /// Returns `true` if this was the last reference.
+    fn unreference(&mut self) -> bool {
+        self.refcount = self.refcount.saturating_sub(1);
+        self.refcount == 0
+    }
+
+    fn init_ref(&mut self) -> bool {
+        if !self.reference() {
+            return false;
+        }
+        if self.refcount_init > 0 {
+            self.refcount_init -= 1;
+            self.unreference();
+        }
+        true
+    }
+}
+
+/// A reference to an object held by a variant. Reference-counted objects are kept alive while
+/// the variant exists, like `Variant` does with `Ref` in the engine.
+pub(super) struct ObjectRef(*mut sys::godot_object);
+
+impl ObjectRef {
+    pub(super) unsafe fn new(obj: *mut sys::godot_object) -> Self {
+        if !obj.is_null() {
+            let object = MockObject::get(obj);
+            if object.refcounted {
+                object.reference();
+            }
+        }
+        ObjectRef(obj)
+    }
+
+    pub(super) fn ptr(&self) -> *mut sys::godot_object {
+        self.0
+    }
+
+    pub(super) fn is_null(&self) -> bool {
+        self.0.is_null()
+    }
+
+    pub(super) fn stringify(&self) -> String {
+        if self.0.is_null() {
+            "[Object:null]".to_string()
+        } else {
+            let object = unsafe { MockObject::get(self.0) };
+            format!("[{}:{}]", object.class, self.0 as usize)
+        }
+    }
+}
+
+impl Clone for ObjectRef {
+    fn clone(&self) -> Self {
+        unsafe { ObjectRef::new(self.0) }
+    }
+}
+
+impl Drop for ObjectRef {
+    fn drop(&mut self) {
+        unsafe {
+            if !self.0.is_null() {
+                let object = MockObject::get(self.0);
+                if object.refcounted && object.unreference() {
+                    godot_object_destroy(self.0);
+                }
+            }
+        }
+    }
+}
+
+/// A method bind returned by `godot_method_bind_get_method`.
+struct MethodBind {
+    class: String,
+    method: String,
+}
+
+unsafe extern "C" fn godot_method_bind_get_method(
+    p_classname: *const libc::c_char,
+    p_methodname: *const libc::c_char,
+) -> *mut sys::godot_method_bind {
+    // Method tables are initialized once per process, so the binds are simply leaked.
+    let bind = MethodBind {
+        class: c_str(p_classname).to_string(),
+        method: c_str(p_methodname).to_string(),
+    };
+    Box::into_raw(Box::new(bind)) as *mut sys::godot_method_bind
+}
+
+unsafe extern "C" fn godot_method_bind_ptrcall(
+    p_method_bind: *mut sys::godot_method_bind,
+    p_instance: *mut sys::godot_object,
+    p_args: *mut *const libc::c_void,
+    p_ret: *mut libc::c_void,
+) {
+    let bind = &*(p_method_bind as *const MethodBind);
+    let object = MockObject::get(p_instance);
+
+    match bind.method.as_str() {
+        "reference" => *(p_ret as *mut bool) = object.reference(),
+        "unreference" => *(p_ret as *mut bool) = object.unreference(),
+        "init_ref" => *(p_ret as *mut bool) = object.init_ref(),
+        "get_reference_count" => *(p_ret as *mut i64) = object.refcount as i64,
+        "is_class" => {
+            let class = string::get(*p_args as *const sys::godot_string);
+            *(p_ret as *mut bool) = object.is_class(class);
+        }
+        "get_class" => {
+            let class = string::new(object.class.clone());
+            ptr::write_unaligned(p_ret as *mut sys::godot_string, class);
+        }
+        "get_instance_id" => *(p_ret as *mut i64) = p_instance as i64,
+        "is_editor_hint" => *(p_ret as *mut bool) = EDITOR_HINT.with(Cell::get),
+        "connect" => {
+            let args = std::slice::from_raw_parts(p_args, 5);
+            let connection = Connection {
+                signal: string::get(args[0] as *const sys::godot_string).clone(),
+                target: args[1] as *mut sys::godot_object,
+                method: string::get(args[2] as *const sys::godot_string).clone(),
+                binds: (*collections::array(args[3] as *const sys::godot_array).get()).clone(),
+            };
+            let connected = object.connections.iter().any(|c| {
+                c.signal == connection.signal
+                    && c.target == connection.target
+                    && c.method == connection.method
+            });
+            let err = if connection.target.is_null() || connected {
+                sys::godot_error_GODOT_ERR_INVALID_PARAMETER
+            } else {
+                object.connections.push(connection);
+                sys::godot_error_GODOT_OK
+            };
+            *(p_ret as *mut sys::godot_error) = err;
+        }
+        "disconnect" => {
+            let args = std::slice::from_raw_parts(p_args, 3);
+            let signal = string::get(args[0] as *const sys::godot_string);
+            let target = args[1] as *mut sys::godot_object;
+            let method = string::get(args[2] as *const sys::godot_string);
+            object
+                .connections
+                .retain(|c| c.signal != *signal || c.target != target || c.method != *method);
+        }
+        _ => {
+            eprintln!(
+                "Missing method: {}::{} is not implemented by the mock API",
+                bind.class, bind.method
+            );
+            std::process::abort()
+        }
+    }
+}
+
+/// Handles the vararg methods of `Object`. Only `emit_signal` is supported.
+unsafe extern "C" fn godot_method_bind_call(
+    p_method_bind: *mut sys::godot_method_bind,
+    p_instance: *mut sys::godot_object,
+    p_args: *mut *const sys::godot_variant,
+    p_num_args: libc::c_int,
+    p_call_error: *mut sys::godot_variant_call_error,
+) -> sys::godot_variant {
+    let bind = &*(p_method_bind as *const MethodBind);
+    if bind.method != "emit_signal" {
+        eprintln!(
+            "Missing method: {}::{} is not implemented by the mock API",
+            bind.class, bind.method
+        );
+        std::process::abort()
+    }
+
+    let args = std::slice::from_raw_parts(p_args, p_num_args as usize);
+    let signal = value::get(args[0]).stringify();
+
+    // Connections may be added or removed by the handlers, so they are copied first.
+    let connections = MockObject::get(p_instance)
+        .connections
+        .iter()
+        .filter(|c| c.signal == signal)
+        .cloned()
+        .collect::<Vec<_>>();
+
+    for connection in connections {
+        let mut call_args = args[1..].to_vec();
+        call_args.extend(connection.binds.iter().map(|bind| &bind.0 as *const _));
+        match call_script_method(connection.target, &connection.method, &call_args) {
+            Some(ret) => drop(VariantSlot(ret)),
+            None => log(
+                MessageLevel::Error,
+                format!(
+                    "Error calling method from signal '{}': '{}'",
+                    signal, connection.method
+                ),
+            ),
+        }
+    }
+
+    if !p_call_error.is_null() {
+        (*p_call_error).error = sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_OK;
+    }
+    value::new(Value::Nil)
+}
+
+unsafe extern "C" fn godot_get_class_constructor(
+    p_classname: *const libc::c_char,
+) -> sys::godot_class_constructor {
+    let class = c_str(p_classname);
+    CLASSES
+        .iter()
+        .find(|(name, _, _)| *name == class)
+        .map(|(_, _, ctor)| *ctor)
+}
+
+unsafe extern "C" fn godot_object_destroy(p_o: *mut sys::godot_object) {
+    let script = MockObject::get(p_o).script.take();
+    if let Some(script) = script {
+        if let Some(destroy) = script.destroy.destroy_func {
+            destroy(p_o, script.destroy.method_data, script.user_data);
+        }
+    }
+    drop(Box::from_raw(p_o as *mut MockObject));
+}
+
+unsafe extern "C" fn godot_nativescript_get_userdata(
+    p_instance: *mut sys::godot_object,
+) -> *mut libc::c_void {
+    if p_instance.is_null() {
+        return ptr::null_mut();
+    }
+    MockObject::get(p_instance)
+        .script
+        .map_or(ptr::null_mut(), |script| script.user_data)
+}
+
+unsafe extern "C" fn godot_nativescript_get_type_tag(
+    p_object: *const sys::godot_object,
+) -> *const libc::c_void {
+    if p_object.is_null() {
+        return ptr::null();
+    }
+    MockObject::get(p_object)
+        .script
+        .map_or(ptr::null(), |script| script.type_tag)
+}
+
+// NativeScript classes are registered per thread, so that tests running in parallel can each
+// register their own classes.
+
+struct MethodEntry {
+    name: String,
+    rpc_mode: sys::godot_method_rpc_mode,
+    method: sys::godot_instance_method,
+}
+
+struct PropertyEntry {
+    info: PropertyInfo,
+    set: sys::godot_property_set_func,
+    get: sys::godot_property_get_func,
+}
+
+struct ClassEntry {
+    name: &'static str,
+    base: String,
+    tool: bool,
+    create: sys::godot_instance_create_func,
+    destroy: sys::godot_instance_destroy_func,
+    type_tag: *const libc::c_void,
+    methods: Vec<MethodEntry>,
+    properties: Vec<PropertyEntry>,
+    signals: Vec<SignalInfo>,
+}
+
+impl Drop for ClassEntry {
+    fn drop(&mut self) {
+        unsafe {
+            let data = self
+                .methods
+                .iter()
+                .map(|m| (m.method.free_func, m.method.method_data))
+                .chain(self.properties.iter().flat_map(|p| {
+                    vec![
+                        (p.set.free_func, p.set.method_data),
+                        (p.get.free_func, p.get.method_data),
+                    ]
+                }))
+                .chain(Some((self.create.free_func, self.create.method_data)))
+                .chain(Some((self.destroy.free_func, self.destroy.method_data)));
+
+            for (free_func, method_data) in data {
+                if let Some(free_func) = free_func {
+                    free_func(method_data);
+                }
+            }
+        }
+    }
+}
+
+thread_local! {
+    static REGISTRY: RefCell<HashMap<String, ClassEntry>> = RefCell::new(HashMap::new());
+}
+
+fn with_class<F, R>(class: &str, f: F) -> R
+where
+    F: FnOnce(&mut ClassEntry) -> R,
+{
+    REGISTRY.with(|registry| {
+        let mut registry = registry.borrow_mut();
+        let entry = registry
+            .get_mut(class)
+            .unwrap_or_else(|| panic!("class {} is not registered", class));
+        f(entry)
+    })
+}
+
+unsafe fn register_class(
+    name: *const libc::c_char,
+    base: *const libc::c_char,
+    create: sys::godot_instance_create_func,
+    destroy: sys::godot_instance_destroy_func,
+    tool: bool,
+) {
+    let name = c_str(name);
+    let entry = ClassEntry {
+        // Leaked once per registration, so that script instances can refer to their class
+        // without borrowing the registry.
+        name: Box::leak(name.to_string().into_boxed_str()),
+        base: c_str(base).to_string(),
+        tool,
+        create,
+        destroy,
+        type_tag: ptr::null(),
+        methods: Vec::new(),
+        properties: Vec::new(),
+        signals: Vec::new(),
+    };
+
+    // The previous entry is dropped outside of the borrow, since its free functions may run
+    // arbitrary code.
+    let previous = REGISTRY.with(|registry| registry.borrow_mut().insert(name.to_string(), entry));
+    drop(previous);
+}
+
+unsafe extern "C" fn godot_nativescript_register_class(
+    _p_gdnative_handle: *mut libc::c_void,
+    p_name: *const libc::c_char,
+    p_base: *const libc::c_char,
+    p_create_func: sys::godot_instance_create_func,
+    p_destroy_func: sys::godot_instance_destroy_func,
+) {
+    register_class(p_name, p_base, p_create_func, p_destroy_func, false);
+}
+
+unsafe extern "C" fn godot_nativescript_register_tool_class(
+    _p_gdnative_handle: *mut libc::c_void,
+    p_name: *const libc::c_char,
+    p_base: *const libc::c_char,
+    p_create_func: sys::godot_instance_create_func,
+    p_destroy_func: sys::godot_instance_destroy_func,
+) {
+    register_class(p_name, p_base, p_create_func, p_destroy_func, true);
+}
+
+unsafe extern "C" fn godot_nativescript_set_type_tag(
+    _p_gdnative_handle: *mut libc::c_void,
+    p_name: *const libc::c_char,
+    p_type_tag: *const libc::c_void,
+) {
+    with_class(c_str(p_name), |class| class.type_tag = p_type_tag);
+}
+
+unsafe extern "C" fn godot_nativescript_register_method(
+    _p_gdnative_handle: *mut libc::c_void,
+    p_name: *const libc::c_char,
+    p_function_name: *const libc::c_char,
+    p_attr: sys::godot_method_attributes,
+    p_method: sys::godot_instance_method,
+) {
+    let method = MethodEntry {
+        name: c_str(p_function_name).to_string(),
+        rpc_mode: p_attr.rpc_type,
+        method: p_method,
+    };
+
+    let previous = with_class(c_str(p_name), |class| {
+        match class.methods.iter_mut().find(|m| m.name == method.name) {
+            Some(existing) => Some(std::mem::replace(existing, method)),
+            None => {
+                class.methods.push(method);
+                None
+            }
+        }
+    });
+
+    if let Some(MethodEntry { method, .. }) = previous {
+        if let Some(free_func) = method.free_func {
+            free_func(method.method_data);
+        }
+    }
+}
+
+unsafe extern "C" fn godot_nativescript_register_property(
+    _p_gdnative_handle: *mut libc::c_void,
+    p_name: *const libc::c_char,
+    p_path: *const libc::c_char,
+    p_attr: *mut sys::godot_property_attributes,
+    p_set_func: sys::godot_property_set_func,
+    p_get_func: sys::godot_property_get_func,
+) {
+    // The attributes are only borrowed for the duration of the call.
+    let attr = &*p_attr;
+    let info = PropertyInfo {
+        path: c_str(p_path).to_string(),
+        variant_type: VariantType::from_sys(attr.type_ as sys::godot_variant_type),
+        hint: attr.hint,
+        hint_string: string::get(&attr.hint_string).clone(),
+        usage: PropertyUsage::from_bits_truncate(attr.usage as u32),
+        default: Variant::from_sys(value::new(value::get(&attr.default_value).clone())),
+        rset_mode: attr.rset_type,
+    };
+
+    with_class(c_str(p_name), |class| {
+        class.properties.push(PropertyEntry {
+            info,
+            set: p_set_func,
+            get: p_get_func,
+        })
+    });
+}
+
+unsafe extern "C" fn godot_nativescript_register_signal(
+    _p_gdnative_handle: *mut libc::c_void,
+    p_name: *const libc::c_char,
+    p_signal: *const sys::godot_signal,
+) {
+    let signal = &*p_signal;
+    let args = if signal.num_args > 0 {
+        std::slice::from_raw_parts(signal.args, signal.num_args as usize)
+    } else {
+        &[]
+    };
+
+    let info = SignalInfo {
+        name: string::get(&signal.name).clone(),
+        args: args
+            .iter()
+            .map(|arg| SignalArgumentInfo {
+                name: string::get(&arg.name).clone(),
+                variant_type: VariantType::from_sys(arg.type_ as sys::godot_variant_type),
+                hint: arg.hint,
+                hint_string: string::get(&arg.hint_string).clone(),
+                usage: PropertyUsage::from_bits_truncate(arg.usage as u32),
+                default: Variant::from_sys(value::new(value::get(&arg.default_value).clone())),
+            })
+            .collect(),
+    };
+
+    with_class(c_str(p_name), |class| class.signals.push(info));
+}
+
+/// Whether `obj` has a NativeScript method called `method`.
+pub(super) unsafe fn has_method(obj: *mut sys::godot_object, method: &str) -> bool {
+    let script = match MockObject::get(obj).script {
+        Some(script) => script,
+        None => return false,
+    };
+
+    REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .get(script.class)
+            .map(|class| class.methods.iter().any(|m| m.name == method))
+            .unwrap_or(false)
+    })
+}
+
+/// Calls a NativeScript method of `obj`. Returns `None` if there is no such method.
+pub(super) unsafe fn call_script_method(
+    obj: *mut sys::godot_object,
+    method: &str,
+    args: &[*const sys::godot_variant],
+) -> Option<sys::godot_variant> {
+    let script = MockObject::get(obj).script?;
+
+    // Copy the callback out of the registry first: the method may register or instance
+    // classes itself.
+    let callback = REGISTRY.with(|registry| {
+        registry
+            .borrow()
+            .get(script.class)
+            .and_then(|class| class.methods.iter().find(|m| m.name == method))
+            .map(|m| m.method)
+    })?;
+
+    let mut args = args
+        .iter()
+        .map(|&arg| arg as *mut sys::godot_variant)
+        .collect::<Vec<_>>();
+
+    let method_fn = callback.method?;
+    Some(method_fn(
+        obj,
+        callback.method_data,
+        script.user_data,
+        args.len() as libc::c_int,
+        args.as_mut_ptr(),
+    ))
+}
+
+/// Runs NativeScript initialization code against the mock, as `godot_nativescript_init` would.
+///
+/// This installs the mock API if necessary. Registered classes are only visible on the
+/// current thread. Registering a class again replaces the previous registration.
+///
+/// ```ignore
+/// gdnative::mock::init_nativescript(|handle| {
+///     handle.add_class::<MyClass>();
+/// });
+/// ```
+pub fn init_nativescript<F>(f: F)
+where
+    F: FnOnce(InitHandle),
+{
+    super::install();
+    f(unsafe { InitHandle::new(ptr::null_mut()) });
+}
+
+/// Describes a NativeScript class registered with the mock.
+#[derive(Clone, Debug)]
+pub struct ClassInfo {
+    pub name: String,
+    pub base: String,
+    pub tool: bool,
+    pub methods: Vec<MethodInfo>,
+    pub properties: Vec<PropertyInfo>,
+    pub signals: Vec<SignalInfo>,
+}
+
+impl ClassInfo {
+    /// Returns the method called `name`, if any.
+    pub fn method(&self, name: &str) -> Option<&MethodInfo> {
+        self.methods.iter().find(|m| m.name == name)
+    }
+
+    /// Returns the property at `path`, if any.
+    pub fn property(&self, path: &str) -> Option<&PropertyInfo> {
+        self.properties.iter().find(|p| p.path == path)
+    }
+
+    /// Returns the signal called `name`, if any.
+    pub fn signal(&self, name: &str) -> Option<&SignalInfo> {
+        self.signals.iter().find(|s| s.name == name)
+    }
+}
+
+/// A method as registered through `godot_nativescript_register_method`.
+#[derive(Clone, Debug)]
+pub struct MethodInfo {
+    pub name: String,
+    pub rpc_mode: sys::godot_method_rpc_mode,
+}
+
+/// A property as registered through `godot_nativescript_register_property`.
+#[derive(Clone, Debug)]
+pub struct PropertyInfo {
+    pub path: String,
+    pub variant_type: VariantType,
+    pub hint: sys::godot_property_hint,
+    pub hint_string: String,
+    pub usage: PropertyUsage,
+    pub default: Variant,
+    pub rset_mode: sys::godot_method_rpc_mode,
+}
+
+/// A signal as registered through `godot_nativescript_register_signal`.
+#[derive(Clone, Debug)]
+pub struct SignalInfo {
+    pub name: String,
+    pub args: Vec<SignalArgumentInfo>,
+}
+
+/// An argument of a registered signal.
+#[derive(Clone, Debug)]
+pub struct SignalArgumentInfo {
+    pub name: String,
+    pub variant_type: VariantType,
+    pub hint: sys::godot_property_hint,
+    pub hint_string: String,
+    pub usage: PropertyUsage,
+    pub default: Variant,
+}
+
+/// Returns what has been registered for the class called `name` on the current thread.
+pub fn class_info(name: &str) -> Option<ClassInfo> {
+    REGISTRY.with(|registry| {
+        registry.borrow().get(name).map(|class| ClassInfo {
+            name: class.name.to_string(),
+            base: class.base.clone(),
+            tool: class.tool,
+            methods: class
+                .methods
+                .iter()
+                .map(|m| MethodInfo {
+                    name: m.name.clone(),
+                    rpc_mode: m.rpc_mode,
+                })
+                .collect(),
+            properties: class.properties.iter().map(|p| p.info.clone()).collect(),
+            signals: class.signals.clone(),
+        })
+    })
+}
+
+/// An instance of a registered NativeScript class, attached to a new object of its base class.
+///
+/// The object is freed when the instance is dropped, or when the last reference to it goes
+/// away if the base class is reference-counted.
+pub struct MockInstance {
+    owner: *mut sys::godot_object,
+}
+
+impl MockInstance {
+    /// Creates an instance of the class called `class`, which must have been registered on the
+    /// current thread. Returns `None` if there is no such class.
+    pub fn new(class: &str) -> Option<Self> {
+        let (name, base, create, destroy, type_tag) = REGISTRY.with(|registry| {
+            registry.borrow().get(class).map(|class| {
+                (
+                    class.name,
+                    class.base.clone(),
+                    class.create,
+                    class.destroy,
+                    class.type_tag,
+                )
+            })
+        })?;
+
+        if !CLASSES.iter().any(|(class, _, _)| *class == base) {
+            log(
+                MessageLevel::Warning,
+                format!(
+                    "{} is not known to the mock API, creating {} on a plain Object",
+                    base, name
+                ),
+            );
+        }
+
+        unsafe {
+            let owner = MockObject::create(&base);
+            MockObject::get(owner).script = Some(ScriptInstance {
+                class: name,
+                user_data: ptr::null_mut(),
+                type_tag,
+                destroy,
+            });
+
+            if let Some(create_func) = create.create_func {
+                let user_data = create_func(owner, create.method_data);
+                if let Some(script) = MockObject::get(owner).script.as_mut() {
+                    script.user_data = user_data;
+                }
+            }
+
+            let object = MockObject::get(owner);
+            if object.refcounted {
+                object.init_ref();
+            }
+
+            Some(MockInstance { owner })
+        }
+    }
+
+    /// Returns the object the script is attached to.
+    pub fn owner(&self) -> *mut sys::godot_object {
+        self.owner
+    }
+
+    /// Returns a variant referring to the owner.
+    pub fn to_variant(&self) -> Variant {
+        unsafe { Variant::from_sys(value::new(Value::Object(ObjectRef::new(self.owner)))) }
+    }
+
+    /// Calls a registered method. Returns `None` if there is no such method.
+    pub fn call(&self, method: &str, args: &[Variant]) -> Option<Variant> {
+        let args = args.iter().map(Variant::sys).collect::<Vec<_>>();
+        unsafe { call_script_method(self.owner, method, &args).map(Variant::from_sys) }
+    }
+
+    /// Reads a registered property. Returns `None` if there is no such property.
+    pub fn get(&self, path: &str) -> Option<Variant> {
+        let (class, user_data) = self.script();
+        let get = REGISTRY.with(|registry| {
+            let registry = registry.borrow();
+            let class = registry.get(class)?;
+            class
+                .properties
+                .iter()
+                .find(|p| p.info.path == path)
+                .map(|p| p.get)
+        })?;
+
+        let get_func = get.get_func?;
+        unsafe {
+            Some(Variant::from_sys(get_func(
+                self.owner,
+                get.method_data,
+                user_data,
+            )))
+        }
+    }
+
+    /// Writes a registered property. Returns `false` if there is no such property.
+    pub fn set(&self, path: &str, value: &Variant) -> bool {
+        let (class, user_data) = self.script();
+        let set = REGISTRY.with(|registry| {
+            let registry = registry.borrow();
+            let class = registry.get(class)?;
+            class
+                .properties
+                .iter()
+                .find(|p| p.info.path == path)
+                .map(|p| p.set)
+        });
+
+        match set.and_then(|set| set.set_func.map(|f| (f, set.method_data))) {
+            Some((set_func, method_data)) => {
+                let mut value = value.clone();
+                unsafe {
+                    set_func(self.owner, method_data, user_data, &mut value.0);
+                }
+                true
+            }
+            None => false,
+        }
+    }
+
+    fn script(&self) -> (&'static str, *mut libc::c_void) {
+        let script = unsafe { MockObject::get(self.owner).script }
+            .expect("the script of a MockInstance should not be detached");
+        (script.class, script.user_data)
+    }
+}
+
+impl Drop for MockInstance {
+    fn drop(&mut self) {
+        unsafe {
+            let object = MockObject::get(self.owner);
+            if !object.refcounted || object.unreference() {
+                godot_object_destroy(self.owner);
+            }
+        }
+    }
+}
+
+thread_local! {
+    static ENGINE: *mut sys::godot_object = MockObject::create("_Engine");
+    static EDITOR_HINT: Cell<bool> = Cell::new(false);
+}
+
+/// Sets the value returned by `Engine.is_editor_hint` on the current thread. It is `false`
+/// until set.
+pub fn set_editor_hint(editor_hint: bool) {
+    EDITOR_HINT.with(|hint| hint.set(editor_hint));
+}
+
+/// Only the `Engine` singleton is available.
+unsafe extern "C" fn godot_global_get_singleton(
+    p_name: *mut libc::c_char,
+) -> *mut sys::godot_object {
+    match c_str(p_name) {
+        "Engine" => ENGINE.with(|engine| *engine),
+        name => {
+            eprintln!(
+                "Missing singleton: {} is not implemented by the mock API",
+                name
+            );
+            std::process::abort()
+        }
+    }
+}
+
+mock_lookup! {
+    pub(super) fn lookup {
+        godot_global_get_singleton,
+        godot_method_bind_get_method,
+        godot_method_bind_ptrcall,
+        godot_method_bind_call,
+        godot_get_class_constructor,
+        godot_object_destroy,
+        godot_nativescript_get_userdata,
+        godot_nativescript_get_type_tag,
+        godot_nativescript_register_class,
+        godot_nativescript_register_tool_class,
+        godot_nativescript_set_type_tag,
+        godot_nativescript_register_method,
+        godot_nativescript_register_property,
+        godot_nativescript_register_signal,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::{ClassBuilder, Property, PropertyHint, PropertyUsage};
+    use crate::user_data::MutexData;
+    use crate::{NativeClass, NativeClassMethods, Reference};
+
+    struct Counter {
+        count: i64,
+    }
+
+    impl NativeClass for Counter {
+        type Base = Reference;
+        type UserData = MutexData<Counter>;
+
+        fn class_name() -> &'static str {
+            "Counter"
+        }
+
+        fn init(_owner: Reference) -> Self {
+            Counter { count: 0 }
+        }
+
+        fn register_properties(builder: &ClassBuilder<Self>) {
+            builder.add_property(Property {
+                name: "count",
+                default: 0,
+                hint: PropertyHint::None,
+                getter: |this: &Counter| this.count,
+                setter: |this: &mut Counter, count| this.count = count,
+                usage: PropertyUsage::DEFAULT,
+            });
+        }
+    }
+
+    impl Counter {
+        fn increment(&mut self, _owner: Reference, by: i64) -> i64 {
+            self.count += by;
+            self.count
+        }
+    }
+
+    impl NativeClassMethods for Counter {
+        fn register(builder: &ClassBuilder<Self>) {
+            builder.add_method(
+                "increment",
+                godot_wrap_method!(Counter, fn increment(&mut self, owner: Reference, by: i64) -> i64),
+            );
+        }
+    }
+
+    #[test]
+    fn register_and_instance() {
+        init_nativescript(|handle| handle.add_class::<Counter>());
+
+        let info = class_info("Counter").expect("class should be registered");
+        assert_eq!("Reference", info.base);
+        assert!(info.method("increment").is_some());
+        assert_eq!(
+            Some(0),
+            info.property("count").and_then(|p| p.default.try_to_i64())
+        );
+
+        let instance = MockInstance::new("Counter").unwrap();
+        let result = instance.call("increment", &[Variant::from_i64(5)]);
+        assert_eq!(Some(5), result.and_then(|v| v.try_to_i64()));
+
+        assert!(instance.set("count", &Variant::from_i64(2)));
+        assert_eq!(Some(2), instance.get("count").and_then(|v| v.try_to_i64()));
+        assert!(instance.call("missing", &[]).is_none());
+    }
+}
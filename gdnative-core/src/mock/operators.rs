@@ -0,0 +1,545 @@
+//! `godot_variant_evaluate`, following `Variant::evaluate` of Godot 3.2 for numbers, booleans,
+//! strings, vectors, colors, quaternions, matrices, arrays and dictionaries.
+//!
+//! Operators on other types, like comparisons of arrays or `in` on objects, are reported as
+//! invalid.
+
+use super::pool_arrays::PoolElement;
+use super::value::{self, Array, Value, VariantSlot};
+use super::{real_at, set_real_at};
+use crate::sys;
+use crate::{QuatGodot, Transform2DGodot};
+use std::cell::UnsafeCell;
+use std::cmp::Ordering;
+use std::mem;
+use std::sync::Arc;
+
+unsafe extern "C" fn godot_variant_evaluate(
+    p_op: sys::godot_variant_operator,
+    p_a: *const sys::godot_variant,
+    p_b: *const sys::godot_variant,
+    r_ret: *mut sys::godot_variant,
+    r_valid: *mut sys::godot_bool,
+) {
+    let result = evaluate(p_op, value::get(p_a), value::get(p_b));
+    *r_valid = result.is_some();
+    value::set(r_ret, result.unwrap_or(Value::Nil));
+}
+
+mock_lookup! {
+    pub(super) fn lookup {
+        godot_variant_evaluate,
+    }
+}
+
+fn evaluate(op: sys::godot_variant_operator, a: &Value, b: &Value) -> Option<Value> {
+    let result = match op {
+        sys::godot_variant_operator_GODOT_VARIANT_OP_EQUAL => Value::Bool(equal(a, b)?),
+        sys::godot_variant_operator_GODOT_VARIANT_OP_NOT_EQUAL => Value::Bool(!equal(a, b)?),
+        sys::godot_variant_operator_GODOT_VARIANT_OP_LESS => {
+            Value::Bool(order(a, b)? == Some(Ordering::Less))
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_LESS_EQUAL => {
+            Value::Bool(order(a, b)?.map(|o| o != Ordering::Greater) == Some(true))
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_GREATER => {
+            Value::Bool(order(a, b)? == Some(Ordering::Greater))
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_GREATER_EQUAL => {
+            Value::Bool(order(a, b)?.map(|o| o != Ordering::Less) == Some(true))
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_ADD => add(a, b)?,
+        sys::godot_variant_operator_GODOT_VARIANT_OP_SUBTRACT => subtract(a, b)?,
+        sys::godot_variant_operator_GODOT_VARIANT_OP_MULTIPLY => multiply(a, b)?,
+        sys::godot_variant_operator_GODOT_VARIANT_OP_DIVIDE => divide(a, b)?,
+        sys::godot_variant_operator_GODOT_VARIANT_OP_NEGATE => negate(a)?,
+        sys::godot_variant_operator_GODOT_VARIANT_OP_POSITIVE => match a {
+            Value::Int(_)
+            | Value::Real(_)
+            | Value::Vector2(_)
+            | Value::Vector3(_)
+            | Value::Plane(_)
+            | Value::Quat(_)
+            | Value::Color(_) => a.clone(),
+            _ => return None,
+        },
+        sys::godot_variant_operator_GODOT_VARIANT_OP_MODULE => match (a, b) {
+            (Value::Int(_), Value::Int(0)) => return None,
+            (Value::Int(a), Value::Int(b)) => Value::Int(a.wrapping_rem(*b)),
+            (Value::String(format), Value::Array(args)) => {
+                let args = unsafe { &*args.get() };
+                Value::String(sprintf(format, args.iter().map(VariantSlot::value))?)
+            }
+            (Value::String(format), arg) => Value::String(sprintf(format, std::iter::once(arg))?),
+            _ => return None,
+        },
+        sys::godot_variant_operator_GODOT_VARIANT_OP_STRING_CONCAT => {
+            Value::String(a.stringify() + &b.stringify())
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_SHIFT_LEFT => match (a, b) {
+            (Value::Int(a), Value::Int(b)) if *b >= 0 && *b < 64 => Value::Int(a << b),
+            _ => return None,
+        },
+        sys::godot_variant_operator_GODOT_VARIANT_OP_SHIFT_RIGHT => match (a, b) {
+            (Value::Int(a), Value::Int(b)) if *b >= 0 && *b < 64 => Value::Int(a >> b),
+            _ => return None,
+        },
+        sys::godot_variant_operator_GODOT_VARIANT_OP_BIT_AND => {
+            let (a, b) = ints(a, b)?;
+            Value::Int(a & b)
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_BIT_OR => {
+            let (a, b) = ints(a, b)?;
+            Value::Int(a | b)
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_BIT_XOR => {
+            let (a, b) = ints(a, b)?;
+            Value::Int(a ^ b)
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_BIT_NEGATE => match a {
+            Value::Int(a) => Value::Int(!a),
+            _ => return None,
+        },
+        sys::godot_variant_operator_GODOT_VARIANT_OP_AND => {
+            Value::Bool(a.booleanize() && b.booleanize())
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_OR => {
+            Value::Bool(a.booleanize() || b.booleanize())
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_XOR => {
+            Value::Bool(a.booleanize() != b.booleanize())
+        }
+        sys::godot_variant_operator_GODOT_VARIANT_OP_NOT => Value::Bool(!a.booleanize()),
+        sys::godot_variant_operator_GODOT_VARIANT_OP_IN => Value::Bool(contains(b, a)?),
+        _ => return None,
+    };
+
+    Some(result)
+}
+
+/// A number operand. Booleans are not numbers for arithmetic.
+#[derive(Copy, Clone)]
+enum Num {
+    Int(i64),
+    Real(f64),
+}
+
+impl Num {
+    fn of(value: &Value) -> Option<Num> {
+        match value {
+            Value::Int(i) => Some(Num::Int(*i)),
+            Value::Real(r) => Some(Num::Real(*r)),
+            _ => None,
+        }
+    }
+
+    fn real(self) -> f64 {
+        match self {
+            Num::Int(i) => i as f64,
+            Num::Real(r) => r,
+        }
+    }
+}
+
+/// Applies an arithmetic operator to two numbers, promoting integers to floats if either of
+/// them is a float. `None` if they are not both numbers or if `int_op` fails.
+fn numbers(
+    a: &Value,
+    b: &Value,
+    int_op: fn(i64, i64) -> Option<i64>,
+    real_op: fn(f64, f64) -> f64,
+) -> Option<Option<Value>> {
+    match (Num::of(a)?, Num::of(b)?) {
+        (Num::Int(a), Num::Int(b)) => Some(int_op(a, b).map(Value::Int)),
+        (a, b) => Some(Some(Value::Real(real_op(a.real(), b.real())))),
+    }
+}
+
+fn ints(a: &Value, b: &Value) -> Option<(i64, i64)> {
+    match (a, b) {
+        (Value::Int(a), Value::Int(b)) => Some((*a, *b)),
+        _ => None,
+    }
+}
+
+fn equal(a: &Value, b: &Value) -> Option<bool> {
+    let comparable = match (a, b) {
+        (Value::Nil, _) | (_, Value::Nil) => true,
+        _ => a.variant_type() == b.variant_type() || (Num::of(a).is_some() && Num::of(b).is_some()),
+    };
+
+    if comparable {
+        Some(a.equals(b))
+    } else {
+        None
+    }
+}
+
+/// The result of `<` and `>`: `None` if the values cannot be compared, `Some(None)` if they are
+/// unordered, like `NaN` and a number.
+fn order(a: &Value, b: &Value) -> Option<Option<Ordering>> {
+    let ordering = unsafe {
+        match (a, b) {
+            (Value::Bool(a), Value::Bool(b)) => a.partial_cmp(b),
+            (Value::Int(a), Value::Int(b)) => a.partial_cmp(b),
+            (Value::String(a), Value::String(b)) => a.partial_cmp(b),
+            (Value::Vector2(a), Value::Vector2(b)) => reals_cmp(a, b),
+            (Value::Vector3(a), Value::Vector3(b)) => reals_cmp(a, b),
+            (Value::Rid(a), Value::Rid(b)) => a.partial_cmp(b),
+            (Value::Object(a), Value::Object(b)) => a.ptr().partial_cmp(&b.ptr()),
+            _ => Num::of(a)?.real().partial_cmp(&Num::of(b)?.real()),
+        }
+    };
+    Some(ordering)
+}
+
+fn add(a: &Value, b: &Value) -> Option<Value> {
+    if let Some(result) = numbers(a, b, |a, b| Some(a.wrapping_add(b)), |a, b| a + b) {
+        return result;
+    }
+
+    let result = unsafe {
+        match (a, b) {
+            (Value::String(a), Value::String(b)) => Value::String(a.clone() + b),
+            (Value::Vector2(a), Value::Vector2(b)) => Value::Vector2(zip_reals(a, b, |a, b| a + b)),
+            (Value::Vector3(a), Value::Vector3(b)) => Value::Vector3(zip_reals(a, b, |a, b| a + b)),
+            (Value::Quat(a), Value::Quat(b)) => Value::Quat(zip_reals(a, b, |a, b| a + b)),
+            (Value::Color(a), Value::Color(b)) => Value::Color(zip_reals(a, b, |a, b| a + b)),
+            (Value::Array(a), Value::Array(b)) => {
+                let mut elements = (*a.get()).clone();
+                elements.extend((*b.get()).iter().cloned());
+                Value::Array(new_array(elements))
+            }
+            _ => return None,
+        }
+    };
+    Some(result)
+}
+
+fn subtract(a: &Value, b: &Value) -> Option<Value> {
+    if let Some(result) = numbers(a, b, |a, b| Some(a.wrapping_sub(b)), |a, b| a - b) {
+        return result;
+    }
+
+    let result = unsafe {
+        match (a, b) {
+            (Value::Vector2(a), Value::Vector2(b)) => Value::Vector2(zip_reals(a, b, |a, b| a - b)),
+            (Value::Vector3(a), Value::Vector3(b)) => Value::Vector3(zip_reals(a, b, |a, b| a - b)),
+            (Value::Quat(a), Value::Quat(b)) => Value::Quat(zip_reals(a, b, |a, b| a - b)),
+            (Value::Color(a), Value::Color(b)) => Value::Color(zip_reals(a, b, |a, b| a - b)),
+            _ => return None,
+        }
+    };
+    Some(result)
+}
+
+fn multiply(a: &Value, b: &Value) -> Option<Value> {
+    if let Some(result) = numbers(a, b, |a, b| Some(a.wrapping_mul(b)), |a, b| a * b) {
+        return result;
+    }
+
+    let result = unsafe {
+        match (a, b) {
+            (Value::Vector2(a), Value::Vector2(b)) => Value::Vector2(zip_reals(a, b, |a, b| a * b)),
+            (Value::Vector3(a), Value::Vector3(b)) => Value::Vector3(zip_reals(a, b, |a, b| a * b)),
+            (Value::Color(a), Value::Color(b)) => Value::Color(zip_reals(a, b, |a, b| a * b)),
+            (Value::Quat(q), Value::Vector3(v)) => {
+                let q: crate::Quat = mem::transmute_copy(q);
+                Value::Vector3(mem::transmute_copy(&q.xform(mem::transmute_copy(v))))
+            }
+            (Value::Basis(a), Value::Basis(b)) => {
+                let product = crate::Basis::from_sys(*a) * crate::Basis::from_sys(*b);
+                Value::Basis(mem::transmute::<crate::Basis, sys::godot_basis>(product))
+            }
+            (Value::Basis(a), Value::Vector3(v)) => {
+                let v = crate::Basis::from_sys(*a).xform(mem::transmute_copy(v));
+                Value::Vector3(mem::transmute_copy(&v))
+            }
+            (Value::Transform(a), Value::Transform(b)) => {
+                let product = crate::Transform::from_sys(*a) * crate::Transform::from_sys(*b);
+                Value::Transform(mem::transmute::<crate::Transform, sys::godot_transform>(
+                    product,
+                ))
+            }
+            (Value::Transform(a), Value::Vector3(v)) => {
+                let v = crate::Transform::from_sys(*a).xform(mem::transmute_copy(v));
+                Value::Vector3(mem::transmute_copy(&v))
+            }
+            (Value::Transform2D(a), Value::Transform2D(b)) => {
+                let a: crate::Transform2D = mem::transmute_copy(a);
+                let b: crate::Transform2D = mem::transmute_copy(b);
+                let product = crate::Transform2D::from_axis_origin(
+                    a.basis_xform(b.x_axis()),
+                    a.basis_xform(b.y_axis()),
+                    a.xform(b.origin()),
+                );
+                Value::Transform2D(mem::transmute_copy(&product))
+            }
+            (Value::Transform2D(a), Value::Vector2(v)) => {
+                let a: crate::Transform2D = mem::transmute_copy(a);
+                let v = a.xform(mem::transmute_copy(v));
+                Value::Vector2(mem::transmute_copy(&v))
+            }
+            (Value::Vector2(v), n) | (n, Value::Vector2(v)) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Vector2(map_reals(v, |v| v * n))
+            }
+            (Value::Vector3(v), n) | (n, Value::Vector3(v)) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Vector3(map_reals(v, |v| v * n))
+            }
+            (Value::Color(c), n) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Color(map_reals(c, |c| c * n))
+            }
+            (Value::Quat(q), n) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Quat(map_reals(q, |q| q * n))
+            }
+            _ => return None,
+        }
+    };
+    Some(result)
+}
+
+fn divide(a: &Value, b: &Value) -> Option<Value> {
+    let int_div = |a: i64, b: i64| {
+        if b == 0 {
+            None
+        } else {
+            Some(a.wrapping_div(b))
+        }
+    };
+    if let Some(result) = numbers(a, b, int_div, |a, b| a / b) {
+        return result;
+    }
+
+    let result = unsafe {
+        match (a, b) {
+            (Value::Vector2(a), Value::Vector2(b)) => Value::Vector2(zip_reals(a, b, |a, b| a / b)),
+            (Value::Vector3(a), Value::Vector3(b)) => Value::Vector3(zip_reals(a, b, |a, b| a / b)),
+            (Value::Color(a), Value::Color(b)) => Value::Color(zip_reals(a, b, |a, b| a / b)),
+            (Value::Vector2(v), n) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Vector2(map_reals(v, |v| v / n))
+            }
+            (Value::Vector3(v), n) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Vector3(map_reals(v, |v| v / n))
+            }
+            (Value::Color(c), n) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Color(map_reals(c, |c| c / n))
+            }
+            (Value::Quat(q), n) => {
+                let n = Num::of(n)?.real() as f32;
+                Value::Quat(map_reals(q, |q| q / n))
+            }
+            _ => return None,
+        }
+    };
+    Some(result)
+}
+
+fn negate(a: &Value) -> Option<Value> {
+    let result = unsafe {
+        match a {
+            Value::Int(i) => Value::Int(i.wrapping_neg()),
+            Value::Real(r) => Value::Real(-r),
+            Value::Vector2(v) => Value::Vector2(map_reals(v, |v| -v)),
+            Value::Vector3(v) => Value::Vector3(map_reals(v, |v| -v)),
+            Value::Plane(p) => Value::Plane(map_reals(p, |p| -p)),
+            Value::Quat(q) => Value::Quat(map_reals(q, |q| -q)),
+            // Colors are inverted.
+            Value::Color(c) => Value::Color(map_reals(c, |c| 1.0 - c)),
+            _ => return None,
+        }
+    };
+    Some(result)
+}
+
+/// Implements `element in container`.
+fn contains(container: &Value, element: &Value) -> Option<bool> {
+    fn in_pool<T: PoolElement>(pool: &[T], element: &Value) -> bool {
+        pool.iter().any(|e| e.to_value().equals(element))
+    }
+
+    let found = unsafe {
+        match container {
+            Value::String(s) => match element {
+                Value::String(element) => s.contains(element.as_str()),
+                _ => return None,
+            },
+            Value::Array(arr) => (*arr.get()).iter().any(|e| e.value().equals(element)),
+            Value::Dictionary(dict) => (*dict.get())
+                .iter()
+                .any(|e| e.key.value().key_equals(element)),
+            Value::ByteArray(pool) => in_pool(pool, element),
+            Value::IntArray(pool) => in_pool(pool, element),
+            Value::RealArray(pool) => in_pool(pool, element),
+            Value::StringArray(pool) => in_pool(pool, element),
+            Value::Vector2Array(pool) => in_pool(pool, element),
+            Value::Vector3Array(pool) => in_pool(pool, element),
+            Value::ColorArray(pool) => in_pool(pool, element),
+            _ => return None,
+        }
+    };
+    Some(found)
+}
+
+fn new_array(elements: Vec<VariantSlot>) -> Array {
+    Arc::new(UnsafeCell::new(elements))
+}
+
+unsafe fn zip_reals<T: Copy>(a: &T, b: &T, op: impl Fn(f32, f32) -> f32) -> T {
+    let mut out = *a;
+    for i in 0..mem::size_of::<T>() / 4 {
+        set_real_at(&mut out, i, op(real_at(a, i), real_at(b, i)));
+    }
+    out
+}
+
+unsafe fn map_reals<T: Copy>(a: &T, op: impl Fn(f32) -> f32) -> T {
+    let mut out = *a;
+    for i in 0..mem::size_of::<T>() / 4 {
+        set_real_at(&mut out, i, op(real_at(a, i)));
+    }
+    out
+}
+
+unsafe fn reals_cmp<T>(a: &T, b: &T) -> Option<Ordering> {
+    for i in 0..mem::size_of::<T>() / 4 {
+        let (a, b) = (real_at(a, i), real_at(b, i));
+        if a != b {
+            return a.partial_cmp(&b);
+        }
+    }
+    Some(Ordering::Equal)
+}
+
+/// `String::sprintf` with the `s`, `c`, `d`, `o`, `x`, `X` and `f` conversions, the `-`, `+` and
+/// `0` flags, width and precision. Fails on missing, superfluous or mistyped arguments.
+fn sprintf<'a, I>(format: &str, args: I) -> Option<String>
+where
+    I: Iterator<Item = &'a Value>,
+{
+    let mut args = args.peekable();
+    let mut out = String::new();
+    let mut chars = format.chars().peekable();
+
+    while let Some(c) = chars.next() {
+        if c != '%' {
+            out.push(c);
+            continue;
+        }
+
+        let mut left_justified = false;
+        let mut show_sign = false;
+        let mut pad_with_zeros = false;
+        loop {
+            match chars.peek() {
+                Some('-') => left_justified = true,
+                Some('+') => show_sign = true,
+                Some('0') => pad_with_zeros = true,
+                _ => break,
+            }
+            chars.next();
+        }
+
+        let mut width = 0;
+        while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+            width = width * 10 + digit as usize;
+            chars.next();
+        }
+
+        let mut precision = None;
+        if chars.peek() == Some(&'.') {
+            chars.next();
+            let mut digits = 0;
+            while let Some(digit) = chars.peek().and_then(|c| c.to_digit(10)) {
+                digits = digits * 10 + digit as usize;
+                chars.next();
+            }
+            precision = Some(digits);
+        }
+
+        let conversion = chars.next()?;
+        if conversion == '%' {
+            out.push('%');
+            continue;
+        }
+
+        let arg = args.next()?;
+        let (sign, body) = match conversion {
+            's' => ("", arg.stringify()),
+            'c' => match arg {
+                Value::String(s) if s.chars().count() == 1 => ("", s.clone()),
+                _ => {
+                    let code = Num::of(arg)?.real() as u32;
+                    ("", std::char::from_u32(code)?.to_string())
+                }
+            },
+            'd' | 'o' | 'x' | 'X' => {
+                let value = match Num::of(arg)? {
+                    Num::Int(i) => i,
+                    Num::Real(r) => r as i64,
+                };
+                let magnitude = magnitude(value);
+                let body = match conversion {
+                    'd' => magnitude.to_string(),
+                    'o' => format!("{:o}", magnitude),
+                    'x' => format!("{:x}", magnitude),
+                    _ => format!("{:X}", magnitude),
+                };
+                (sign(value < 0, show_sign), body)
+            }
+            'f' => {
+                let value = Num::of(arg)?.real();
+                let body = format!("{:.*}", precision.unwrap_or(6), value.abs());
+                (sign(value < 0.0, show_sign), body)
+            }
+            _ => return None,
+        };
+
+        let len = sign.chars().count() + body.chars().count();
+        let padding = width.saturating_sub(len);
+        if left_justified {
+            out.push_str(sign);
+            out.push_str(&body);
+            out.push_str(&" ".repeat(padding));
+        } else if pad_with_zeros && conversion != 's' && conversion != 'c' {
+            out.push_str(sign);
+            out.push_str(&"0".repeat(padding));
+            out.push_str(&body);
+        } else {
+            out.push_str(&" ".repeat(padding));
+            out.push_str(sign);
+            out.push_str(&body);
+        }
+    }
+
+    if args.peek().is_some() {
+        // Not all arguments were converted.
+        return None;
+    }
+
+    Some(out)
+}
+
+fn sign(negative: bool, show_sign: bool) -> &'static str {
+    if negative {
+        "-"
+    } else if show_sign {
+        "+"
+    } else {
+        ""
+    }
+}
+
+fn magnitude(value: i64) -> u64 {
+    if value < 0 {
+        (value as u64).wrapping_neg()
+    } else {
+        value as u64
+    }
+}
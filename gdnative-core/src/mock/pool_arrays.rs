@@ -0,0 +1,429 @@
+use super::collections;
+use super::value::{Pool, Value};
+use super::{handle_init, handle_new, handle_ref, handle_take, string, MessageLevel};
+use crate::sys;
+use libc;
+use std::mem;
+use std::sync::Arc;
+
+// All seven pool array types share one generic implementation. The handles are passed as
+// `c_void` pointers, which is ABI-compatible with the typed pointers in the API signatures.
+// Element arguments and return values go through `PoolElement::Arg` and `PoolElement::Ret`,
+// which are exactly the types used by the API for each pool array.
+
+/// An element type of a pool array.
+pub(super) trait PoolElement: Clone + 'static {
+    type Arg: Copy;
+    type Ret;
+
+    unsafe fn from_arg(arg: Self::Arg) -> Self;
+    fn to_ret(&self) -> Self::Ret;
+    fn empty() -> Self;
+
+    fn to_value(&self) -> Value;
+    fn from_value(value: &Value) -> Self;
+
+    fn wrap(pool: Pool<Self>) -> Value;
+    fn unwrap(value: &Value) -> Option<&Pool<Self>>;
+}
+
+/// A `godot_string` owned by the mock, as an element of a string pool array.
+#[repr(transparent)]
+pub(super) struct StringSlot(sys::godot_string);
+
+impl StringSlot {
+    pub(super) fn new(s: String) -> Self {
+        unsafe { StringSlot(string::new(s)) }
+    }
+
+    pub(super) fn get(&self) -> &String {
+        unsafe { string::get(&self.0) }
+    }
+}
+
+impl Clone for StringSlot {
+    fn clone(&self) -> Self {
+        StringSlot::new(self.get().clone())
+    }
+}
+
+impl Drop for StringSlot {
+    fn drop(&mut self) {
+        unsafe { drop(handle_take::<_, String>(&mut self.0)) }
+    }
+}
+
+macro_rules! impl_pool_element_by_value {
+    ($ty:ty, $variant:ident, $value:ident => $to_value:expr, $from:ident => $from_value:expr) => {
+        impl PoolElement for $ty {
+            type Arg = $ty;
+            type Ret = $ty;
+
+            unsafe fn from_arg(arg: $ty) -> Self {
+                arg
+            }
+
+            fn to_ret(&self) -> $ty {
+                *self
+            }
+
+            fn empty() -> Self {
+                Default::default()
+            }
+
+            fn to_value(&self) -> Value {
+                let $value = *self;
+                $to_value
+            }
+
+            fn from_value($from: &Value) -> Self {
+                $from_value
+            }
+
+            fn wrap(pool: Pool<Self>) -> Value {
+                Value::$variant(pool)
+            }
+
+            fn unwrap(value: &Value) -> Option<&Pool<Self>> {
+                match value {
+                    Value::$variant(pool) => Some(pool),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_pool_element_by_value!(u8, ByteArray, v => Value::Int(i64::from(v)), v => v.to_int() as u8);
+impl_pool_element_by_value!(i32, IntArray, v => Value::Int(i64::from(v)), v => v.to_int() as i32);
+impl_pool_element_by_value!(f32, RealArray, v => Value::Real(f64::from(v)), v => v.to_real() as f32);
+
+macro_rules! impl_pool_element_by_ptr {
+    ($ty:ty, $variant:ident, $value_variant:ident) => {
+        impl PoolElement for $ty {
+            type Arg = *const $ty;
+            type Ret = $ty;
+
+            unsafe fn from_arg(arg: *const $ty) -> Self {
+                std::ptr::read_unaligned(arg)
+            }
+
+            fn to_ret(&self) -> $ty {
+                *self
+            }
+
+            fn empty() -> Self {
+                unsafe { mem::zeroed() }
+            }
+
+            fn to_value(&self) -> Value {
+                Value::$value_variant(*self)
+            }
+
+            fn from_value(value: &Value) -> Self {
+                match value {
+                    Value::$value_variant(v) => *v,
+                    _ => Self::empty(),
+                }
+            }
+
+            fn wrap(pool: Pool<Self>) -> Value {
+                Value::$variant(pool)
+            }
+
+            fn unwrap(value: &Value) -> Option<&Pool<Self>> {
+                match value {
+                    Value::$variant(pool) => Some(pool),
+                    _ => None,
+                }
+            }
+        }
+    };
+}
+
+impl_pool_element_by_ptr!(sys::godot_vector2, Vector2Array, Vector2);
+impl_pool_element_by_ptr!(sys::godot_vector3, Vector3Array, Vector3);
+impl_pool_element_by_ptr!(sys::godot_color, ColorArray, Color);
+
+impl PoolElement for StringSlot {
+    type Arg = *const sys::godot_string;
+    type Ret = sys::godot_string;
+
+    unsafe fn from_arg(arg: *const sys::godot_string) -> Self {
+        StringSlot::new(string::get(arg).clone())
+    }
+
+    fn to_ret(&self) -> sys::godot_string {
+        unsafe { string::new(self.get().clone()) }
+    }
+
+    fn empty() -> Self {
+        StringSlot::new(String::new())
+    }
+
+    fn to_value(&self) -> Value {
+        Value::String(self.get().clone())
+    }
+
+    fn from_value(value: &Value) -> Self {
+        StringSlot::new(value.stringify())
+    }
+
+    fn wrap(pool: Pool<Self>) -> Value {
+        Value::StringArray(pool)
+    }
+
+    fn unwrap(value: &Value) -> Option<&Pool<Self>> {
+        match value {
+            Value::StringArray(pool) => Some(pool),
+            _ => None,
+        }
+    }
+}
+
+/// Converts a value to a pool, as the `Variant::operator Pool*Array` conversions do.
+pub(super) fn from_value<T: PoolElement>(value: &Value) -> Pool<T> {
+    if let Some(pool) = T::unwrap(value) {
+        return pool.clone();
+    }
+
+    match value {
+        Value::Array(arr) => {
+            let elements = unsafe { &*arr.get() };
+            Arc::new(elements.iter().map(|e| T::from_value(e.value())).collect())
+        }
+        _ => Arc::new(Vec::new()),
+    }
+}
+
+/// The pool array handle types differ per element type, so they are all treated as a plain
+/// pointer to the boxed storage.
+type PoolHandle<T> = *mut Pool<T>;
+
+pub(super) unsafe fn pool<'a, T: PoolElement>(p: *const libc::c_void) -> &'a mut Pool<T> {
+    handle_ref(p as *const PoolHandle<T>)
+}
+
+pub(super) unsafe fn new_pool<H, T: PoolElement>(pool: Pool<T>) -> H {
+    handle_new(pool)
+}
+
+unsafe fn vec<'a, T: PoolElement>(p: *mut libc::c_void) -> &'a mut Vec<T> {
+    Arc::make_mut(pool(p))
+}
+
+fn index_error(idx: sys::godot_int, len: usize) {
+    super::log(
+        MessageLevel::Error,
+        format!("pool array index {} out of bounds (size {})", idx, len),
+    );
+}
+
+unsafe extern "C" fn new<T: PoolElement>(r_dest: *mut libc::c_void) {
+    handle_init(r_dest as *mut PoolHandle<T>, Arc::new(Vec::<T>::new()));
+}
+
+unsafe extern "C" fn new_copy<T: PoolElement>(
+    r_dest: *mut libc::c_void,
+    p_src: *const libc::c_void,
+) {
+    handle_init(r_dest as *mut PoolHandle<T>, pool::<T>(p_src).clone());
+}
+
+unsafe extern "C" fn new_with_array<T: PoolElement>(
+    r_dest: *mut libc::c_void,
+    p_a: *const sys::godot_array,
+) {
+    let arr = Value::Array(collections::array(p_a).clone());
+    handle_init(r_dest as *mut PoolHandle<T>, from_value::<T>(&arr));
+}
+
+unsafe extern "C" fn destroy<T: PoolElement>(p_self: *mut libc::c_void) {
+    drop(handle_take::<_, Pool<T>>(p_self as *mut PoolHandle<T>));
+}
+
+unsafe extern "C" fn append<T: PoolElement>(p_self: *mut libc::c_void, p_data: T::Arg) {
+    let element = T::from_arg(p_data);
+    vec::<T>(p_self).push(element);
+}
+
+unsafe extern "C" fn append_array<T: PoolElement>(
+    p_self: *mut libc::c_void,
+    p_array: *const libc::c_void,
+) {
+    let other = pool::<T>(p_array).clone();
+    vec::<T>(p_self).extend(other.iter().cloned());
+}
+
+unsafe extern "C" fn insert<T: PoolElement>(
+    p_self: *mut libc::c_void,
+    p_idx: sys::godot_int,
+    p_data: T::Arg,
+) -> sys::godot_error {
+    let element = T::from_arg(p_data);
+    let vec = vec::<T>(p_self);
+    if p_idx < 0 || p_idx as usize > vec.len() {
+        return sys::godot_error_GODOT_ERR_INVALID_PARAMETER;
+    }
+    vec.insert(p_idx as usize, element);
+    sys::godot_error_GODOT_OK
+}
+
+unsafe extern "C" fn invert<T: PoolElement>(p_self: *mut libc::c_void) {
+    vec::<T>(p_self).reverse();
+}
+
+unsafe extern "C" fn remove<T: PoolElement>(p_self: *mut libc::c_void, p_idx: sys::godot_int) {
+    let vec = vec::<T>(p_self);
+    if p_idx < 0 || p_idx as usize >= vec.len() {
+        index_error(p_idx, vec.len());
+        return;
+    }
+    vec.remove(p_idx as usize);
+}
+
+unsafe extern "C" fn resize<T: PoolElement>(p_self: *mut libc::c_void, p_size: sys::godot_int) {
+    vec::<T>(p_self).resize_with(p_size.max(0) as usize, T::empty);
+}
+
+unsafe extern "C" fn set<T: PoolElement>(
+    p_self: *mut libc::c_void,
+    p_idx: sys::godot_int,
+    p_data: T::Arg,
+) {
+    let element = T::from_arg(p_data);
+    let vec = vec::<T>(p_self);
+    if p_idx < 0 || p_idx as usize >= vec.len() {
+        index_error(p_idx, vec.len());
+        return;
+    }
+    vec[p_idx as usize] = element;
+}
+
+unsafe extern "C" fn get<T: PoolElement>(
+    p_self: *const libc::c_void,
+    p_idx: sys::godot_int,
+) -> T::Ret {
+    let pool = pool::<T>(p_self);
+    if p_idx < 0 || p_idx as usize >= pool.len() {
+        index_error(p_idx, pool.len());
+        return T::empty().to_ret();
+    }
+    pool[p_idx as usize].to_ret()
+}
+
+unsafe extern "C" fn size<T: PoolElement>(p_self: *const libc::c_void) -> sys::godot_int {
+    pool::<T>(p_self).len() as sys::godot_int
+}
+
+// A read access keeps the storage alive, so that it is not affected by later writes. A write
+// access points into storage that was made unique when the access was created.
+
+struct ReadAccess<T>(Pool<T>);
+
+struct WriteAccess<T>(*mut Vec<T>);
+
+unsafe extern "C" fn read<T: PoolElement>(p_self: *const libc::c_void) -> *mut libc::c_void {
+    let access = ReadAccess(pool::<T>(p_self).clone());
+    Box::into_raw(Box::new(access)) as *mut libc::c_void
+}
+
+unsafe extern "C" fn read_access_copy<T: PoolElement>(
+    p_read: *const libc::c_void,
+) -> *mut libc::c_void {
+    let access = &*(p_read as *const ReadAccess<T>);
+    Box::into_raw(Box::new(ReadAccess(access.0.clone()))) as *mut libc::c_void
+}
+
+unsafe extern "C" fn read_access_ptr<T: PoolElement>(p_read: *const libc::c_void) -> *const T {
+    let access = &*(p_read as *const ReadAccess<T>);
+    access.0.as_ptr()
+}
+
+unsafe extern "C" fn read_access_operator_assign<T: PoolElement>(
+    p_read: *mut libc::c_void,
+    p_other: *mut libc::c_void,
+) {
+    let other = (*(p_other as *const ReadAccess<T>)).0.clone();
+    (*(p_read as *mut ReadAccess<T>)).0 = other;
+}
+
+unsafe extern "C" fn read_access_destroy<T: PoolElement>(p_read: *mut libc::c_void) {
+    drop(Box::from_raw(p_read as *mut ReadAccess<T>));
+}
+
+unsafe extern "C" fn write<T: PoolElement>(p_self: *mut libc::c_void) -> *mut libc::c_void {
+    let access = WriteAccess(vec::<T>(p_self) as *mut Vec<T>);
+    Box::into_raw(Box::new(access)) as *mut libc::c_void
+}
+
+unsafe extern "C" fn write_access_copy<T: PoolElement>(
+    p_write: *const libc::c_void,
+) -> *mut libc::c_void {
+    let access = &*(p_write as *const WriteAccess<T>);
+    Box::into_raw(Box::new(WriteAccess(access.0))) as *mut libc::c_void
+}
+
+unsafe extern "C" fn write_access_ptr<T: PoolElement>(p_write: *const libc::c_void) -> *mut T {
+    let access = &*(p_write as *const WriteAccess<T>);
+    (*access.0).as_mut_ptr()
+}
+
+unsafe extern "C" fn write_access_operator_assign<T: PoolElement>(
+    p_write: *mut libc::c_void,
+    p_other: *mut libc::c_void,
+) {
+    let other = (*(p_other as *const WriteAccess<T>)).0;
+    (*(p_write as *mut WriteAccess<T>)).0 = other;
+}
+
+unsafe extern "C" fn write_access_destroy<T: PoolElement>(p_write: *mut libc::c_void) {
+    drop(Box::from_raw(p_write as *mut WriteAccess<T>));
+}
+
+fn ops<T: PoolElement>(op: &str) -> Option<*const libc::c_void> {
+    let f = match op {
+        "new" => new::<T> as *const libc::c_void,
+        "new_copy" => new_copy::<T> as *const libc::c_void,
+        "new_with_array" => new_with_array::<T> as *const libc::c_void,
+        "destroy" => destroy::<T> as *const libc::c_void,
+        "append" | "push_back" => append::<T> as *const libc::c_void,
+        "append_array" => append_array::<T> as *const libc::c_void,
+        "insert" => insert::<T> as *const libc::c_void,
+        "invert" => invert::<T> as *const libc::c_void,
+        "remove" => remove::<T> as *const libc::c_void,
+        "resize" => resize::<T> as *const libc::c_void,
+        "set" => set::<T> as *const libc::c_void,
+        "get" => get::<T> as *const libc::c_void,
+        "size" => size::<T> as *const libc::c_void,
+        "read" => read::<T> as *const libc::c_void,
+        "read_access_copy" => read_access_copy::<T> as *const libc::c_void,
+        "read_access_ptr" => read_access_ptr::<T> as *const libc::c_void,
+        "read_access_operator_assign" => read_access_operator_assign::<T> as *const libc::c_void,
+        "read_access_destroy" => read_access_destroy::<T> as *const libc::c_void,
+        "write" => write::<T> as *const libc::c_void,
+        "write_access_copy" => write_access_copy::<T> as *const libc::c_void,
+        "write_access_ptr" => write_access_ptr::<T> as *const libc::c_void,
+        "write_access_operator_assign" => write_access_operator_assign::<T> as *const libc::c_void,
+        "write_access_destroy" => write_access_destroy::<T> as *const libc::c_void,
+        _ => return None,
+    };
+    Some(f)
+}
+
+pub(super) fn lookup(name: &str) -> Option<*const libc::c_void> {
+    let rest = name.strip_prefix("godot_pool_")?;
+    let split = rest.find("_array_")?;
+    let (kind, op) = (&rest[..split], &rest[split + "_array_".len()..]);
+
+    match kind {
+        "byte" => ops::<u8>(op),
+        "int" => ops::<i32>(op),
+        "real" => ops::<f32>(op),
+        "string" => ops::<StringSlot>(op),
+        "vector2" => ops::<sys::godot_vector2>(op),
+        "vector3" => ops::<sys::godot_vector3>(op),
+        "color" => ops::<sys::godot_color>(op),
+        _ => None,
+    }
+}
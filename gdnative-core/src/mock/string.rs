@@ -0,0 +1,645 @@
+use super::value::{Value, VariantSlot};
+use super::{c_str, collections, handle_init, handle_new, handle_ref, handle_take};
+use crate::sys;
+use libc;
+use std::cell::UnsafeCell;
+use std::cmp::Ordering;
+use std::ffi::CStr;
+use std::sync::Arc;
+
+// Strings, string names and node paths are all stored as a boxed `String`. Char strings are a
+// boxed, NUL-terminated byte buffer.
+
+pub(super) unsafe fn get<'a>(s: *const sys::godot_string) -> &'a String {
+    handle_ref(s)
+}
+
+pub(super) unsafe fn new(s: String) -> sys::godot_string {
+    handle_new(s)
+}
+
+/// Index of the first char of the `char_idx`-th character, or `s.len()` if out of range.
+fn byte_offset(s: &str, char_idx: usize) -> usize {
+    s.char_indices().nth(char_idx).map_or(s.len(), |(i, _)| i)
+}
+
+fn char_index(s: &str, byte_idx: usize) -> sys::godot_int {
+    s[..byte_idx].chars().count() as sys::godot_int
+}
+
+/// The `String::hash` function of the engine (djb2 over the characters).
+pub(super) fn hash(s: &str) -> u32 {
+    s.chars().fold(5381u32, |hash, c| {
+        (hash << 5).wrapping_add(hash).wrapping_add(c as u32)
+    })
+}
+
+fn hash64(s: &str) -> u64 {
+    s.chars().fold(5381u64, |hash, c| {
+        (hash << 5).wrapping_add(hash).wrapping_add(c as u64)
+    })
+}
+
+unsafe extern "C" fn godot_string_new(r_dest: *mut sys::godot_string) {
+    handle_init(r_dest, String::new());
+}
+
+unsafe extern "C" fn godot_string_new_copy(
+    r_dest: *mut sys::godot_string,
+    p_src: *const sys::godot_string,
+) {
+    handle_init(r_dest, get(p_src).clone());
+}
+
+unsafe extern "C" fn godot_string_destroy(p_self: *mut sys::godot_string) {
+    drop(handle_take::<_, String>(p_self));
+}
+
+unsafe extern "C" fn godot_string_chars_to_utf8_with_len(
+    p_utf8: *const libc::c_char,
+    p_len: sys::godot_int,
+) -> sys::godot_string {
+    let bytes = std::slice::from_raw_parts(p_utf8 as *const u8, p_len.max(0) as usize);
+    let end = bytes.iter().position(|&b| b == 0).unwrap_or(bytes.len());
+    new(String::from_utf8_lossy(&bytes[..end]).into_owned())
+}
+
+unsafe extern "C" fn godot_string_utf8(p_self: *const sys::godot_string) -> sys::godot_char_string {
+    let mut data = get(p_self).clone().into_bytes();
+    data.push(0);
+    handle_new(data)
+}
+
+unsafe extern "C" fn godot_string_length(p_self: *const sys::godot_string) -> sys::godot_int {
+    get(p_self).chars().count() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_empty(p_self: *const sys::godot_string) -> sys::godot_bool {
+    get(p_self).is_empty()
+}
+
+unsafe extern "C" fn godot_string_operator_equal(
+    p_self: *const sys::godot_string,
+    p_b: *const sys::godot_string,
+) -> sys::godot_bool {
+    get(p_self) == get(p_b)
+}
+
+unsafe extern "C" fn godot_string_operator_less(
+    p_self: *const sys::godot_string,
+    p_b: *const sys::godot_string,
+) -> sys::godot_bool {
+    get(p_self) < get(p_b)
+}
+
+unsafe extern "C" fn godot_string_operator_plus(
+    p_self: *const sys::godot_string,
+    p_b: *const sys::godot_string,
+) -> sys::godot_string {
+    new(format!("{}{}", get(p_self), get(p_b)))
+}
+
+unsafe extern "C" fn godot_string_hash(p_self: *const sys::godot_string) -> u32 {
+    hash(get(p_self))
+}
+
+unsafe extern "C" fn godot_string_hash64(p_self: *const sys::godot_string) -> u64 {
+    hash64(get(p_self))
+}
+
+unsafe extern "C" fn godot_string_begins_with(
+    p_self: *const sys::godot_string,
+    p_string: *const sys::godot_string,
+) -> sys::godot_bool {
+    get(p_self).starts_with(get(p_string).as_str())
+}
+
+unsafe extern "C" fn godot_string_begins_with_char_array(
+    p_self: *const sys::godot_string,
+    p_char_array: *const libc::c_char,
+) -> sys::godot_bool {
+    get(p_self).starts_with(c_str(p_char_array))
+}
+
+unsafe extern "C" fn godot_string_ends_with(
+    p_self: *const sys::godot_string,
+    p_string: *const sys::godot_string,
+) -> sys::godot_bool {
+    get(p_self).ends_with(get(p_string).as_str())
+}
+
+unsafe extern "C" fn godot_string_find_from(
+    p_self: *const sys::godot_string,
+    p_what: sys::godot_string,
+    p_from: sys::godot_int,
+) -> sys::godot_int {
+    let s = get(p_self);
+    let from = byte_offset(s, p_from.max(0) as usize);
+    let what = get(&p_what);
+    s[from..]
+        .find(what.as_str())
+        .map_or(-1, |i| char_index(s, from + i))
+}
+
+unsafe extern "C" fn godot_string_find(
+    p_self: *const sys::godot_string,
+    p_what: sys::godot_string,
+) -> sys::godot_int {
+    godot_string_find_from(p_self, p_what, 0)
+}
+
+unsafe extern "C" fn godot_string_find_last(
+    p_self: *const sys::godot_string,
+    p_what: sys::godot_string,
+) -> sys::godot_int {
+    let s = get(p_self);
+    s.rfind(get(&p_what).as_str())
+        .map_or(-1, |i| char_index(s, i))
+}
+
+unsafe extern "C" fn godot_string_substr(
+    p_self: *const sys::godot_string,
+    p_from: sys::godot_int,
+    p_chars: sys::godot_int,
+) -> sys::godot_string {
+    let s = get(p_self);
+    let substr = s
+        .chars()
+        .skip(p_from.max(0) as usize)
+        .take(p_chars.max(0) as usize)
+        .collect();
+    new(substr)
+}
+
+unsafe extern "C" fn godot_string_to_lower(p_self: *const sys::godot_string) -> sys::godot_string {
+    new(get(p_self).to_lowercase())
+}
+
+unsafe extern "C" fn godot_string_to_upper(p_self: *const sys::godot_string) -> sys::godot_string {
+    new(get(p_self).to_uppercase())
+}
+
+unsafe extern "C" fn godot_string_to_int(p_self: *const sys::godot_string) -> sys::godot_int {
+    super::value::Value::String(get(p_self).clone()).to_int() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_string_to_double(p_self: *const sys::godot_string) -> f64 {
+    get(p_self).trim().parse().unwrap_or(0.0)
+}
+
+unsafe extern "C" fn godot_string_to_float(p_self: *const sys::godot_string) -> sys::godot_real {
+    godot_string_to_double(p_self) as sys::godot_real
+}
+
+unsafe extern "C" fn godot_string_is_valid_integer(
+    p_self: *const sys::godot_string,
+) -> sys::godot_bool {
+    let s = get(p_self);
+    let digits = s.trim_start_matches(&['-', '+'][..]);
+    s.len() - digits.len() <= 1 && !digits.is_empty() && digits.chars().all(|c| c.is_ascii_digit())
+}
+
+unsafe extern "C" fn godot_string_is_valid_float(
+    p_self: *const sys::godot_string,
+) -> sys::godot_bool {
+    get(p_self).parse::<f64>().is_ok()
+}
+
+unsafe extern "C" fn godot_string_is_numeric(p_self: *const sys::godot_string) -> sys::godot_bool {
+    godot_string_is_valid_float(p_self)
+}
+
+unsafe extern "C" fn godot_string_is_valid_identifier(
+    p_self: *const sys::godot_string,
+) -> sys::godot_bool {
+    let s = get(p_self);
+    let mut chars = s.chars();
+    match chars.next() {
+        Some(c) if c == '_' || c.is_ascii_alphabetic() => {
+            chars.all(|c| c == '_' || c.is_ascii_alphanumeric())
+        }
+        _ => false,
+    }
+}
+
+unsafe extern "C" fn godot_string_is_abs_path(p_self: *const sys::godot_string) -> sys::godot_bool {
+    let s = get(p_self);
+    s.starts_with('/') || s.starts_with('\\') || s.contains(":/") || s.contains(":\\")
+}
+
+unsafe extern "C" fn godot_string_is_rel_path(p_self: *const sys::godot_string) -> sys::godot_bool {
+    !godot_string_is_abs_path(p_self)
+}
+
+unsafe extern "C" fn godot_string_get_file(p_self: *const sys::godot_string) -> sys::godot_string {
+    let s = get(p_self);
+    let start = s.rfind(&['/', '\\'][..]).map_or(0, |i| i + 1);
+    new(s[start..].to_string())
+}
+
+unsafe extern "C" fn godot_string_get_base_dir(
+    p_self: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = get(p_self);
+    let end = s.rfind(&['/', '\\'][..]).unwrap_or(0);
+    new(s[..end].to_string())
+}
+
+unsafe extern "C" fn godot_string_ord_at(
+    p_self: *const sys::godot_string,
+    p_idx: sys::godot_int,
+) -> sys::wchar_t {
+    let c = get(p_self).chars().nth(p_idx.max(0) as usize);
+    c.map_or(0, |c| c as sys::wchar_t)
+}
+
+unsafe extern "C" fn godot_string_left(
+    p_self: *const sys::godot_string,
+    p_pos: sys::godot_int,
+) -> sys::godot_string {
+    new(get(p_self).chars().take(p_pos.max(0) as usize).collect())
+}
+
+unsafe extern "C" fn godot_string_right(
+    p_self: *const sys::godot_string,
+    p_pos: sys::godot_int,
+) -> sys::godot_string {
+    new(get(p_self).chars().skip(p_pos.max(0) as usize).collect())
+}
+
+unsafe extern "C" fn godot_string_strip_edges(
+    p_self: *const sys::godot_string,
+    p_left: sys::godot_bool,
+    p_right: sys::godot_bool,
+) -> sys::godot_string {
+    // The engine strips every character up to and including the space.
+    let strip = |c: char| c <= ' ';
+    let mut s = get(p_self).as_str();
+    if p_left {
+        s = s.trim_start_matches(strip);
+    }
+    if p_right {
+        s = s.trim_end_matches(strip);
+    }
+    new(s.to_string())
+}
+
+fn split(s: &str, divisor: &str, allow_empty: bool) -> sys::godot_array {
+    let parts = s
+        .split(divisor)
+        .filter(|part| allow_empty || !part.is_empty())
+        .map(|part| VariantSlot::new(Value::String(part.to_string())))
+        .collect();
+    unsafe { collections::new_array(Arc::new(UnsafeCell::new(parts))) }
+}
+
+unsafe extern "C" fn godot_string_split(
+    p_self: *const sys::godot_string,
+    p_splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    split(get(p_self), get(p_splitter), false)
+}
+
+unsafe extern "C" fn godot_string_split_allow_empty(
+    p_self: *const sys::godot_string,
+    p_splitter: *const sys::godot_string,
+) -> sys::godot_array {
+    split(get(p_self), get(p_splitter), true)
+}
+
+/// Wildcard matching as done by `String::match`: `*` matches any sequence, `?` any character.
+fn wildcard_match(s: &[char], pattern: &[char]) -> bool {
+    match pattern.split_first() {
+        None => s.is_empty(),
+        Some(('*', rest)) => (0..=s.len()).any(|i| wildcard_match(&s[i..], rest)),
+        Some((&p, rest)) => match s.split_first() {
+            Some((&c, s)) => (p == '?' || p == c) && wildcard_match(s, rest),
+            None => false,
+        },
+    }
+}
+
+unsafe extern "C" fn godot_string_match(
+    p_self: *const sys::godot_string,
+    p_wildcard: *const sys::godot_string,
+) -> sys::godot_bool {
+    let s: Vec<char> = get(p_self).chars().collect();
+    let pattern: Vec<char> = get(p_wildcard).chars().collect();
+    wildcard_match(&s, &pattern)
+}
+
+unsafe extern "C" fn godot_string_matchn(
+    p_self: *const sys::godot_string,
+    p_wildcard: *const sys::godot_string,
+) -> sys::godot_bool {
+    let s: Vec<char> = get(p_self).to_lowercase().chars().collect();
+    let pattern: Vec<char> = get(p_wildcard).to_lowercase().chars().collect();
+    wildcard_match(&s, &pattern)
+}
+
+/// Case-insensitive comparison in which runs of digits are compared by value.
+fn natural_cmp(a: &str, b: &str) -> Ordering {
+    let (a, b) = (a.to_lowercase(), b.to_lowercase());
+    let (mut a, mut b) = (a.as_str(), b.as_str());
+    loop {
+        let (ca, cb) = match (a.chars().next(), b.chars().next()) {
+            (None, None) => return Ordering::Equal,
+            (None, Some(_)) => return Ordering::Less,
+            (Some(_), None) => return Ordering::Greater,
+            (Some(ca), Some(cb)) => (ca, cb),
+        };
+        if ca.is_ascii_digit() && cb.is_ascii_digit() {
+            let len_a = a.find(|c: char| !c.is_ascii_digit()).unwrap_or(a.len());
+            let len_b = b.find(|c: char| !c.is_ascii_digit()).unwrap_or(b.len());
+            let num_a: u64 = a[..len_a].parse().unwrap_or(0);
+            let num_b: u64 = b[..len_b].parse().unwrap_or(0);
+            match num_a.cmp(&num_b) {
+                Ordering::Equal => {}
+                other => return other,
+            }
+            a = &a[len_a..];
+            b = &b[len_b..];
+        } else {
+            match ca.cmp(&cb) {
+                Ordering::Equal => {}
+                other => return other,
+            }
+            a = &a[ca.len_utf8()..];
+            b = &b[cb.len_utf8()..];
+        }
+    }
+}
+
+unsafe extern "C" fn godot_string_naturalnocasecmp_to(
+    p_self: *const sys::godot_string,
+    p_str: *const sys::godot_string,
+) -> libc::c_schar {
+    natural_cmp(get(p_self), get(p_str)) as libc::c_schar
+}
+
+fn xml_escape(s: &str) -> String {
+    s.replace('&', "&amp;")
+        .replace('<', "&lt;")
+        .replace('>', "&gt;")
+}
+
+unsafe extern "C" fn godot_string_xml_escape(
+    p_self: *const sys::godot_string,
+) -> sys::godot_string {
+    new(xml_escape(get(p_self)))
+}
+
+unsafe extern "C" fn godot_string_xml_escape_with_quotes(
+    p_self: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = xml_escape(get(p_self))
+        .replace('\'', "&apos;")
+        .replace('"', "&quot;");
+    new(s)
+}
+
+unsafe extern "C" fn godot_string_xml_unescape(
+    p_self: *const sys::godot_string,
+) -> sys::godot_string {
+    let s = get(p_self)
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&gt;", ">")
+        .replace("&lt;", "<")
+        .replace("&amp;", "&");
+    new(s)
+}
+
+unsafe extern "C" fn godot_char_string_length(
+    p_cs: *const sys::godot_char_string,
+) -> sys::godot_int {
+    let data: &mut Vec<u8> = handle_ref(p_cs);
+    (data.len() - 1) as sys::godot_int
+}
+
+unsafe extern "C" fn godot_char_string_get_data(
+    p_cs: *const sys::godot_char_string,
+) -> *const libc::c_char {
+    let data: &mut Vec<u8> = handle_ref(p_cs);
+    data.as_ptr() as *const libc::c_char
+}
+
+unsafe extern "C" fn godot_char_string_destroy(p_cs: *mut sys::godot_char_string) {
+    drop(handle_take::<_, Vec<u8>>(p_cs));
+}
+
+unsafe extern "C" fn godot_string_name_new(
+    r_dest: *mut sys::godot_string_name,
+    p_name: *const sys::godot_string,
+) {
+    handle_init(r_dest, get(p_name).clone());
+}
+
+unsafe extern "C" fn godot_string_name_new_data(
+    r_dest: *mut sys::godot_string_name,
+    p_name: *const libc::c_char,
+) {
+    let name = CStr::from_ptr(p_name).to_string_lossy().into_owned();
+    handle_init(r_dest, name);
+}
+
+unsafe extern "C" fn godot_string_name_get_name(
+    p_self: *const sys::godot_string_name,
+) -> sys::godot_string {
+    let name: &mut String = handle_ref(p_self);
+    new(name.clone())
+}
+
+unsafe extern "C" fn godot_string_name_get_hash(p_self: *const sys::godot_string_name) -> u32 {
+    let name: &mut String = handle_ref(p_self);
+    hash(name)
+}
+
+unsafe extern "C" fn godot_string_name_operator_equal(
+    p_self: *const sys::godot_string_name,
+    p_other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    let (a, b): (&mut String, &mut String) = (handle_ref(p_self), handle_ref(p_other));
+    a == b
+}
+
+unsafe extern "C" fn godot_string_name_operator_less(
+    p_self: *const sys::godot_string_name,
+    p_other: *const sys::godot_string_name,
+) -> sys::godot_bool {
+    let (a, b): (&mut String, &mut String) = (handle_ref(p_self), handle_ref(p_other));
+    a < b
+}
+
+unsafe extern "C" fn godot_string_name_destroy(p_self: *mut sys::godot_string_name) {
+    drop(handle_take::<_, String>(p_self));
+}
+
+pub(super) unsafe fn node_path<'a>(np: *const sys::godot_node_path) -> &'a String {
+    handle_ref(np)
+}
+
+/// Splits a node path into its names and subnames, like the `NodePath` constructor does.
+fn parse_node_path(path: &str) -> (bool, Vec<&str>, Vec<&str>) {
+    let absolute = path.starts_with('/');
+    let (names, subnames) = match path.find(':') {
+        Some(i) => (&path[..i], &path[i + 1..]),
+        None => (path, ""),
+    };
+    let names = names.split('/').filter(|s| !s.is_empty()).collect();
+    let subnames = subnames.split(':').filter(|s| !s.is_empty()).collect();
+    (absolute, names, subnames)
+}
+
+unsafe extern "C" fn godot_node_path_new(
+    r_dest: *mut sys::godot_node_path,
+    p_from: *const sys::godot_string,
+) {
+    handle_init(r_dest, get(p_from).clone());
+}
+
+unsafe extern "C" fn godot_node_path_new_copy(
+    r_dest: *mut sys::godot_node_path,
+    p_src: *const sys::godot_node_path,
+) {
+    handle_init(r_dest, node_path(p_src).clone());
+}
+
+unsafe extern "C" fn godot_node_path_destroy(p_self: *mut sys::godot_node_path) {
+    drop(handle_take::<_, String>(p_self));
+}
+
+unsafe extern "C" fn godot_node_path_as_string(
+    p_self: *const sys::godot_node_path,
+) -> sys::godot_string {
+    new(node_path(p_self).clone())
+}
+
+unsafe extern "C" fn godot_node_path_is_absolute(
+    p_self: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    parse_node_path(node_path(p_self)).0
+}
+
+unsafe extern "C" fn godot_node_path_is_empty(
+    p_self: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    let (absolute, names, subnames) = parse_node_path(node_path(p_self));
+    !absolute && names.is_empty() && subnames.is_empty()
+}
+
+unsafe extern "C" fn godot_node_path_get_name_count(
+    p_self: *const sys::godot_node_path,
+) -> sys::godot_int {
+    parse_node_path(node_path(p_self)).1.len() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_node_path_get_name(
+    p_self: *const sys::godot_node_path,
+    p_idx: sys::godot_int,
+) -> sys::godot_string {
+    let names = parse_node_path(node_path(p_self)).1;
+    new(names.get(p_idx as usize).copied().unwrap_or("").to_string())
+}
+
+unsafe extern "C" fn godot_node_path_get_subname_count(
+    p_self: *const sys::godot_node_path,
+) -> sys::godot_int {
+    parse_node_path(node_path(p_self)).2.len() as sys::godot_int
+}
+
+unsafe extern "C" fn godot_node_path_get_subname(
+    p_self: *const sys::godot_node_path,
+    p_idx: sys::godot_int,
+) -> sys::godot_string {
+    let subnames = parse_node_path(node_path(p_self)).2;
+    new(subnames
+        .get(p_idx as usize)
+        .copied()
+        .unwrap_or("")
+        .to_string())
+}
+
+unsafe extern "C" fn godot_node_path_get_concatenated_subnames(
+    p_self: *const sys::godot_node_path,
+) -> sys::godot_string {
+    new(parse_node_path(node_path(p_self)).2.join(":"))
+}
+
+unsafe extern "C" fn godot_node_path_operator_equal(
+    p_self: *const sys::godot_node_path,
+    p_b: *const sys::godot_node_path,
+) -> sys::godot_bool {
+    parse_node_path(node_path(p_self)) == parse_node_path(node_path(p_b))
+}
+
+mock_lookup! {
+    pub(super) fn lookup {
+        godot_string_new,
+        godot_string_new_copy,
+        godot_string_destroy,
+        godot_string_chars_to_utf8_with_len,
+        godot_string_utf8,
+        godot_string_length,
+        godot_string_empty,
+        godot_string_operator_equal,
+        godot_string_operator_less,
+        godot_string_operator_plus,
+        godot_string_hash,
+        godot_string_hash64,
+        godot_string_begins_with,
+        godot_string_begins_with_char_array,
+        godot_string_ends_with,
+        godot_string_find,
+        godot_string_find_from,
+        godot_string_find_last,
+        godot_string_substr,
+        godot_string_to_lower,
+        godot_string_to_upper,
+        godot_string_to_int,
+        godot_string_to_double,
+        godot_string_to_float,
+        godot_string_is_valid_integer,
+        godot_string_is_valid_float,
+        godot_string_is_numeric,
+        godot_string_is_valid_identifier,
+        godot_string_is_abs_path,
+        godot_string_is_rel_path,
+        godot_string_get_file,
+        godot_string_get_base_dir,
+        godot_string_ord_at,
+        godot_string_left,
+        godot_string_right,
+        godot_string_strip_edges,
+        godot_string_split,
+        godot_string_split_allow_empty,
+        godot_string_match,
+        godot_string_matchn,
+        godot_string_naturalnocasecmp_to,
+        godot_string_xml_escape,
+        godot_string_xml_escape_with_quotes,
+        godot_string_xml_unescape,
+        godot_char_string_length,
+        godot_char_string_get_data,
+        godot_char_string_destroy,
+        godot_string_name_new,
+        godot_string_name_new_data,
+        godot_string_name_get_name,
+        godot_string_name_get_hash,
+        godot_string_name_operator_equal,
+        godot_string_name_operator_less,
+        godot_string_name_destroy,
+        godot_node_path_new,
+        godot_node_path_new_copy,
+        godot_node_path_destroy,
+        godot_node_path_as_string,
+        godot_node_path_is_absolute,
+        godot_node_path_is_empty,
+        godot_node_path_get_name_count,
+        godot_node_path_get_name,
+        godot_node_path_get_subname_count,
+        godot_node_path_get_subname,
+        godot_node_path_get_concatenated_subnames,
+        godot_node_path_operator_equal,
+    }
+}
@@ -0,0 +1,445 @@
+use super::object::ObjectRef;
+use super::pool_arrays::{PoolElement, StringSlot};
+use super::{handle_init, handle_ptr, handle_take, real_at};
+use crate::sys;
+use std::cell::UnsafeCell;
+use std::mem;
+use std::sync::Arc;
+
+/// Shared storage of a `godot_array`. Copies of an array refer to the same elements.
+pub(super) type Array = Arc<UnsafeCell<Vec<VariantSlot>>>;
+
+/// Shared storage of a `godot_dictionary`, in insertion order.
+///
+/// Entries are boxed so that references returned by `operator_index` stay valid while other
+/// entries are added or removed.
+pub(super) type Dictionary = Arc<UnsafeCell<Vec<Box<DictionaryEntry>>>>;
+
+/// Copy-on-write storage of a pool array.
+pub(super) type Pool<T> = Arc<Vec<T>>;
+
+pub(super) struct DictionaryEntry {
+    pub(super) key: VariantSlot,
+    pub(super) value: VariantSlot,
+}
+
+/// The contents of a `godot_variant`.
+#[derive(Clone)]
+pub(super) enum Value {
+    Nil,
+    Bool(bool),
+    Int(i64),
+    Real(f64),
+    String(String),
+    Vector2(sys::godot_vector2),
+    Rect2(sys::godot_rect2),
+    Vector3(sys::godot_vector3),
+    Transform2D(sys::godot_transform2d),
+    Plane(sys::godot_plane),
+    Quat(sys::godot_quat),
+    Aabb(sys::godot_aabb),
+    Basis(sys::godot_basis),
+    Transform(sys::godot_transform),
+    Color(sys::godot_color),
+    NodePath(String),
+    Rid(usize),
+    Object(ObjectRef),
+    Dictionary(Dictionary),
+    Array(Array),
+    ByteArray(Pool<u8>),
+    IntArray(Pool<i32>),
+    RealArray(Pool<f32>),
+    StringArray(Pool<StringSlot>),
+    Vector2Array(Pool<sys::godot_vector2>),
+    Vector3Array(Pool<sys::godot_vector3>),
+    ColorArray(Pool<sys::godot_color>),
+}
+
+// The mock does not synchronize anything, just like the engine does not synchronize access to
+// variants. This is only needed for the static `Nil` below.
+unsafe impl Sync for Value {}
+
+static NIL: Value = Value::Nil;
+
+impl Value {
+    pub(super) fn variant_type(&self) -> sys::godot_variant_type {
+        match self {
+            Value::Nil => sys::godot_variant_type_GODOT_VARIANT_TYPE_NIL,
+            Value::Bool(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_BOOL,
+            Value::Int(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_INT,
+            Value::Real(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_REAL,
+            Value::String(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_STRING,
+            Value::Vector2(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_VECTOR2,
+            Value::Rect2(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_RECT2,
+            Value::Vector3(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_VECTOR3,
+            Value::Transform2D(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_TRANSFORM2D,
+            Value::Plane(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_PLANE,
+            Value::Quat(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_QUAT,
+            Value::Aabb(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_AABB,
+            Value::Basis(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_BASIS,
+            Value::Transform(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_TRANSFORM,
+            Value::Color(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_COLOR,
+            Value::NodePath(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_NODE_PATH,
+            Value::Rid(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_RID,
+            Value::Object(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_OBJECT,
+            Value::Dictionary(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_DICTIONARY,
+            Value::Array(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_ARRAY,
+            Value::ByteArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_BYTE_ARRAY,
+            Value::IntArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_INT_ARRAY,
+            Value::RealArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_REAL_ARRAY,
+            Value::StringArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_STRING_ARRAY,
+            Value::Vector2Array(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR2_ARRAY,
+            Value::Vector3Array(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_VECTOR3_ARRAY,
+            Value::ColorArray(_) => sys::godot_variant_type_GODOT_VARIANT_TYPE_POOL_COLOR_ARRAY,
+        }
+    }
+
+    /// Equality as implemented by `Variant::operator==` in Godot 3.
+    ///
+    /// Numbers compare by value regardless of their type, arrays compare by content, and
+    /// dictionaries and objects compare by identity.
+    pub(super) fn equals(&self, other: &Value) -> bool {
+        match (self, other) {
+            (Value::Nil, Value::Nil) => true,
+            (Value::Nil, Value::Object(obj)) | (Value::Object(obj), Value::Nil) => obj.is_null(),
+            (Value::Bool(a), Value::Bool(b)) => a == b,
+            (Value::Int(a), Value::Int(b)) => a == b,
+            (Value::Int(a), Value::Real(b)) => *a as f64 == *b,
+            (Value::Real(a), Value::Int(b)) => *a == *b as f64,
+            (Value::Real(a), Value::Real(b)) => a == b,
+            (Value::String(a), Value::String(b)) => a == b,
+            (Value::NodePath(a), Value::NodePath(b)) => a == b,
+            (Value::Vector2(a), Value::Vector2(b)) => unsafe { reals_eq(a, b) },
+            (Value::Rect2(a), Value::Rect2(b)) => unsafe { reals_eq(a, b) },
+            (Value::Vector3(a), Value::Vector3(b)) => unsafe { reals_eq(a, b) },
+            (Value::Transform2D(a), Value::Transform2D(b)) => unsafe { reals_eq(a, b) },
+            (Value::Plane(a), Value::Plane(b)) => unsafe { reals_eq(a, b) },
+            (Value::Quat(a), Value::Quat(b)) => unsafe { reals_eq(a, b) },
+            (Value::Aabb(a), Value::Aabb(b)) => unsafe { reals_eq(a, b) },
+            (Value::Basis(a), Value::Basis(b)) => unsafe { reals_eq(a, b) },
+            (Value::Transform(a), Value::Transform(b)) => unsafe { reals_eq(a, b) },
+            (Value::Color(a), Value::Color(b)) => unsafe { reals_eq(a, b) },
+            (Value::Rid(a), Value::Rid(b)) => a == b,
+            (Value::Object(a), Value::Object(b)) => a.ptr() == b.ptr(),
+            (Value::Dictionary(a), Value::Dictionary(b)) => Arc::ptr_eq(a, b),
+            (Value::Array(a), Value::Array(b)) => {
+                let (a, b) = unsafe { (&*a.get(), &*b.get()) };
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| a.value().equals(b.value()))
+            }
+            (Value::ByteArray(a), Value::ByteArray(b)) => pool_eq(a, b),
+            (Value::IntArray(a), Value::IntArray(b)) => pool_eq(a, b),
+            (Value::RealArray(a), Value::RealArray(b)) => pool_eq(a, b),
+            (Value::StringArray(a), Value::StringArray(b)) => pool_eq(a, b),
+            (Value::Vector2Array(a), Value::Vector2Array(b)) => pool_eq(a, b),
+            (Value::Vector3Array(a), Value::Vector3Array(b)) => pool_eq(a, b),
+            (Value::ColorArray(a), Value::ColorArray(b)) => pool_eq(a, b),
+            _ => false,
+        }
+    }
+
+    /// Equality as used for dictionary keys: like `equals`, but values of different types are
+    /// never equal, and `NaN` is equal to itself.
+    pub(super) fn key_equals(&self, other: &Value) -> bool {
+        if self.variant_type() != other.variant_type() {
+            return false;
+        }
+
+        match (self, other) {
+            (Value::Real(a), Value::Real(b)) => a == b || (a.is_nan() && b.is_nan()),
+            (Value::Array(a), Value::Array(b)) => {
+                let (a, b) = unsafe { (&*a.get(), &*b.get()) };
+                a.len() == b.len()
+                    && a.iter()
+                        .zip(b.iter())
+                        .all(|(a, b)| a.value().key_equals(b.value()))
+            }
+            _ => self.equals(other),
+        }
+    }
+
+    /// Mirrors `Variant::booleanize`: every value except the "zero" of its type is truthy.
+    pub(super) fn booleanize(&self) -> bool {
+        match self {
+            Value::Nil => false,
+            Value::Bool(b) => *b,
+            Value::Int(i) => *i != 0,
+            Value::Real(r) => *r != 0.0,
+            Value::String(s) | Value::NodePath(s) => !s.is_empty(),
+            Value::Vector2(v) => unsafe { !reals_zero(v) },
+            Value::Rect2(v) => unsafe { !reals_zero(v) },
+            Value::Vector3(v) => unsafe { !reals_zero(v) },
+            Value::Transform2D(v) => unsafe { !reals_zero(v) },
+            Value::Plane(v) => unsafe { !reals_zero(v) },
+            Value::Quat(v) => unsafe { !reals_zero(v) },
+            Value::Aabb(v) => unsafe { !reals_zero(v) },
+            Value::Basis(v) => unsafe { !reals_zero(v) },
+            Value::Transform(v) => unsafe { !reals_zero(v) },
+            Value::Color(v) => unsafe { !reals_zero(v) },
+            Value::Rid(id) => *id != 0,
+            Value::Object(obj) => !obj.is_null(),
+            Value::Dictionary(dict) => unsafe { !(*dict.get()).is_empty() },
+            Value::Array(arr) => unsafe { !(*arr.get()).is_empty() },
+            Value::ByteArray(pool) => !pool.is_empty(),
+            Value::IntArray(pool) => !pool.is_empty(),
+            Value::RealArray(pool) => !pool.is_empty(),
+            Value::StringArray(pool) => !pool.is_empty(),
+            Value::Vector2Array(pool) => !pool.is_empty(),
+            Value::Vector3Array(pool) => !pool.is_empty(),
+            Value::ColorArray(pool) => !pool.is_empty(),
+        }
+    }
+
+    pub(super) fn to_int(&self) -> i64 {
+        match self {
+            Value::Bool(b) => *b as i64,
+            Value::Int(i) => *i,
+            Value::Real(r) => *r as i64,
+            Value::String(s) => parse_int(s),
+            _ => 0,
+        }
+    }
+
+    pub(super) fn to_real(&self) -> f64 {
+        match self {
+            Value::Bool(b) => *b as i64 as f64,
+            Value::Int(i) => *i as f64,
+            Value::Real(r) => *r,
+            Value::String(s) => s.trim().parse().unwrap_or(0.0),
+            _ => 0.0,
+        }
+    }
+
+    /// Mirrors `Variant::operator String` in Godot 3.
+    pub(super) fn stringify(&self) -> String {
+        unsafe {
+            match self {
+                Value::Nil => "Null".to_string(),
+                Value::Bool(true) => "True".to_string(),
+                Value::Bool(false) => "False".to_string(),
+                Value::Int(i) => i.to_string(),
+                Value::Real(r) => r.to_string(),
+                Value::String(s) | Value::NodePath(s) => s.clone(),
+                Value::Vector2(v) => join_reals(v, ", "),
+                Value::Rect2(v) => join_reals(v, ", "),
+                Value::Vector3(v) => join_reals(v, ", "),
+                Value::Transform2D(v) => join_reals(v, ", "),
+                Value::Plane(v) => join_reals(v, ", "),
+                Value::Quat(v) => join_reals(v, ", "),
+                Value::Aabb(v) => join_reals(v, ", "),
+                Value::Basis(v) => join_reals(v, ", "),
+                Value::Transform(v) => join_reals(v, ", "),
+                Value::Color(v) => join_reals(v, ","),
+                Value::Rid(_) => "[RID]".to_string(),
+                Value::Object(obj) => obj.stringify(),
+                Value::Dictionary(dict) => {
+                    let entries = (*dict.get())
+                        .iter()
+                        .map(|e| {
+                            format!(
+                                "{}:{}",
+                                e.key.value().stringify(),
+                                e.value.value().stringify()
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    format!("{{{}}}", entries.join(", "))
+                }
+                Value::Array(arr) => {
+                    join_values((*arr.get()).iter().map(|slot| slot.value().clone()))
+                }
+                Value::ByteArray(pool) => join_values(pool.iter().map(PoolElement::to_value)),
+                Value::IntArray(pool) => join_values(pool.iter().map(PoolElement::to_value)),
+                Value::RealArray(pool) => join_values(pool.iter().map(PoolElement::to_value)),
+                Value::StringArray(pool) => join_values(pool.iter().map(PoolElement::to_value)),
+                Value::Vector2Array(pool) => join_values(pool.iter().map(PoolElement::to_value)),
+                Value::Vector3Array(pool) => join_values(pool.iter().map(PoolElement::to_value)),
+                Value::ColorArray(pool) => join_values(pool.iter().map(PoolElement::to_value)),
+            }
+        }
+    }
+
+    /// Converts the value to an array, as `Variant::operator Array` does.
+    pub(super) fn to_array(&self) -> Array {
+        fn from_pool<T: PoolElement>(pool: &Pool<T>) -> Array {
+            let slots = pool
+                .iter()
+                .map(|e| VariantSlot::new(e.to_value()))
+                .collect();
+            Arc::new(UnsafeCell::new(slots))
+        }
+
+        match self {
+            Value::Array(arr) => arr.clone(),
+            Value::ByteArray(pool) => from_pool(pool),
+            Value::IntArray(pool) => from_pool(pool),
+            Value::RealArray(pool) => from_pool(pool),
+            Value::StringArray(pool) => from_pool(pool),
+            Value::Vector2Array(pool) => from_pool(pool),
+            Value::Vector3Array(pool) => from_pool(pool),
+            Value::ColorArray(pool) => from_pool(pool),
+            _ => Arc::new(UnsafeCell::new(Vec::new())),
+        }
+    }
+
+    /// Ordering as used by `Array::sort`. Values that cannot be compared with `<` are ordered
+    /// by their type.
+    pub(super) fn less(&self, other: &Value) -> bool {
+        unsafe {
+            match (self, other) {
+                (Value::Bool(a), Value::Bool(b)) => a < b,
+                (Value::Int(a), Value::Int(b)) => a < b,
+                (Value::Int(_), Value::Real(_))
+                | (Value::Real(_), Value::Int(_))
+                | (Value::Real(_), Value::Real(_)) => self.to_real() < other.to_real(),
+                (Value::String(a), Value::String(b)) => a < b,
+                (Value::Vector2(a), Value::Vector2(b)) => reals_less(a, b),
+                (Value::Vector3(a), Value::Vector3(b)) => reals_less(a, b),
+                (Value::Color(a), Value::Color(b)) => reals_less(a, b),
+                (Value::Rid(a), Value::Rid(b)) => a < b,
+                (Value::Object(a), Value::Object(b)) => a.ptr() < b.ptr(),
+                (Value::Array(a), Value::Array(b)) => {
+                    let (a, b) = (&*a.get(), &*b.get());
+                    for (a, b) in a.iter().zip(b.iter()) {
+                        if a.value().less(b.value()) {
+                            return true;
+                        }
+                        if b.value().less(a.value()) {
+                            return false;
+                        }
+                    }
+                    a.len() < b.len()
+                }
+                _ => self.variant_type() < other.variant_type(),
+            }
+        }
+    }
+
+    /// Returns a hash of the value's contents, consistent with `key_equals`.
+    pub(super) fn hash(&self) -> u32 {
+        // FNV-1a over the type and the string representation is good enough for tests.
+        let mut hash: u32 = 2_166_136_261;
+        let text = self.stringify();
+        let bytes = std::iter::once(self.variant_type() as u8).chain(text.bytes());
+        for byte in bytes {
+            hash ^= u32::from(byte);
+            hash = hash.wrapping_mul(16_777_619);
+        }
+        hash
+    }
+}
+
+fn parse_int(s: &str) -> i64 {
+    let s = s.trim();
+    let end = s
+        .char_indices()
+        .find(|&(i, c)| !(c.is_ascii_digit() || (i == 0 && (c == '-' || c == '+'))))
+        .map_or(s.len(), |(i, _)| i);
+    s[..end].parse().unwrap_or(0)
+}
+
+fn join_values<I: Iterator<Item = Value>>(values: I) -> String {
+    let values = values.map(|v| v.stringify()).collect::<Vec<_>>();
+    format!("[{}]", values.join(", "))
+}
+
+fn pool_eq<T: PoolElement>(a: &Pool<T>, b: &Pool<T>) -> bool {
+    a.len() == b.len()
+        && a.iter()
+            .zip(b.iter())
+            .all(|(a, b)| a.to_value().equals(&b.to_value()))
+}
+
+pub(super) unsafe fn reals_eq<T>(a: &T, b: &T) -> bool {
+    (0..mem::size_of::<T>() / 4).all(|i| real_at(a, i) == real_at(b, i))
+}
+
+unsafe fn reals_less<T>(a: &T, b: &T) -> bool {
+    for i in 0..mem::size_of::<T>() / 4 {
+        let (a, b) = (real_at(a, i), real_at(b, i));
+        if a != b {
+            return a < b;
+        }
+    }
+    false
+}
+
+unsafe fn reals_zero<T>(a: &T) -> bool {
+    (0..mem::size_of::<T>() / 4).all(|i| real_at(a, i) == 0.0)
+}
+
+unsafe fn join_reals<T>(a: &T, sep: &str) -> String {
+    let reals = (0..mem::size_of::<T>() / 4)
+        .map(|i| real_at(a, i).to_string())
+        .collect::<Vec<_>>();
+    reals.join(sep)
+}
+
+/// A `godot_variant` owned by the mock, e.g. as an element of an array.
+///
+/// This has the same layout as `godot_variant`, so references to it can be handed out as
+/// variant pointers.
+#[repr(transparent)]
+pub(super) struct VariantSlot(pub(super) sys::godot_variant);
+
+impl VariantSlot {
+    pub(super) fn new(value: Value) -> Self {
+        unsafe {
+            let mut slot = VariantSlot(mem::zeroed());
+            init(&mut slot.0, value);
+            slot
+        }
+    }
+
+    pub(super) fn value(&self) -> &Value {
+        unsafe { get(&self.0) }
+    }
+}
+
+impl Clone for VariantSlot {
+    fn clone(&self) -> Self {
+        VariantSlot::new(self.value().clone())
+    }
+}
+
+impl Drop for VariantSlot {
+    fn drop(&mut self) {
+        unsafe { destroy(&mut self.0) }
+    }
+}
+
+/// Returns the value behind a variant. Null variants read as `Nil`.
+pub(super) unsafe fn get<'a>(variant: *const sys::godot_variant) -> &'a Value {
+    let ptr = handle_ptr::<_, Value>(variant);
+    if ptr.is_null() {
+        &NIL
+    } else {
+        &*ptr
+    }
+}
+
+/// Initializes a variant with `value`, without dropping any previous contents.
+pub(super) unsafe fn init(variant: *mut sys::godot_variant, value: Value) {
+    match value {
+        Value::Nil => std::ptr::write_unaligned(variant as *mut *mut Value, std::ptr::null_mut()),
+        value => handle_init(variant, value),
+    }
+}
+
+/// Replaces the value of an initialized variant.
+pub(super) unsafe fn set(variant: *mut sys::godot_variant, value: Value) {
+    destroy(variant);
+    init(variant, value);
+}
+
+pub(super) unsafe fn destroy(variant: *mut sys::godot_variant) {
+    drop(handle_take::<_, Value>(variant));
+}
+
+pub(super) unsafe fn new(value: Value) -> sys::godot_variant {
+    let mut variant = mem::zeroed();
+    init(&mut variant, value);
+    variant
+}
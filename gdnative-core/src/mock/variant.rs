@@ -0,0 +1,343 @@
+use super::object::{self, ObjectRef};
+use super::pool_arrays::{self, PoolElement, StringSlot};
+use super::value::{self, Value};
+use super::{collections, string};
+use crate::sys;
+use std::mem;
+use std::ptr;
+
+unsafe extern "C" fn godot_variant_new_nil(r_dest: *mut sys::godot_variant) {
+    value::init(r_dest, Value::Nil);
+}
+
+unsafe extern "C" fn godot_variant_new_copy(
+    r_dest: *mut sys::godot_variant,
+    p_src: *const sys::godot_variant,
+) {
+    value::init(r_dest, value::get(p_src).clone());
+}
+
+unsafe extern "C" fn godot_variant_destroy(p_self: *mut sys::godot_variant) {
+    value::destroy(p_self);
+}
+
+unsafe extern "C" fn godot_variant_new_bool(r_dest: *mut sys::godot_variant, p_b: sys::godot_bool) {
+    value::init(r_dest, Value::Bool(p_b));
+}
+
+unsafe extern "C" fn godot_variant_new_uint(r_dest: *mut sys::godot_variant, p_i: u64) {
+    value::init(r_dest, Value::Int(p_i as i64));
+}
+
+unsafe extern "C" fn godot_variant_new_int(r_dest: *mut sys::godot_variant, p_i: i64) {
+    value::init(r_dest, Value::Int(p_i));
+}
+
+unsafe extern "C" fn godot_variant_new_real(r_dest: *mut sys::godot_variant, p_r: f64) {
+    value::init(r_dest, Value::Real(p_r));
+}
+
+unsafe extern "C" fn godot_variant_new_string(
+    r_dest: *mut sys::godot_variant,
+    p_s: *const sys::godot_string,
+) {
+    value::init(r_dest, Value::String(string::get(p_s).clone()));
+}
+
+unsafe extern "C" fn godot_variant_new_node_path(
+    r_dest: *mut sys::godot_variant,
+    p_np: *const sys::godot_node_path,
+) {
+    value::init(r_dest, Value::NodePath(string::node_path(p_np).clone()));
+}
+
+unsafe extern "C" fn godot_variant_new_rid(
+    r_dest: *mut sys::godot_variant,
+    p_rid: *const sys::godot_rid,
+) {
+    value::init(
+        r_dest,
+        Value::Rid(ptr::read_unaligned(p_rid as *const usize)),
+    );
+}
+
+unsafe extern "C" fn godot_variant_new_object(
+    r_dest: *mut sys::godot_variant,
+    p_obj: *const sys::godot_object,
+) {
+    value::init(r_dest, Value::Object(ObjectRef::new(p_obj as *mut _)));
+}
+
+unsafe extern "C" fn godot_variant_new_dictionary(
+    r_dest: *mut sys::godot_variant,
+    p_dict: *const sys::godot_dictionary,
+) {
+    value::init(
+        r_dest,
+        Value::Dictionary(collections::dictionary(p_dict).clone()),
+    );
+}
+
+unsafe extern "C" fn godot_variant_new_array(
+    r_dest: *mut sys::godot_variant,
+    p_arr: *const sys::godot_array,
+) {
+    value::init(r_dest, Value::Array(collections::array(p_arr).clone()));
+}
+
+macro_rules! inline_types {
+    ($($new:ident, $as:ident: $ty:ty => $variant:ident;)*) => {
+        $(
+            unsafe extern "C" fn $new(r_dest: *mut sys::godot_variant, p_val: *const $ty) {
+                value::init(r_dest, Value::$variant(ptr::read_unaligned(p_val)));
+            }
+
+            unsafe extern "C" fn $as(p_self: *const sys::godot_variant) -> $ty {
+                match value::get(p_self) {
+                    Value::$variant(val) => *val,
+                    _ => mem::zeroed(),
+                }
+            }
+        )*
+
+        mock_lookup! {
+            fn lookup_inline {
+                $($new, $as,)*
+            }
+        }
+    };
+}
+
+inline_types! {
+    godot_variant_new_vector2, godot_variant_as_vector2: sys::godot_vector2 => Vector2;
+    godot_variant_new_rect2, godot_variant_as_rect2: sys::godot_rect2 => Rect2;
+    godot_variant_new_vector3, godot_variant_as_vector3: sys::godot_vector3 => Vector3;
+    godot_variant_new_transform2d, godot_variant_as_transform2d: sys::godot_transform2d => Transform2D;
+    godot_variant_new_plane, godot_variant_as_plane: sys::godot_plane => Plane;
+    godot_variant_new_quat, godot_variant_as_quat: sys::godot_quat => Quat;
+    godot_variant_new_aabb, godot_variant_as_aabb: sys::godot_aabb => Aabb;
+    godot_variant_new_basis, godot_variant_as_basis: sys::godot_basis => Basis;
+    godot_variant_new_transform, godot_variant_as_transform: sys::godot_transform => Transform;
+    godot_variant_new_color, godot_variant_as_color: sys::godot_color => Color;
+}
+
+macro_rules! pool_types {
+    ($($new:ident, $as:ident: $ty:ty => $elem:ty;)*) => {
+        $(
+            unsafe extern "C" fn $new(r_dest: *mut sys::godot_variant, p_val: *const $ty) {
+                let pool = pool_arrays::pool::<$elem>(p_val as *const _).clone();
+                value::init(r_dest, <$elem as PoolElement>::wrap(pool));
+            }
+
+            unsafe extern "C" fn $as(p_self: *const sys::godot_variant) -> $ty {
+                pool_arrays::new_pool(pool_arrays::from_value::<$elem>(value::get(p_self)))
+            }
+        )*
+
+        mock_lookup! {
+            fn lookup_pool {
+                $($new, $as,)*
+            }
+        }
+    };
+}
+
+pool_types! {
+    godot_variant_new_pool_byte_array, godot_variant_as_pool_byte_array: sys::godot_pool_byte_array => u8;
+    godot_variant_new_pool_int_array, godot_variant_as_pool_int_array: sys::godot_pool_int_array => i32;
+    godot_variant_new_pool_real_array, godot_variant_as_pool_real_array: sys::godot_pool_real_array => f32;
+    godot_variant_new_pool_string_array, godot_variant_as_pool_string_array: sys::godot_pool_string_array => StringSlot;
+    godot_variant_new_pool_vector2_array, godot_variant_as_pool_vector2_array: sys::godot_pool_vector2_array => sys::godot_vector2;
+    godot_variant_new_pool_vector3_array, godot_variant_as_pool_vector3_array: sys::godot_pool_vector3_array => sys::godot_vector3;
+    godot_variant_new_pool_color_array, godot_variant_as_pool_color_array: sys::godot_pool_color_array => sys::godot_color;
+}
+
+unsafe extern "C" fn godot_variant_as_bool(p_self: *const sys::godot_variant) -> sys::godot_bool {
+    value::get(p_self).booleanize()
+}
+
+unsafe extern "C" fn godot_variant_as_uint(p_self: *const sys::godot_variant) -> u64 {
+    value::get(p_self).to_int() as u64
+}
+
+unsafe extern "C" fn godot_variant_as_int(p_self: *const sys::godot_variant) -> i64 {
+    value::get(p_self).to_int()
+}
+
+unsafe extern "C" fn godot_variant_as_real(p_self: *const sys::godot_variant) -> f64 {
+    value::get(p_self).to_real()
+}
+
+unsafe extern "C" fn godot_variant_as_string(
+    p_self: *const sys::godot_variant,
+) -> sys::godot_string {
+    string::new(value::get(p_self).stringify())
+}
+
+unsafe extern "C" fn godot_variant_as_node_path(
+    p_self: *const sys::godot_variant,
+) -> sys::godot_node_path {
+    let path = match value::get(p_self) {
+        Value::NodePath(path) | Value::String(path) => path.clone(),
+        _ => String::new(),
+    };
+    super::handle_new(path)
+}
+
+unsafe extern "C" fn godot_variant_as_rid(p_self: *const sys::godot_variant) -> sys::godot_rid {
+    let id = match value::get(p_self) {
+        Value::Rid(id) => *id,
+        _ => 0,
+    };
+    let mut rid: sys::godot_rid = mem::zeroed();
+    ptr::write_unaligned(&mut rid as *mut _ as *mut usize, id);
+    rid
+}
+
+unsafe extern "C" fn godot_variant_as_object(
+    p_self: *const sys::godot_variant,
+) -> *mut sys::godot_object {
+    match value::get(p_self) {
+        Value::Object(obj) => obj.ptr(),
+        _ => ptr::null_mut(),
+    }
+}
+
+unsafe extern "C" fn godot_variant_as_dictionary(
+    p_self: *const sys::godot_variant,
+) -> sys::godot_dictionary {
+    let dict = match value::get(p_self) {
+        Value::Dictionary(dict) => dict.clone(),
+        _ => Default::default(),
+    };
+    collections::new_dictionary(dict)
+}
+
+unsafe extern "C" fn godot_variant_as_array(p_self: *const sys::godot_variant) -> sys::godot_array {
+    collections::new_array(value::get(p_self).to_array())
+}
+
+unsafe extern "C" fn godot_variant_get_type(
+    p_self: *const sys::godot_variant,
+) -> sys::godot_variant_type {
+    value::get(p_self).variant_type()
+}
+
+unsafe extern "C" fn godot_variant_operator_equal(
+    p_self: *const sys::godot_variant,
+    p_other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    // Unlike the `==` operator of scripts, `Variant::operator==` never equates different types.
+    let (a, b) = (value::get(p_self), value::get(p_other));
+    a.variant_type() == b.variant_type() && a.equals(b)
+}
+
+unsafe extern "C" fn godot_variant_hash_compare(
+    p_self: *const sys::godot_variant,
+    p_other: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value::get(p_self).key_equals(value::get(p_other))
+}
+
+unsafe extern "C" fn godot_variant_booleanize(
+    p_self: *const sys::godot_variant,
+) -> sys::godot_bool {
+    value::get(p_self).booleanize()
+}
+
+unsafe extern "C" fn godot_variant_has_method(
+    p_self: *const sys::godot_variant,
+    p_method: *const sys::godot_string,
+) -> sys::godot_bool {
+    match value::get(p_self) {
+        Value::Object(obj) => object::has_method(obj.ptr(), string::get(p_method)),
+        _ => false,
+    }
+}
+
+/// Calls methods of NativeScript instances. Built-in methods of other types are not
+/// supported.
+unsafe extern "C" fn godot_variant_call(
+    p_self: *mut sys::godot_variant,
+    p_method: *const sys::godot_string,
+    p_args: *mut *const sys::godot_variant,
+    p_argcount: sys::godot_int,
+    r_error: *mut sys::godot_variant_call_error,
+) -> sys::godot_variant {
+    let method = string::get(p_method);
+    let args = if p_argcount > 0 {
+        std::slice::from_raw_parts(p_args, p_argcount as usize)
+    } else {
+        &[]
+    };
+
+    let mut error = sys::godot_variant_call_error {
+        error: sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_OK,
+        argument: 0,
+        expected: sys::godot_variant_type_GODOT_VARIANT_TYPE_NIL,
+    };
+
+    let ret = match value::get(p_self) {
+        Value::Object(obj) if obj.is_null() => {
+            error.error =
+                sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_ERROR_INSTANCE_IS_NULL;
+            None
+        }
+        Value::Object(obj) => object::call_script_method(obj.ptr(), method, args),
+        _ => None,
+    };
+
+    let ret = ret.unwrap_or_else(|| {
+        if error.error == sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_OK {
+            error.error =
+                sys::godot_variant_call_error_error_GODOT_CALL_ERROR_CALL_ERROR_INVALID_METHOD;
+        }
+        value::new(Value::Nil)
+    });
+
+    if !r_error.is_null() {
+        *r_error = error;
+    }
+
+    ret
+}
+
+mock_lookup! {
+    fn lookup_scalar {
+        godot_variant_new_nil,
+        godot_variant_new_copy,
+        godot_variant_destroy,
+        godot_variant_new_bool,
+        godot_variant_new_uint,
+        godot_variant_new_int,
+        godot_variant_new_real,
+        godot_variant_new_string,
+        godot_variant_new_node_path,
+        godot_variant_new_rid,
+        godot_variant_new_object,
+        godot_variant_new_dictionary,
+        godot_variant_new_array,
+        godot_variant_as_bool,
+        godot_variant_as_uint,
+        godot_variant_as_int,
+        godot_variant_as_real,
+        godot_variant_as_string,
+        godot_variant_as_node_path,
+        godot_variant_as_rid,
+        godot_variant_as_object,
+        godot_variant_as_dictionary,
+        godot_variant_as_array,
+        godot_variant_get_type,
+        godot_variant_operator_equal,
+        godot_variant_hash_compare,
+        godot_variant_booleanize,
+        godot_variant_has_method,
+        godot_variant_call,
+    }
+}
+
+pub(super) fn lookup(name: &str) -> Option<*const libc::c_void> {
+    lookup_scalar(name)
+        .or_else(|| lookup_inline(name))
+        .or_else(|| lookup_pool(name))
+}
@@ -3,7 +3,12 @@ use crate::sys;
 use crate::GodotString;
 use crate::ToVariant;
 use crate::Variant;
+use std::convert::Infallible;
 use std::fmt;
+use std::hash::{Hash, Hasher};
+use std::iter::{self, FromIterator};
+use std::ops::Range;
+use std::str::FromStr;
 
 /// A reference-counted relative or absolute path in a scene tree, for use with `Node.get_node()` and similar
 /// functions. It can reference a node, a resource within a node, or a property of a node or
@@ -62,8 +67,22 @@ impl NodePath {
     }
 
     /// Get the number of node names which make up the path.
-    pub fn name_count(&mut self) -> i32 {
-        unsafe { (get_api().godot_node_path_get_name_count)(&mut self.0) }
+    pub fn name_count(&self) -> i32 {
+        unsafe { (get_api().godot_node_path_get_name_count)(&self.0) }
+    }
+
+    /// Returns the node name of the specified `idx`, 0 to name_count()
+    pub fn get_name(&self, idx: i32) -> GodotString {
+        unsafe { GodotString((get_api().godot_node_path_get_name)(&self.0, idx)) }
+    }
+
+    /// Returns an iterator over the node names of the path.
+    pub fn names(&self) -> NodePathNames<'_> {
+        NodePathNames {
+            path: self,
+            range: 0..self.name_count(),
+            get: NodePath::get_name,
+        }
     }
 
     /// Returns the resource name of the specified `idx`, 0 to subname_count()
@@ -84,16 +103,101 @@ impl NodePath {
         }
     }
 
+    /// Returns an iterator over the resource and property names of the path.
+    pub fn subnames(&self) -> NodePathNames<'_> {
+        NodePathNames {
+            path: self,
+            range: 0..self.get_subname_count(),
+            get: NodePath::get_subname,
+        }
+    }
+
+    /// Returns the path with all its node names turned into subnames, relative to the current
+    /// node, e.g. `":Sprite:texture"` for `"Sprite:texture"`.
+    pub fn get_as_property_path(&self) -> NodePath {
+        unsafe { NodePath((get_api().godot_node_path_get_as_property_path)(&self.0)) }
+    }
+
+    /// Returns `path` relative to this path, e.g. `"Level/Player:position"` for
+    /// `"Level".join("Player:position")`.
+    ///
+    /// The subnames of this path are discarded. If `path` is absolute, it is returned as is.
+    pub fn join(&self, path: &NodePath) -> NodePath {
+        if path.is_absolute() {
+            return path.new_ref();
+        }
+
+        NodePath::from_parts(
+            self.is_absolute(),
+            self.names().chain(path.names()),
+            path.subnames(),
+        )
+    }
+
+    /// Returns the path to the parent of the node this path points to, without subnames.
+    ///
+    /// `".."` is appended to paths that end in `".."`, so that the result still points to the
+    /// parent. Returns `None` for paths without node names, like `"/"` and `":position"`.
+    pub fn parent(&self) -> Option<NodePath> {
+        let mut names: Vec<GodotString> = self.names().collect();
+        let last = names.pop()?;
+
+        if last == ".." {
+            names.push(last);
+            names.push("..".into());
+        } else if last == "." {
+            names.push("..".into());
+        } else if names.is_empty() && !self.is_absolute() {
+            names.push(".".into());
+        }
+
+        Some(NodePath::from_parts(
+            self.is_absolute(),
+            names,
+            iter::empty(),
+        ))
+    }
+
+    /// Returns the path with `property` appended to its subnames, e.g.
+    /// `"Sprite:texture:size"` for `"Sprite:texture".with_property("size")`.
+    pub fn with_property(&self, property: &str) -> NodePath {
+        NodePath::from_parts(
+            self.is_absolute(),
+            self.names(),
+            self.subnames().chain(iter::once(property.into())),
+        )
+    }
+
+    fn from_parts<N, S>(absolute: bool, names: N, subnames: S) -> NodePath
+    where
+        N: IntoIterator<Item = GodotString>,
+        S: IntoIterator<Item = GodotString>,
+    {
+        let mut path = String::new();
+        if absolute {
+            path.push('/');
+        }
+
+        for (i, name) in names.into_iter().enumerate() {
+            if i > 0 {
+                path.push('/');
+            }
+            path.push_str(name.to_utf8().as_str());
+        }
+
+        for subname in subnames {
+            path.push(':');
+            path.push_str(subname.to_utf8().as_str());
+        }
+
+        NodePath::from_str(&path)
+    }
+
     /// Returns the `NodePath` as a `GodotString`
     pub fn to_godot_string(&self) -> GodotString {
         unsafe { GodotString((get_api().godot_node_path_as_string)(&self.0)) }
     }
 
-    /// Returns the `NodePath` as a `String`
-    pub fn to_string(&self) -> String {
-        self.to_godot_string().to_string()
-    }
-
     #[doc(hidden)]
     pub fn sys(&self) -> *const sys::godot_node_path {
         &self.0
@@ -119,6 +223,22 @@ where
     }
 }
 
+impl FromStr for NodePath {
+    type Err = Infallible;
+
+    /// Parses a path. This never fails: like in the engine, any string is a valid path.
+    fn from_str(path: &str) -> Result<Self, Infallible> {
+        Ok(NodePath::from_str(path))
+    }
+}
+
+impl FromIterator<GodotString> for NodePath {
+    /// Creates a relative path from node names.
+    fn from_iter<I: IntoIterator<Item = GodotString>>(names: I) -> Self {
+        NodePath::from_parts(false, names, iter::empty())
+    }
+}
+
 impl Into<String> for NodePath {
     fn into(self) -> String {
         self.to_string()
@@ -150,8 +270,85 @@ impl ToVariant for NodePath {
     }
 }
 
+/// Node paths are hashed by their names and subnames, like they are compared.
+impl Hash for NodePath {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.is_absolute().hash(state);
+        self.name_count().hash(state);
+        for name in self.names() {
+            name.hash(state);
+        }
+        for subname in self.subnames() {
+            subname.hash(state);
+        }
+    }
+}
+
 impl fmt::Debug for NodePath {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
-        write!(f, "NodePath({})", self.to_string())
+        write!(f, "NodePath({})", self)
     }
 }
+
+impl fmt::Display for NodePath {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.to_godot_string(), f)
+    }
+}
+
+/// Iterator over the node names or the subnames of a `NodePath`.
+pub struct NodePathNames<'a> {
+    path: &'a NodePath,
+    range: Range<i32>,
+    get: fn(&NodePath, i32) -> GodotString,
+}
+
+impl<'a> Iterator for NodePathNames<'a> {
+    type Item = GodotString;
+
+    fn next(&mut self) -> Option<GodotString> {
+        self.range.next().map(|idx| (self.get)(self.path, idx))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+impl<'a> DoubleEndedIterator for NodePathNames<'a> {
+    fn next_back(&mut self) -> Option<GodotString> {
+        self.range.next_back().map(|idx| (self.get)(self.path, idx))
+    }
+}
+
+impl<'a> ExactSizeIterator for NodePathNames<'a> {}
+
+godot_test!(test_node_path {
+    let path: NodePath = "Level/Player:position:x".parse().unwrap();
+    assert!(!path.is_absolute());
+    assert_eq!(path.to_string(), "Level/Player:position:x");
+    assert_eq!(path.names().collect::<Vec<_>>(), ["Level", "Player"]);
+    assert_eq!(path.subnames().collect::<Vec<_>>(), ["position", "x"]);
+    assert_eq!(path.names().rev().next().unwrap(), "Player");
+
+    let level = NodePath::from_str("/root/Level");
+    assert_eq!(level.join(&"Player:position".into()), "/root/Level/Player:position".into());
+    assert_eq!(level.join(&"/root".into()), "/root".into());
+    assert_eq!(level.parent(), Some("/root".into()));
+    assert_eq!(NodePath::from_str("/root").parent(), Some("/".into()));
+    assert_eq!(NodePath::from_str("/").parent(), None);
+    assert_eq!(path.parent(), Some("Level".into()));
+    assert_eq!(NodePath::from_str("Level").parent(), Some(".".into()));
+    assert_eq!(NodePath::from_str("..").parent(), Some("../..".into()));
+    assert_eq!(
+        NodePath::from_str("Sprite:texture").with_property("size"),
+        "Sprite:texture:size".into()
+    );
+
+    let names = vec![GodotString::from("A"), GodotString::from("B")];
+    assert_eq!(names.into_iter().collect::<NodePath>(), "A/B".into());
+
+    let mut paths = std::collections::HashSet::new();
+    paths.insert(path.new_ref());
+    assert!(paths.contains(&"Level/Player:position:x".into()));
+});
@@ -0,0 +1,1227 @@
+use crate::access::{Aligned, Guard, MaybeUnaligned, WritePtr};
+use crate::get_api;
+use crate::result_from_sys;
+use crate::sys;
+use crate::Color;
+use crate::FromVariant;
+use crate::FromVariantError;
+use crate::GodotResult;
+use crate::GodotString;
+use crate::ToVariant;
+use crate::Variant;
+use crate::VariantArray;
+use crate::Vector2;
+use crate::Vector3;
+
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+use std::mem::transmute;
+
+/// A reference-counted vector of `T` that uses Godot's pool allocator.
+///
+/// Writes to an array that is shared with other references copy its storage first, so
+/// `new_ref` and read accesses are not affected by them.
+pub struct PoolArray<T: PoolElement>(pub(crate) T::SysArray);
+
+/// A reference-counted vector of bytes that uses Godot's pool allocator.
+pub type ByteArray = PoolArray<u8>;
+
+/// A reference-counted vector of `i32` that uses Godot's pool allocator.
+pub type Int32Array = PoolArray<i32>;
+
+/// A reference-counted vector of `f32` that uses Godot's pool allocator.
+pub type Float32Array = PoolArray<f32>;
+
+/// A reference-counted vector of `GodotString` that uses Godot's pool allocator.
+pub type StringArray = PoolArray<GodotString>;
+
+/// A reference-counted vector of `Vector2` that uses Godot's pool allocator.
+pub type Vector2Array = PoolArray<Vector2>;
+
+/// A reference-counted vector of `Vector3` that uses Godot's pool allocator.
+pub type Vector3Array = PoolArray<Vector3>;
+
+/// A reference-counted vector of `Color` that uses Godot's pool allocator.
+pub type ColorArray = PoolArray<Color>;
+
+pub type Read<'a, T> = Aligned<ReadGuard<'a, T>>;
+pub type Write<'a, T> = Aligned<WriteGuard<'a, T>>;
+
+impl<T: PoolElement> PoolArray<T> {
+    /// Creates an empty array.
+    pub fn new() -> Self {
+        PoolArray::default()
+    }
+
+    /// Creates an array by trying to convert each variant.
+    ///
+    /// When no viable conversion exists, the default value of `T` is pushed.
+    pub fn from_variant_array(array: &VariantArray) -> Self {
+        unsafe {
+            let mut result = T::SysArray::default();
+            (T::new_with_array_fn(get_api()))(&mut result, &array.0);
+            PoolArray(result)
+        }
+    }
+
+    /// Creates an array by copying a slice in bulk. The array is resized once, and the
+    /// elements are copied through a single write access.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` has more than `i32::MAX` elements.
+    pub fn from_slice(src: &[T]) -> Self {
+        let mut array = PoolArray::new();
+        if !src.is_empty() {
+            array.resize(pool_len(src.len()));
+            array.write().clone_from_slice(src);
+        }
+        array
+    }
+
+    /// Creates an array by moving the elements of a `Vec` in bulk, without cloning them.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `src` has more than `i32::MAX` elements.
+    pub fn from_vec(mut src: Vec<T>) -> Self {
+        let mut array = PoolArray::new();
+        if !src.is_empty() {
+            array.resize(pool_len(src.len()));
+            // The default elements the array was resized with are dropped along with `src`.
+            array.write().swap_with_slice(&mut src);
+        }
+        array
+    }
+
+    /// Copies the elements of the array into a `Vec` through a single read access.
+    pub fn to_vec(&self) -> Vec<T> {
+        if self.is_empty() {
+            Vec::new()
+        } else {
+            self.read().to_vec()
+        }
+    }
+
+    /// Appends an element to the end of the array.
+    pub fn push(&mut self, val: &T) {
+        unsafe {
+            (T::append_fn(get_api()))(&mut self.0, val.element_to_sys());
+        }
+    }
+
+    /// Appends each element of `src` to the end of the array.
+    pub fn append(&mut self, src: &PoolArray<T>) {
+        unsafe {
+            (T::append_array_fn(get_api()))(&mut self.0, &src.0);
+        }
+    }
+
+    /// Inserts an element at the given offset. Fails if the offset is out of bounds.
+    pub fn insert(&mut self, offset: i32, val: &T) -> GodotResult {
+        unsafe {
+            let status = (T::insert_fn(get_api()))(&mut self.0, offset, val.element_to_sys());
+            result_from_sys(status)
+        }
+    }
+
+    /// Inverts the order of the elements in the array.
+    pub fn invert(&mut self) {
+        unsafe { (T::invert_fn(get_api()))(&mut self.0) }
+    }
+
+    /// Removes an element at the given offset.
+    pub fn remove(&mut self, idx: i32) {
+        unsafe {
+            (T::remove_fn(get_api()))(&mut self.0, idx);
+        }
+    }
+
+    /// Changes the size of the array, possibly removing elements or pushing default values.
+    pub fn resize(&mut self, size: i32) {
+        unsafe {
+            (T::resize_fn(get_api()))(&mut self.0, size);
+        }
+    }
+
+    /// Returns a copy of the element at the given offset.
+    pub fn get(&self, idx: i32) -> T {
+        unsafe { T::element_from_sys((T::get_fn(get_api()))(&self.0, idx)) }
+    }
+
+    /// Sets the value of the element at the given offset.
+    pub fn set(&mut self, idx: i32, val: &T) {
+        unsafe {
+            (T::set_fn(get_api()))(&mut self.0, idx, val.element_to_sys());
+        }
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> i32 {
+        unsafe { (T::size_fn(get_api()))(&self.0) }
+    }
+
+    /// Returns `true` if the array contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns a read access to the elements of the array.
+    pub fn read(&self) -> Read<'_, T> {
+        unsafe {
+            MaybeUnaligned::new(ReadGuard::new(self.sys()))
+                .try_into_aligned()
+                .expect("Pool array access should be aligned. This indicates a bug in Godot")
+        }
+    }
+
+    /// Returns a write access to the elements of the array. The storage is copied first if it
+    /// is shared with other references.
+    pub fn write(&mut self) -> Write<'_, T> {
+        unsafe {
+            MaybeUnaligned::new(WriteGuard::new(&mut self.0))
+                .try_into_aligned()
+                .expect("Pool array access should be aligned. This indicates a bug in Godot")
+        }
+    }
+
+    /// Returns an iterator over copies of the elements of the array.
+    pub fn iter(&self) -> PoolArrayIter<'_, T> {
+        // Empty arrays may not have storage to access.
+        let read = if self.is_empty() {
+            None
+        } else {
+            Some(self.read())
+        };
+        PoolArrayIter {
+            range: 0..self.len() as usize,
+            read,
+        }
+    }
+
+    /// Creates a new reference to this array.
+    pub fn new_ref(&self) -> Self {
+        unsafe {
+            let mut result = T::SysArray::default();
+            (T::new_copy_fn(get_api()))(&mut result, &self.0);
+            PoolArray(result)
+        }
+    }
+
+    #[doc(hidden)]
+    pub fn sys(&self) -> *const T::SysArray {
+        &self.0
+    }
+
+    #[doc(hidden)]
+    pub fn from_sys(sys: T::SysArray) -> Self {
+        PoolArray(sys)
+    }
+}
+
+impl<T: PoolElement> Drop for PoolArray<T> {
+    fn drop(&mut self) {
+        unsafe { (T::destroy_fn(get_api()))(&mut self.0) }
+    }
+}
+
+impl<T: PoolElement> Default for PoolArray<T> {
+    fn default() -> Self {
+        unsafe {
+            let mut result = T::SysArray::default();
+            (T::new_fn(get_api()))(&mut result);
+            PoolArray(result)
+        }
+    }
+}
+
+/// Converts the length of a Rust collection to the length of a pool array.
+fn pool_len(len: usize) -> i32 {
+    use std::convert::TryFrom;
+
+    i32::try_from(len).unwrap_or_else(|_| {
+        panic!(
+            "length {} exceeds the maximum length of a pool array, {}",
+            len,
+            i32::MAX
+        )
+    })
+}
+
+impl<T: PoolElement> From<&[T]> for PoolArray<T> {
+    fn from(src: &[T]) -> Self {
+        PoolArray::from_slice(src)
+    }
+}
+
+impl<T: PoolElement> From<Vec<T>> for PoolArray<T> {
+    fn from(src: Vec<T>) -> Self {
+        PoolArray::from_vec(src)
+    }
+}
+
+impl<T: PoolElement> FromIterator<T> for PoolArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array = PoolArray::new();
+        array.extend(iter);
+        array
+    }
+}
+
+impl<T: PoolElement> Extend<T> for PoolArray<T> {
+    /// Appends the elements in bulk, resizing the array once. Panics if the array would have
+    /// more than `i32::MAX` elements.
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        let elements = iter.into_iter().collect::<Vec<_>>();
+        if elements.is_empty() {
+            return;
+        }
+
+        let len = self.len();
+        self.resize(pool_len(len as usize + elements.len()));
+        let mut write = self.write();
+        for (slot, element) in write[len as usize..].iter_mut().zip(elements) {
+            *slot = element;
+        }
+    }
+}
+
+impl<'a, T: PoolElement> IntoIterator for &'a PoolArray<T> {
+    type Item = T;
+    type IntoIter = PoolArrayIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+/// Iterator over copies of the elements of a `PoolArray`.
+///
+/// The iterator holds a read access, so writes to other references of the array are not
+/// visible to it.
+pub struct PoolArrayIter<'a, T: PoolElement> {
+    read: Option<Read<'a, T>>,
+    range: std::ops::Range<usize>,
+}
+
+impl<'a, T: PoolElement> Iterator for PoolArrayIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let read = self.read.as_ref()?;
+        self.range.next().map(|idx| read[idx].clone())
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+macro_rules! impl_to_variant_for_pool_arrays {
+    ($(impl ToVariant for $Array:ident : $ctor:ident;)*) => {
+        $(
+            impl ToVariant for $Array {
+                fn to_variant(&self) -> Variant {
+                    Variant::$ctor(self)
+                }
+            }
+        )*
+    };
+}
+
+impl_to_variant_for_pool_arrays! {
+    impl ToVariant for ByteArray : from_byte_array;
+    impl ToVariant for Int32Array : from_int32_array;
+    impl ToVariant for Float32Array : from_float32_array;
+    impl ToVariant for StringArray : from_string_array;
+    impl ToVariant for Vector2Array : from_vector2_array;
+    impl ToVariant for Vector3Array : from_vector3_array;
+    impl ToVariant for ColorArray : from_color_array;
+}
+
+/// A `Vec` that converts to and from the matching pool array variant in bulk.
+///
+/// `Vec<T>` converts to a `VariantArray` element by element. Wrapping it, e.g. as
+/// `PoolVec<u8>` or `PoolVec<Vector3>`, copies it into a `ByteArray` or `Vector3Array`
+/// with `PoolArray::from_slice` instead, and reads it back with `PoolArray::to_vec`.
+#[derive(Clone, Debug, Default, PartialEq)]
+pub struct PoolVec<T>(pub Vec<T>);
+
+impl<T> PoolVec<T> {
+    /// Returns the wrapped `Vec`.
+    pub fn into_inner(self) -> Vec<T> {
+        self.0
+    }
+}
+
+impl<T> From<Vec<T>> for PoolVec<T> {
+    fn from(vec: Vec<T>) -> Self {
+        PoolVec(vec)
+    }
+}
+
+impl<T> std::ops::Deref for PoolVec<T> {
+    type Target = Vec<T>;
+
+    fn deref(&self) -> &Vec<T> {
+        &self.0
+    }
+}
+
+impl<T> std::ops::DerefMut for PoolVec<T> {
+    fn deref_mut(&mut self) -> &mut Vec<T> {
+        &mut self.0
+    }
+}
+
+impl<T: PoolElement> ToVariant for PoolVec<T>
+where
+    PoolArray<T>: ToVariant,
+{
+    fn to_variant(&self) -> Variant {
+        PoolArray::from_slice(&self.0).to_variant()
+    }
+}
+
+impl<T: PoolElement> FromVariant for PoolVec<T>
+where
+    PoolArray<T>: FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        PoolArray::<T>::from_variant(variant).map(|array| PoolVec(array.to_vec()))
+    }
+}
+
+/// Read access guard of a `PoolArray`.
+pub struct ReadGuard<'a, T: PoolElement> {
+    access: *mut T::SysReadAccess,
+    len: usize,
+    _marker: PhantomData<&'a T>,
+}
+
+impl<'a, T: PoolElement> ReadGuard<'a, T> {
+    unsafe fn new(arr: *const T::SysArray) -> Self {
+        let len = (T::size_fn(get_api()))(arr) as usize;
+        let access = (T::read_fn(get_api()))(arr);
+        ReadGuard {
+            access,
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<'a, T: PoolElement> Guard for ReadGuard<'a, T> {
+    type Target = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn read_ptr(&self) -> *const T {
+        unsafe { (T::read_access_ptr_fn(get_api()))(self.access) as *const T }
+    }
+}
+
+impl<'a, T: PoolElement> Drop for ReadGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe { (T::read_access_destroy_fn(get_api()))(self.access) }
+    }
+}
+
+impl<'a, T: PoolElement> Clone for ReadGuard<'a, T> {
+    fn clone(&self) -> Self {
+        let access = unsafe { (T::read_access_copy_fn(get_api()))(self.access) };
+        ReadGuard {
+            access,
+            len: self.len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Write access guard of a `PoolArray`.
+pub struct WriteGuard<'a, T: PoolElement> {
+    access: *mut T::SysWriteAccess,
+    len: usize,
+    _marker: PhantomData<&'a mut T>,
+}
+
+impl<'a, T: PoolElement> WriteGuard<'a, T> {
+    unsafe fn new(arr: *mut T::SysArray) -> Self {
+        let len = (T::size_fn(get_api()))(arr) as usize;
+        let access = (T::write_fn(get_api()))(arr);
+        WriteGuard {
+            access,
+            len,
+            _marker: PhantomData,
+        }
+    }
+}
+
+unsafe impl<'a, T: PoolElement> Guard for WriteGuard<'a, T> {
+    type Target = T;
+
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    fn read_ptr(&self) -> *const T {
+        unsafe { (T::write_access_ptr_fn(get_api()))(self.access) as *const T }
+    }
+}
+
+unsafe impl<'a, T: PoolElement> WritePtr for WriteGuard<'a, T> {}
+
+impl<'a, T: PoolElement> Drop for WriteGuard<'a, T> {
+    fn drop(&mut self) {
+        unsafe { (T::write_access_destroy_fn(get_api()))(self.access) }
+    }
+}
+
+mod private {
+    pub trait Sealed {}
+}
+
+/// Types that can be stored in a `PoolArray`: `u8`, `i32`, `f32`, `GodotString`, `Vector2`,
+/// `Vector3` and `Color`.
+///
+/// This trait is sealed. Its items are the glue to the per-type functions of the API.
+pub trait PoolElement: Clone + private::Sealed {
+    #[doc(hidden)]
+    type SysArray: Default;
+    #[doc(hidden)]
+    type SysReadAccess;
+    #[doc(hidden)]
+    type SysWriteAccess;
+    #[doc(hidden)]
+    type SysElement;
+    /// The type elements are passed as to the API: by value for numbers, by pointer otherwise.
+    #[doc(hidden)]
+    type SysArg;
+
+    #[doc(hidden)]
+    fn element_to_sys(&self) -> Self::SysArg;
+    #[doc(hidden)]
+    unsafe fn element_from_sys(sys: Self::SysElement) -> Self;
+
+    #[doc(hidden)]
+    fn new_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray);
+    #[doc(hidden)]
+    fn new_copy_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray);
+    #[doc(hidden)]
+    fn new_with_array_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, *const sys::godot_array);
+    #[doc(hidden)]
+    fn destroy_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray);
+    #[doc(hidden)]
+    fn append_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, Self::SysArg);
+    #[doc(hidden)]
+    fn append_array_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray);
+    #[doc(hidden)]
+    fn insert_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysArg) -> sys::godot_error;
+    #[doc(hidden)]
+    fn invert_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray);
+    #[doc(hidden)]
+    fn remove_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int);
+    #[doc(hidden)]
+    fn resize_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int);
+    #[doc(hidden)]
+    fn set_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysArg);
+    #[doc(hidden)]
+    fn get_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysArray, sys::godot_int) -> Self::SysElement;
+    #[doc(hidden)]
+    fn size_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysArray) -> sys::godot_int;
+    #[doc(hidden)]
+    fn read_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysArray) -> *mut Self::SysReadAccess;
+    #[doc(hidden)]
+    fn read_access_copy_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *mut Self::SysReadAccess;
+    #[doc(hidden)]
+    fn read_access_ptr_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *const Self::SysElement;
+    #[doc(hidden)]
+    fn read_access_destroy_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysReadAccess);
+    #[doc(hidden)]
+    fn write_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysArray) -> *mut Self::SysWriteAccess;
+    #[doc(hidden)]
+    fn write_access_ptr_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*const Self::SysWriteAccess) -> *mut Self::SysElement;
+    #[doc(hidden)]
+    fn write_access_destroy_fn(
+        api: &sys::GodotApi,
+    ) -> unsafe extern "C" fn(*mut Self::SysWriteAccess);
+}
+
+// Elements are passed by value or by pointer, as in the API. Elements that are not numbers have
+// the same layout as their sys types, which is also what the access guards rely on.
+macro_rules! pool_element_arg {
+    (value, $Element:ty) => { $Element };
+    (ptr, $Element:ty) => { *const $Element };
+}
+
+macro_rules! pool_element_to_sys {
+    (value, $self:ident, $Element:ty) => {
+        *$self
+    };
+    (ptr, $self:ident, $Element:ty) => {
+        $self as *const Self as *const $Element
+    };
+}
+
+macro_rules! impl_pool_element {
+    (
+        $(
+            impl PoolElement for $T:ty as $SysArray:ident ($arg:ident $Element:ty) {
+                read_access = $SysReadAccess:ident,
+                write_access = $SysWriteAccess:ident,
+                new = $new:ident,
+                new_copy = $new_copy:ident,
+                new_with_array = $new_with_array:ident,
+                destroy = $destroy:ident,
+                append = $append:ident,
+                append_array = $append_array:ident,
+                insert = $insert:ident,
+                invert = $invert:ident,
+                remove = $remove:ident,
+                resize = $resize:ident,
+                set = $set:ident,
+                get = $get:ident,
+                size = $size:ident,
+                read = $read:ident,
+                read_access_copy = $read_access_copy:ident,
+                read_access_ptr = $read_access_ptr:ident,
+                read_access_destroy = $read_access_destroy:ident,
+                write = $write:ident,
+                write_access_ptr = $write_access_ptr:ident,
+                write_access_destroy = $write_access_destroy:ident,
+            }
+        )*
+    ) => {
+        $(
+            impl private::Sealed for $T {}
+
+            impl PoolElement for $T {
+                type SysArray = sys::$SysArray;
+                type SysReadAccess = sys::$SysReadAccess;
+                type SysWriteAccess = sys::$SysWriteAccess;
+                type SysElement = $Element;
+                type SysArg = pool_element_arg!($arg, $Element);
+
+                fn element_to_sys(&self) -> Self::SysArg {
+                    pool_element_to_sys!($arg, self, $Element)
+                }
+
+                #[allow(clippy::useless_transmute)]
+                unsafe fn element_from_sys(sys: Self::SysElement) -> Self {
+                    transmute::<$Element, $T>(sys)
+                }
+
+                fn new_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray) {
+                    api.$new
+                }
+
+                fn new_copy_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray) {
+                    api.$new_copy
+                }
+
+                fn new_with_array_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray, *const sys::godot_array) {
+                    api.$new_with_array
+                }
+
+                fn destroy_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray) {
+                    api.$destroy
+                }
+
+                fn append_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray, Self::SysArg) {
+                    api.$append
+                }
+
+                fn append_array_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray, *const Self::SysArray) {
+                    api.$append_array
+                }
+
+                fn insert_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(
+                    *mut Self::SysArray,
+                    sys::godot_int,
+                    Self::SysArg,
+                ) -> sys::godot_error {
+                    api.$insert
+                }
+
+                fn invert_fn(api: &sys::GodotApi) -> unsafe extern "C" fn(*mut Self::SysArray) {
+                    api.$invert
+                }
+
+                fn remove_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int) {
+                    api.$remove
+                }
+
+                fn resize_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int) {
+                    api.$resize
+                }
+
+                fn set_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray, sys::godot_int, Self::SysArg) {
+                    api.$set
+                }
+
+                fn get_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*const Self::SysArray, sys::godot_int) -> Self::SysElement
+                {
+                    api.$get
+                }
+
+                fn size_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*const Self::SysArray) -> sys::godot_int {
+                    api.$size
+                }
+
+                fn read_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*const Self::SysArray) -> *mut Self::SysReadAccess {
+                    api.$read
+                }
+
+                fn read_access_copy_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *mut Self::SysReadAccess
+                {
+                    api.$read_access_copy
+                }
+
+                fn read_access_ptr_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*const Self::SysReadAccess) -> *const Self::SysElement
+                {
+                    api.$read_access_ptr
+                }
+
+                fn read_access_destroy_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysReadAccess) {
+                    api.$read_access_destroy
+                }
+
+                fn write_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysArray) -> *mut Self::SysWriteAccess {
+                    api.$write
+                }
+
+                fn write_access_ptr_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*const Self::SysWriteAccess) -> *mut Self::SysElement
+                {
+                    api.$write_access_ptr
+                }
+
+                fn write_access_destroy_fn(
+                    api: &sys::GodotApi,
+                ) -> unsafe extern "C" fn(*mut Self::SysWriteAccess) {
+                    api.$write_access_destroy
+                }
+            }
+        )*
+    };
+}
+
+impl_pool_element! {
+    impl PoolElement for u8 as godot_pool_byte_array (value u8) {
+        read_access = godot_pool_byte_array_read_access,
+        write_access = godot_pool_byte_array_write_access,
+        new = godot_pool_byte_array_new,
+        new_copy = godot_pool_byte_array_new_copy,
+        new_with_array = godot_pool_byte_array_new_with_array,
+        destroy = godot_pool_byte_array_destroy,
+        append = godot_pool_byte_array_append,
+        append_array = godot_pool_byte_array_append_array,
+        insert = godot_pool_byte_array_insert,
+        invert = godot_pool_byte_array_invert,
+        remove = godot_pool_byte_array_remove,
+        resize = godot_pool_byte_array_resize,
+        set = godot_pool_byte_array_set,
+        get = godot_pool_byte_array_get,
+        size = godot_pool_byte_array_size,
+        read = godot_pool_byte_array_read,
+        read_access_copy = godot_pool_byte_array_read_access_copy,
+        read_access_ptr = godot_pool_byte_array_read_access_ptr,
+        read_access_destroy = godot_pool_byte_array_read_access_destroy,
+        write = godot_pool_byte_array_write,
+        write_access_ptr = godot_pool_byte_array_write_access_ptr,
+        write_access_destroy = godot_pool_byte_array_write_access_destroy,
+    }
+
+    impl PoolElement for i32 as godot_pool_int_array (value sys::godot_int) {
+        read_access = godot_pool_int_array_read_access,
+        write_access = godot_pool_int_array_write_access,
+        new = godot_pool_int_array_new,
+        new_copy = godot_pool_int_array_new_copy,
+        new_with_array = godot_pool_int_array_new_with_array,
+        destroy = godot_pool_int_array_destroy,
+        append = godot_pool_int_array_append,
+        append_array = godot_pool_int_array_append_array,
+        insert = godot_pool_int_array_insert,
+        invert = godot_pool_int_array_invert,
+        remove = godot_pool_int_array_remove,
+        resize = godot_pool_int_array_resize,
+        set = godot_pool_int_array_set,
+        get = godot_pool_int_array_get,
+        size = godot_pool_int_array_size,
+        read = godot_pool_int_array_read,
+        read_access_copy = godot_pool_int_array_read_access_copy,
+        read_access_ptr = godot_pool_int_array_read_access_ptr,
+        read_access_destroy = godot_pool_int_array_read_access_destroy,
+        write = godot_pool_int_array_write,
+        write_access_ptr = godot_pool_int_array_write_access_ptr,
+        write_access_destroy = godot_pool_int_array_write_access_destroy,
+    }
+
+    impl PoolElement for f32 as godot_pool_real_array (value sys::godot_real) {
+        read_access = godot_pool_real_array_read_access,
+        write_access = godot_pool_real_array_write_access,
+        new = godot_pool_real_array_new,
+        new_copy = godot_pool_real_array_new_copy,
+        new_with_array = godot_pool_real_array_new_with_array,
+        destroy = godot_pool_real_array_destroy,
+        append = godot_pool_real_array_append,
+        append_array = godot_pool_real_array_append_array,
+        insert = godot_pool_real_array_insert,
+        invert = godot_pool_real_array_invert,
+        remove = godot_pool_real_array_remove,
+        resize = godot_pool_real_array_resize,
+        set = godot_pool_real_array_set,
+        get = godot_pool_real_array_get,
+        size = godot_pool_real_array_size,
+        read = godot_pool_real_array_read,
+        read_access_copy = godot_pool_real_array_read_access_copy,
+        read_access_ptr = godot_pool_real_array_read_access_ptr,
+        read_access_destroy = godot_pool_real_array_read_access_destroy,
+        write = godot_pool_real_array_write,
+        write_access_ptr = godot_pool_real_array_write_access_ptr,
+        write_access_destroy = godot_pool_real_array_write_access_destroy,
+    }
+
+    impl PoolElement for GodotString as godot_pool_string_array (ptr sys::godot_string) {
+        read_access = godot_pool_string_array_read_access,
+        write_access = godot_pool_string_array_write_access,
+        new = godot_pool_string_array_new,
+        new_copy = godot_pool_string_array_new_copy,
+        new_with_array = godot_pool_string_array_new_with_array,
+        destroy = godot_pool_string_array_destroy,
+        append = godot_pool_string_array_append,
+        append_array = godot_pool_string_array_append_array,
+        insert = godot_pool_string_array_insert,
+        invert = godot_pool_string_array_invert,
+        remove = godot_pool_string_array_remove,
+        resize = godot_pool_string_array_resize,
+        set = godot_pool_string_array_set,
+        get = godot_pool_string_array_get,
+        size = godot_pool_string_array_size,
+        read = godot_pool_string_array_read,
+        read_access_copy = godot_pool_string_array_read_access_copy,
+        read_access_ptr = godot_pool_string_array_read_access_ptr,
+        read_access_destroy = godot_pool_string_array_read_access_destroy,
+        write = godot_pool_string_array_write,
+        write_access_ptr = godot_pool_string_array_write_access_ptr,
+        write_access_destroy = godot_pool_string_array_write_access_destroy,
+    }
+
+    impl PoolElement for Vector2 as godot_pool_vector2_array (ptr sys::godot_vector2) {
+        read_access = godot_pool_vector2_array_read_access,
+        write_access = godot_pool_vector2_array_write_access,
+        new = godot_pool_vector2_array_new,
+        new_copy = godot_pool_vector2_array_new_copy,
+        new_with_array = godot_pool_vector2_array_new_with_array,
+        destroy = godot_pool_vector2_array_destroy,
+        append = godot_pool_vector2_array_append,
+        append_array = godot_pool_vector2_array_append_array,
+        insert = godot_pool_vector2_array_insert,
+        invert = godot_pool_vector2_array_invert,
+        remove = godot_pool_vector2_array_remove,
+        resize = godot_pool_vector2_array_resize,
+        set = godot_pool_vector2_array_set,
+        get = godot_pool_vector2_array_get,
+        size = godot_pool_vector2_array_size,
+        read = godot_pool_vector2_array_read,
+        read_access_copy = godot_pool_vector2_array_read_access_copy,
+        read_access_ptr = godot_pool_vector2_array_read_access_ptr,
+        read_access_destroy = godot_pool_vector2_array_read_access_destroy,
+        write = godot_pool_vector2_array_write,
+        write_access_ptr = godot_pool_vector2_array_write_access_ptr,
+        write_access_destroy = godot_pool_vector2_array_write_access_destroy,
+    }
+
+    impl PoolElement for Vector3 as godot_pool_vector3_array (ptr sys::godot_vector3) {
+        read_access = godot_pool_vector3_array_read_access,
+        write_access = godot_pool_vector3_array_write_access,
+        new = godot_pool_vector3_array_new,
+        new_copy = godot_pool_vector3_array_new_copy,
+        new_with_array = godot_pool_vector3_array_new_with_array,
+        destroy = godot_pool_vector3_array_destroy,
+        append = godot_pool_vector3_array_append,
+        append_array = godot_pool_vector3_array_append_array,
+        insert = godot_pool_vector3_array_insert,
+        invert = godot_pool_vector3_array_invert,
+        remove = godot_pool_vector3_array_remove,
+        resize = godot_pool_vector3_array_resize,
+        set = godot_pool_vector3_array_set,
+        get = godot_pool_vector3_array_get,
+        size = godot_pool_vector3_array_size,
+        read = godot_pool_vector3_array_read,
+        read_access_copy = godot_pool_vector3_array_read_access_copy,
+        read_access_ptr = godot_pool_vector3_array_read_access_ptr,
+        read_access_destroy = godot_pool_vector3_array_read_access_destroy,
+        write = godot_pool_vector3_array_write,
+        write_access_ptr = godot_pool_vector3_array_write_access_ptr,
+        write_access_destroy = godot_pool_vector3_array_write_access_destroy,
+    }
+
+    impl PoolElement for Color as godot_pool_color_array (ptr sys::godot_color) {
+        read_access = godot_pool_color_array_read_access,
+        write_access = godot_pool_color_array_write_access,
+        new = godot_pool_color_array_new,
+        new_copy = godot_pool_color_array_new_copy,
+        new_with_array = godot_pool_color_array_new_with_array,
+        destroy = godot_pool_color_array_destroy,
+        append = godot_pool_color_array_append,
+        append_array = godot_pool_color_array_append_array,
+        insert = godot_pool_color_array_insert,
+        invert = godot_pool_color_array_invert,
+        remove = godot_pool_color_array_remove,
+        resize = godot_pool_color_array_resize,
+        set = godot_pool_color_array_set,
+        get = godot_pool_color_array_get,
+        size = godot_pool_color_array_size,
+        read = godot_pool_color_array_read,
+        read_access_copy = godot_pool_color_array_read_access_copy,
+        read_access_ptr = godot_pool_color_array_read_access_ptr,
+        read_access_destroy = godot_pool_color_array_read_access_destroy,
+        write = godot_pool_color_array_write,
+        write_access_ptr = godot_pool_color_array_write_access_ptr,
+        write_access_destroy = godot_pool_color_array_write_access_destroy,
+    }
+}
+
+godot_test!(
+    test_byte_array_access {
+        let mut arr = ByteArray::new();
+        for i in 0..8 {
+            arr.push(&i);
+        }
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(8, write.len());
+            for i in write.as_mut_slice() {
+                *i *= 2;
+            }
+        }
+
+        for i in 0..8 {
+            assert_eq!(i * 2, cow_arr.get(i as i32));
+        }
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], original_read.as_slice());
+    }
+
+    test_int32_array_access {
+        let mut arr = Int32Array::new();
+        for i in 0..8 {
+            arr.push(&i);
+        }
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(8, write.len());
+            for i in write.as_mut_slice() {
+                *i *= 2;
+            }
+        }
+
+        for i in 0..8 {
+            assert_eq!(i * 2, cow_arr.get(i));
+        }
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[0, 1, 2, 3, 4, 5, 6, 7], original_read.as_slice());
+    }
+
+    test_float32_array_access {
+        let mut arr = Float32Array::new();
+        for i in 0..8 {
+            arr.push(&(i as f32));
+        }
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(8, write.len());
+            for i in write.as_mut_slice() {
+                *i *= 2.0;
+            }
+        }
+
+        for i in 0..8 {
+            assert_eq!(i as f32 * 2.0, cow_arr.get(i));
+        }
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[0.0, 1.0, 2.0, 3.0, 4.0, 5.0, 6.0, 7.0], original_read.as_slice());
+    }
+
+    test_string_array_access {
+        let mut arr = StringArray::new();
+        arr.push(&GodotString::from("foo"));
+        arr.push(&GodotString::from("bar"));
+        arr.push(&GodotString::from("baz"));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                GodotString::from("foo"),
+                GodotString::from("bar"),
+                GodotString::from("baz"),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for s in write.as_mut_slice() {
+                *s = s.to_uppercase();
+            }
+        }
+
+        assert_eq!(GodotString::from("FOO"), cow_arr.get(0));
+        assert_eq!(GodotString::from("BAR"), cow_arr.get(1));
+        assert_eq!(GodotString::from("BAZ"), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            GodotString::from("foo"),
+            GodotString::from("bar"),
+            GodotString::from("baz"),
+        ], original_read.as_slice());
+    }
+
+    test_vector2_array_access {
+        let mut arr = Vector2Array::new();
+        arr.push(&Vector2::new(1.0, 2.0));
+        arr.push(&Vector2::new(3.0, 4.0));
+        arr.push(&Vector2::new(5.0, 6.0));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                Vector2::new(1.0, 2.0),
+                Vector2::new(3.0, 4.0),
+                Vector2::new(5.0, 6.0),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for s in write.as_mut_slice() {
+                s.x += 1.0;
+            }
+        }
+
+        assert_eq!(Vector2::new(2.0, 2.0), cow_arr.get(0));
+        assert_eq!(Vector2::new(4.0, 4.0), cow_arr.get(1));
+        assert_eq!(Vector2::new(6.0, 6.0), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            Vector2::new(1.0, 2.0),
+            Vector2::new(3.0, 4.0),
+            Vector2::new(5.0, 6.0),
+        ], original_read.as_slice());
+    }
+
+    test_vector3_array_access {
+        let mut arr = Vector3Array::new();
+        arr.push(&Vector3::new(1.0, 2.0, 3.0));
+        arr.push(&Vector3::new(3.0, 4.0, 5.0));
+        arr.push(&Vector3::new(5.0, 6.0, 7.0));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                Vector3::new(1.0, 2.0, 3.0),
+                Vector3::new(3.0, 4.0, 5.0),
+                Vector3::new(5.0, 6.0, 7.0),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for s in write.as_mut_slice() {
+                s.x += 2.0;
+                s.y += 1.0;
+            }
+        }
+
+        assert_eq!(Vector3::new(3.0, 3.0, 3.0), cow_arr.get(0));
+        assert_eq!(Vector3::new(5.0, 5.0, 5.0), cow_arr.get(1));
+        assert_eq!(Vector3::new(7.0, 7.0, 7.0), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            Vector3::new(1.0, 2.0, 3.0),
+            Vector3::new(3.0, 4.0, 5.0),
+            Vector3::new(5.0, 6.0, 7.0),
+        ], original_read.as_slice());
+    }
+
+    test_color_array_access {
+        let mut arr = ColorArray::new();
+        arr.push(&Color::rgb(1.0, 0.0, 0.0));
+        arr.push(&Color::rgb(0.0, 1.0, 0.0));
+        arr.push(&Color::rgb(0.0, 0.0, 1.0));
+
+        let original_read = {
+            let read = arr.read();
+            assert_eq!(&[
+                Color::rgb(1.0, 0.0, 0.0),
+                Color::rgb(0.0, 1.0, 0.0),
+                Color::rgb(0.0, 0.0, 1.0),
+            ], read.as_slice());
+            read.clone()
+        };
+
+        let mut cow_arr = arr.new_ref();
+
+        {
+            let mut write = cow_arr.write();
+            assert_eq!(3, write.len());
+            for i in write.as_mut_slice() {
+                i.b = 1.0;
+            }
+        }
+
+        assert_eq!(Color::rgb(1.0, 0.0, 1.0), cow_arr.get(0));
+        assert_eq!(Color::rgb(0.0, 1.0, 1.0), cow_arr.get(1));
+        assert_eq!(Color::rgb(0.0, 0.0, 1.0), cow_arr.get(2));
+
+        // the write shouldn't have affected the original array
+        assert_eq!(&[
+            Color::rgb(1.0, 0.0, 0.0),
+            Color::rgb(0.0, 1.0, 0.0),
+            Color::rgb(0.0, 0.0, 1.0),
+        ], original_read.as_slice());
+    }
+
+    test_pool_array_api {
+        let strings = vec![GodotString::from("a"), GodotString::from("b")];
+        let mut arr = StringArray::from_slice(&strings);
+        assert_eq!(strings, arr.to_vec());
+
+        arr.extend(vec![GodotString::from("d")]);
+        assert!(arr.insert(2, &GodotString::from("c")).is_ok());
+        assert!(arr.insert(10, &GodotString::from("z")).is_err());
+        assert_eq!(
+            vec!["a", "b", "c", "d"],
+            arr.iter().map(|s| s.to_string()).collect::<Vec<_>>()
+        );
+
+        let mut copy = arr.new_ref();
+        copy.append(&arr);
+        copy.remove(0);
+        copy.invert();
+        assert_eq!(7, copy.len());
+        assert_eq!(GodotString::from("b"), copy.get(6));
+        assert_eq!(4, arr.len());
+
+        let mut ints: Int32Array = (1..=3).collect();
+        ints.set(0, &10);
+        assert_eq!(vec![10, 2, 3], (&ints).into_iter().collect::<Vec<_>>());
+        ints.resize(5);
+        assert_eq!(vec![10, 2, 3, 0, 0], ints.to_vec());
+
+        let vectors = Vector2Array::from_vec(vec![Vector2::new(1.0, 2.0)]);
+        assert_eq!(Vector2::new(1.0, 2.0), vectors.get(0));
+
+        let moved = StringArray::from_vec(strings.clone());
+        assert_eq!(strings, moved.to_vec());
+
+        let empty = ColorArray::from_slice(&[]);
+        assert!(empty.is_empty());
+        assert!(empty.to_vec().is_empty());
+        assert_eq!(0, empty.iter().count());
+    }
+);
@@ -0,0 +1,200 @@
+use crate::{Rect2, Vector2};
+use euclid::{point2, size2};
+
+/// Helper methods for `Rect2`.
+///
+/// Trait used to provide additional methods that are equivalent to Godot's methods.
+/// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.1/classes/class_rect2.html).
+///
+/// `Rect2::intersects` from euclid already follows the rules of the engine: rectangles that only
+/// touch do not intersect.
+pub trait Rect2Godot {
+    /// Returns the rectangle with a non-negative size, covering the same area.
+    fn abs(self) -> Self;
+    /// Returns the intersection of the rectangles, or an empty rectangle at the origin if they
+    /// don't intersect.
+    fn clip(self, other: Self) -> Self;
+    /// Returns `true` if the rectangle completely encloses `other`. Like in the engine, the far
+    /// edges of the rectangles must not touch.
+    fn encloses(self, other: Self) -> bool;
+    /// Returns the rectangle expanded to include `to`.
+    fn expand(self, to: Vector2) -> Self;
+    /// Returns the rectangle grown by `by` on every side.
+    fn grow(self, by: f32) -> Self;
+    /// Returns the rectangle grown by the given amounts on each side.
+    fn grow_individual(self, left: f32, top: f32, right: f32, bottom: f32) -> Self;
+    /// Returns `true` if the rectangle is flat or empty.
+    fn has_no_area(self) -> bool;
+    /// Returns `true` if `point` is inside the rectangle. Points on the left and top edges are
+    /// inside, points on the right and bottom edges are not.
+    fn has_point(self, point: Vector2) -> bool;
+    /// Returns the smallest rectangle enclosing both rectangles. Unlike `Rect2::union`, empty
+    /// rectangles are not ignored.
+    fn merge(self, other: Self) -> Self;
+}
+
+impl Rect2Godot for Rect2 {
+    #[inline]
+    fn abs(self) -> Self {
+        Rect2::new(
+            point2(
+                self.origin.x + self.size.width.min(0.0),
+                self.origin.y + self.size.height.min(0.0),
+            ),
+            size2(self.size.width.abs(), self.size.height.abs()),
+        )
+    }
+
+    #[inline]
+    fn clip(self, other: Self) -> Self {
+        if !self.intersects(&other) {
+            return Rect2::zero();
+        }
+
+        let origin = point2(
+            self.origin.x.max(other.origin.x),
+            self.origin.y.max(other.origin.y),
+        );
+        let end = point2(
+            self.max_x().min(other.max_x()),
+            self.max_y().min(other.max_y()),
+        );
+        Rect2::new(origin, (end - origin).to_size())
+    }
+
+    #[inline]
+    fn encloses(self, other: Self) -> bool {
+        other.origin.x >= self.origin.x
+            && other.origin.y >= self.origin.y
+            && other.max_x() < self.max_x()
+            && other.max_y() < self.max_y()
+    }
+
+    #[inline]
+    fn expand(self, to: Vector2) -> Self {
+        let begin = point2(self.origin.x.min(to.x), self.origin.y.min(to.y));
+        let end = point2(self.max_x().max(to.x), self.max_y().max(to.y));
+        Rect2::new(begin, (end - begin).to_size())
+    }
+
+    #[inline]
+    fn grow(self, by: f32) -> Self {
+        self.grow_individual(by, by, by, by)
+    }
+
+    #[inline]
+    fn grow_individual(self, left: f32, top: f32, right: f32, bottom: f32) -> Self {
+        Rect2::new(
+            point2(self.origin.x - left, self.origin.y - top),
+            size2(
+                self.size.width + left + right,
+                self.size.height + top + bottom,
+            ),
+        )
+    }
+
+    #[inline]
+    fn has_no_area(self) -> bool {
+        self.size.width <= 0.0 || self.size.height <= 0.0
+    }
+
+    #[inline]
+    fn has_point(self, point: Vector2) -> bool {
+        point.x >= self.origin.x
+            && point.y >= self.origin.y
+            && point.x < self.max_x()
+            && point.y < self.max_y()
+    }
+
+    #[inline]
+    fn merge(self, other: Self) -> Self {
+        let begin = point2(
+            self.origin.x.min(other.origin.x),
+            self.origin.y.min(other.origin.y),
+        );
+        let end = point2(
+            self.max_x().max(other.max_x()),
+            self.max_y().max(other.max_y()),
+        );
+        Rect2::new(begin, (end - begin).to_size())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rect(x: f32, y: f32, width: f32, height: f32) -> Rect2 {
+        Rect2::new(point2(x, y), size2(width, height))
+    }
+
+    #[test]
+    fn edge_rules() {
+        let r = rect(0.0, 0.0, 2.0, 2.0);
+        assert!(r.has_point(Vector2::new(0.0, 0.0)));
+        assert!(r.has_point(Vector2::new(1.9, 1.9)));
+        assert!(!r.has_point(Vector2::new(2.0, 1.0)));
+        assert!(!r.has_point(Vector2::new(1.0, 2.0)));
+
+        assert!(!r.encloses(r));
+        assert!(r.encloses(rect(0.0, 0.0, 1.0, 1.0)));
+        assert!(!r.encloses(rect(2.0, 2.0, 0.0, 0.0)));
+        assert!(!r.encloses(rect(1.0, 1.0, 2.0, 1.0)));
+
+        assert!(rect(0.0, 0.0, 0.0, 1.0).has_no_area());
+        assert!(!r.has_no_area());
+    }
+
+    #[test]
+    fn grow_and_expand() {
+        let r = rect(1.0, 1.0, 2.0, 2.0);
+        assert_eq!(rect(0.0, 0.0, 4.0, 4.0), r.grow(1.0));
+        assert_eq!(
+            rect(0.0, 0.5, 6.0, 4.5),
+            r.grow_individual(1.0, 0.5, 3.0, 2.0)
+        );
+        assert_eq!(rect(1.0, -1.0, 4.0, 4.0), r.expand(Vector2::new(5.0, -1.0)));
+        assert_eq!(r, r.expand(Vector2::new(2.0, 2.0)));
+    }
+
+    #[test]
+    fn merge_clip_and_abs() {
+        let a = rect(0.0, 0.0, 2.0, 2.0);
+        let b = rect(1.0, 1.0, 2.0, 2.0);
+        assert_eq!(rect(0.0, 0.0, 3.0, 3.0), a.merge(b));
+        assert_eq!(rect(0.0, 0.0, 2.0, 5.0), a.merge(rect(1.0, 5.0, 0.0, 0.0)));
+        assert_eq!(rect(1.0, 1.0, 1.0, 1.0), a.clip(b));
+        assert_eq!(Rect2::zero(), a.clip(rect(2.0, 0.0, 1.0, 1.0)));
+        assert_eq!(rect(-1.0, -2.0, 1.0, 2.0), rect(0.0, 0.0, -1.0, -2.0).abs());
+    }
+
+    // Expected values are worked out from the Godot 3.2 implementation in `core/math/rect2.h`,
+    // using the same formulas as the engine.
+    #[test]
+    fn matches_godot_3_2() {
+        let a = rect(0.5, -1.0, 3.0, 2.0);
+        let b = rect(2.0, 0.5, 4.0, 3.0);
+
+        // Rect2(0.5, -1, 3, 2).clip(Rect2(2, 0.5, 4, 3)) and .merge(...)
+        assert_eq!(rect(2.0, 0.5, 1.5, 0.5), a.clip(b));
+        assert_eq!(rect(0.5, -1.0, 5.5, 4.5), a.merge(b));
+
+        // Rect2(0.5, -1, 3, 2).encloses(...), which is strict on the far edges
+        assert!(a.encloses(rect(1.0, -0.5, 2.0, 1.0)));
+        assert!(!a.encloses(rect(1.0, -0.5, 2.5, 1.0)));
+
+        // Rect2(0.5, -1, 3, 2).has_point(...)
+        assert!(a.has_point(Vector2::new(0.5, -1.0)));
+        assert!(!a.has_point(Vector2::new(3.5, 0.0)));
+
+        // Rect2(0.5, -1, 3, 2).grow_individual(1, 2, 3, 4) and .expand(Vector2(5, -3))
+        assert_eq!(
+            rect(-0.5, -3.0, 7.0, 8.0),
+            a.grow_individual(1.0, 2.0, 3.0, 4.0)
+        );
+        assert_eq!(rect(0.5, -3.0, 4.5, 4.0), a.expand(Vector2::new(5.0, -3.0)));
+
+        // Rect2(2, 3, -4, -1).abs()
+        assert_eq!(rect(-2.0, 2.0, 4.0, 1.0), rect(2.0, 3.0, -4.0, -1.0).abs());
+    }
+}
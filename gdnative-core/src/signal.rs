@@ -0,0 +1,290 @@
+//! Signals declared as Rust types.
+//!
+//! A type implementing [`Signal`](trait.Signal.html) describes the name and the arguments of a
+//! signal. Registering, emitting and connecting such a signal goes through the type, so the
+//! number and the types of the arguments are checked by the compiler instead of at runtime.
+//!
+//! The trait is usually implemented with the `Signal` derive macro:
+//!
+//! ```ignore
+//! #[derive(Signal)]
+//! struct TickWithData {
+//!     #[signal(default = 100)]
+//!     data: i64,
+//! }
+//!
+//! #[derive(NativeClass)]
+//! #[inherit(Node)]
+//! #[signals(TickWithData)]
+//! struct SignalEmitter;
+//!
+//! // Emitting, from a method of `SignalEmitter`:
+//! TickWithData { data: 42 }.emit(&mut owner);
+//!
+//! // Connecting an exported method of `SignalSubscriber` with the signature
+//! // `fn notify_with_data(&mut self, owner: Label, data: i64)`:
+//! godot_connect!(emitter, TickWithData => owner, SignalSubscriber::notify_with_data)?;
+//! ```
+
+use crate::init::SignalArgument;
+use crate::{GodotObject, GodotResult, GodotString, NativeClass, Object, Variant, VariantArray};
+
+/// A signal with statically known arguments.
+pub trait Signal: Sized {
+    /// The name of the signal, as seen by the engine.
+    const NAME: &'static str;
+
+    /// The argument types of the signal, as a tuple in declaration order.
+    type Args;
+
+    /// Returns the argument descriptions used for registration.
+    fn arguments() -> Vec<SignalArgument<'static>>;
+
+    /// Converts the signal into the argument list passed to `emit_signal`.
+    fn into_args(self) -> Vec<Variant>;
+
+    /// Emits the signal from `owner`.
+    ///
+    /// # Safety
+    ///
+    /// `owner` must be a live object. Like all engine calls, emitting the signal can invalidate
+    /// objects that are still referenced elsewhere.
+    unsafe fn emit<O: GodotObject>(self, owner: &mut O) {
+        let mut object = Object::from_sys(owner.to_sys());
+        object.emit_signal(Self::NAME, &self.into_args());
+    }
+}
+
+/// Connects the signal `S` of `emitter` to the method `method` of the script attached to
+/// `target`.
+///
+/// `handler` is not called: it is the method itself, and is only used to check that its
+/// signature matches the arguments of the signal. The `godot_connect!` macro passes the method
+/// and its name together.
+///
+/// # Safety
+///
+/// `emitter` and `target` must be live objects.
+pub unsafe fn connect<S, E, C, F, M>(
+    emitter: &mut E,
+    target: &C::Base,
+    method: &str,
+    flags: i64,
+    _handler: F,
+) -> GodotResult
+where
+    S: Signal,
+    E: GodotObject,
+    C: NativeClass,
+    F: Handler<C, S::Args, M>,
+{
+    let mut emitter = Object::from_sys(emitter.to_sys());
+    emitter.connect(
+        S::NAME,
+        Some(Object::from_sys(target.to_sys())),
+        GodotString::from_str(method),
+        VariantArray::new(),
+        flags,
+    )
+}
+
+/// Disconnects a connection made with [`connect`](fn.connect.html).
+///
+/// # Safety
+///
+/// `emitter` and `target` must be live objects.
+pub unsafe fn disconnect<S, E, C, F, M>(
+    emitter: &mut E,
+    target: &C::Base,
+    method: &str,
+    _handler: F,
+) where
+    S: Signal,
+    E: GodotObject,
+    C: NativeClass,
+    F: Handler<C, S::Args, M>,
+{
+    let mut emitter = Object::from_sys(emitter.to_sys());
+    emitter.disconnect(
+        S::NAME,
+        Some(Object::from_sys(target.to_sys())),
+        GodotString::from_str(method),
+    );
+}
+
+/// Methods of `C` that can receive a signal with the arguments `Args`.
+///
+/// This is implemented for methods taking `&self` or `&mut self`, the owner and then exactly
+/// the arguments of the signal. `M` only tells the two receiver kinds apart.
+pub trait Handler<C: NativeClass, Args, M> {}
+
+#[doc(hidden)]
+pub struct ByRef;
+
+#[doc(hidden)]
+pub struct ByMut;
+
+macro_rules! impl_handler {
+    ($($arg:ident),*) => {
+        impl<C, F, $($arg),*> Handler<C, ($($arg,)*), ByRef> for F
+        where
+            C: NativeClass,
+            F: Fn(&C, C::Base $(, $arg)*),
+        {
+        }
+
+        impl<C, F, $($arg),*> Handler<C, ($($arg,)*), ByMut> for F
+        where
+            C: NativeClass,
+            F: Fn(&mut C, C::Base $(, $arg)*),
+        {
+        }
+    };
+}
+
+impl_handler!();
+impl_handler!(A);
+impl_handler!(A, B);
+impl_handler!(A, B, C0);
+impl_handler!(A, B, C0, D);
+impl_handler!(A, B, C0, D, E);
+impl_handler!(A, B, C0, D, E, F0);
+impl_handler!(A, B, C0, D, E, F0, G);
+impl_handler!(A, B, C0, D, E, F0, G, H);
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::init::{ClassBuilder, PropertyHint, PropertyUsage};
+    use crate::mock::{self, MockInstance};
+    use crate::user_data::MutexData;
+    use crate::{NativeClassMethods, Reference, ToVariant};
+
+    struct Tick;
+
+    impl Signal for Tick {
+        const NAME: &'static str = "tick";
+        type Args = ();
+
+        fn arguments() -> Vec<SignalArgument<'static>> {
+            vec![]
+        }
+
+        fn into_args(self) -> Vec<Variant> {
+            vec![]
+        }
+    }
+
+    struct Count {
+        by: i64,
+    }
+
+    impl Signal for Count {
+        const NAME: &'static str = "count";
+        type Args = (i64,);
+
+        fn arguments() -> Vec<SignalArgument<'static>> {
+            vec![SignalArgument {
+                name: "by",
+                default: Variant::from_i64(1),
+                hint: PropertyHint::None,
+                usage: PropertyUsage::DEFAULT,
+            }]
+        }
+
+        fn into_args(self) -> Vec<Variant> {
+            vec![self.by.to_variant()]
+        }
+    }
+
+    struct Receiver {
+        ticks: i64,
+        count: i64,
+    }
+
+    impl NativeClass for Receiver {
+        type Base = Reference;
+        type UserData = MutexData<Receiver>;
+
+        fn class_name() -> &'static str {
+            "Receiver"
+        }
+
+        fn init(_owner: Reference) -> Self {
+            Receiver { ticks: 0, count: 0 }
+        }
+
+        fn register_properties(builder: &ClassBuilder<Self>) {
+            builder.register_signal::<Tick>();
+            builder.register_signal::<Count>();
+        }
+    }
+
+    impl Receiver {
+        fn on_tick(&mut self, _owner: Reference) {
+            self.ticks += 1;
+        }
+
+        fn on_count(&mut self, _owner: Reference, by: i64) {
+            self.count += by;
+        }
+
+        fn total(&self, _owner: Reference) -> i64 {
+            self.ticks * 100 + self.count
+        }
+    }
+
+    impl NativeClassMethods for Receiver {
+        fn register(builder: &ClassBuilder<Self>) {
+            builder.add_method(
+                "on_tick",
+                godot_wrap_method!(Receiver, fn on_tick(&mut self, owner: Reference)),
+            );
+            builder.add_method(
+                "on_count",
+                godot_wrap_method!(Receiver, fn on_count(&mut self, owner: Reference, by: i64)),
+            );
+            builder.add_method(
+                "total",
+                godot_wrap_method!(Receiver, fn total(&self, owner: Reference) -> i64),
+            );
+        }
+    }
+
+    #[test]
+    fn register_typed_signals() {
+        mock::init_nativescript(|handle| handle.add_class::<Receiver>());
+
+        let info = mock::class_info("Receiver").unwrap();
+        assert!(info.signal("tick").unwrap().args.is_empty());
+
+        let count = info.signal("count").unwrap();
+        assert_eq!(1, count.args.len());
+        assert_eq!("by", count.args[0].name);
+        assert_eq!(Some(1), count.args[0].default.try_to_i64());
+    }
+
+    #[test]
+    fn emit_and_connect() {
+        mock::init_nativescript(|handle| handle.add_class::<Receiver>());
+
+        let instance = MockInstance::new("Receiver").unwrap();
+        let target = unsafe { Reference::from_sys(instance.owner()) };
+        let mut emitter = Reference::new();
+
+        unsafe {
+            godot_connect!(emitter, Tick => target, Receiver::on_tick).unwrap();
+            godot_connect!(emitter, Count => target, Receiver::on_count).unwrap();
+
+            Tick.emit(&mut emitter);
+            Count { by: 2 }.emit(&mut emitter);
+            Count { by: 3 }.emit(&mut emitter);
+
+            godot_disconnect!(emitter, Count => target, Receiver::on_count);
+            Count { by: 4 }.emit(&mut emitter);
+        }
+
+        let total = instance.call("total", &[]).unwrap();
+        assert_eq!(Some(105), total.try_to_i64());
+    }
+}
@@ -1,13 +1,20 @@
 use crate::get_api;
 use crate::sys;
+use crate::ByteArray;
+use crate::StringArray;
 use crate::ToVariant;
+use crate::TypedArray;
 use crate::Variant;
+use crate::VariantArray;
 
+use parking_lot::Mutex;
 use std::cmp::Ordering;
-use std::ffi::CStr;
+use std::collections::HashMap;
+use std::ffi::{CStr, CString};
 use std::fmt;
+use std::iter::FromIterator;
 use std::mem::{forget, transmute};
-use std::ops::Range;
+use std::ops::{Add, AddAssign, Range};
 use std::slice;
 use std::str;
 
@@ -81,6 +88,9 @@ impl GodotString {
         pub fn u64_hash(&self) -> u64 : godot_string_hash64;
         pub fn hex_to_int(&self) -> i32 : godot_string_hex_to_int;
         pub fn hex_to_int_without_prefix(&self) -> i32 : godot_string_hex_to_int_without_prefix;
+        pub fn to_i64(&self) -> i64 : godot_string_to_int64;
+        pub fn hex_to_int64(&self) -> i64 : godot_string_hex_to_int64;
+        pub fn hex_to_int64_with_prefix(&self) -> i64 : godot_string_hex_to_int64_with_prefix;
     );
 
     impl_methods!(
@@ -105,6 +115,10 @@ impl GodotString {
         pub fn xml_unescape(&self) -> Self: godot_string_xml_unescape;
         pub fn percent_decode(&self) -> Self : godot_string_percent_decode;
         pub fn percent_encode(&self) -> Self : godot_string_percent_encode;
+        pub fn get_basename(&self) -> Self : godot_string_get_basename;
+        pub fn get_extension(&self) -> Self : godot_string_get_extension;
+        pub fn dedent(&self) -> Self : godot_string_dedent;
+        pub fn strip_escapes(&self) -> Self : godot_string_strip_escapes;
     );
 
     pub fn is_valid_hex_number(&self, with_prefix: bool) -> bool {
@@ -151,6 +165,442 @@ impl GodotString {
         unsafe { (get_api().godot_string_find_last)(&self.0, what.0) }
     }
 
+    /// Returns the position of the first case-insensitive occurrence of `what`, or `-1`.
+    pub fn findn(&self, what: &GodotString) -> i32 {
+        unsafe { (get_api().godot_string_findn)(&self.0, what.0) }
+    }
+
+    pub fn findn_from(&self, what: &GodotString, from: i32) -> i32 {
+        unsafe { (get_api().godot_string_findn_from)(&self.0, what.0, from) }
+    }
+
+    /// Returns the position of the last occurrence of `what`, or `-1`.
+    pub fn rfind(&self, what: &GodotString) -> i32 {
+        unsafe { (get_api().godot_string_rfind)(&self.0, what.0) }
+    }
+
+    /// Searches backwards from `from`. A negative `from` counts from the end of the string.
+    pub fn rfind_from(&self, what: &GodotString, from: i32) -> i32 {
+        unsafe { (get_api().godot_string_rfind_from)(&self.0, what.0, from) }
+    }
+
+    pub fn rfindn(&self, what: &GodotString) -> i32 {
+        unsafe { (get_api().godot_string_rfindn)(&self.0, what.0) }
+    }
+
+    pub fn rfindn_from(&self, what: &GodotString, from: i32) -> i32 {
+        unsafe { (get_api().godot_string_rfindn_from)(&self.0, what.0, from) }
+    }
+
+    /// Returns the position of the first occurrence of any of `keys` at or after `from`, and
+    /// the index of the key that was found.
+    pub fn findmk(&self, keys: &[GodotString], from: i32) -> Option<(i32, usize)> {
+        let keys = string_array(keys);
+        let mut key = -1;
+        let pos = unsafe {
+            (get_api().godot_string_findmk_from_in_place)(&self.0, &keys.0, from, &mut key)
+        };
+        if pos < 0 {
+            None
+        } else {
+            Some((pos, key as usize))
+        }
+    }
+
+    /// Returns the Unicode code point at the given position.
+    pub fn ord_at(&self, idx: i32) -> u32 {
+        unsafe { (get_api().godot_string_ord_at)(&self.0, idx) as u32 }
+    }
+
+    /// Returns an iterator over the characters of the string.
+    ///
+    /// Code points that are not valid `char`s are replaced with `U+FFFD`.
+    pub fn chars(&self) -> Chars<'_> {
+        Chars {
+            string: self,
+            range: 0..self.len() as i32,
+        }
+    }
+
+    /// Encodes the string as UTF-16.
+    pub fn to_utf16(&self) -> Vec<u16> {
+        self.to_string().encode_utf16().collect()
+    }
+
+    /// Returns the first `pos` characters of the string.
+    pub fn left(&self, pos: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_left)(&self.0, pos)) }
+    }
+
+    /// Returns the characters of the string from `pos` on.
+    pub fn right(&self, pos: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_right)(&self.0, pos)) }
+    }
+
+    /// Returns a copy of the string with `what` inserted at the given position.
+    pub fn insert(&self, at: i32, what: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_insert)(&self.0, at, what.0)) }
+    }
+
+    /// Removes `chars` characters from the given position.
+    pub fn erase(&mut self, pos: i32, chars: i32) {
+        unsafe { (get_api().godot_string_erase)(&mut self.0, pos, chars) }
+    }
+
+    /// Replaces all occurrences of `what` with `with`.
+    pub fn replace(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replace)(&self.0, what.0, with.0)) }
+    }
+
+    /// Replaces the first occurrence of `what` with `with`.
+    pub fn replace_first(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_replace_first)(
+                &self.0, what.0, with.0,
+            ))
+        }
+    }
+
+    /// Replaces all case-insensitive occurrences of `what` with `with`.
+    pub fn replacen(&self, what: &GodotString, with: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_replacen)(&self.0, what.0, with.0)) }
+    }
+
+    /// Removes whitespace and control characters from either end of the string.
+    pub fn strip_edges(&self, left: bool, right: bool) -> Self {
+        unsafe { GodotString((get_api().godot_string_strip_edges)(&self.0, left, right)) }
+    }
+
+    /// Removes any of the characters in `chars` from the end of the string.
+    pub fn rstrip(&self, chars: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_rstrip)(&self.0, &chars.0)) }
+    }
+
+    pub fn trim_prefix(&self, prefix: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_trim_prefix)(&self.0, &prefix.0)) }
+    }
+
+    pub fn trim_suffix(&self, suffix: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_trim_suffix)(&self.0, &suffix.0)) }
+    }
+
+    /// Pads the string with spaces on the left up to `min_length` characters.
+    pub fn lpad(&self, min_length: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_lpad)(&self.0, min_length)) }
+    }
+
+    /// Pads the string with `character` on the left up to `min_length` characters.
+    pub fn lpad_with(&self, min_length: i32, character: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_lpad_with_custom_character)(
+                &self.0,
+                min_length,
+                &character.0,
+            ))
+        }
+    }
+
+    /// Pads the string with spaces on the right up to `min_length` characters.
+    pub fn rpad(&self, min_length: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_rpad)(&self.0, min_length)) }
+    }
+
+    /// Pads the string with `character` on the right up to `min_length` characters.
+    pub fn rpad_with(&self, min_length: i32, character: &GodotString) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_rpad_with_custom_character)(
+                &self.0,
+                min_length,
+                &character.0,
+            ))
+        }
+    }
+
+    /// Formats a number string with exactly `digits` decimals.
+    pub fn pad_decimals(&self, digits: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_pad_decimals)(&self.0, digits)) }
+    }
+
+    /// Pads a number string with zeros before the decimal point up to `digits` digits.
+    pub fn pad_zeros(&self, digits: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_pad_zeros)(&self.0, digits)) }
+    }
+
+    /// Wraps the string into lines of at most `chars_per_line` characters.
+    pub fn word_wrap(&self, chars_per_line: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_word_wrap)(&self.0, chars_per_line)) }
+    }
+
+    /// Splits the string by `divisor`. Empty parts are skipped unless `allow_empty` is `true`.
+    pub fn split(&self, divisor: &GodotString, allow_empty: bool) -> TypedArray<GodotString> {
+        unsafe {
+            let api = get_api();
+            let split = if allow_empty {
+                api.godot_string_split_allow_empty
+            } else {
+                api.godot_string_split
+            };
+            typed_array(split(&self.0, &divisor.0))
+        }
+    }
+
+    /// Splits the string by `divisor` from the end, at most `max_split` times if it is not
+    /// zero.
+    pub fn rsplit(&self, divisor: &GodotString, allow_empty: bool, max_split: i32) -> StringArray {
+        unsafe {
+            StringArray::from_sys((get_api().godot_string_rsplit)(
+                &self.0,
+                &divisor.0,
+                allow_empty,
+                max_split,
+            ))
+        }
+    }
+
+    /// Splits the string by whitespace, skipping empty parts.
+    pub fn split_spaces(&self) -> TypedArray<GodotString> {
+        unsafe { typed_array((get_api().godot_string_split_spaces)(&self.0)) }
+    }
+
+    /// Splits the string by `divisor` and parses each part as an integer.
+    pub fn split_ints(&self, divisor: &GodotString, allow_empty: bool) -> TypedArray<i64> {
+        unsafe {
+            let api = get_api();
+            let split = if allow_empty {
+                api.godot_string_split_ints_allows_empty
+            } else {
+                api.godot_string_split_ints
+            };
+            typed_array(split(&self.0, &divisor.0))
+        }
+    }
+
+    /// Splits the string by any of `divisors` and parses each part as an integer.
+    pub fn split_ints_mk(&self, divisors: &[GodotString], allow_empty: bool) -> TypedArray<i64> {
+        let divisors = string_array(divisors);
+        unsafe {
+            let api = get_api();
+            let split = if allow_empty {
+                api.godot_string_split_ints_mk_allows_empty
+            } else {
+                api.godot_string_split_ints_mk
+            };
+            typed_array(split(&self.0, &divisors.0))
+        }
+    }
+
+    /// Splits the string by `divisor` and parses each part as a float.
+    pub fn split_floats(&self, divisor: &GodotString, allow_empty: bool) -> TypedArray<f64> {
+        unsafe {
+            let api = get_api();
+            let split = if allow_empty {
+                api.godot_string_split_floats_allows_empty
+            } else {
+                api.godot_string_split_floats
+            };
+            typed_array(split(&self.0, &divisor.0))
+        }
+    }
+
+    /// Splits the string by any of `divisors` and parses each part as a float.
+    pub fn split_floats_mk(&self, divisors: &[GodotString], allow_empty: bool) -> TypedArray<f64> {
+        let divisors = string_array(divisors);
+        unsafe {
+            let api = get_api();
+            let split = if allow_empty {
+                api.godot_string_split_floats_mk_allows_empty
+            } else {
+                api.godot_string_split_floats_mk
+            };
+            typed_array(split(&self.0, &divisors.0))
+        }
+    }
+
+    /// Returns the part at index `slice` of the string split by `splitter`.
+    pub fn get_slice(&self, splitter: &GodotString, slice: i32) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_get_slice)(
+                &self.0, splitter.0, slice,
+            ))
+        }
+    }
+
+    /// Returns the part at index `slice` of the string split by the character `splitter`.
+    pub fn get_slicec(&self, splitter: char, slice: i32) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_get_slicec)(
+                &self.0,
+                splitter as sys::wchar_t,
+                slice,
+            ))
+        }
+    }
+
+    /// Returns the number of parts of the string split by `splitter`.
+    pub fn get_slice_count(&self, splitter: &GodotString) -> i32 {
+        unsafe { (get_api().godot_string_get_slice_count)(&self.0, splitter.0) }
+    }
+
+    /// Returns the pairs of adjacent characters in the string.
+    pub fn bigrams(&self) -> TypedArray<GodotString> {
+        unsafe { typed_array((get_api().godot_string_bigrams)(&self.0)) }
+    }
+
+    /// Returns the similarity of two strings, from `0.0` to `1.0`, based on their bigrams.
+    pub fn similarity(&self, other: &GodotString) -> f32 {
+        unsafe { (get_api().godot_string_similarity)(&self.0, &other.0) }
+    }
+
+    /// Compares with another string, case-sensitively.
+    pub fn casecmp_to(&self, other: &GodotString) -> Ordering {
+        unsafe { (get_api().godot_string_casecmp_to)(&self.0, &other.0).cmp(&0) }
+    }
+
+    /// Compares with another string, case-insensitively.
+    pub fn nocasecmp_to(&self, other: &GodotString) -> Ordering {
+        unsafe { (get_api().godot_string_nocasecmp_to)(&self.0, &other.0).cmp(&0) }
+    }
+
+    /// Compares with another string case-insensitively, ordering numbers in the strings by
+    /// value, e.g. `file2` before `file10`.
+    pub fn naturalnocasecmp_to(&self, other: &GodotString) -> Ordering {
+        unsafe { (get_api().godot_string_naturalnocasecmp_to)(&self.0, &other.0).cmp(&0) }
+    }
+
+    /// Matches the string against a wildcard pattern, where `*` matches any sequence of
+    /// characters and `?` any single character. This is `match` in GDScript.
+    pub fn matches(&self, wildcard: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_match)(&self.0, &wildcard.0) }
+    }
+
+    /// Like `matches`, but case-insensitive. This is `matchn` in GDScript.
+    pub fn matchesn(&self, wildcard: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_matchn)(&self.0, &wildcard.0) }
+    }
+
+    /// Returns `true` if all characters of the string appear in `other`, in order.
+    pub fn is_subsequence_of(&self, other: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_is_subsequence_of)(&self.0, &other.0) }
+    }
+
+    /// Like `is_subsequence_of`, but case-insensitive.
+    pub fn is_subsequence_ofi(&self, other: &GodotString) -> bool {
+        unsafe { (get_api().godot_string_is_subsequence_ofi)(&self.0, &other.0) }
+    }
+
+    /// Joins the string and `file` as paths.
+    pub fn plus_file(&self, file: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_plus_file)(&self.0, &file.0)) }
+    }
+
+    /// Returns the relative path from this directory to `path`.
+    pub fn path_to(&self, path: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_path_to)(&self.0, &path.0)) }
+    }
+
+    /// Returns the relative path from this file to `path`.
+    pub fn path_to_file(&self, path: &GodotString) -> Self {
+        unsafe { GodotString((get_api().godot_string_path_to_file)(&self.0, &path.0)) }
+    }
+
+    /// Replaces the placeholders `{key}` with the values of a dictionary, or `{index}` with the
+    /// elements of an array.
+    pub fn format(&self, values: &Variant) -> Self {
+        unsafe { GodotString((get_api().godot_string_format)(&self.0, &values.0)) }
+    }
+
+    /// Like `format`, with a custom placeholder in which `_` stands for the key, e.g. `$_`.
+    pub fn format_with_placeholder(&self, values: &Variant, placeholder: &str) -> Self {
+        let placeholder = CString::new(placeholder).expect("placeholder should not contain NUL");
+        unsafe {
+            GodotString((get_api().godot_string_format_with_custom_placeholder)(
+                &self.0,
+                &values.0,
+                placeholder.as_ptr(),
+            ))
+        }
+    }
+
+    /// Formats the values with the string as a format string, like the `%` operator of
+    /// GDScript. On failure, returns the error message.
+    pub fn sprintf(&self, values: &VariantArray) -> Result<Self, Self> {
+        let mut error = false;
+        let result = unsafe {
+            GodotString((get_api().godot_string_sprintf)(
+                &self.0, &values.0, &mut error,
+            ))
+        };
+        if error {
+            Err(result)
+        } else {
+            Ok(result)
+        }
+    }
+
+    pub fn md5_buffer(&self) -> ByteArray {
+        unsafe { ByteArray::from_sys((get_api().godot_string_md5_buffer)(&self.0)) }
+    }
+
+    pub fn sha256_buffer(&self) -> ByteArray {
+        unsafe { ByteArray::from_sys((get_api().godot_string_sha256_buffer)(&self.0)) }
+    }
+
+    /// Creates a string from a single character.
+    pub fn chr(c: char) -> Self {
+        unsafe { GodotString((get_api().godot_string_chr)(c as sys::wchar_t)) }
+    }
+
+    /// Formats a number, without trailing zeros.
+    pub fn num(num: f64) -> Self {
+        unsafe { GodotString((get_api().godot_string_num)(num)) }
+    }
+
+    /// Formats a number with the given number of decimals.
+    pub fn num_with_decimals(num: f64, decimals: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_num_with_decimals)(num, decimals)) }
+    }
+
+    /// Formats a number, always with a decimal point.
+    pub fn num_real(num: f64) -> Self {
+        unsafe { GodotString((get_api().godot_string_num_real)(num)) }
+    }
+
+    /// Formats a number in scientific notation.
+    pub fn num_scientific(num: f64) -> Self {
+        unsafe { GodotString((get_api().godot_string_num_scientific)(num)) }
+    }
+
+    /// Formats an integer in the given base.
+    pub fn num_int64(num: i64, base: i32) -> Self {
+        unsafe { GodotString((get_api().godot_string_num_int64)(num, base)) }
+    }
+
+    /// Formats an integer in the given base, with uppercase digits if `capitalize_hex` is true.
+    pub fn num_int64_capitalized(num: i64, base: i32, capitalize_hex: bool) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_num_int64_capitalized)(
+                num,
+                base,
+                capitalize_hex,
+            ))
+        }
+    }
+
+    /// Formats a size in bytes with a unit, e.g. `1.5 KiB`.
+    pub fn humanize_size(size: usize) -> Self {
+        unsafe { GodotString((get_api().godot_string_humanize_size)(size)) }
+    }
+
+    /// Encodes a buffer in lowercase hexadecimal.
+    pub fn hex_encode_buffer(buffer: &[u8]) -> Self {
+        unsafe {
+            GodotString((get_api().godot_string_hex_encode_buffer)(
+                buffer.as_ptr(),
+                buffer.len() as sys::godot_int,
+            ))
+        }
+    }
+
     /// Returns the internal ffi representation of the string and consumes
     /// the rust object without running the destructor.
     ///
@@ -180,7 +630,6 @@ impl GodotString {
     pub fn from_sys(sys: sys::godot_string) -> Self {
         GodotString(sys)
     }
-    // TODO: many missing methods.
 
     impl_common_methods! {
         /// Creates a new reference to this array.
@@ -196,6 +645,13 @@ impl_basic_traits!(
     }
 );
 
+/// Strings are copy-on-write, so cloning is as cheap as `new_ref`.
+impl Clone for GodotString {
+    fn clone(&self) -> Self {
+        self.new_ref()
+    }
+}
+
 impl ToVariant for GodotString {
     fn to_variant(&self) -> Variant {
         Variant::from_godot_string(self)
@@ -208,12 +664,132 @@ impl fmt::Debug for GodotString {
     }
 }
 
-impl ToString for GodotString {
-    fn to_string(&self) -> String {
-        self.to_utf8().to_string()
+impl fmt::Display for GodotString {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        f.write_str(self.to_utf8().as_str())
+    }
+}
+
+impl PartialEq<str> for GodotString {
+    fn eq(&self, other: &str) -> bool {
+        self.to_utf8().as_str() == other
+    }
+}
+
+impl<'a> PartialEq<&'a str> for GodotString {
+    fn eq(&self, other: &&'a str) -> bool {
+        self == *other
+    }
+}
+
+impl PartialEq<GodotString> for str {
+    fn eq(&self, other: &GodotString) -> bool {
+        other == self
+    }
+}
+
+impl<'a> PartialEq<GodotString> for &'a str {
+    fn eq(&self, other: &GodotString) -> bool {
+        other == *self
+    }
+}
+
+impl PartialOrd for GodotString {
+    fn partial_cmp(&self, other: &GodotString) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+/// Strings are ordered by code points, like the `<` operator of the engine.
+impl Ord for GodotString {
+    fn cmp(&self, other: &GodotString) -> Ordering {
+        unsafe {
+            let less = get_api().godot_string_operator_less;
+            if less(&self.0, &other.0) {
+                Ordering::Less
+            } else if less(&other.0, &self.0) {
+                Ordering::Greater
+            } else {
+                Ordering::Equal
+            }
+        }
+    }
+}
+
+impl<'a> Add<&'a GodotString> for &'a GodotString {
+    type Output = GodotString;
+
+    fn add(self, other: &GodotString) -> GodotString {
+        unsafe { GodotString((get_api().godot_string_operator_plus)(&self.0, &other.0)) }
+    }
+}
+
+impl<'a> Add<&'a GodotString> for GodotString {
+    type Output = GodotString;
+
+    fn add(self, other: &GodotString) -> GodotString {
+        &self + other
+    }
+}
+
+impl<'a> Add<&'a str> for GodotString {
+    type Output = GodotString;
+
+    fn add(self, other: &str) -> GodotString {
+        &self + &GodotString::from_str(other)
+    }
+}
+
+impl<'a> AddAssign<&'a GodotString> for GodotString {
+    fn add_assign(&mut self, other: &GodotString) {
+        *self = &*self + other;
     }
 }
 
+impl<'a> AddAssign<&'a str> for GodotString {
+    fn add_assign(&mut self, other: &str) {
+        *self += &GodotString::from_str(other);
+    }
+}
+
+impl FromIterator<char> for GodotString {
+    fn from_iter<I: IntoIterator<Item = char>>(iter: I) -> Self {
+        GodotString::from_str(iter.into_iter().collect::<String>())
+    }
+}
+
+/// Iterator over the characters of a `GodotString`.
+pub struct Chars<'a> {
+    string: &'a GodotString,
+    range: Range<i32>,
+}
+
+impl<'a> Iterator for Chars<'a> {
+    type Item = char;
+
+    fn next(&mut self) -> Option<char> {
+        let idx = self.range.next()?;
+        let code = self.string.ord_at(idx);
+        Some(std::char::from_u32(code).unwrap_or(std::char::REPLACEMENT_CHARACTER))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+unsafe fn typed_array<T>(array: sys::godot_array) -> TypedArray<T> {
+    TypedArray::from_untyped_unchecked(VariantArray::from_sys(array))
+}
+
+fn string_array(strings: &[GodotString]) -> VariantArray {
+    let mut array = VariantArray::new();
+    for s in strings {
+        array.push(&s.to_variant());
+    }
+    array
+}
+
 impl std::hash::Hash for GodotString {
     fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
         state.write_u64(self.u64_hash());
@@ -262,15 +838,24 @@ impl fmt::Debug for Utf8String {
     }
 }
 
+/// Interned string used by the engine for the names of methods, signals and properties.
+///
+/// Two `StringName`s created from the same string refer to the same data, so comparing and
+/// hashing them does not look at the characters.
 pub struct StringName(pub(crate) sys::godot_string_name);
 
+// The engine interns string names in a global table protected by a lock, and reference counts
+// them atomically.
+unsafe impl Send for StringName {}
+unsafe impl Sync for StringName {}
+
 impl StringName {
-    pub fn from_str<S>(s: S)
+    pub fn from_str<S>(s: S) -> Self
     where
         S: AsRef<str>,
     {
         let gd_string = GodotString::from_str(s);
-        StringName::from_godot_string(&gd_string);
+        StringName::from_godot_string(&gd_string)
     }
 
     pub fn from_c_str(s: &CStr) -> Self {
@@ -289,6 +874,17 @@ impl StringName {
         }
     }
 
+    /// Returns the `StringName` for `name` from a table shared by the whole process, creating
+    /// it on first use.
+    ///
+    /// Interned names live until the process exits.
+    pub fn interned(name: &'static str) -> &'static StringName {
+        let mut table = INTERNED.lock();
+        *table
+            .entry(name)
+            .or_insert_with(|| &*Box::leak(Box::new(StringName::from_str(name))))
+    }
+
     pub fn get_hash(&self) -> u32 {
         unsafe { (get_api().godot_string_name_get_hash)(&self.0) }
     }
@@ -302,6 +898,11 @@ impl StringName {
     }
 }
 
+lazy_static! {
+    static ref INTERNED: Mutex<HashMap<&'static str, &'static StringName>> =
+        Mutex::new(HashMap::new());
+}
+
 impl_basic_traits! {
     for StringName as godot_string_name {
         Drop => godot_string_name_destroy;
@@ -309,26 +910,107 @@ impl_basic_traits! {
     }
 }
 
+/// The engine has no copy constructor for string names, so cloning looks the name up again.
+impl Clone for StringName {
+    fn clone(&self) -> Self {
+        StringName::from_godot_string(&self.get_name())
+    }
+}
+
 impl fmt::Debug for StringName {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         self.get_name().to_string().fmt(f)
     }
 }
 
+impl fmt::Display for StringName {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        fmt::Display::fmt(&self.get_name(), f)
+    }
+}
+
+impl std::hash::Hash for StringName {
+    fn hash<H: std::hash::Hasher>(&self, state: &mut H) {
+        state.write_u32(self.get_hash());
+    }
+}
+
 impl PartialOrd for StringName {
     fn partial_cmp(&self, other: &StringName) -> Option<Ordering> {
-        unsafe {
-            let native = (get_api().godot_string_name_operator_less)(&self.0, &other.0);
+        Some(self.cmp(other))
+    }
+}
 
-            if native {
-                Some(Ordering::Less)
-            } else {
-                Some(Ordering::Greater)
-            }
+/// String names are ordered like the `<` operator of the engine, which compares the addresses of
+/// the interned data. The order is consistent within a process, but it is not alphabetical.
+impl Ord for StringName {
+    fn cmp(&self, other: &StringName) -> Ordering {
+        if self.operator_less(other) {
+            Ordering::Less
+        } else if other.operator_less(self) {
+            Ordering::Greater
+        } else {
+            Ordering::Equal
         }
     }
 }
 
+impl From<&'static str> for StringName {
+    fn from(name: &'static str) -> StringName {
+        StringName::interned(name).clone()
+    }
+}
+
+impl<'a> From<&'a GodotString> for StringName {
+    fn from(name: &GodotString) -> StringName {
+        StringName::from_godot_string(name)
+    }
+}
+
+/// Values that the generated bindings accept as the name of a method, signal or property.
+///
+/// Static strings are looked up in the table of [`StringName::interned`](struct.StringName.html#method.interned),
+/// so that passing the same literal every frame does not convert it from UTF-8 every time.
+pub trait IntoName {
+    fn into_name(self) -> GodotString;
+}
+
+impl IntoName for GodotString {
+    fn into_name(self) -> GodotString {
+        self
+    }
+}
+
+impl<'a> IntoName for &'a GodotString {
+    fn into_name(self) -> GodotString {
+        self.new_ref()
+    }
+}
+
+impl IntoName for StringName {
+    fn into_name(self) -> GodotString {
+        self.get_name()
+    }
+}
+
+impl<'a> IntoName for &'a StringName {
+    fn into_name(self) -> GodotString {
+        self.get_name()
+    }
+}
+
+impl IntoName for &'static str {
+    fn into_name(self) -> GodotString {
+        StringName::interned(self).get_name()
+    }
+}
+
+impl IntoName for String {
+    fn into_name(self) -> GodotString {
+        GodotString::from_str(self)
+    }
+}
+
 impl<S> From<S> for GodotString
 where
     S: AsRef<str>,
@@ -359,4 +1041,50 @@ godot_test!(test_string {
     }
 
     assert_eq!(foo.to_utf8().as_str(), "foo");
+    assert_eq!(foo, "foo");
+    assert_eq!(format!("{}", foo), "foo");
+
+    let foobar = foo.clone() + "bar";
+    assert_eq!(foobar, "foobar");
+    assert!(foo < foobar);
+    assert_eq!(foobar.chars().collect::<String>(), "foobar");
+    assert_eq!(foobar.left(3), "foo");
+    assert_eq!(foobar.right(3), "bar");
+    assert_eq!(foobar.find_last(&"o".into()), 2);
+
+    let csv: GodotString = "a,b,,c".into();
+    let parts: Vec<GodotString> = csv.split(&",".into(), false).iter().collect();
+    assert_eq!(parts, ["a", "b", "c"]);
+
+    let mut name: GodotString = "file".into();
+    name += "10";
+    assert_eq!(name.naturalnocasecmp_to(&"File2".into()), Ordering::Greater);
+    assert!(name.matches(&"f*1?".into()));
+    assert_eq!(GodotString::from("  x ").strip_edges(true, true), "x");
+});
+
+godot_test!(test_string_name {
+    let foo = StringName::from_str("foo");
+    assert_eq!(foo, StringName::from_godot_string(&"foo".into()));
+    assert_eq!(foo, foo.clone());
+    assert_ne!(foo, StringName::from_str("bar"));
+    assert_eq!(foo.to_string(), "foo");
+    assert_eq!(format!("{:?}", foo), "\"foo\"");
+
+    let interned = StringName::interned("foo");
+    assert!(std::ptr::eq(interned, StringName::interned("foo")));
+    assert_eq!(*interned, foo);
+    assert_eq!(StringName::from("foo"), foo);
+    assert_eq!("foo".into_name(), "foo");
+
+    let bar = StringName::from_str("bar");
+    assert_eq!(foo.cmp(&foo.clone()), Ordering::Equal);
+    assert_eq!(foo.cmp(&bar), bar.cmp(&foo).reverse());
+
+    let mut names = std::collections::HashSet::new();
+    names.insert(foo.clone());
+    names.insert(bar);
+    names.insert(StringName::from("foo"));
+    assert_eq!(names.len(), 2);
+    assert!(names.contains(interned));
 });
@@ -0,0 +1,273 @@
+use crate::{Angle, Transform2D, Vector2};
+
+/// Helper methods for `Transform2D`.
+///
+/// Trait used to provide additional methods that are equivalent to Godot's methods.
+/// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.1/classes/class_transform2d.html).
+///
+/// The memory layout of `Transform2D` is the same as in the engine: `m11` and `m12` are the X
+/// axis, `m21` and `m22` the Y axis and `m31` and `m32` the origin.
+pub trait Transform2DGodot {
+    /// Creates a transform from its axes and origin, like the constructor in GDScript.
+    fn from_axis_origin(x_axis: Vector2, y_axis: Vector2, origin: Vector2) -> Self;
+    /// Returns the X axis, `x` in GDScript.
+    fn x_axis(self) -> Vector2;
+    /// Returns the Y axis, `y` in GDScript.
+    fn y_axis(self) -> Vector2;
+    /// Returns the translation offset.
+    fn origin(self) -> Vector2;
+    /// Returns the inverse of the transform, which may also contain scaling. A transform that
+    /// cannot be inverted is returned unchanged.
+    fn affine_inverse(self) -> Self;
+    /// Transforms `v` by the basis of the transform, without translation.
+    fn basis_xform(self, v: Vector2) -> Vector2;
+    /// Inverse-transforms `v` by the basis of the transform, without translation, assuming that
+    /// the basis is orthonormal.
+    fn basis_xform_inv(self, v: Vector2) -> Vector2;
+    /// Returns the transform with orthogonal and normalized axes.
+    fn orthonormalized(self) -> Self;
+    /// Returns the transform rotated by `angle`, relative to the parent.
+    fn rotated(self, angle: Angle) -> Self;
+    /// Returns the rotation of the transform.
+    fn rotation(self) -> Angle;
+    /// Returns the transform scaled by `scale`, relative to the parent.
+    fn scaled(self, scale: Vector2) -> Self;
+    /// Returns the length of each axis. The Y length is negative if the transform is mirrored.
+    fn scale(self) -> Vector2;
+    /// Returns the transform translated by `offset`, relative to the transform itself.
+    fn translated(self, offset: Vector2) -> Self;
+    /// Transforms `v` by this transform.
+    fn xform(self, v: Vector2) -> Vector2;
+    /// Inverse-transforms `v` by this transform, assuming that the basis is orthonormal.
+    fn xform_inv(self, v: Vector2) -> Vector2;
+}
+
+impl Transform2DGodot for Transform2D {
+    #[inline]
+    fn from_axis_origin(x_axis: Vector2, y_axis: Vector2, origin: Vector2) -> Self {
+        Transform2D::row_major(x_axis.x, x_axis.y, y_axis.x, y_axis.y, origin.x, origin.y)
+    }
+
+    #[inline]
+    fn x_axis(self) -> Vector2 {
+        Vector2::new(self.m11, self.m12)
+    }
+
+    #[inline]
+    fn y_axis(self) -> Vector2 {
+        Vector2::new(self.m21, self.m22)
+    }
+
+    #[inline]
+    fn origin(self) -> Vector2 {
+        Vector2::new(self.m31, self.m32)
+    }
+
+    fn affine_inverse(self) -> Self {
+        let det = self.m11 * self.m22 - self.m12 * self.m21;
+        if det == 0.0 {
+            return self;
+        }
+
+        let idet = 1.0 / det;
+        let inverse = Transform2D::from_axis_origin(
+            Vector2::new(self.m22 * idet, -self.m12 * idet),
+            Vector2::new(-self.m21 * idet, self.m11 * idet),
+            Vector2::zero(),
+        );
+        Transform2D::from_axis_origin(
+            inverse.x_axis(),
+            inverse.y_axis(),
+            inverse.basis_xform(-self.origin()),
+        )
+    }
+
+    #[inline]
+    fn basis_xform(self, v: Vector2) -> Vector2 {
+        self.x_axis() * v.x + self.y_axis() * v.y
+    }
+
+    #[inline]
+    fn basis_xform_inv(self, v: Vector2) -> Vector2 {
+        Vector2::new(self.x_axis().dot(v), self.y_axis().dot(v))
+    }
+
+    fn orthonormalized(self) -> Self {
+        let x = self.x_axis().normalize();
+        let y = self.y_axis();
+        let y = (y - x * x.dot(y)).normalize();
+        Transform2D::from_axis_origin(x, y, self.origin())
+    }
+
+    #[inline]
+    fn rotated(self, angle: Angle) -> Self {
+        let (sin, cos) = angle.radians.sin_cos();
+        let rotation = Transform2D::from_axis_origin(
+            Vector2::new(cos, sin),
+            Vector2::new(-sin, cos),
+            Vector2::zero(),
+        );
+        Transform2D::from_axis_origin(
+            rotation.basis_xform(self.x_axis()),
+            rotation.basis_xform(self.y_axis()),
+            rotation.xform(self.origin()),
+        )
+    }
+
+    #[inline]
+    fn rotation(self) -> Angle {
+        Angle::radians(self.m12.atan2(self.m11))
+    }
+
+    #[inline]
+    fn scaled(self, scale: Vector2) -> Self {
+        Transform2D::row_major(
+            self.m11 * scale.x,
+            self.m12 * scale.y,
+            self.m21 * scale.x,
+            self.m22 * scale.y,
+            self.m31 * scale.x,
+            self.m32 * scale.y,
+        )
+    }
+
+    #[inline]
+    fn scale(self) -> Vector2 {
+        let det = self.m11 * self.m22 - self.m12 * self.m21;
+        let sign = if det < 0.0 { -1.0 } else { 1.0 };
+        Vector2::new(self.x_axis().length(), self.y_axis().length() * sign)
+    }
+
+    #[inline]
+    fn translated(self, offset: Vector2) -> Self {
+        Transform2D::from_axis_origin(
+            self.x_axis(),
+            self.y_axis(),
+            self.origin() + self.basis_xform(offset),
+        )
+    }
+
+    #[inline]
+    fn xform(self, v: Vector2) -> Vector2 {
+        self.basis_xform(v) + self.origin()
+    }
+
+    #[inline]
+    fn xform_inv(self, v: Vector2) -> Vector2 {
+        self.basis_xform_inv(v - self.origin())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use euclid::approxeq::ApproxEq;
+    use std::f32::consts::FRAC_PI_2;
+
+    fn transform() -> Transform2D {
+        Transform2D::from_axis_origin(
+            Vector2::new(0.0, 2.0),
+            Vector2::new(-2.0, 0.0),
+            Vector2::new(3.0, 4.0),
+        )
+    }
+
+    #[test]
+    fn column_layout() {
+        let t = transform();
+        assert_eq!(Vector2::new(3.0, 6.0), t.xform(Vector2::new(1.0, 0.0)));
+        assert_eq!(Vector2::new(1.0, 4.0), t.xform(Vector2::new(0.0, 1.0)));
+        assert_eq!(
+            t.transform_point(euclid::point2(5.0, -1.0)).to_vector(),
+            t.xform(Vector2::new(5.0, -1.0))
+        );
+        assert!((t.rotation().radians - FRAC_PI_2).abs() < 1e-6);
+        assert!(Vector2::new(2.0, 2.0).approx_eq(&t.scale()));
+    }
+
+    #[test]
+    fn inverses() {
+        let t = transform();
+        let v = Vector2::new(-7.0, 0.5);
+        assert!(v.approx_eq(&t.affine_inverse().xform(t.xform(v))));
+
+        let r = t.orthonormalized();
+        assert!(v.approx_eq(&r.xform_inv(r.xform(v))));
+
+        let singular = Transform2D::from_axis_origin(
+            Vector2::new(1.0, 1.0),
+            Vector2::new(2.0, 2.0),
+            Vector2::zero(),
+        );
+        assert_eq!(singular, singular.affine_inverse());
+    }
+
+    #[test]
+    fn rotated_scaled_translated() {
+        let t = Transform2D::from_axis_origin(
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, 1.0),
+            Vector2::new(1.0, 0.0),
+        );
+
+        let rotated = t.rotated(Angle::radians(FRAC_PI_2));
+        assert!(Vector2::new(0.0, 1.0).approx_eq(&rotated.origin()));
+        assert!(Vector2::new(0.0, 1.0).approx_eq(&rotated.x_axis()));
+
+        let scaled = t.scaled(Vector2::new(2.0, 3.0));
+        assert_eq!(Vector2::new(2.0, 0.0), scaled.origin());
+        assert_eq!(Vector2::new(0.0, 3.0), scaled.y_axis());
+
+        let translated = rotated.translated(Vector2::new(1.0, 0.0));
+        assert!(Vector2::new(0.0, 2.0).approx_eq(&translated.origin()));
+    }
+
+    // Expected values are worked out from the Godot 3.2 implementation in
+    // `core/math/transform_2d.cpp`, using the same formulas as the engine.
+    #[test]
+    fn matches_godot_3_2() {
+        // Transform2D(Vector2(1, 2), Vector2(-1, 1), Vector2(3, -2)), which is not orthogonal
+        let t = Transform2D::from_axis_origin(
+            Vector2::new(1.0, 2.0),
+            Vector2::new(-1.0, 1.0),
+            Vector2::new(3.0, -2.0),
+        );
+
+        assert_eq!(Vector2::new(4.0, 3.0), t.xform(Vector2::new(2.0, 1.0)));
+        assert!((t.rotation().radians - 1.107_148_8).abs() < 1e-6);
+        assert!(Vector2::new(2.236_068, 1.414_213_6).approx_eq(&t.scale()));
+
+        let inverse = t.affine_inverse();
+        assert!(Vector2::new(1.0 / 3.0, -2.0 / 3.0).approx_eq(&inverse.x_axis()));
+        assert!(Vector2::new(1.0 / 3.0, 1.0 / 3.0).approx_eq(&inverse.y_axis()));
+        assert!(Vector2::new(-1.0 / 3.0, 8.0 / 3.0).approx_eq(&inverse.origin()));
+
+        let orthonormalized = t.orthonormalized();
+        assert!(Vector2::new(0.447_213_6, 0.894_427_2).approx_eq(&orthonormalized.x_axis()));
+        assert!(Vector2::new(-0.894_427_2, 0.447_213_6).approx_eq(&orthonormalized.y_axis()));
+        assert_eq!(Vector2::new(3.0, -2.0), orthonormalized.origin());
+
+        let rotated = t.rotated(Angle::radians(FRAC_PI_2));
+        assert!(Vector2::new(-2.0, 1.0).approx_eq(&rotated.x_axis()));
+        assert!(Vector2::new(-1.0, -1.0).approx_eq(&rotated.y_axis()));
+        assert!(Vector2::new(2.0, 3.0).approx_eq(&rotated.origin()));
+
+        let scaled = t.scaled(Vector2::new(2.0, 3.0));
+        assert_eq!(Vector2::new(2.0, 6.0), scaled.x_axis());
+        assert_eq!(Vector2::new(-2.0, 3.0), scaled.y_axis());
+        assert_eq!(Vector2::new(6.0, -6.0), scaled.origin());
+
+        assert_eq!(
+            Vector2::new(3.0, 1.0),
+            t.translated(Vector2::new(1.0, 1.0)).origin()
+        );
+
+        // Transform2D(Vector2(1, 0), Vector2(0, -2), Vector2()).get_scale(), which is mirrored
+        let mirrored = Transform2D::from_axis_origin(
+            Vector2::new(1.0, 0.0),
+            Vector2::new(0.0, -2.0),
+            Vector2::zero(),
+        );
+        assert_eq!(Vector2::new(1.0, -2.0), mirrored.scale());
+    }
+}
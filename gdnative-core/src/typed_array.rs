@@ -0,0 +1,333 @@
+use crate::FromVariant;
+use crate::FromVariantError;
+use crate::ToVariant;
+use crate::Variant;
+use crate::VariantArray;
+
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+/// A `VariantArray` whose elements are all of type `T`.
+///
+/// The elements are checked when the array is created from an untyped value. Arrays are
+/// reference-counted, so the elements may still be replaced with values of another type
+/// through an untyped reference, e.g. from GDScript. Accessors panic when they read such a value.
+///
+/// A `TypedArray` has the same representation as a `VariantArray`, so it can be passed to
+/// engine APIs with `as_untyped` or `into_untyped` at no cost.
+#[repr(transparent)]
+pub struct TypedArray<T> {
+    array: VariantArray,
+    _marker: PhantomData<T>,
+}
+
+impl<T> TypedArray<T> {
+    /// Creates an empty array.
+    pub fn new() -> Self {
+        TypedArray {
+            array: VariantArray::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of elements in the array.
+    pub fn len(&self) -> i32 {
+        self.array.len()
+    }
+
+    /// Returns `true` if the array contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.array.is_empty()
+    }
+
+    /// Clears the array, resizing to 0.
+    pub fn clear(&mut self) {
+        self.array.clear()
+    }
+
+    /// Removes the element at the given offset.
+    pub fn remove(&mut self, idx: i32) {
+        self.array.remove(idx)
+    }
+
+    /// Inverts the order of the elements in the array.
+    pub fn invert(&mut self) {
+        self.array.invert()
+    }
+
+    /// Returns the untyped array.
+    pub fn as_untyped(&self) -> &VariantArray {
+        &self.array
+    }
+
+    /// Converts into the untyped array.
+    pub fn into_untyped(self) -> VariantArray {
+        self.array
+    }
+
+    /// Creates a new reference to this array.
+    pub fn new_ref(&self) -> Self {
+        TypedArray {
+            array: self.array.new_ref(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Wraps an array returned by the engine, whose elements are known to be of type `T`.
+    pub(crate) fn from_untyped_unchecked(array: VariantArray) -> Self {
+        TypedArray {
+            array,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: FromVariant> TypedArray<T> {
+    /// Checks that every element of `array` can be converted to `T`.
+    pub fn try_from_untyped(array: VariantArray) -> Result<Self, FromVariantError> {
+        for (index, element) in array.iter().enumerate() {
+            T::from_variant(element).map_err(|e| FromVariantError::InvalidItem {
+                index,
+                error: Box::new(e),
+            })?;
+        }
+
+        Ok(TypedArray {
+            array,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns the element at the given offset, or `None` if it is out of bounds.
+    pub fn get(&self, idx: i32) -> Option<T> {
+        if idx >= 0 && idx < self.len() {
+            Some(convert_element(self.array.get_ref(idx)))
+        } else {
+            None
+        }
+    }
+
+    /// Removes the element at the end of the array and returns it.
+    pub fn pop(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(convert_element(&self.array.pop()))
+        }
+    }
+
+    /// Removes the element at the front of the array and returns it.
+    pub fn pop_front(&mut self) -> Option<T> {
+        if self.is_empty() {
+            None
+        } else {
+            Some(convert_element(&self.array.pop_front()))
+        }
+    }
+
+    /// Returns an iterator over the elements of the array.
+    pub fn iter(&self) -> TypedArrayIter<'_, T> {
+        TypedArrayIter {
+            array: &self.array,
+            range: 0..self.len(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T: ToVariant> TypedArray<T> {
+    /// Sets the value of the element at the given offset.
+    pub fn set(&mut self, idx: i32, val: &T) {
+        self.array.set(idx, &val.to_variant())
+    }
+
+    /// Appends an element at the end of the array.
+    pub fn push(&mut self, val: &T) {
+        self.array.push(&val.to_variant())
+    }
+
+    /// Appends an element to the front of the array.
+    pub fn push_front(&mut self, val: &T) {
+        self.array.push_front(&val.to_variant())
+    }
+
+    /// Inserts an element at the given offset.
+    pub fn insert(&mut self, at: i32, val: &T) {
+        self.array.insert(at, &val.to_variant())
+    }
+
+    /// Returns `true` if the array contains the specified value.
+    pub fn contains(&self, val: &T) -> bool {
+        self.array.contains(&val.to_variant())
+    }
+
+    /// Searches the array for a value and returns its index, starting at `from`.
+    /// Returns `-1` if the value is not found.
+    pub fn find(&self, val: &T, from: i32) -> i32 {
+        self.array.find(&val.to_variant(), from)
+    }
+}
+
+/// Converts an element of a typed collection, which has been checked on construction.
+pub(crate) fn convert_element<T: FromVariant>(variant: &Variant) -> T {
+    T::from_variant(variant).unwrap_or_else(|e| {
+        panic!(
+            "typed collection contains a value of the wrong type, {}: {}",
+            std::any::type_name::<T>(),
+            e
+        )
+    })
+}
+
+impl<T> Default for TypedArray<T> {
+    fn default() -> Self {
+        TypedArray::new()
+    }
+}
+
+impl<T> From<TypedArray<T>> for VariantArray {
+    fn from(array: TypedArray<T>) -> Self {
+        array.into_untyped()
+    }
+}
+
+impl<T> ToVariant for TypedArray<T> {
+    fn to_variant(&self) -> Variant {
+        self.array.to_variant()
+    }
+}
+
+impl<T: FromVariant> FromVariant for TypedArray<T> {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        VariantArray::from_variant(variant).and_then(TypedArray::try_from_untyped)
+    }
+}
+
+impl<T: ToVariant> FromIterator<T> for TypedArray<T> {
+    fn from_iter<I: IntoIterator<Item = T>>(iter: I) -> Self {
+        let mut array = TypedArray::new();
+        array.extend(iter);
+        array
+    }
+}
+
+impl<T: ToVariant> Extend<T> for TypedArray<T> {
+    fn extend<I: IntoIterator<Item = T>>(&mut self, iter: I) {
+        for val in iter {
+            self.push(&val);
+        }
+    }
+}
+
+impl<'a, T: FromVariant> IntoIterator for &'a TypedArray<T> {
+    type Item = T;
+    type IntoIter = TypedArrayIter<'a, T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<T: FromVariant> IntoIterator for TypedArray<T> {
+    type Item = T;
+    type IntoIter = TypedArrayIntoIter<T>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        TypedArrayIntoIter {
+            range: 0..self.len(),
+            array: self.array,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Iterator over the elements of a `TypedArray`.
+pub struct TypedArrayIter<'a, T> {
+    array: &'a VariantArray,
+    range: std::ops::Range<i32>,
+    _marker: PhantomData<T>,
+}
+
+impl<'a, T: FromVariant> Iterator for TypedArrayIter<'a, T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let array = self.array;
+        self.range
+            .next()
+            .map(|idx| convert_element(array.get_ref(idx)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+/// Owning iterator over the elements of a `TypedArray`.
+pub struct TypedArrayIntoIter<T> {
+    array: VariantArray,
+    range: std::ops::Range<i32>,
+    _marker: PhantomData<T>,
+}
+
+impl<T: FromVariant> Iterator for TypedArrayIntoIter<T> {
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let array = &self.array;
+        self.range
+            .next()
+            .map(|idx| convert_element(array.get_ref(idx)))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+godot_test!(test_typed_array {
+    use crate::VariantType;
+
+    let mut array: TypedArray<i64> = vec![1, 2, 3].into_iter().collect();
+    assert_eq!(3, array.len());
+    assert_eq!(Some(2), array.get(1));
+    assert_eq!(None, array.get(3));
+    assert_eq!(None, array.get(-1));
+
+    array.push(&4);
+    array.set(0, &10);
+    array.insert(1, &5);
+    assert!(array.contains(&5));
+    assert_eq!(2, array.find(&2, 0));
+    assert_eq!(vec![10, 5, 2, 3, 4], array.iter().collect::<Vec<_>>());
+
+    assert_eq!(Some(4), array.pop());
+    assert_eq!(Some(10), array.pop_front());
+    array.extend(vec![6, 7]);
+    assert_eq!(vec![5, 2, 3, 6, 7], (&array).into_iter().collect::<Vec<_>>());
+
+    let untyped: &VariantArray = array.as_untyped();
+    assert_eq!(Some(5), untyped.get_ref(0).try_to_i64());
+
+    let variant = array.to_variant();
+    let copy = TypedArray::<i64>::from_variant(&variant).expect("should be an i64 array");
+    assert_eq!(vec![5, 2, 3, 6, 7], copy.into_iter().collect::<Vec<_>>());
+
+    let mut mixed = array.into_untyped();
+    mixed.push(&Variant::from_str("eight"));
+    assert_eq!(
+        Err(FromVariantError::InvalidItem {
+            index: 5,
+            error: Box::new(FromVariantError::InvalidVariantType {
+                variant_type: VariantType::GodotString,
+                expected: VariantType::I64,
+            }),
+        }),
+        TypedArray::<i64>::from_variant(&mixed.to_variant()).map(|a| a.len()),
+    );
+    assert!(TypedArray::<Variant>::try_from_untyped(mixed).is_ok());
+
+    let mut empty = TypedArray::<String>::new();
+    assert!(empty.is_empty());
+    assert_eq!(None, empty.pop());
+});
@@ -0,0 +1,312 @@
+use crate::typed_array::convert_element;
+use crate::Dictionary;
+use crate::FromVariant;
+use crate::FromVariantError;
+use crate::ToVariant;
+use crate::Variant;
+use crate::VariantArray;
+
+use std::iter::FromIterator;
+use std::marker::PhantomData;
+
+/// A `Dictionary` whose keys are all of type `K` and values of type `V`.
+///
+/// The entries are checked when the dictionary is created from an untyped value. Like in
+/// `TypedArray`, accessors panic when they read a key or value that was replaced with a value of
+/// another type through an untyped reference.
+///
+/// A `TypedDictionary` has the same representation as a `Dictionary`, so it can be passed to
+/// engine APIs with `as_untyped` or `into_untyped` at no cost.
+#[repr(transparent)]
+pub struct TypedDictionary<K, V> {
+    dict: Dictionary,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K, V> TypedDictionary<K, V> {
+    /// Creates an empty dictionary.
+    pub fn new() -> Self {
+        TypedDictionary {
+            dict: Dictionary::new(),
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns the number of entries in the dictionary.
+    pub fn len(&self) -> i32 {
+        self.dict.len()
+    }
+
+    /// Returns `true` if the dictionary contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.dict.is_empty()
+    }
+
+    /// Clears the dictionary, removing all entries.
+    pub fn clear(&mut self) {
+        self.dict.clear()
+    }
+
+    /// Returns the untyped dictionary.
+    pub fn as_untyped(&self) -> &Dictionary {
+        &self.dict
+    }
+
+    /// Converts into the untyped dictionary.
+    pub fn into_untyped(self) -> Dictionary {
+        self.dict
+    }
+
+    /// Creates a new reference to this dictionary.
+    pub fn new_ref(&self) -> Self {
+        TypedDictionary {
+            dict: self.dict.new_ref(),
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<K: FromVariant, V: FromVariant> TypedDictionary<K, V> {
+    /// Checks that every key of `dict` can be converted to `K`, and every value to `V`.
+    /// Errors are reported with the index of the entry in the dictionary, as `InvalidKey` or
+    /// `InvalidValue`.
+    pub fn try_from_untyped(dict: Dictionary) -> Result<Self, FromVariantError> {
+        for (index, key) in dict.keys().iter().enumerate() {
+            K::from_variant(key).map_err(|e| FromVariantError::InvalidKey {
+                index,
+                error: Box::new(e),
+            })?;
+            V::from_variant(dict.get_ref(key)).map_err(|e| FromVariantError::InvalidValue {
+                index,
+                error: Box::new(e),
+            })?;
+        }
+
+        Ok(TypedDictionary {
+            dict,
+            _marker: PhantomData,
+        })
+    }
+
+    /// Returns an iterator over the entries of the dictionary.
+    pub fn iter(&self) -> TypedDictionaryIter<'_, K, V> {
+        let keys = self.dict.keys();
+        TypedDictionaryIter {
+            dict: &self.dict,
+            range: 0..keys.len(),
+            keys,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Returns an iterator over the keys of the dictionary.
+    pub fn keys(&self) -> impl Iterator<Item = K> + '_ {
+        self.iter().map(|(key, _)| key)
+    }
+
+    /// Returns an iterator over the values of the dictionary.
+    pub fn values(&self) -> impl Iterator<Item = V> + '_ {
+        self.iter().map(|(_, value)| value)
+    }
+}
+
+impl<K: ToVariant, V: FromVariant> TypedDictionary<K, V> {
+    /// Returns the value corresponding to the key, or `None` if the key is not present.
+    pub fn get(&self, key: &K) -> Option<V> {
+        let key = key.to_variant();
+        if self.dict.contains(&key) {
+            Some(convert_element(self.dict.get_ref(&key)))
+        } else {
+            None
+        }
+    }
+
+    /// Returns `true` if the dictionary contains the key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.dict.contains(&key.to_variant())
+    }
+
+    /// Removes the entry of the key and returns its value, or `None` if the key is not present.
+    pub fn remove(&mut self, key: &K) -> Option<V> {
+        let key = key.to_variant();
+        let value = if self.dict.contains(&key) {
+            Some(convert_element(self.dict.get_ref(&key)))
+        } else {
+            None
+        };
+        self.dict.erase(&key);
+        value
+    }
+}
+
+impl<K: ToVariant, V: ToVariant + FromVariant> TypedDictionary<K, V> {
+    /// Sets the value of the key, and returns the previous value if the key was present.
+    pub fn insert(&mut self, key: &K, value: &V) -> Option<V> {
+        let key = key.to_variant();
+        let old = if self.dict.contains(&key) {
+            Some(convert_element(self.dict.get_ref(&key)))
+        } else {
+            None
+        };
+        self.dict.set(&key, &value.to_variant());
+        old
+    }
+}
+
+impl<K, V> Default for TypedDictionary<K, V> {
+    fn default() -> Self {
+        TypedDictionary::new()
+    }
+}
+
+impl<K, V> From<TypedDictionary<K, V>> for Dictionary {
+    fn from(dict: TypedDictionary<K, V>) -> Self {
+        dict.into_untyped()
+    }
+}
+
+impl<K, V> ToVariant for TypedDictionary<K, V> {
+    fn to_variant(&self) -> Variant {
+        self.dict.to_variant()
+    }
+}
+
+impl<K: FromVariant, V: FromVariant> FromVariant for TypedDictionary<K, V> {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        Dictionary::from_variant(variant).and_then(TypedDictionary::try_from_untyped)
+    }
+}
+
+impl<K: ToVariant, V: ToVariant> FromIterator<(K, V)> for TypedDictionary<K, V> {
+    fn from_iter<I: IntoIterator<Item = (K, V)>>(iter: I) -> Self {
+        let mut dict = TypedDictionary::new();
+        dict.extend(iter);
+        dict
+    }
+}
+
+impl<K: ToVariant, V: ToVariant> Extend<(K, V)> for TypedDictionary<K, V> {
+    fn extend<I: IntoIterator<Item = (K, V)>>(&mut self, iter: I) {
+        for (key, value) in iter {
+            self.dict.set(&key.to_variant(), &value.to_variant());
+        }
+    }
+}
+
+impl<'a, K: FromVariant, V: FromVariant> IntoIterator for &'a TypedDictionary<K, V> {
+    type Item = (K, V);
+    type IntoIter = TypedDictionaryIter<'a, K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        self.iter()
+    }
+}
+
+impl<K: FromVariant, V: FromVariant> IntoIterator for TypedDictionary<K, V> {
+    type Item = (K, V);
+    type IntoIter = TypedDictionaryIntoIter<K, V>;
+
+    fn into_iter(self) -> Self::IntoIter {
+        let keys = self.dict.keys();
+        TypedDictionaryIntoIter {
+            dict: self.dict,
+            range: 0..keys.len(),
+            keys,
+            _marker: PhantomData,
+        }
+    }
+}
+
+/// Iterator over the entries of a `TypedDictionary`.
+///
+/// The keys are copied when the iterator is created.
+pub struct TypedDictionaryIter<'a, K, V> {
+    dict: &'a Dictionary,
+    keys: VariantArray,
+    range: std::ops::Range<i32>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<'a, K: FromVariant, V: FromVariant> Iterator for TypedDictionaryIter<'a, K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.range.next()?;
+        let key = self.keys.get_ref(idx);
+        Some((
+            convert_element(key),
+            convert_element(self.dict.get_ref(key)),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+/// Owning iterator over the entries of a `TypedDictionary`.
+pub struct TypedDictionaryIntoIter<K, V> {
+    dict: Dictionary,
+    keys: VariantArray,
+    range: std::ops::Range<i32>,
+    _marker: PhantomData<(K, V)>,
+}
+
+impl<K: FromVariant, V: FromVariant> Iterator for TypedDictionaryIntoIter<K, V> {
+    type Item = (K, V);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let idx = self.range.next()?;
+        let key = self.keys.get_ref(idx);
+        Some((
+            convert_element(key),
+            convert_element(self.dict.get_ref(key)),
+        ))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.range.size_hint()
+    }
+}
+
+godot_test!(test_typed_dictionary {
+    use crate::VariantType;
+
+    let mut dict: TypedDictionary<String, i64> =
+        vec![("a".to_string(), 1), ("b".to_string(), 2)].into_iter().collect();
+    assert_eq!(2, dict.len());
+    assert_eq!(Some(1), dict.get(&"a".to_string()));
+    assert_eq!(None, dict.get(&"c".to_string()));
+    assert!(dict.contains_key(&"b".to_string()));
+
+    assert_eq!(Some(2), dict.insert(&"b".to_string(), &20));
+    assert_eq!(None, dict.insert(&"c".to_string(), &3));
+    assert_eq!(Some(3), dict.remove(&"c".to_string()));
+    assert_eq!(None, dict.remove(&"c".to_string()));
+
+    let mut entries = dict.iter().collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(vec![("a".to_string(), 1), ("b".to_string(), 20)], entries);
+    assert_eq!(21, dict.values().sum::<i64>());
+
+    dict.extend(vec![("d".to_string(), 4)]);
+    assert_eq!(Some(4), dict.as_untyped().get_ref(&"d".into()).try_to_i64());
+
+    let variant = dict.to_variant();
+    let copy = TypedDictionary::<String, i64>::from_variant(&variant).expect("should be typed");
+    assert_eq!(3, copy.into_iter().count());
+
+    let mut untyped = dict.into_untyped();
+    untyped.set(&Variant::from_i64(5), &Variant::from_i64(5));
+    match TypedDictionary::<String, i64>::from_variant(&untyped.to_variant()) {
+        Err(FromVariantError::InvalidKey { error, .. }) => assert_eq!(
+            FromVariantError::InvalidVariantType {
+                variant_type: VariantType::I64,
+                expected: VariantType::GodotString,
+            },
+            *error,
+        ),
+        _ => panic!("a key of the wrong type should be an error"),
+    }
+    assert!(TypedDictionary::<Variant, i64>::try_from_untyped(untyped).is_ok());
+});
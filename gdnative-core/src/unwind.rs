@@ -0,0 +1,62 @@
+//! Reporting of panics caught before they unwind into the engine.
+
+use std::any::Any;
+use std::cell::RefCell;
+use std::panic;
+use std::sync::Once;
+
+thread_local! {
+    static LOCATION: RefCell<Option<String>> = RefCell::new(None);
+}
+
+/// Installs a panic hook that remembers where the last panic of each thread happened. The
+/// previous hook still runs after it.
+pub fn install_hook() {
+    static INSTALL: Once = Once::new();
+
+    INSTALL.call_once(|| {
+        let previous = panic::take_hook();
+        panic::set_hook(Box::new(move |info| {
+            let location = info
+                .location()
+                .map(|loc| format!("{}:{}:{}", loc.file(), loc.line(), loc.column()));
+            LOCATION.with(|last| *last.borrow_mut() = location);
+            previous(info);
+        }));
+    });
+}
+
+/// Describes a panic caught with `catch_unwind`, with its message and, if the hook was
+/// installed, its location.
+pub fn describe(payload: &(dyn Any + Send)) -> String {
+    let message = if let Some(message) = payload.downcast_ref::<&str>() {
+        message.to_string()
+    } else if let Some(message) = payload.downcast_ref::<String>() {
+        message.clone()
+    } else {
+        "Box<Any>".to_string()
+    };
+
+    match LOCATION.with(|last| last.borrow_mut().take()) {
+        Some(location) => format!("'{}' at {}", message, location),
+        None => format!("'{}'", message),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn describe_panics() {
+        install_hook();
+
+        let payload = panic::catch_unwind(|| panic!("at the {}", "disco")).unwrap_err();
+        let description = describe(&*payload);
+        assert!(description.starts_with("'at the disco' at "));
+        assert!(description.contains("unwind.rs"));
+
+        // The location is only reported once.
+        assert_eq!("'oops'", describe(&"oops"));
+    }
+}
@@ -1,7 +1,12 @@
 use super::*;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
 use std::default::Default;
 use std::fmt;
+use std::hash::{BuildHasher, Hash};
 use std::mem::{forget, transmute};
+use std::rc::Rc;
+use std::sync::Arc;
 
 // TODO: implement Debug, PartialEq, etc.
 
@@ -173,9 +178,9 @@ impl CallError {
     }
 }
 
-// TODO: Looks like this is missing from the godot_headers bindings.
-// It's risky to redefine it here and count on the fact that the integer
-// constants will be the same.
+/// Operators that can be applied to variants with [`Variant::evaluate`](struct.Variant.html#method.evaluate).
+///
+/// The discriminants are the values of `sys::godot_variant_operator`.
 #[repr(u32)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq, Hash)]
 pub enum VariantOperator {
@@ -579,6 +584,39 @@ impl Variant {
         }
     }
 
+    /// Evaluates `a op b` with the rules of the engine, exactly like GDScript does: integers
+    /// are promoted to floats when mixed with them, vectors can be multiplied and divided by
+    /// numbers, `Module` formats a string with `b` as argument (or as arguments if it is an
+    /// array), and `In` checks whether `a` is contained in the string, array or dictionary `b`.
+    ///
+    /// The unary operators `Negate`, `Positive`, `BitNegate` and `Not` only use `a`.
+    ///
+    /// Returns `None` if the operator is not defined for the types of the operands, or if the
+    /// evaluation fails, e.g. on an integer division by zero.
+    pub fn evaluate(op: VariantOperator, a: &Variant, b: &Variant) -> Option<Variant> {
+        if op == VariantOperator::Max {
+            return None;
+        }
+
+        unsafe {
+            let mut ret = Variant::new();
+            let mut valid = false;
+            (get_api().godot_variant_evaluate)(
+                op as u32 as sys::godot_variant_operator,
+                &a.0,
+                &b.0,
+                &mut ret.0,
+                &mut valid,
+            );
+
+            if valid {
+                Some(ret)
+            } else {
+                None
+            }
+        }
+    }
+
     pub(crate) fn cast_ref<'l>(ptr: *const sys::godot_variant) -> &'l Variant {
         unsafe { transmute(ptr) }
     }
@@ -633,6 +671,113 @@ impl Default for Variant {
     }
 }
 
+/// Orders variants with the `<` and `>` operators of the engine. Variants are only equal if
+/// they are equal with `==`, which never equates different types, so an integer and a float of
+/// the same value have no ordering. Neither do variants that cannot be compared, like an
+/// integer and a string.
+impl PartialOrd for Variant {
+    fn partial_cmp(&self, other: &Variant) -> Option<std::cmp::Ordering> {
+        use std::cmp::Ordering;
+
+        if Variant::evaluate(VariantOperator::Less, self, other)?.to_bool() {
+            Some(Ordering::Less)
+        } else if Variant::evaluate(VariantOperator::Greater, self, other)?.to_bool() {
+            Some(Ordering::Greater)
+        } else if self == other {
+            Some(Ordering::Equal)
+        } else {
+            None
+        }
+    }
+}
+
+fn evaluate_or_panic(op: VariantOperator, a: &Variant, b: Option<&Variant>) -> Variant {
+    let nil = Variant::new();
+    Variant::evaluate(op, a, b.unwrap_or(&nil)).unwrap_or_else(|| match b {
+        Some(b) => panic!(
+            "invalid operands {:?} and {:?} for operator {:?}",
+            a.get_type(),
+            b.get_type(),
+            op
+        ),
+        None => panic!("invalid operand {:?} for operator {:?}", a.get_type(), op),
+    })
+}
+
+macro_rules! impl_variant_binary_ops {
+    ($($Trait:ident, $method:ident => $op:ident;)*) => {
+        $(
+            /// Evaluates the operator like [`Variant::evaluate`](struct.Variant.html#method.evaluate).
+            ///
+            /// # Panics
+            ///
+            /// If the operator is not defined for the operands, or if the evaluation fails.
+            impl<'a, 'b> std::ops::$Trait<&'b Variant> for &'a Variant {
+                type Output = Variant;
+
+                fn $method(self, rhs: &'b Variant) -> Variant {
+                    evaluate_or_panic(VariantOperator::$op, self, Some(rhs))
+                }
+            }
+
+            impl std::ops::$Trait for Variant {
+                type Output = Variant;
+
+                fn $method(self, rhs: Variant) -> Variant {
+                    std::ops::$Trait::$method(&self, &rhs)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_binary_ops!(
+    Add, add => Add;
+    Sub, sub => Subtact;
+    Mul, mul => Multiply;
+    Div, div => Divide;
+    Rem, rem => Module;
+    Shl, shl => ShiftLeft;
+    Shr, shr => ShiftRight;
+    BitAnd, bitand => BitAnd;
+    BitOr, bitor => BitOr;
+    BitXor, bitxor => BitXor;
+);
+
+macro_rules! impl_variant_unary_ops {
+    ($($Trait:ident, $method:ident => $op:ident;)*) => {
+        $(
+            /// Evaluates the operator like [`Variant::evaluate`](struct.Variant.html#method.evaluate).
+            ///
+            /// # Panics
+            ///
+            /// If the operator is not defined for the operand.
+            impl<'a> std::ops::$Trait for &'a Variant {
+                type Output = Variant;
+
+                fn $method(self) -> Variant {
+                    evaluate_or_panic(VariantOperator::$op, self, None)
+                }
+            }
+
+            impl std::ops::$Trait for Variant {
+                type Output = Variant;
+
+                fn $method(self) -> Variant {
+                    std::ops::$Trait::$method(&self)
+                }
+            }
+        )*
+    };
+}
+
+// `!` is the logical `not` of GDScript, like for `bool`. `BitNegate` is only available through
+// `evaluate`.
+impl_variant_unary_ops!(
+    Neg, neg => Negate;
+    Not, not => Not;
+);
+
 impl fmt::Debug for Variant {
     fn fmt(&self, f: &mut fmt::Formatter) -> Result<(), fmt::Error> {
         write!(f, "{:?}({})", self.get_type(), self.to_string())
@@ -834,6 +979,8 @@ pub enum FromVariantError {
     CannotCast { class: String, to: &'static str },
     /// Length of the collection is different from the expected one.
     InvalidLength { len: usize, expected: usize },
+    /// Integer is out of the range of the integer type it is converted to.
+    IntegerOutOfRange { value: i64, target: &'static str },
     /// Invalid enum representation.
     InvalidEnumRepr {
         expected: VariantEnumRepr,
@@ -882,11 +1029,28 @@ pub enum FromVariantError {
         index: usize,
         error: Box<FromVariantError>,
     },
+    /// Dictionary contains an invalid key, at the given index in its keys.
+    InvalidKey {
+        index: usize,
+        error: Box<FromVariantError>,
+    },
+    /// Dictionary contains an invalid value, for the key at the given index in its keys.
+    InvalidValue {
+        index: usize,
+        error: Box<FromVariantError>,
+    },
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
 pub enum VariantEnumRepr {
+    /// `{ "Variant": value }`.
     ExternallyTagged,
+    /// `{ "tag": "Variant", "field": value, ... }`.
+    InternallyTagged,
+    /// `{ "tag": "Variant", "content": value }`.
+    AdjacentlyTagged,
+    /// The discriminant of a C-like enum.
+    Int,
 }
 
 #[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
@@ -896,13 +1060,79 @@ pub enum VariantStructRepr {
     Struct,
 }
 
+/// A step on the path from a converted value to a nested value, as reported by
+/// `FromVariantError::path`.
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub enum FromVariantPathSegment {
+    /// A named field of a struct or a dictionary, displayed as `.name`.
+    Field(&'static str),
+    /// An item of an array, displayed as `[index]`.
+    Item(usize),
+    /// The key of a dictionary entry, by index, displayed as `[key index]`.
+    Key(usize),
+    /// The value of a dictionary entry, by index, displayed as `[value index]`.
+    Value(usize),
+    /// A variant of an enum, displayed as `::Variant`.
+    EnumVariant(&'static str),
+}
+
+impl fmt::Display for FromVariantPathSegment {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            FromVariantPathSegment::Field(name) => write!(f, ".{}", name),
+            FromVariantPathSegment::Item(index) => write!(f, "[{}]", index),
+            FromVariantPathSegment::Key(index) => write!(f, "[key {}]", index),
+            FromVariantPathSegment::Value(index) => write!(f, "[value {}]", index),
+            FromVariantPathSegment::EnumVariant(variant) => write!(f, "::{}", variant),
+        }
+    }
+}
+
 impl FromVariantError {
     /// Returns a `FromVariantError` with a custom message.
     pub fn custom<T: fmt::Display>(message: T) -> Self {
         FromVariantError::Custom(format!("{}", message))
     }
+
+    /// Returns the path to the nested value that failed to convert, following `InvalidField`,
+    /// `InvalidItem`, `InvalidKey`, `InvalidValue` and `InvalidEnumVariant` errors. The path is
+    /// empty for errors about the value itself.
+    pub fn path(&self) -> Vec<FromVariantPathSegment> {
+        let mut path = Vec::new();
+        let mut error = self;
+        while let Some((segment, next)) = error.nested() {
+            path.push(segment);
+            error = next;
+        }
+        path
+    }
+
+    /// Returns the error of the nested value at the end of `path`.
+    pub fn leaf(&self) -> &FromVariantError {
+        let mut error = self;
+        while let Some((_, next)) = error.nested() {
+            error = next;
+        }
+        error
+    }
+
+    fn nested(&self) -> Option<(FromVariantPathSegment, &FromVariantError)> {
+        use FromVariantError as E;
+        use FromVariantPathSegment as S;
+
+        match self {
+            E::InvalidField { field_name, error } => Some((S::Field(field_name), error)),
+            E::InvalidItem { index, error } => Some((S::Item(*index), error)),
+            E::InvalidKey { index, error } => Some((S::Key(*index), error)),
+            E::InvalidValue { index, error } => Some((S::Value(*index), error)),
+            E::InvalidEnumVariant { variant, error } => Some((S::EnumVariant(variant), error)),
+            _ => None,
+        }
+    }
 }
 
+/// Errors about nested values are displayed with the path to the value, followed by the error
+/// of the value itself, e.g. `.inventory[3].item_id: expected I64, got GodotString`.
 impl fmt::Display for FromVariantError {
     fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
         use FromVariantError as E;
@@ -914,27 +1144,33 @@ impl fmt::Display for FromVariantError {
             E::InvalidVariantType {
                 variant_type,
                 expected,
-            } => write!(
-                f,
-                "invalid variant type: expected {:?}, got {:?}",
-                expected, variant_type
-            ),
+            } => write!(f, "expected {:?}, got {:?}", expected, variant_type),
             E::CannotCast { class, to } => {
                 write!(f, "cannot cast object of class {} to {}", class, to)
             }
             E::InvalidLength { len, expected } => {
                 write!(f, "expected collection of length {}, got {}", expected, len)
             }
-            E::InvalidEnumRepr { expected, error } => write!(
-                f,
-                "invalid enum representation: expected {:?}, {}",
-                expected, error
-            ),
-            E::InvalidStructRepr { expected, error } => write!(
-                f,
-                "invalid struct representation: expected {:?}, {}",
-                expected, error
-            ),
+            E::IntegerOutOfRange { value, target } => {
+                write!(f, "integer {} is out of range for {}", value, target)
+            }
+            E::InvalidEnumRepr { expected, error } => {
+                let repr = match expected {
+                    VariantEnumRepr::ExternallyTagged => "externally tagged enum",
+                    VariantEnumRepr::InternallyTagged => "internally tagged enum",
+                    VariantEnumRepr::AdjacentlyTagged => "adjacently tagged enum",
+                    VariantEnumRepr::Int => "integer enum",
+                };
+                write!(f, "invalid {} representation: {}", repr, error)
+            }
+            E::InvalidStructRepr { expected, error } => {
+                let repr = match expected {
+                    VariantStructRepr::Unit => "unit struct",
+                    VariantStructRepr::Tuple => "tuple struct",
+                    VariantStructRepr::Struct => "struct",
+                };
+                write!(f, "invalid {} representation: {}", repr, error)
+            }
             E::UnknownEnumVariant { variant, expected } => {
                 write!(
                     f,
@@ -952,40 +1188,25 @@ impl fmt::Display for FromVariantError {
                 }
                 Ok(())
             }
-            E::InvalidEnumVariant { variant, error } => {
-                write!(f, "invalid value for variant {}: {}", variant, error)
-            }
             E::InvalidInstance { expected } => {
                 write!(f, "object is not an instance of NativeClass {}", expected)
             }
-            E::InvalidField { field_name, error } => {
-                write!(f, "invalid value for field {}", field_name)?;
-
-                let mut next_error = error.as_ref();
-                loop {
-                    match next_error {
-                        E::InvalidField { field_name, error } => {
-                            write!(f, ".{}", field_name)?;
-                            next_error = error.as_ref();
-                        }
-                        E::InvalidItem { index, error } => {
-                            write!(f, "[{}]", index)?;
-                            next_error = error.as_ref();
-                        }
-                        _ => {
-                            write!(f, ": {}", next_error)?;
-                            return Ok(());
-                        }
-                    }
+            E::InvalidEnumVariant { .. }
+            | E::InvalidField { .. }
+            | E::InvalidItem { .. }
+            | E::InvalidKey { .. }
+            | E::InvalidValue { .. } => {
+                for segment in self.path() {
+                    write!(f, "{}", segment)?;
                 }
-            }
-            E::InvalidItem { index, error } => {
-                write!(f, "invalid value for item at index {}: {}", index, error)
+                write!(f, ": {}", self.leaf())
             }
         }
     }
 }
 
+impl std::error::Error for FromVariantError {}
+
 impl ToVariant for () {
     fn to_variant(&self) -> Variant {
         Variant::new()
@@ -1018,7 +1239,6 @@ macro_rules! from_variant_direct {
 from_variant_direct!(
     impl FromVariant for f64 : VariantType::F64 => godot_variant_as_real;
     impl FromVariant for i64 : VariantType::I64 => godot_variant_as_int;
-    impl FromVariant for u64 : VariantType::I64 => godot_variant_as_uint;
     impl FromVariant for bool : VariantType::Bool => godot_variant_as_bool;
 );
 
@@ -1034,6 +1254,18 @@ impl ToVariant for u64 {
     }
 }
 
+impl FromVariant for u64 {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        use std::convert::TryFrom;
+
+        let value = i64::from_variant(variant)?;
+        u64::try_from(value).map_err(|_| FromVariantError::IntegerOutOfRange {
+            value,
+            target: "u64",
+        })
+    }
+}
+
 impl ToVariant for f64 {
     fn to_variant(&self) -> Variant {
         Variant::from_f64(*self)
@@ -1061,15 +1293,44 @@ macro_rules! impl_to_variant_for_num {
 }
 
 impl_to_variant_for_num!(
+    f32: f64
+);
+
+macro_rules! impl_to_variant_for_int {
+    (
+        $($ty:ident : $src_ty:ty)*
+    ) => {
+        $(
+            impl ToVariant for $ty {
+                fn to_variant(&self) -> Variant {
+                    ((*self) as $src_ty).to_variant()
+                }
+            }
+
+            impl FromVariant for $ty {
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    use std::convert::TryFrom;
+
+                    let value = <$src_ty>::from_variant(variant)?;
+                    $ty::try_from(value).map_err(|_| FromVariantError::IntegerOutOfRange {
+                        value: value as i64,
+                        target: stringify!($ty),
+                    })
+                }
+            }
+        )*
+    };
+}
+
+impl_to_variant_for_int!(
     i8: i64
     i16: i64
     i32: i64
     isize: i64
-    u8: u64
-    u16: u64
-    u32: u64
-    usize: u64
-    f32: f64
+    u8: i64
+    u16: i64
+    u32: i64
+    usize: i64
 );
 
 macro_rules! from_variant_transmute {
@@ -1308,7 +1569,75 @@ impl<T: FromVariant, E: FromVariant> FromVariant for Result<T, E> {
     }
 }
 
-impl<T: ToVariant> ToVariant for &[T] {
+impl ToVariant for str {
+    fn to_variant(&self) -> Variant {
+        Variant::from_str(self)
+    }
+}
+
+impl ToVariant for char {
+    fn to_variant(&self) -> Variant {
+        let mut buf = [0; 4];
+        Variant::from_str(self.encode_utf8(&mut buf))
+    }
+}
+
+impl FromVariant for char {
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        let s = String::from_variant(variant)?;
+        let mut chars = s.chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(FromVariantError::InvalidLength {
+                expected: 1,
+                len: s.chars().count(),
+            }),
+        }
+    }
+}
+
+impl<'a, T> ToVariant for Cow<'a, T>
+where
+    T: ToOwned + ToVariant + ?Sized,
+{
+    fn to_variant(&self) -> Variant {
+        (**self).to_variant()
+    }
+}
+
+impl<'a, T> FromVariant for Cow<'a, T>
+where
+    T: ToOwned + ?Sized,
+    T::Owned: FromVariant,
+{
+    fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+        T::Owned::from_variant(variant).map(Cow::Owned)
+    }
+}
+
+macro_rules! impl_variant_for_pointers {
+    (
+        $($ptr:ident)*
+    ) => {
+        $(
+            impl<T: ToVariant + ?Sized> ToVariant for $ptr<T> {
+                fn to_variant(&self) -> Variant {
+                    (**self).to_variant()
+                }
+            }
+
+            impl<T: FromVariant> FromVariant for $ptr<T> {
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    T::from_variant(variant).map($ptr::new)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_pointers!(Box Rc Arc);
+
+impl<T: ToVariant> ToVariant for [T] {
     fn to_variant(&self) -> Variant {
         let mut array = VariantArray::new();
         for val in self.iter() {
@@ -1319,16 +1648,67 @@ impl<T: ToVariant> ToVariant for &[T] {
     }
 }
 
+impl<T: ToVariant> ToVariant for &[T] {
+    fn to_variant(&self) -> Variant {
+        (**self).to_variant()
+    }
+}
+
+/// Converts a `Vec` to a `VariantArray`.
+///
+/// Vectors of pool array elements are not converted to pool arrays, because the impl can't
+/// be specialized. Use `ByteArray::from` or `Vector3Array::from` to create those directly.
 impl<T: ToVariant> ToVariant for Vec<T> {
     fn to_variant(&self) -> Variant {
         self.as_slice().to_variant()
     }
 }
 
+/// Converts a `VariantArray` or any pool array to a `Vec`.
 impl<T: FromVariant> FromVariant for Vec<T> {
     fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
         use std::convert::TryInto;
 
+        match variant.get_type() {
+            VariantType::ByteArray => {
+                return vec_from_pool(&ByteArray::from_variant(variant)?.read(), u8::to_variant)
+            }
+            VariantType::Int32Array => {
+                return vec_from_pool(&Int32Array::from_variant(variant)?.read(), i32::to_variant)
+            }
+            VariantType::Float32Array => {
+                return vec_from_pool(
+                    &Float32Array::from_variant(variant)?.read(),
+                    f32::to_variant,
+                )
+            }
+            VariantType::StringArray => {
+                return vec_from_pool(
+                    &StringArray::from_variant(variant)?.read(),
+                    GodotString::to_variant,
+                )
+            }
+            VariantType::Vector2Array => {
+                return vec_from_pool(
+                    &Vector2Array::from_variant(variant)?.read(),
+                    Vector2::to_variant,
+                )
+            }
+            VariantType::Vector3Array => {
+                return vec_from_pool(
+                    &Vector3Array::from_variant(variant)?.read(),
+                    Vector3::to_variant,
+                )
+            }
+            VariantType::ColorArray => {
+                return vec_from_pool(
+                    &ColorArray::from_variant(variant)?.read(),
+                    Variant::from_color,
+                )
+            }
+            _ => {}
+        }
+
         let arr = VariantArray::from_variant(variant)?;
         let len: usize = arr
             .len()
@@ -1347,6 +1727,119 @@ impl<T: FromVariant> FromVariant for Vec<T> {
     }
 }
 
+fn vec_from_pool<T, E>(
+    elements: &[E],
+    to_variant: impl Fn(&E) -> Variant,
+) -> Result<Vec<T>, FromVariantError>
+where
+    T: FromVariant,
+{
+    elements
+        .iter()
+        .enumerate()
+        .map(|(index, element)| {
+            T::from_variant(&to_variant(element)).map_err(|e| FromVariantError::InvalidItem {
+                index,
+                error: Box::new(e),
+            })
+        })
+        .collect()
+}
+
+macro_rules! impl_variant_for_maps {
+    (
+        $(
+            impl $map:ident < K $(: $($key_bound:path)+)?, V $(, $s:ident : $s_bound:path)? >;
+        )*
+    ) => {
+        $(
+            /// Converts the map to a `Dictionary`.
+            impl<K: ToVariant, V: ToVariant $(, $s)?> ToVariant for $map<K, V $(, $s)?> {
+                fn to_variant(&self) -> Variant {
+                    let mut dict = Dictionary::new();
+                    for (key, value) in self.iter() {
+                        dict.set(&key.to_variant(), &value.to_variant());
+                    }
+                    dict.to_variant()
+                }
+            }
+
+            /// Converts a `Dictionary` to the map. Errors are reported with the index of the
+            /// entry in the dictionary, as `InvalidKey` or `InvalidValue`.
+            impl<K, V $(, $s)?> FromVariant for $map<K, V $(, $s)?>
+            where
+                K: FromVariant $($(+ $key_bound)+)?,
+                V: FromVariant,
+                $($s: $s_bound + Default,)?
+            {
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    let dict = Dictionary::from_variant(variant)?;
+                    let keys = dict.keys();
+                    let mut map = Self::default();
+                    for (index, key) in keys.iter().enumerate() {
+                        let key_value = K::from_variant(key).map_err(|e| {
+                            FromVariantError::InvalidKey {
+                                index,
+                                error: Box::new(e),
+                            }
+                        })?;
+                        let value = V::from_variant(dict.get_ref(key)).map_err(|e| {
+                            FromVariantError::InvalidValue {
+                                index,
+                                error: Box::new(e),
+                            }
+                        })?;
+                        map.insert(key_value, value);
+                    }
+                    Ok(map)
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_maps!(
+    impl HashMap<K: Eq Hash, V, S: BuildHasher>;
+    impl BTreeMap<K: Ord, V>;
+);
+
+macro_rules! impl_variant_for_sets {
+    (
+        $(
+            impl $set:ident < T $(: $($item_bound:path)+)? $(, $s:ident : $s_bound:path)? >;
+        )*
+    ) => {
+        $(
+            /// Converts the set to a `VariantArray`.
+            impl<T: ToVariant $(, $s)?> ToVariant for $set<T $(, $s)?> {
+                fn to_variant(&self) -> Variant {
+                    let mut array = VariantArray::new();
+                    for val in self.iter() {
+                        array.push(&val.to_variant());
+                    }
+                    array.to_variant()
+                }
+            }
+
+            /// Converts a `VariantArray` or any pool array to the set. Duplicates are ignored.
+            impl<T $(, $s)?> FromVariant for $set<T $(, $s)?>
+            where
+                T: FromVariant $($(+ $item_bound)+)?,
+                $($s: $s_bound + Default,)?
+            {
+                fn from_variant(variant: &Variant) -> Result<Self, FromVariantError> {
+                    Vec::<T>::from_variant(variant).map(|vec| vec.into_iter().collect())
+                }
+            }
+        )*
+    };
+}
+
+impl_variant_for_sets!(
+    impl HashSet<T: Eq Hash, S: BuildHasher>;
+    impl BTreeSet<T: Ord>;
+);
+
 macro_rules! tuple_length {
     () => { 0usize };
     ($_x:ident, $($xs:ident,)*) => {
@@ -1406,6 +1899,50 @@ macro_rules! impl_variant_for_tuples {
 
 impl_variant_for_tuples!(T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12,);
 
+macro_rules! impl_variant_for_arrays_next {
+    () => {};
+    ($_x:ident, $($xs:ident,)*) => {
+        impl_variant_for_arrays!($($xs,)*);
+    }
+}
+
+// Arrays are implemented up to a length of 32, like the traits of the standard library.
+macro_rules! impl_variant_for_arrays {
+    ( $($name:ident,)* ) => {
+        impl<T: ToVariant> ToVariant for [T; tuple_length!($($name,)*)] {
+            fn to_variant(&self) -> Variant {
+                self[..].to_variant()
+            }
+        }
+
+        impl<T: FromVariant> FromVariant for [T; tuple_length!($($name,)*)] {
+            #[allow(non_snake_case, unused_mut, unused_variables)]
+            fn from_variant(v: &Variant) -> Result<Self, FromVariantError> {
+                let expected = tuple_length!($($name,)*);
+                let vec = Vec::<T>::from_variant(v)?;
+                let len = vec.len();
+                if len != expected {
+                    return Err(FromVariantError::InvalidLength { expected, len });
+                }
+
+                let mut iter = vec.into_iter();
+                $(
+                    let $name = iter.next().unwrap();
+                )*
+
+                Ok([$($name,)*])
+            }
+        }
+
+        impl_variant_for_arrays_next!($($name,)*);
+    };
+}
+
+impl_variant_for_arrays!(
+    T1, T2, T3, T4, T5, T6, T7, T8, T9, T10, T11, T12, T13, T14, T15, T16, T17, T18, T19, T20, T21,
+    T22, T23, T24, T25, T26, T27, T28, T29, T30, T31, T32,
+);
+
 godot_test!(
     test_variant_option {
         use std::marker::PhantomData;
@@ -1513,6 +2050,92 @@ godot_test!(
         assert_eq!(Some(&f64::to_variant(&54.0)), vec_maybe[2].as_ref().err());
     }
 
+    test_variant_evaluate {
+        use VariantOperator as Op;
+
+        assert_eq!(
+            sys::godot_variant_operator_GODOT_VARIANT_OP_IN,
+            Op::In as u32 as sys::godot_variant_operator
+        );
+        assert_eq!(
+            sys::godot_variant_operator_GODOT_VARIANT_OP_MAX,
+            Op::Max as u32 as sys::godot_variant_operator
+        );
+
+        let int = |i: i64| Variant::from_i64(i);
+        let real = |r: f64| Variant::from_f64(r);
+        let eval = |op, a: &Variant, b: &Variant| Variant::evaluate(op, a, b);
+
+        assert_eq!(Some(VariantType::I64), eval(Op::Add, &int(1), &int(2)).map(|v| v.get_type()));
+        assert_eq!(Some(int(3)), eval(Op::Add, &int(1), &int(2)));
+        assert_eq!(Some(VariantType::F64), eval(Op::Add, &int(1), &real(2.5)).map(|v| v.get_type()));
+        assert_eq!(Some(real(3.5)), eval(Op::Add, &int(1), &real(2.5)));
+        assert_eq!(Some(int(3)), eval(Op::Divide, &int(7), &int(2)));
+        assert_eq!(Some(real(3.5)), eval(Op::Divide, &int(7), &real(2.0)));
+        assert_eq!(None, eval(Op::Divide, &int(1), &int(0)));
+        assert_eq!(Some(int(-1)), eval(Op::Module, &int(-7), &int(2)));
+        assert_eq!(None, eval(Op::Add, &int(1), &"1".into()));
+        assert_eq!(None, eval(Op::Add, &Variant::from_bool(true), &int(1)));
+        assert_eq!(None, eval(Op::Max, &int(1), &int(1)));
+
+        assert_eq!(
+            Some(Variant::from_vector2(&Vector2::new(2.0, 4.0))),
+            eval(Op::Multiply, &Variant::from_vector2(&Vector2::new(1.0, 2.0)), &int(2))
+        );
+        assert_eq!(
+            Some(Variant::from_vector3(&Vector3::new(0.5, 1.0, 1.5))),
+            eval(Op::Divide, &Variant::from_vector3(&Vector3::new(1.0, 2.0, 3.0)), &real(2.0))
+        );
+
+        assert_eq!(Some("ab".into()), eval(Op::Add, &"a".into(), &"b".into()));
+        assert_eq!(Some("a1".into()), eval(Op::Concat, &"a".into(), &int(1)));
+        assert_eq!(Some("x=5".into()), eval(Op::Module, &"x=%d".into(), &int(5)));
+        assert_eq!(Some("01.23, ff".into()), eval(Op::Module, &"%05.2f, %x".into(), &(1.23456, 255).to_variant()));
+        assert_eq!(Some("[  a|b  ]".into()), eval(Op::Module, &"[%3s|%-3s]".into(), &(String::from("a"), String::from("b")).to_variant()));
+        assert_eq!(None, eval(Op::Module, &"%d %d".into(), &int(5)));
+        assert_eq!(None, eval(Op::Module, &"%d".into(), &"five".into()));
+
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::Equal, &int(1), &real(1.0)));
+        assert_eq!(Some(Variant::from_bool(false)), eval(Op::Equal, &Variant::new(), &int(1)));
+        assert_eq!(None, eval(Op::Equal, &int(1), &"1".into()));
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::Less, &"a".into(), &"b".into()));
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::GreaterEqual, &int(2), &real(1.5)));
+
+        let array = vec![int(1), int(2)].to_variant();
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::In, &real(2.0), &array));
+        assert_eq!(Some(Variant::from_bool(false)), eval(Op::In, &int(3), &array));
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::In, &"ell".into(), &"hello".into()));
+        let mut dict = Dictionary::new();
+        dict.set(&"k".into(), &int(1));
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::In, &"k".into(), &dict.to_variant()));
+
+        assert_eq!(Some(int(8)), eval(Op::ShiftLeft, &int(1), &int(3)));
+        assert_eq!(None, eval(Op::ShiftLeft, &int(1), &int(-1)));
+        assert_eq!(Some(int(!5)), eval(Op::BitNegate, &int(5), &Variant::new()));
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::Not, &"".into(), &Variant::new()));
+        assert_eq!(Some(Variant::from_bool(true)), eval(Op::Xor, &int(0), &"a".into()));
+    }
+
+    test_variant_ops {
+        let int = |i: i64| Variant::from_i64(i);
+
+        assert_eq!(int(7), int(1) + int(2) * int(3));
+        assert_eq!(Variant::from_f64(-0.5), -(&int(1) / &Variant::from_f64(2.0)));
+        assert_eq!(Variant::from_bool(true), !Variant::new());
+        assert_eq!(int(6), int(3) << int(1));
+        assert_eq!(Variant::from_str("2 + 2 = 4"), Variant::from_str("%d + %d = %d") % (2, 2, 4).to_variant());
+
+        assert!(int(1) < Variant::from_f64(1.5));
+        assert!(Variant::from_str("b") > Variant::from_str("a"));
+        assert_eq!(Some(std::cmp::Ordering::Equal), int(1).partial_cmp(&int(1)));
+        assert_ne!(int(1), Variant::from_f64(1.0));
+        assert_eq!(None, int(1).partial_cmp(&Variant::from_f64(1.0)));
+        assert_eq!(None, int(1).partial_cmp(&Variant::from_str("1")));
+
+        let panicked = std::panic::catch_unwind(|| Variant::from_i64(1) / Variant::from_i64(0));
+        assert!(panicked.is_err());
+    }
+
     test_variant_tuple {
         let variant = (42i64, 54i64).to_variant();
         let arr = variant.try_to_array().expect("should be array");
@@ -1522,4 +2145,118 @@ godot_test!(
         let tuple = <(i64, i64)>::from_variant(&variant);
         assert_eq!(Ok((42, 54)), tuple);
     }
+
+    test_variant_std_types {
+        use std::borrow::Cow;
+        use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+        use std::rc::Rc;
+        use std::sync::Arc;
+
+        let mut map = HashMap::new();
+        map.insert(String::from("a"), 1i64);
+        map.insert(String::from("b"), 2i64);
+        let variant = map.to_variant();
+        let dict = variant.try_to_dictionary().expect("should be dictionary");
+        assert_eq!(Some(2), dict.get_ref(&"b".into()).try_to_i64());
+        assert_eq!(Ok(map), HashMap::from_variant(&variant));
+
+        let tree: BTreeMap<i64, bool> = vec![(1, true), (2, false)].into_iter().collect();
+        assert_eq!(Ok(tree.clone()), BTreeMap::from_variant(&tree.to_variant()));
+        let bad: BTreeMap<i64, i64> = vec![(1, 1)].into_iter().collect();
+        assert_eq!(
+            Err(FromVariantError::InvalidValue {
+                index: 0,
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    expected: VariantType::Bool,
+                    variant_type: VariantType::I64,
+                }),
+            }),
+            BTreeMap::<i64, bool>::from_variant(&bad.to_variant()),
+        );
+        let err = BTreeMap::<bool, i64>::from_variant(&bad.to_variant()).unwrap_err();
+        assert_eq!(vec![FromVariantPathSegment::Key(0)], err.path());
+        assert_eq!("[key 0]: expected Bool, got I64", err.to_string());
+
+        let set: HashSet<i64> = vec![1, 2, 3].into_iter().collect();
+        assert_eq!(3, set.to_variant().try_to_array().expect("should be array").len());
+        assert_eq!(Ok(set.clone()), HashSet::from_variant(&set.to_variant()));
+        let set: BTreeSet<i64> = BTreeSet::from_variant(&[1i64, 1, 2].to_variant()).unwrap();
+        assert_eq!(vec![1, 2], set.into_iter().collect::<Vec<_>>());
+
+        assert_eq!(Ok([1i64, 2, 3]), <[i64; 3]>::from_variant(&[1i64, 2, 3].to_variant()));
+        assert_eq!(Ok([]), <[i64; 0]>::from_variant(&VariantArray::new().to_variant()));
+        assert_eq!(Ok([1u8, 2]), <[u8; 2]>::from_variant(&ByteArray::from(vec![1u8, 2]).to_variant()));
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { expected: 2, len: 3 }),
+            <[i64; 2]>::from_variant(&[1i64, 2, 3].to_variant()),
+        );
+
+        assert_eq!(Ok(Box::new(42i64)), Box::from_variant(&Box::new(42i64).to_variant()));
+        assert_eq!(Ok(Rc::new(42i64)), Rc::from_variant(&Rc::new(42i64).to_variant()));
+        assert_eq!(Ok(Arc::new(42i64)), Arc::from_variant(&Arc::new(42i64).to_variant()));
+        let boxed: Box<str> = "boxed".into();
+        assert_eq!(Variant::from_str("boxed"), boxed.to_variant());
+
+        let cow: Cow<str> = Cow::Borrowed("cow");
+        assert_eq!(Variant::from_str("cow"), cow.to_variant());
+        assert_eq!(Ok(cow), Cow::<str>::from_variant(&Variant::from_str("cow")));
+
+        assert_eq!(Variant::from_str("é"), 'é'.to_variant());
+        assert_eq!(Ok('é'), char::from_variant(&Variant::from_str("é")));
+        assert_eq!(
+            Err(FromVariantError::InvalidLength { expected: 1, len: 2 }),
+            char::from_variant(&Variant::from_str("ab")),
+        );
+
+        assert_eq!(Ok(255), u8::from_variant(&Variant::from_i64(255)));
+        assert_eq!(
+            Err(FromVariantError::IntegerOutOfRange { value: 256, target: "u8" }),
+            u8::from_variant(&Variant::from_i64(256)),
+        );
+        assert_eq!(
+            Err(FromVariantError::IntegerOutOfRange { value: -1, target: "u32" }),
+            u32::from_variant(&Variant::from_i64(-1)),
+        );
+        assert_eq!(Ok(i32::MIN), i32::from_variant(&i32::MIN.to_variant()));
+        assert_eq!(Ok(u32::MAX as usize), usize::from_variant(&(u32::MAX as usize).to_variant()));
+        assert_eq!(
+            Err(FromVariantError::IntegerOutOfRange { value: -1, target: "usize" }),
+            usize::from_variant(&Variant::from_i64(-1)),
+        );
+        assert_eq!(
+            Err(FromVariantError::IntegerOutOfRange { value: -1, target: "u64" }),
+            u64::from_variant(&Variant::from_i64(-1)),
+        );
+
+        let bytes = ByteArray::from(vec![1u8, 2, 3]);
+        assert_eq!(&[1, 2, 3], &*bytes.read());
+        assert_eq!(Ok(vec![1u8, 2, 3]), Vec::from_variant(&bytes.to_variant()));
+        assert_eq!(Ok(vec![1i64, 2, 3]), Vec::from_variant(&bytes.to_variant()));
+
+        let vectors = vec![Vector3::new(1.0, 2.0, 3.0), Vector3::new(4.0, 5.0, 6.0)];
+        let pool = Vector3Array::from(vectors.as_slice());
+        assert_eq!(2, pool.len());
+        assert_eq!(Ok(vectors.clone()), Vec::from_variant(&pool.to_variant()));
+
+        let pool_vec = PoolVec(vectors);
+        let variant = pool_vec.to_variant();
+        assert_eq!(VariantType::Vector3Array, variant.get_type());
+        assert_eq!(Ok(pool_vec), PoolVec::from_variant(&variant));
+        assert_eq!(VariantType::ByteArray, PoolVec(vec![1u8, 2, 3]).to_variant().get_type());
+        assert_eq!(
+            Ok(PoolVec(vec![1u8, 2, 3])),
+            PoolVec::from_variant(&bytes.to_variant()),
+        );
+        assert!(PoolVec::<u8>::from_variant(&vec![1u8].to_variant()).is_err());
+        assert_eq!(
+            Err(FromVariantError::InvalidItem {
+                index: 0,
+                error: Box::new(FromVariantError::InvalidVariantType {
+                    expected: VariantType::I64,
+                    variant_type: VariantType::Vector3,
+                }),
+            }),
+            Vec::<i64>::from_variant(&pool.to_variant()),
+        );
+    }
 );
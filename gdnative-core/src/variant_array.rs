@@ -1,10 +1,15 @@
 use crate::get_api;
 use crate::sys;
+use crate::GodotObject;
+use crate::IntoName;
 use crate::ToVariant;
 use crate::Variant;
+use std::ops::{Index, IndexMut};
 
 /// A reference-counted `Variant` vector. Godot's generic array data type.
-/// Negative indices can be used to count from the right.
+///
+/// Unlike in GDScript, negative indices don't count from the right: they are out of bounds like
+/// indices past the end. Element accessors panic on them, while `get` returns `None`.
 pub struct VariantArray(pub(crate) sys::godot_array);
 
 impl VariantArray {
@@ -14,27 +19,67 @@ impl VariantArray {
     }
 
     /// Sets the value of the element at the given offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
     pub fn set(&mut self, idx: i32, val: &Variant) {
+        self.check_bounds(idx);
         unsafe { (get_api().godot_array_set)(&mut self.0, idx, &val.0) }
     }
 
+    /// Returns a copy of the element at the given offset, or `None` if it is out of bounds.
+    pub fn get(&self, idx: i32) -> Option<Variant> {
+        if idx >= 0 && idx < self.len() {
+            Some(self.get_val(idx))
+        } else {
+            None
+        }
+    }
+
     /// Returns a copy of the element at the given offset.
-    pub fn get_val(&mut self, idx: i32) -> Variant {
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
+    pub fn get_val(&self, idx: i32) -> Variant {
+        self.check_bounds(idx);
         unsafe { Variant((get_api().godot_array_get)(&self.0, idx)) }
     }
 
     /// Returns a reference to the element at the given offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
     pub fn get_ref(&self, idx: i32) -> &Variant {
+        self.check_bounds(idx);
         unsafe { Variant::cast_ref((get_api().godot_array_operator_index_const)(&self.0, idx)) }
     }
 
     /// Returns a mutable reference to the element at the given offset.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
     pub fn get_mut_ref(&mut self, idx: i32) -> &mut Variant {
+        self.check_bounds(idx);
         unsafe { Variant::cast_mut_ref((get_api().godot_array_operator_index)(&mut self.0, idx)) }
     }
 
-    pub fn count(&mut self, val: &Variant) -> i32 {
-        unsafe { (get_api().godot_array_count)(&mut self.0, &val.0) }
+    fn check_bounds(&self, idx: i32) {
+        let len = self.len();
+        assert!(
+            idx >= 0 && idx < len,
+            "index out of bounds: the len is {} but the index is {}",
+            len,
+            idx
+        );
+    }
+
+    /// Returns the number of times `val` appears in the array.
+    pub fn count(&self, val: &Variant) -> i32 {
+        unsafe { (get_api().godot_array_count)(&self.0, &val.0) }
     }
 
     /// Clears the array, resizing to 0.
@@ -44,7 +89,13 @@ impl VariantArray {
         }
     }
 
+    /// Removes the element at the given offset, shifting the following elements to the left.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `idx` is out of bounds.
     pub fn remove(&mut self, idx: i32) {
+        self.check_bounds(idx);
         unsafe { (get_api().godot_array_remove)(&mut self.0, idx) }
     }
 
@@ -134,20 +185,39 @@ impl VariantArray {
         unsafe { (get_api().godot_array_sort)(&mut self.0) }
     }
 
-    // TODO
-    // pub fn sort_custom(&mut self, obj: ?, s: ?) {
-    //     unimplemented!()
-    // }
+    /// Sorts the array with the method `func` of `obj`, which is called with two elements and
+    /// returns `true` if the first one is less than the second one.
+    pub fn sort_custom<O: GodotObject>(&mut self, obj: &O, func: impl IntoName) {
+        let func = func.into_name();
+        unsafe { (get_api().godot_array_sort_custom)(&mut self.0, obj.to_sys(), &func.0) }
+    }
 
-    // pub fn bsearch(&mut self, val: (), before: bool) -> i32 {
-    //     unsafe {
-    //         (get_api().godot_array_bsearch)(&mut self.0, val, before)
-    //     }
-    // }
+    /// Finds the index at which `val` would be inserted in the sorted array, using a binary
+    /// search. If `before` is `true`, the index is before all elements equal to `val`.
+    pub fn bsearch(&self, val: &Variant, before: bool) -> i32 {
+        // The engine does not modify the array, but the function takes a mutable pointer.
+        unsafe { (get_api().godot_array_bsearch)(self.sys() as *mut _, &val.0, before) }
+    }
 
-    // pub fn bsearch_custom(&mut self, val: ?, obj: ?, s: ?, before: bool) -> i32 {
-    //     unimplemented!();
-    // }
+    /// Like `bsearch`, for an array sorted with `sort_custom` and the same `obj` and `func`.
+    pub fn bsearch_custom<O: GodotObject>(
+        &self,
+        val: &Variant,
+        obj: &O,
+        func: impl IntoName,
+        before: bool,
+    ) -> i32 {
+        let func = func.into_name();
+        unsafe {
+            (get_api().godot_array_bsearch_custom)(
+                self.sys() as *mut _,
+                &val.0,
+                obj.to_sys(),
+                &func.0,
+                before,
+            )
+        }
+    }
 
     pub fn iter(&self) -> Iter {
         Iter {
@@ -193,6 +263,20 @@ impl ToVariant for VariantArray {
     }
 }
 
+impl Index<i32> for VariantArray {
+    type Output = Variant;
+
+    fn index(&self, idx: i32) -> &Variant {
+        self.get_ref(idx)
+    }
+}
+
+impl IndexMut<i32> for VariantArray {
+    fn index_mut(&mut self, idx: i32) -> &mut Variant {
+        self.get_mut_ref(idx)
+    }
+}
+
 pub struct Iter<'a> {
     arr: &'a VariantArray,
     range: std::ops::Range<i32>,
@@ -308,6 +392,25 @@ godot_test!(test_array {
         &[54, 54, 54],
         array3.iter().map(|v| v.try_to_i64().unwrap()).collect::<Vec<_>>().as_slice(),
     );
+
+    assert_eq!(array3.get(2), Some(Variant::from_i64(54)));
+    assert_eq!(array3.get(3), None);
+    assert_eq!(array3.get(-1), None);
+    assert_eq!(array3.count(&Variant::from_i64(54)), 3);
+
+    array3[1] = Variant::from_i64(7);
+    assert_eq!(array3[1], Variant::from_i64(7));
+
+    let out_of_bounds = std::panic::catch_unwind(|| array3.get_ref(3).clone());
+    assert!(out_of_bounds.is_err());
+
+    let mut sorted = VariantArray::new();
+    for i in &[1, 3, 3, 5] {
+        sorted.push(&Variant::from_i64(*i));
+    }
+    assert_eq!(sorted.bsearch(&Variant::from_i64(3), true), 1);
+    assert_eq!(sorted.bsearch(&Variant::from_i64(3), false), 3);
+    assert_eq!(sorted.bsearch(&Variant::from_i64(0), true), 0);
 });
 
 // TODO: clear arrays without affecting clones
@@ -1,3 +1,4 @@
+use crate::geom::CMP_EPSILON;
 use crate::{Angle, Rotation2D, Vector2};
 use crate::{ToVariant, Variant};
 
@@ -12,12 +13,22 @@ impl ToVariant for Vector2 {
 /// Trait used to provide additional methods that are equivalent to Godot's methods.
 /// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.1/classes/class_vector2.html).
 pub trait Vector2Godot {
+    /// Returns the angle between the line connecting the two points and the x coordinate.
+    fn angle_to_point(self, point: Self) -> Angle;
     /// Returns the ratio of x to y.
     fn aspect(self) -> f32;
+    /// Returns the vector bounced off a plane defined by the given normal. This is the same as
+    /// `Vector2::reflect`.
+    fn bounce(self, normal: Self) -> Self;
     /// Cubicly interpolates between this vector and `b` using `pre_a` and `post_b` as handles,
     /// and returns the result at position `t`. `t` is in the range of 0.0 - 1.0, representing
     /// the amount of interpolation.
     fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self;
+    /// Returns the vector reflected from a plane defined by the given normal, like `reflect`
+    /// in GDScript. Note that `Vector2::reflect` returns the opposite vector.
+    fn godot_reflect(self, normal: Self) -> Self;
+    /// Returns the vector moved towards `to` by at most `delta`.
+    fn move_toward(self, to: Self, delta: f32) -> Self;
     /// Returns the vector rotated by `angle` radians.
     fn rotated(self, angle: Angle) -> Self;
     /// Returns the component of the vector along a plane defined by the given normal.
@@ -29,11 +40,21 @@ pub trait Vector2Godot {
 }
 
 impl Vector2Godot for Vector2 {
+    #[inline]
+    fn angle_to_point(self, point: Self) -> Angle {
+        Angle::radians((self.y - point.y).atan2(self.x - point.x))
+    }
+
     #[inline]
     fn aspect(self) -> f32 {
         self.x / self.y
     }
 
+    #[inline]
+    fn bounce(self, normal: Self) -> Self {
+        -self.godot_reflect(normal)
+    }
+
     #[inline]
     fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self {
         let v0 = pre_a;
@@ -51,6 +72,22 @@ impl Vector2Godot for Vector2 {
             * 0.5
     }
 
+    #[inline]
+    fn godot_reflect(self, normal: Self) -> Self {
+        normal * 2.0 * self.dot(normal) - self
+    }
+
+    #[inline]
+    fn move_toward(self, to: Self, delta: f32) -> Self {
+        let vd = to - self;
+        let len = vd.length();
+        if len <= delta || len < CMP_EPSILON {
+            to
+        } else {
+            self + vd / len * delta
+        }
+    }
+
     #[inline]
     fn rotated(self, angle: Angle) -> Self {
         let r = Rotation2D::new(angle);
@@ -204,4 +241,63 @@ mod tests {
             assert!(expected.approx_eq(&v.snapped(by)));
         }
     }
+
+    #[test]
+    fn reflect_and_bounce_match_engine() {
+        use euclid::approxeq::ApproxEq;
+        use Vector2 as V;
+
+        let normal = V::new(0.0, 1.0);
+        assert!(V::new(-1.0, 2.0).approx_eq(&V::new(1.0, 2.0).godot_reflect(normal)));
+        assert!(V::new(1.0, -2.0).approx_eq(&V::new(1.0, 2.0).bounce(normal)));
+        assert!(V::new(1.0, 2.0)
+            .reflect(normal)
+            .approx_eq(&V::new(1.0, 2.0).bounce(normal)));
+    }
+
+    #[test]
+    fn angle_to_point_is_sane() {
+        use Vector2 as V;
+
+        let angle = V::new(1.0, 1.0).angle_to_point(V::new(0.0, 0.0));
+        assert!((angle.radians - std::f32::consts::FRAC_PI_4).abs() < 1e-6);
+        let angle = V::new(0.0, 0.0).angle_to_point(V::new(1.0, 0.0));
+        assert!((angle.radians - std::f32::consts::PI).abs() < 1e-6);
+    }
+
+    #[test]
+    fn move_toward_is_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector2 as V;
+
+        let from = V::new(1.0, 1.0);
+        assert!(V::new(1.0, 3.0).approx_eq(&from.move_toward(V::new(1.0, 5.0), 2.0)));
+        assert_eq!(V::new(1.0, 5.0), from.move_toward(V::new(1.0, 5.0), 10.0));
+        assert_eq!(from, from.move_toward(from, 1.0));
+    }
+
+    // Expected values are worked out from the Godot 3.2 implementation in
+    // `core/math/vector2.cpp`, using the same formulas as the engine.
+    #[test]
+    fn matches_godot_3_2() {
+        use euclid::approxeq::ApproxEq;
+        use Vector2 as V;
+
+        // Vector2(3, 4).reflect(Vector2(-3, 1).normalized()) and .bounce(...)
+        let normal = V::new(-3.0, 1.0).normalize();
+        assert!(V::new(0.0, -5.0).approx_eq(&V::new(3.0, 4.0).godot_reflect(normal)));
+        assert!(V::new(0.0, 5.0).approx_eq(&V::new(3.0, 4.0).bounce(normal)));
+
+        // Vector2(1, 2).angle_to_point(Vector2(4, -2)) == atan2(4, -3)
+        let angle = V::new(1.0, 2.0).angle_to_point(V::new(4.0, -2.0));
+        assert!((angle.radians - 2.214_297_4).abs() < 1e-6);
+
+        // Vector2(1, 1).move_toward(Vector2(4, 5), 2)
+        assert!(V::new(2.2, 2.6).approx_eq(&V::new(1.0, 1.0).move_toward(V::new(4.0, 5.0), 2.0)));
+
+        // Vector2(3, 4).rotated(PI / 2) and .tangent()
+        let rotated = V::new(3.0, 4.0).rotated(crate::Angle::radians(std::f32::consts::FRAC_PI_2));
+        assert!(V::new(-4.0, 3.0).approx_eq(&rotated));
+        assert_eq!(V::new(4.0, -3.0), V::new(3.0, 4.0).tangent());
+    }
 }
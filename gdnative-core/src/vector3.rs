@@ -1,3 +1,5 @@
+use crate::geom::CMP_EPSILON;
+use crate::Basis;
 use crate::ToVariant;
 use crate::Variant;
 use crate::Vector3;
@@ -8,6 +10,93 @@ impl ToVariant for Vector3 {
     }
 }
 
+/// Helper methods for `Vector3`.
+///
+/// Trait used to provide additional methods that are equivalent to Godot's methods.
+/// See the official [`Godot documentation`](https://docs.godotengine.org/en/3.1/classes/class_vector3.html).
+pub trait Vector3Godot {
+    /// Returns the vector bounced off a plane defined by the given normal. This is the same as
+    /// `Vector3::reflect`.
+    fn bounce(self, normal: Self) -> Self;
+    /// Cubicly interpolates between this vector and `b` using `pre_a` and `post_b` as handles,
+    /// and returns the result at position `t`. `t` is in the range of 0.0 - 1.0, representing
+    /// the amount of interpolation.
+    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self;
+    /// Returns the vector reflected from a plane defined by the given normal, like `reflect`
+    /// in GDScript. Note that `Vector3::reflect` returns the opposite vector.
+    fn godot_reflect(self, normal: Self) -> Self;
+    /// Returns the vector moved towards `to` by at most `delta`.
+    fn move_toward(self, to: Self, delta: f32) -> Self;
+    /// Returns the vector rotated by `phi` radians around `axis`, which must be normalized.
+    fn rotated(self, axis: Self, phi: f32) -> Self;
+    /// Returns the component of the vector along a plane defined by the given normal.
+    fn slide(self, normal: Self) -> Self;
+    /// Returns the vector snapped to a grid with the given size.
+    fn snapped(self, by: Self) -> Self;
+}
+
+impl Vector3Godot for Vector3 {
+    #[inline]
+    fn bounce(self, normal: Self) -> Self {
+        -self.godot_reflect(normal)
+    }
+
+    #[inline]
+    fn cubic_interpolate(self, b: Self, pre_a: Self, post_b: Self, t: f32) -> Self {
+        let v0 = pre_a;
+        let v1 = self;
+        let v2 = b;
+        let v3 = post_b;
+
+        let t2 = t * t;
+        let t3 = t2 * t;
+
+        ((v1 * 2.0)
+            + (-v0 + v2) * t
+            + (v0 * 2.0 - v1 * 5.0 + v2 * 4.0 - v3) * t2
+            + (-v0 + v1 * 3.0 - v2 * 3.0 + v3) * t3)
+            * 0.5
+    }
+
+    #[inline]
+    fn godot_reflect(self, normal: Self) -> Self {
+        normal * 2.0 * self.dot(normal) - self
+    }
+
+    #[inline]
+    fn move_toward(self, to: Self, delta: f32) -> Self {
+        let vd = to - self;
+        let len = vd.length();
+        if len <= delta || len < CMP_EPSILON {
+            to
+        } else {
+            self + vd / len * delta
+        }
+    }
+
+    #[inline]
+    fn rotated(self, axis: Self, phi: f32) -> Self {
+        Basis::from_axis_angle(axis, phi).xform(self)
+    }
+
+    #[inline]
+    fn slide(self, normal: Self) -> Self {
+        self - normal * self.dot(normal)
+    }
+
+    #[inline]
+    fn snapped(self, by: Self) -> Self {
+        let snap = |v: f32, by: f32| {
+            if by != 0.0 {
+                (v / by + 0.5).floor() * by
+            } else {
+                v
+            }
+        };
+        Vector3::new(snap(self.x, by.x), snap(self.y, by.y), snap(self.z, by.z))
+    }
+}
+
 godot_test!(
     test_vector3_variants {
         fn test(vector: Vector3, set_to: Vector3) {
@@ -64,7 +153,7 @@ godot_test!(
 
 #[cfg(test)]
 mod tests {
-    use super::Vector3;
+    use super::{Vector3, Vector3Godot};
 
     #[test]
     fn it_is_copy() {
@@ -87,4 +176,62 @@ mod tests {
     fn it_supports_inequality() {
         assert_ne!(Vector3::new(1.0, 10.0, 100.0), Vector3::new(1.0, 2.0, 3.0));
     }
+
+    #[test]
+    fn reflect_and_slide_are_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector3 as V;
+
+        let v = V::new(1.0, 2.0, 3.0);
+        let normal = V::new(0.0, 1.0, 0.0);
+        assert!(V::new(-1.0, 2.0, -3.0).approx_eq(&v.godot_reflect(normal)));
+        assert!(V::new(1.0, -2.0, 3.0).approx_eq(&v.bounce(normal)));
+        assert!(V::new(1.0, 0.0, 3.0).approx_eq(&v.slide(normal)));
+    }
+
+    #[test]
+    fn rotated_and_snapped_are_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector3 as V;
+
+        let v = V::new(1.0, 0.0, 0.0).rotated(V::new(0.0, 1.0, 0.0), std::f32::consts::FRAC_PI_2);
+        assert!((v - V::new(0.0, 0.0, -1.0)).length() < 1e-6);
+
+        let v = V::new(1.5, 5.6, -6.8).snapped(V::new(1.0, 4.0, 0.3));
+        assert!(V::new(2.0, 4.0, -6.9).approx_eq(&v));
+    }
+
+    #[test]
+    fn move_toward_is_sane() {
+        use euclid::approxeq::ApproxEq;
+        use Vector3 as V;
+
+        let from = V::new(1.0, 1.0, 1.0);
+        let to = V::new(1.0, 1.0, 5.0);
+        assert!(V::new(1.0, 1.0, 3.0).approx_eq(&from.move_toward(to, 2.0)));
+        assert_eq!(to, from.move_toward(to, 10.0));
+    }
+
+    // Expected values are worked out from the Godot 3.2 implementation in `core/math/vector3.h`,
+    // using the same formulas as the engine.
+    #[test]
+    fn matches_godot_3_2() {
+        use euclid::approxeq::ApproxEq;
+        use Vector3 as V;
+
+        // Vector3(1, 2, 3).reflect(Vector3(0, 1, 1).normalized()), .bounce(...) and .slide(...)
+        let v = V::new(1.0, 2.0, 3.0);
+        let normal = V::new(0.0, 1.0, 1.0).normalize();
+        assert!(V::new(-1.0, 3.0, 2.0).approx_eq(&v.godot_reflect(normal)));
+        assert!(V::new(1.0, -3.0, -2.0).approx_eq(&v.bounce(normal)));
+        assert!(V::new(1.0, -0.5, 0.5).approx_eq(&v.slide(normal)));
+
+        // Vector3(1, 2, 3).rotated(Vector3(0, 0, 1), PI / 2)
+        let rotated = v.rotated(V::new(0.0, 0.0, 1.0), std::f32::consts::FRAC_PI_2);
+        assert!((rotated - V::new(-2.0, 1.0, 3.0)).length() < 1e-6);
+
+        // Vector3(1, 1, 1).move_toward(Vector3(3, -1, 2), 1.5)
+        let moved = V::new(1.0, 1.0, 1.0).move_toward(V::new(3.0, -1.0, 2.0), 1.5);
+        assert!(V::new(2.0, 0.0, 1.5).approx_eq(&moved));
+    }
 }
@@ -5,37 +5,99 @@ use proc_macro2::{Literal, Span, TokenStream as TokenStream2};
 use syn::visit::{self, Visit};
 use syn::{Data, DeriveInput, Fields, Generics, Ident, Type, TypePath};
 
+use crate::method_macro::push_error;
+
+mod variant_args;
+use variant_args::{ContainerAttrs, EnumRepr, FieldAttrs, FieldDefault, VariantAttrs};
+
 pub(crate) struct DeriveData {
     pub(crate) ident: Ident,
     pub(crate) repr: Repr,
     pub(crate) generics: Generics,
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) enum Repr {
     Struct(VariantRepr),
-    Enum(Vec<(Ident, VariantRepr)>),
+    Enum(EnumRepr, Vec<EnumVariant>),
+}
+
+pub(crate) struct EnumVariant {
+    pub(crate) ident: Ident,
+    /// Name of the variant in variants, which is its identifier unless renamed.
+    pub(crate) name: String,
+    pub(crate) repr: VariantRepr,
+}
+
+pub(crate) struct Field {
+    pub(crate) ident: Ident,
+    pub(crate) ty: Type,
+    pub(crate) attrs: FieldAttrs,
 }
 
-#[derive(Clone, Eq, PartialEq, Debug)]
 pub(crate) enum VariantRepr {
     Unit,
-    Struct(Vec<(Ident, Type)>),
-    Tuple(Vec<(Ident, Type)>),
+    Struct(Vec<Field>),
+    Tuple(Vec<Field>),
+}
+
+impl Field {
+    /// Key of a named field in dictionaries.
+    fn key(&self) -> Literal {
+        match &self.attrs.rename {
+            Some(rename) => Literal::string(&rename.value()),
+            None => Literal::string(&self.ident.to_string()),
+        }
+    }
+
+    /// Converts the field, bound by reference to its identifier, to a variant.
+    fn convert_to_variant(&self) -> TokenStream2 {
+        let ident = &self.ident;
+        match &self.attrs.to_variant_with {
+            Some(path) => quote! { #path(#ident) },
+            None => quote! { (#ident).to_variant() },
+        }
+    }
+
+    /// Converts the `&Variant` given by `source` to the type of the field.
+    fn convert_from_variant(&self, source: TokenStream2) -> TokenStream2 {
+        match &self.attrs.from_variant_with {
+            Some(path) => quote! { #path(#source) },
+            None => quote! { FromVariant::from_variant(#source) },
+        }
+    }
+
+    fn default_value(&self) -> TokenStream2 {
+        match &self.attrs.default {
+            Some(FieldDefault::Path(path)) => quote! { #path() },
+            _ => quote! { ::std::default::Default::default() },
+        }
+    }
 }
 
 impl VariantRepr {
-    fn repr_for(fields: &Fields) -> Self {
-        match fields {
+    fn repr_for(fields: &Fields) -> syn::Result<Self> {
+        let mut errors = None;
+        let mut parse_field =
+            |ident: Ident, field: &syn::Field| match FieldAttrs::parse(&field.attrs) {
+                Ok(attrs) => Some(Field {
+                    ident,
+                    ty: field.ty.clone(),
+                    attrs,
+                }),
+                Err(err) => {
+                    push_error(&mut errors, err);
+                    None
+                }
+            };
+
+        let repr = match fields {
             Fields::Named(fields) => VariantRepr::Struct(
                 fields
                     .named
                     .iter()
-                    .map(|f| {
-                        (
-                            f.ident.clone().expect("fields should be named"),
-                            f.ty.clone(),
-                        )
+                    .filter_map(|f| {
+                        let ident = f.ident.clone().expect("fields should be named");
+                        parse_field(ident, f)
                     })
                     .collect(),
             ),
@@ -44,15 +106,48 @@ impl VariantRepr {
                     .unnamed
                     .iter()
                     .enumerate()
-                    .map(|(n, f)| {
-                        (
-                            Ident::new(&format!("__field_{}", n), Span::call_site()),
-                            f.ty.clone(),
-                        )
+                    .filter_map(|(n, f)| {
+                        let ident = Ident::new(&format!("__field_{}", n), Span::call_site());
+                        parse_field(ident, f)
                     })
                     .collect(),
             ),
             Fields::Unit => VariantRepr::Unit,
+        };
+
+        if let VariantRepr::Tuple(fields) = &repr {
+            for field in fields {
+                if let Some(rename) = &field.attrs.rename {
+                    push_error(
+                        &mut errors,
+                        syn::Error::new_spanned(rename, "only named fields can be renamed"),
+                    );
+                }
+                if field.attrs.default.is_some() && !field.attrs.skip {
+                    push_error(
+                        &mut errors,
+                        syn::Error::new_spanned(
+                            &field.ty,
+                            "`default` on a tuple field requires `skip`",
+                        ),
+                    );
+                }
+            }
+        }
+
+        match errors {
+            Some(errors) => Err(errors),
+            None => Ok(repr),
+        }
+    }
+
+    /// Returns the only field of a newtype, which is converted as the field itself.
+    fn newtype_field(&self) -> Option<&Field> {
+        match self {
+            VariantRepr::Tuple(fields) if fields.len() == 1 && !fields[0].attrs.skip => {
+                Some(&fields[0])
+            }
+            _ => None,
         }
     }
 
@@ -60,13 +155,27 @@ impl VariantRepr {
         match self {
             VariantRepr::Unit => quote! {},
             VariantRepr::Tuple(fields) => {
-                let names = fields.iter().map(|(ident, _)| ident);
+                let names = fields.iter().map(|field| {
+                    if field.attrs.skip {
+                        quote! { _ }
+                    } else {
+                        let ident = &field.ident;
+                        quote! { #ident }
+                    }
+                });
                 quote! {
                     ( #( #names ),* )
                 }
             }
             VariantRepr::Struct(fields) => {
-                let names = fields.iter().map(|(ident, _)| ident);
+                let names = fields.iter().map(|field| {
+                    let ident = &field.ident;
+                    if field.attrs.skip {
+                        quote! { #ident: _ }
+                    } else {
+                        quote! { #ident }
+                    }
+                });
                 quote! {
                     { #( #names ),* }
                 }
@@ -74,50 +183,54 @@ impl VariantRepr {
         }
     }
 
+    /// Statements that insert the fields of a struct into `__dict`.
+    fn dict_inserts(fields: &[Field]) -> TokenStream2 {
+        let inserts = fields.iter().filter(|f| !f.attrs.skip).map(|field| {
+            let key = field.key();
+            let to_variant = field.convert_to_variant();
+            quote! {
+                {
+                    let __key = ::gdnative::GodotString::from(#key).to_variant();
+                    __dict.set(&__key, &#to_variant);
+                }
+            }
+        });
+
+        quote! { #( #inserts )* }
+    }
+
     fn to_variant(&self) -> TokenStream2 {
+        if let Some(field) = self.newtype_field() {
+            return field.convert_to_variant();
+        }
+
         match self {
             VariantRepr::Unit => {
                 quote! { ::gdnative::Dictionary::new().to_variant() }
             }
             VariantRepr::Tuple(fields) => {
-                let names: Vec<&Ident> = fields.iter().map(|(ident, _)| ident).collect();
+                let items = fields
+                    .iter()
+                    .filter(|f| !f.attrs.skip)
+                    .map(Field::convert_to_variant);
 
-                if names.len() == 1 {
-                    // as newtype
-                    let inner = names.get(0).unwrap();
-                    quote! {
-                        #inner.to_variant()
-                    }
-                } else {
-                    quote! {
-                        {
-                            let mut __array = ::gdnative::VariantArray::new();
-                            #(
-                                __array.push(&(#names).to_variant());
-                            )*
-                            __array.to_variant()
-                        }
+                quote! {
+                    {
+                        let mut __array = ::gdnative::VariantArray::new();
+                        #(
+                            __array.push(&#items);
+                        )*
+                        __array.to_variant()
                     }
                 }
             }
             VariantRepr::Struct(fields) => {
-                let names: Vec<&Ident> = fields.iter().map(|(ident, _)| ident).collect();
-
-                let name_strings: Vec<String> =
-                    names.iter().map(|ident| format!("{}", ident)).collect();
-
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(&string));
+                let inserts = Self::dict_inserts(fields);
 
                 quote! {
                     {
                         let mut __dict = ::gdnative::Dictionary::new();
-                        #(
-                            {
-                                let __key = ::gdnative::GodotString::from(#name_string_literals).to_variant();
-                                __dict.set(&__key, &(#names).to_variant());
-                            }
-                        )*
+                        #inserts
                         __dict.to_variant()
                     }
                 }
@@ -126,6 +239,15 @@ impl VariantRepr {
     }
 
     fn from_variant(&self, variant: &Ident, ctor: &TokenStream2) -> TokenStream2 {
+        if let Some(field) = self.newtype_field() {
+            let from_variant = field.convert_from_variant(quote! { #variant });
+            return quote! {
+                {
+                    #from_variant.map(#ctor)
+                }
+            };
+        }
+
         match self {
             VariantRepr::Unit => {
                 quote! {
@@ -141,64 +263,93 @@ impl VariantRepr {
                 }
             }
             VariantRepr::Tuple(fields) => {
-                let types: Vec<&Type> = fields.iter().map(|(_, ty)| ty).collect();
-
-                if types.len() == 1 {
-                    // as newtype
-                    quote! {
-                        {
-                            FromVariant::from_variant(#variant).map(#ctor)
+                let mut index = 0;
+                let decls = fields
+                    .iter()
+                    .map(|field| {
+                        let ident = &field.ident;
+                        if field.attrs.skip {
+                            let default = field.default_value();
+                            return quote! { let #ident = #default; };
                         }
-                    }
-                } else {
-                    let idents: Vec<&Ident> = fields.iter().map(|(ident, _)| ident).collect();
 
-                    let decl_idents = idents.iter();
-                    let ctor_idents = idents.iter();
+                        let index_literal = Literal::i32_suffixed(index);
+                        index += 1;
+                        let from_variant =
+                            field.convert_from_variant(quote! { __array.get_ref(__index) });
+                        quote! {
+                            let __index = #index_literal;
+                            let #ident = #from_variant
+                                .map_err(|err| FromVariantError::InvalidItem {
+                                    index: __index as usize,
+                                    error: Box::new(err),
+                                })?;
+                        }
+                    })
+                    .collect::<Vec<_>>();
 
-                    let self_len = Literal::i32_suffixed(types.len() as i32);
-                    let indices = (0..fields.len() as i32).map(|n| Literal::i32_suffixed(n));
+                let self_len = Literal::usize_suffixed(index as usize);
+                let ctor_idents = fields.iter().map(|field| &field.ident);
 
-                    quote! {
-                        {
-                            ::gdnative::VariantArray::from_variant(#variant)
-                                .map_err(|__err| FVE::InvalidStructRepr {
-                                    expected: VariantStructRepr::Tuple,
-                                    error: Box::new(__err),
-                                })
-                                .and_then(|__array| {
-                                    let __expected = #self_len;
-                                    let __len = __array.len() as usize;
-                                    if __len != __expected {
-                                        Err(FVE::InvalidLength { expected: __expected, len: __len })
-                                    }
-                                    else {
-                                        #(
-                                            let __index = #indices;
-                                            let #decl_idents = FromVariant::from_variant(__array.get_ref(__index))
-                                                .map_err(|err| FromVariantError::InvalidItem {
-                                                    index: __index as usize,
-                                                    error: Box::new(err),
-                                                })?;
-                                        )*
-                                        Ok(#ctor( #(#ctor_idents),* ))
-                                    }
-                                })
-                        }
+                quote! {
+                    {
+                        ::gdnative::VariantArray::from_variant(#variant)
+                            .map_err(|__err| FVE::InvalidStructRepr {
+                                expected: VariantStructRepr::Tuple,
+                                error: Box::new(__err),
+                            })
+                            .and_then(|__array| {
+                                let __expected = #self_len;
+                                let __len = __array.len() as usize;
+                                if __len != __expected {
+                                    Err(FVE::InvalidLength { expected: __expected, len: __len })
+                                }
+                                else {
+                                    #( #decls )*
+                                    Ok(#ctor( #(#ctor_idents),* ))
+                                }
+                            })
                     }
                 }
             }
             VariantRepr::Struct(fields) => {
-                let names: Vec<&Ident> = fields.iter().map(|(ident, _)| ident).collect();
+                let decls = fields.iter().map(|field| {
+                    let ident = &field.ident;
+                    let default = field.default_value();
+                    if field.attrs.skip {
+                        return quote! { let #ident = #default; };
+                    }
 
-                let name_strings: Vec<String> =
-                    names.iter().map(|ident| format!("{}", ident)).collect();
+                    let key = field.key();
+                    let from_variant =
+                        field.convert_from_variant(quote! { __dict.get_ref(&__key) });
+                    let from_variant = quote! {
+                        #from_variant
+                            .map_err(|err| FVE::InvalidField {
+                                field_name: __field_name,
+                                error: Box::new(err),
+                            })?
+                    };
+                    let value = if field.attrs.default.is_some() {
+                        quote! {
+                            if __dict.contains(&__key) {
+                                #from_variant
+                            } else {
+                                #default
+                            }
+                        }
+                    } else {
+                        from_variant
+                    };
 
-                let name_string_literals =
-                    name_strings.iter().map(|string| Literal::string(&string));
+                    quote! {
+                        let __field_name = #key;
+                        let __key = ::gdnative::GodotString::from(__field_name).to_variant();
+                        let #ident = #value;
+                    }
+                });
 
-                let decl_idents = names.iter();
-                let ctor_idents = names.iter();
+                let ctor_idents = fields.iter().map(|field| &field.ident);
 
                 quote! {
                     {
@@ -208,15 +359,7 @@ impl VariantRepr {
                                 error: Box::new(__err),
                             })
                             .and_then(|__dict| {
-                                #(
-                                    let __field_name = #name_string_literals;
-                                    let __key = ::gdnative::GodotString::from(__field_name).to_variant();
-                                    let #decl_idents = FromVariant::from_variant(__dict.get_ref(&__key))
-                                        .map_err(|err| FVE::InvalidField {
-                                            field_name: __field_name,
-                                            error: Box::new(err),
-                                        })?;
-                                )*
+                                #( #decls )*
                                 Ok(#ctor { #( #ctor_idents ),* })
                             })
                     }
@@ -226,7 +369,12 @@ impl VariantRepr {
     }
 }
 
-pub(crate) fn extend_bounds(generics: Generics, repr: &Repr, bound: &syn::Path) -> Generics {
+pub(crate) fn extend_bounds(
+    generics: Generics,
+    repr: &Repr,
+    bound: &syn::Path,
+    needs_bound: fn(&FieldAttrs) -> bool,
+) -> Generics {
     // recursively visit all the field types to find what types should be bounded
     struct Visitor<'ast> {
         all_type_params: HashSet<Ident>,
@@ -266,31 +414,31 @@ pub(crate) fn extend_bounds(generics: Generics, repr: &Repr, bound: &syn::Path)
         used: HashSet::new(),
     };
 
-    // iterate through parsed variant representations and visit the types of each field
-    fn visit_var_repr<'ast>(visitor: &mut Visitor<'ast>, repr: &'ast VariantRepr) {
+    // iterate through parsed variant representations and visit the types of each field that is
+    // converted with the bounded trait
+    fn visit_var_repr<'ast>(
+        visitor: &mut Visitor<'ast>,
+        repr: &'ast VariantRepr,
+        needs_bound: fn(&FieldAttrs) -> bool,
+    ) {
         match repr {
             VariantRepr::Unit => {}
-            VariantRepr::Tuple(tys) => {
-                for (_, ty) in tys.iter() {
-                    visitor.visit_type(ty);
-                }
-            }
-            VariantRepr::Struct(fields) => {
-                for (_, ty) in fields.iter() {
-                    visitor.visit_type(ty);
+            VariantRepr::Tuple(fields) | VariantRepr::Struct(fields) => {
+                for field in fields.iter().filter(|f| needs_bound(&f.attrs)) {
+                    visitor.visit_type(&field.ty);
                 }
             }
         }
     }
 
     match repr {
-        Repr::Enum(ref variants) => {
-            for (_, var_repr) in variants.iter() {
-                visit_var_repr(&mut visitor, var_repr);
+        Repr::Enum(_, ref variants) => {
+            for variant in variants.iter() {
+                visit_var_repr(&mut visitor, &variant.repr, needs_bound);
             }
         }
         Repr::Struct(var_repr) => {
-            visit_var_repr(&mut visitor, var_repr);
+            visit_var_repr(&mut visitor, var_repr, needs_bound);
         }
     }
 
@@ -327,47 +475,97 @@ pub(crate) fn extend_bounds(generics: Generics, repr: &Repr, bound: &syn::Path)
     generics
 }
 
-pub(crate) fn parse_derive_input(input: TokenStream, bound: &syn::Path) -> DeriveData {
-    let input = match syn::parse_macro_input::parse::<DeriveInput>(input) {
-        Ok(val) => val,
-        Err(err) => {
-            panic!("{}", err);
+fn parse_enum(enum_repr: EnumRepr, data: &syn::DataEnum) -> syn::Result<Repr> {
+    let mut variants = Vec::new();
+    let mut errors = None;
+
+    for variant in data.variants.iter() {
+        let attrs = VariantAttrs::parse(&variant.attrs);
+        let repr = VariantRepr::repr_for(&variant.fields);
+        let (attrs, repr) = match (attrs, repr) {
+            (Ok(attrs), Ok(repr)) => (attrs, repr),
+            (attrs, repr) => {
+                for err in attrs.err().into_iter().chain(repr.err()) {
+                    push_error(&mut errors, err);
+                }
+                continue;
+            }
+        };
+
+        let unsupported = match (&enum_repr, &repr) {
+            (EnumRepr::Int, VariantRepr::Tuple(_)) | (EnumRepr::Int, VariantRepr::Struct(_)) => {
+                Some("`repr = \"int\"` requires all variants to be unit variants")
+            }
+            (EnumRepr::InternallyTagged { .. }, VariantRepr::Tuple(_)) => {
+                Some("internally tagged enums only support unit and struct variants")
+            }
+            _ => None,
+        };
+        if let Some(message) = unsupported {
+            push_error(&mut errors, syn::Error::new_spanned(variant, message));
+            continue;
         }
-    };
+
+        variants.push(EnumVariant {
+            ident: variant.ident.clone(),
+            name: attrs.rename.unwrap_or_else(|| variant.ident.to_string()),
+            repr,
+        });
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(Repr::Enum(enum_repr, variants)),
+    }
+}
+
+pub(crate) fn parse_derive_input(
+    input: TokenStream,
+    bound: &syn::Path,
+    needs_bound: fn(&FieldAttrs) -> bool,
+) -> syn::Result<DeriveData> {
+    let input = syn::parse::<DeriveInput>(input)?;
+    let container = ContainerAttrs::parse(&input.attrs)?;
 
     let repr = match input.data {
-        Data::Struct(struct_data) => Repr::Struct(VariantRepr::repr_for(&struct_data.fields)),
-        Data::Enum(enum_data) => Repr::Enum(
-            enum_data
-                .variants
-                .iter()
-                .map(|variant| {
-                    (
-                        variant.ident.clone(),
-                        VariantRepr::repr_for(&variant.fields),
-                    )
-                })
-                .collect(),
-        ),
-        Data::Union(_) => panic!("Variant conversion derive macro does not work on unions."),
+        Data::Struct(struct_data) => {
+            if let Some(arg) = container.first_arg {
+                return Err(syn::Error::new_spanned(
+                    &arg,
+                    format!("`{}` is only supported on enums", arg),
+                ));
+            }
+            Repr::Struct(VariantRepr::repr_for(&struct_data.fields)?)
+        }
+        Data::Enum(enum_data) => parse_enum(container.enum_repr, &enum_data)?,
+        Data::Union(union_data) => {
+            return Err(syn::Error::new(
+                union_data.union_token.span,
+                "Variant conversion derive macro does not work on unions",
+            ))
+        }
     };
 
-    let generics = extend_bounds(input.generics, &repr, bound);
+    let generics = extend_bounds(input.generics, &repr, bound, needs_bound);
 
-    DeriveData {
+    Ok(DeriveData {
         ident: input.ident,
         repr,
         generics,
-    }
+    })
 }
 
 pub(crate) fn derive_to_variant(input: TokenStream) -> TokenStream {
     let bound: syn::Path = syn::parse2(quote! { ::gdnative::ToVariant }).unwrap();
+    let needs_bound = |attrs: &FieldAttrs| !attrs.skip && attrs.to_variant_with.is_none();
     let DeriveData {
         ident,
         repr,
         mut generics,
-    } = parse_derive_input(input, &bound);
+    } = match parse_derive_input(input, &bound, needs_bound) {
+        Ok(data) => data,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     for param in generics.type_params_mut() {
         param.default = None;
@@ -384,29 +582,75 @@ pub(crate) fn derive_to_variant(input: TokenStream) -> TokenStream {
                 }
             }
         }
-        Repr::Enum(variants) => {
+        Repr::Enum(enum_repr, variants) => {
             if variants.is_empty() {
                 quote! {
                     unreachable!("this is an uninhabitable enum");
                 }
             } else {
-                let match_arms = variants
-                    .iter()
-                    .map(|(var_ident, var_repr)| {
-                        let destructure_pattern = var_repr.destructure_pattern();
-                        let to_variant = var_repr.to_variant();
-                        let var_ident_string = format!("{}", var_ident);
-                        let var_ident_string_literal = Literal::string(&var_ident_string);
-                        quote! {
-                            #ident::#var_ident #destructure_pattern => {
+                let match_arms = variants.iter().map(|variant| {
+                    let var_ident = &variant.ident;
+                    let var_repr = &variant.repr;
+                    let destructure_pattern = var_repr.destructure_pattern();
+                    let name = Literal::string(&variant.name);
+
+                    let body = match &enum_repr {
+                        EnumRepr::ExternallyTagged => {
+                            let to_variant = var_repr.to_variant();
+                            quote! {
                                 let mut __dict = ::gdnative::Dictionary::new();
-                                let __key = ::gdnative::GodotString::from(#var_ident_string_literal).to_variant();
+                                let __key = ::gdnative::GodotString::from(#name).to_variant();
                                 let __value = #to_variant;
                                 __dict.set(&__key, &__value);
                                 __dict.to_variant()
                             }
                         }
-                    });
+                        EnumRepr::InternallyTagged { tag } => {
+                            let tag = Literal::string(tag);
+                            let inserts = match var_repr {
+                                VariantRepr::Struct(fields) => VariantRepr::dict_inserts(fields),
+                                _ => quote! {},
+                            };
+                            quote! {
+                                let mut __dict = ::gdnative::Dictionary::new();
+                                let __key = ::gdnative::GodotString::from(#tag).to_variant();
+                                __dict.set(&__key, &::gdnative::GodotString::from(#name).to_variant());
+                                #inserts
+                                __dict.to_variant()
+                            }
+                        }
+                        EnumRepr::AdjacentlyTagged { tag, content } => {
+                            let tag = Literal::string(tag);
+                            let content = Literal::string(content);
+                            let set_content = match var_repr {
+                                VariantRepr::Unit => quote! {},
+                                _ => {
+                                    let to_variant = var_repr.to_variant();
+                                    quote! {
+                                        let __key = ::gdnative::GodotString::from(#content).to_variant();
+                                        __dict.set(&__key, &#to_variant);
+                                    }
+                                }
+                            };
+                            quote! {
+                                let mut __dict = ::gdnative::Dictionary::new();
+                                let __key = ::gdnative::GodotString::from(#tag).to_variant();
+                                __dict.set(&__key, &::gdnative::GodotString::from(#name).to_variant());
+                                #set_content
+                                __dict.to_variant()
+                            }
+                        }
+                        EnumRepr::Int => quote! {
+                            ::gdnative::Variant::from_i64(#ident::#var_ident as i64)
+                        },
+                    };
+
+                    quote! {
+                        #ident::#var_ident #destructure_pattern => {
+                            #body
+                        }
+                    }
+                });
 
                 quote! {
                     match &self {
@@ -433,13 +677,68 @@ pub(crate) fn derive_to_variant(input: TokenStream) -> TokenStream {
     result.into()
 }
 
+/// Matches the variant name in `__tag` against the variants of an enum, and converts the
+/// contents of the matching variant from the `&Variant` given by `source`.
+fn match_variant_name(
+    ident: &Ident,
+    variants: &[EnumVariant],
+    source: TokenStream2,
+    tagged_unit: bool,
+) -> TokenStream2 {
+    let var_input_ident = Ident::new("__enum_variant", Span::call_site());
+
+    let names = variants
+        .iter()
+        .map(|variant| Literal::string(&variant.name))
+        .collect::<Vec<_>>();
+
+    let arms = variants.iter().zip(&names).map(|(variant, name)| {
+        let var_ident = &variant.ident;
+        let ctor = quote! { #ident::#var_ident };
+
+        // Unit variants of tagged enums are fully described by the tag.
+        if tagged_unit {
+            if let VariantRepr::Unit = variant.repr {
+                return quote! {
+                    #name => Ok(#ctor),
+                };
+            }
+        }
+
+        let from_variant = variant.repr.from_variant(&var_input_ident, &ctor);
+        quote! {
+            #name => {
+                let #var_input_ident = #source;
+                (#from_variant).map_err(|err| FVE::InvalidEnumVariant {
+                    variant: #name,
+                    error: Box::new(err),
+                })
+            },
+        }
+    });
+
+    quote! {
+        match __tag.as_str() {
+            #( #arms )*
+            variant => Err(FVE::UnknownEnumVariant {
+                variant: variant.to_string(),
+                expected: &[#(#names),*],
+            }),
+        }
+    }
+}
+
 pub(crate) fn derive_from_variant(input: TokenStream) -> TokenStream {
     let bound: syn::Path = syn::parse2(quote! { ::gdnative::FromVariant }).unwrap();
+    let needs_bound = |attrs: &FieldAttrs| !attrs.skip && attrs.from_variant_with.is_none();
     let DeriveData {
         ident,
         repr,
         mut generics,
-    } = parse_derive_input(input, &bound);
+    } = match parse_derive_input(input, &bound, needs_bound) {
+        Ok(data) => data,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     for param in generics.type_params_mut() {
         param.default = None;
@@ -456,69 +755,118 @@ pub(crate) fn derive_from_variant(input: TokenStream) -> TokenStream {
                 }
             }
         }
-        Repr::Enum(variants) => {
+        Repr::Enum(enum_repr, variants) => {
             if variants.is_empty() {
-                panic!("cannot derive FromVariant for an uninhabited enum");
+                return syn::Error::new(
+                    ident.span(),
+                    "cannot derive FromVariant for an uninhabited enum",
+                )
+                .to_compile_error()
+                .into();
             }
 
-            let var_input_ident = Ident::new("__enum_variant", Span::call_site());
-
-            let var_ident_strings: Vec<String> = variants
-                .iter()
-                .map(|(var_ident, _)| format!("{}", var_ident))
-                .collect();
+            match enum_repr {
+                EnumRepr::ExternallyTagged => {
+                    let match_variant = match_variant_name(
+                        &ident,
+                        &variants,
+                        quote! { __dict.get_ref(__keys.get_ref(0)) },
+                        false,
+                    );
 
-            let var_ident_string_literals = var_ident_strings
-                .iter()
-                .map(|string| Literal::string(&string))
-                .collect::<Vec<_>>();
+                    quote! {
+                        {
+                            let __dict = ::gdnative::Dictionary::from_variant(#input_ident)
+                                .map_err(|__err| FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::ExternallyTagged,
+                                    error: Box::new(__err),
+                                })?;
+
+                            let __keys = __dict.keys();
+                            if __keys.len() != 1 {
+                                Err(FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::ExternallyTagged,
+                                    error: Box::new(FVE::InvalidLength {
+                                        expected: 1,
+                                        len: __keys.len() as usize,
+                                    }),
+                                })
+                            }
+                            else {
+                                let __tag = String::from_variant(__keys.get_ref(0))
+                                    .map_err(|__err| FVE::InvalidEnumRepr {
+                                        expected: VariantEnumRepr::ExternallyTagged,
+                                        error: Box::new(__err),
+                                    })?;
+                                #match_variant
+                            }
+                        }
+                    }
+                }
+                EnumRepr::InternallyTagged { tag } => {
+                    let match_variant =
+                        match_variant_name(&ident, &variants, quote! { #input_ident }, true);
+                    let read_tag = read_tag(
+                        &input_ident,
+                        &tag,
+                        quote! { VariantEnumRepr::InternallyTagged },
+                    );
 
-            let ref_var_ident_string_literals = &var_ident_string_literals;
+                    quote! {
+                        {
+                            #read_tag
+                            #match_variant
+                        }
+                    }
+                }
+                EnumRepr::AdjacentlyTagged { tag, content } => {
+                    let content = Literal::string(&content);
+                    let match_variant = match_variant_name(
+                        &ident,
+                        &variants,
+                        quote! {
+                            __dict.get_ref(&::gdnative::GodotString::from(#content).to_variant())
+                        },
+                        true,
+                    );
+                    let read_tag = read_tag(
+                        &input_ident,
+                        &tag,
+                        quote! { VariantEnumRepr::AdjacentlyTagged },
+                    );
 
-            let var_from_variants = variants.iter().map(|(var_ident, var_repr)| {
-                var_repr.from_variant(&var_input_ident, &quote! { #ident::#var_ident })
-            });
+                    quote! {
+                        {
+                            #read_tag
+                            #match_variant
+                        }
+                    }
+                }
+                EnumRepr::Int => {
+                    let var_idents = variants.iter().map(|variant| &variant.ident);
+                    let var_idents_ctor = var_idents.clone();
+                    let names = variants
+                        .iter()
+                        .map(|variant| Literal::string(&variant.name));
 
-            let var_input_ident_iter = std::iter::repeat(&var_input_ident);
+                    quote! {
+                        {
+                            let __value = i64::from_variant(#input_ident)
+                                .map_err(|__err| FVE::InvalidEnumRepr {
+                                    expected: VariantEnumRepr::Int,
+                                    error: Box::new(__err),
+                                })?;
 
-            quote! {
-                {
-                    let __dict = ::gdnative::Dictionary::from_variant(#input_ident)
-                        .map_err(|__err| FVE::InvalidEnumRepr {
-                            expected: VariantEnumRepr::ExternallyTagged,
-                            error: Box::new(__err),
-                        })?;
-
-                    let __keys = __dict.keys();
-                    if __keys.len() != 1 {
-                        Err(FVE::InvalidEnumRepr {
-                            expected: VariantEnumRepr::ExternallyTagged,
-                            error: Box::new(FVE::InvalidLength {
-                                expected: 1,
-                                len: __keys.len() as usize,
-                            }),
-                        })
-                    }
-                    else {
-                        let __key = String::from_variant(__keys.get_ref(0))
-                            .map_err(|__err| FVE::InvalidEnumRepr {
-                                expected: VariantEnumRepr::ExternallyTagged,
-                                error: Box::new(__err),
-                            })?;
-                        match __key.as_str() {
                             #(
-                                #ref_var_ident_string_literals => {
-                                    let #var_input_ident_iter = __dict.get_ref(__keys.get_ref(0));
-                                    (#var_from_variants).map_err(|err| FVE::InvalidEnumVariant {
-                                        variant: "Ok",
-                                        error: Box::new(err),
-                                    })
-                                },
+                                if __value == #ident::#var_idents as i64 {
+                                    return Ok(#ident::#var_idents_ctor);
+                                }
                             )*
-                            variant => Err(FVE::UnknownEnumVariant {
-                                variant: variant.to_string(),
-                                expected: &[#(#ref_var_ident_string_literals),*],
-                            }),
+
+                            Err(FVE::UnknownEnumVariant {
+                                variant: __value.to_string(),
+                                expected: &[#(#names),*],
+                            })
                         }
                     }
                 }
@@ -546,3 +894,26 @@ pub(crate) fn derive_from_variant(input: TokenStream) -> TokenStream {
 
     result.into()
 }
+
+/// Statements that read the dictionary of a tagged enum into `__dict`, and its tag into `__tag`.
+fn read_tag(input_ident: &Ident, tag: &str, expected: TokenStream2) -> TokenStream2 {
+    let tag = Literal::string(tag);
+
+    quote! {
+        let __dict = ::gdnative::Dictionary::from_variant(#input_ident)
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected,
+                error: Box::new(__err),
+            })?;
+
+        let __key = ::gdnative::GodotString::from(#tag).to_variant();
+        let __tag = String::from_variant(__dict.get_ref(&__key))
+            .map_err(|__err| FVE::InvalidEnumRepr {
+                expected: #expected,
+                error: Box::new(FVE::InvalidField {
+                    field_name: #tag,
+                    error: Box::new(__err),
+                }),
+            })?;
+    }
+}
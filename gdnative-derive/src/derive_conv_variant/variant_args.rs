@@ -0,0 +1,227 @@
+use crate::derive_macro::property_args::{path_value, string_value};
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Attribute, Expr, Ident, LitStr, Path, Token};
+
+/// A single argument of the `#[variant(...)]` attribute: either `name = "value"` or a flag.
+pub struct VariantAttrArg {
+    name: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for VariantAttrArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(VariantAttrArg { name, value })
+    }
+}
+
+/// Parses the arguments of all `#[variant(...)]` attributes in `attrs`.
+fn parse_variant_args(attrs: &[Attribute]) -> syn::Result<Vec<VariantAttrArg>> {
+    let mut args = Vec::new();
+    for attr in attrs.iter().filter(|a| a.path.is_ident("variant")) {
+        args.extend(
+            attr.parse_args_with(Punctuated::<VariantAttrArg, Token![,]>::parse_terminated)?,
+        );
+    }
+    Ok(args)
+}
+
+fn set<T>(slot: &mut Option<T>, name: &Ident, value: T) -> syn::Result<()> {
+    if slot.replace(value).is_some() {
+        Err(syn::Error::new_spanned(
+            name,
+            format!("`{}` is already set", name),
+        ))
+    } else {
+        Ok(())
+    }
+}
+
+fn unexpected(name: &Ident) -> syn::Error {
+    syn::Error::new_spanned(name, "unexpected argument")
+}
+
+/// How an enum is represented in variants.
+pub enum EnumRepr {
+    /// `{ "Variant": value }`, the default.
+    ExternallyTagged,
+    /// `{ tag: "Variant", field: value, ... }`, from `#[variant(tag = "...")]`.
+    InternallyTagged { tag: String },
+    /// `{ tag: "Variant", content: value }`, from `#[variant(tag = "...", content = "...")]`.
+    AdjacentlyTagged { tag: String, content: String },
+    /// The discriminant as an integer, from `#[variant(repr = "int")]`.
+    Int,
+}
+
+/// Arguments given to the type itself.
+pub struct ContainerAttrs {
+    pub enum_repr: EnumRepr,
+    /// The first argument, to report container arguments on structs.
+    pub first_arg: Option<Ident>,
+}
+
+impl ContainerAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut first_arg = None;
+        let mut repr = None;
+        let mut tag = None;
+        let mut content = None;
+
+        for arg in parse_variant_args(attrs)? {
+            let name = arg.name;
+            let value = arg.value.ok_or_else(|| unexpected(&name))?;
+            let value = string_value(&name, &value)?;
+            match name.to_string().as_str() {
+                "repr" => {
+                    if value.value() != "int" {
+                        return Err(syn::Error::new_spanned(
+                            value,
+                            "the only supported `repr` is \"int\"",
+                        ));
+                    }
+                    set(&mut repr, &name, value)?
+                }
+                "tag" => set(&mut tag, &name, value)?,
+                "content" => set(&mut content, &name, value)?,
+                _ => return Err(unexpected(&name)),
+            }
+            first_arg.get_or_insert(name);
+        }
+
+        let enum_repr = match (repr, tag, content) {
+            (None, None, None) => EnumRepr::ExternallyTagged,
+            (None, Some(tag), None) => EnumRepr::InternallyTagged { tag: tag.value() },
+            (None, Some(tag), Some(content)) => EnumRepr::AdjacentlyTagged {
+                tag: tag.value(),
+                content: content.value(),
+            },
+            (None, None, Some(content)) => {
+                return Err(syn::Error::new_spanned(
+                    content,
+                    "`content` requires a `tag`",
+                ))
+            }
+            (Some(_), None, None) => EnumRepr::Int,
+            (Some(repr), _, _) => {
+                return Err(syn::Error::new_spanned(
+                    repr,
+                    "`repr` cannot be combined with `tag` or `content`",
+                ))
+            }
+        };
+
+        Ok(ContainerAttrs {
+            enum_repr,
+            first_arg,
+        })
+    }
+}
+
+/// Arguments given to an enum variant.
+pub struct VariantAttrs {
+    pub rename: Option<String>,
+}
+
+impl VariantAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut rename = None;
+
+        for arg in parse_variant_args(attrs)? {
+            let name = arg.name;
+            let value = arg.value.ok_or_else(|| unexpected(&name))?;
+            match name.to_string().as_str() {
+                "rename" => set(&mut rename, &name, string_value(&name, &value)?.value())?,
+                _ => return Err(unexpected(&name)),
+            }
+        }
+
+        Ok(VariantAttrs { rename })
+    }
+}
+
+/// Value of a field that is skipped, or missing from a dictionary.
+pub enum FieldDefault {
+    /// `Default::default()`, from `default` or `skip`.
+    Trait,
+    /// A function without arguments, from `default = "path"`.
+    Path(Path),
+}
+
+/// Arguments given to a field.
+pub struct FieldAttrs {
+    pub rename: Option<LitStr>,
+    pub skip: bool,
+    pub default: Option<FieldDefault>,
+    pub to_variant_with: Option<Path>,
+    pub from_variant_with: Option<Path>,
+}
+
+impl FieldAttrs {
+    pub fn parse(attrs: &[Attribute]) -> syn::Result<Self> {
+        let mut rename = None;
+        let mut skip = None;
+        let mut default = None;
+        let mut to_variant_with = None;
+        let mut from_variant_with = None;
+
+        for arg in parse_variant_args(attrs)? {
+            let name = arg.name;
+            let value = match arg.value {
+                Some(value) => value,
+                None if name == "skip" => {
+                    set(&mut skip, &name, ())?;
+                    continue;
+                }
+                None if name == "default" => {
+                    set(&mut default, &name, FieldDefault::Trait)?;
+                    continue;
+                }
+                None => return Err(unexpected(&name)),
+            };
+
+            match name.to_string().as_str() {
+                "rename" => set(&mut rename, &name, string_value(&name, &value)?)?,
+                "default" => {
+                    let path = path_value(&name, &value)?;
+                    set(&mut default, &name, FieldDefault::Path(path))?
+                }
+                "to_variant_with" => set(&mut to_variant_with, &name, path_value(&name, &value)?)?,
+                "from_variant_with" => {
+                    set(&mut from_variant_with, &name, path_value(&name, &value)?)?
+                }
+                "with" => {
+                    let lit = string_value(&name, &value)?;
+                    let module = lit.parse::<Path>().map_err(|_| {
+                        syn::Error::new_spanned(&lit, "`with` should name a module")
+                    })?;
+                    set(
+                        &mut to_variant_with,
+                        &name,
+                        parse_quote!(#module::to_variant),
+                    )?;
+                    set(
+                        &mut from_variant_with,
+                        &name,
+                        parse_quote!(#module::from_variant),
+                    )?;
+                }
+                _ => return Err(unexpected(&name)),
+            }
+        }
+
+        Ok(FieldAttrs {
+            rename,
+            skip: skip.is_some(),
+            default,
+            to_variant_with,
+            from_variant_with,
+        })
+    }
+}
@@ -1,111 +1,119 @@
-use proc_macro::TokenStream;
-use std::collections::HashMap;
-use syn::{Data, DeriveInput, Fields, Ident, Meta, MetaList, NestedMeta, Path, Type};
+use syn::punctuated::Punctuated;
+use syn::{Data, DeriveInput, Field, Ident, Path, Type};
 
-mod property_args;
-use property_args::{PropertyAttrArgs, PropertyAttrArgsBuilder};
+use crate::method_macro::push_error;
+
+pub(crate) mod property_args;
+use property_args::{parse_property_args, PropertyAttrArgs, PropertyAttrArgsBuilder};
 
 pub(crate) struct DeriveData {
     pub(crate) name: Ident,
     pub(crate) base: Type,
     pub(crate) register_callback: Option<Path>,
+    pub(crate) signals: Vec<Path>,
     pub(crate) user_data: Type,
-    pub(crate) properties: HashMap<Ident, PropertyAttrArgs>,
+    pub(crate) properties: Vec<(Ident, PropertyAttrArgs)>,
 }
 
-pub(crate) fn parse_derive_input(input: TokenStream) -> DeriveData {
-    let input = match syn::parse_macro_input::parse::<DeriveInput>(input) {
-        Ok(val) => val,
-        Err(err) => {
-            panic!("{}", err);
-        }
-    };
-
-    let ident = input.ident;
+pub(crate) fn parse_derive_input(input: &DeriveInput) -> syn::Result<DeriveData> {
+    let ident = input.ident.clone();
 
     let inherit_attr = input
         .attrs
         .iter()
         .find(|a| a.path.is_ident("inherit"))
-        .expect("No \"inherit\" attribute found");
+        .ok_or_else(|| {
+            syn::Error::new(
+                ident.span(),
+                "missing base class: add `#[inherit(BaseClass)]` to the struct",
+            )
+        })?;
 
     // read base class
-    let base = inherit_attr
-        .parse_args::<Type>()
-        .expect("`inherits` attribute requires the base type as an argument.");
+    let base = inherit_attr.parse_args::<Type>()?;
 
     let register_callback = input
         .attrs
         .iter()
         .find(|a| a.path.is_ident("register_with"))
-        .map(|attr| {
-            attr.parse_args::<Path>()
-                .expect("`register_with` attributes requires a function as an argument.")
-        });
+        .map(|attr| attr.parse_args::<Path>())
+        .transpose()?;
+
+    let mut signals = Vec::new();
+    for attr in input.attrs.iter().filter(|a| a.path.is_ident("signals")) {
+        signals.extend(attr.parse_args_with(Punctuated::<Path, Token![,]>::parse_terminated)?);
+    }
 
     let user_data = input
         .attrs
         .iter()
         .find(|a| a.path.is_ident("user_data"))
-        .map(|attr| {
-            attr.parse_args::<Type>()
-                .expect("`userdata` attribute requires a type as an argument.")
-        })
-        .unwrap_or_else(|| {
-            syn::parse::<Type>(quote! { ::gdnative::user_data::DefaultUserData<#ident> }.into())
-                .expect("quoted tokens should be a valid type")
-        });
+        .map(|attr| attr.parse_args::<Type>())
+        .transpose()?
+        .unwrap_or_else(|| parse_quote! { ::gdnative::user_data::DefaultUserData<#ident> });
 
     // make sure it's a struct
-    let struct_data = if let Data::Struct(data) = input.data {
-        data
-    } else {
-        panic!("NativeClass derive macro only works on structs.");
+    let struct_data = match &input.data {
+        Data::Struct(data) => data,
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span,
+                "NativeClass derive macro only works on structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "NativeClass derive macro only works on structs",
+            ))
+        }
     };
 
-    // read exported properties
-    let properties = if let Fields::Named(names) = &struct_data.fields {
-        names
-            .named
-            .iter()
-            .filter_map(|field| {
-                let mut property_args = None;
-
-                for attr in field.attrs.iter() {
-                    if !attr.path.is_ident("property") {
-                        continue;
-                    }
-
-                    let meta = attr
-                        .parse_meta()
-                        .expect("should be able to parse attribute arguments");
-                    if let Meta::List(MetaList { nested, .. }) = meta {
-                        property_args
-                            .get_or_insert_with(PropertyAttrArgsBuilder::default)
-                            .extend(nested.iter().map(|arg| match arg {
-                                NestedMeta::Meta(Meta::NameValue(ref pair)) => pair,
-                                _ => panic!("unexpected argument: {:?}", arg),
-                            }));
-                    } else {
-                        panic!("unexpected meta variant: {:?}", meta);
-                    }
-                }
-
-                property_args.map(|builder| {
-                    let ident = field.ident.clone().expect("fields should be named");
-                    (ident, builder.done())
-                })
-            })
-            .collect::<HashMap<_, _>>()
-    } else {
-        HashMap::new()
-    };
+    // read exported properties, reporting the errors of all fields at once.
+    let mut properties = Vec::new();
+    let mut errors = None;
+    for field in struct_data.fields.iter() {
+        match parse_property(field) {
+            Ok(Some(property)) => properties.push(property),
+            Ok(None) => {}
+            Err(err) => push_error(&mut errors, err),
+        }
+    }
+    if let Some(errors) = errors {
+        return Err(errors);
+    }
 
-    DeriveData {
+    Ok(DeriveData {
         name: ident,
         base,
         register_callback,
+        signals,
         user_data,
         properties,
+    })
+}
+
+fn parse_property(field: &Field) -> syn::Result<Option<(Ident, PropertyAttrArgs)>> {
+    let mut property_args = None;
+
+    for attr in field.attrs.iter() {
+        if !attr.path.is_ident("property") {
+            continue;
+        }
+
+        let builder = property_args.get_or_insert_with(PropertyAttrArgsBuilder::default);
+        for arg in parse_property_args(attr)? {
+            builder.add(arg)?;
+        }
+    }
+
+    match property_args {
+        Some(builder) => {
+            let ident = field.ident.clone().ok_or_else(|| {
+                syn::Error::new_spanned(field, "only named fields can be exported as properties")
+            })?;
+            Ok(Some((ident, builder.done()?)))
+        }
+        None => Ok(None),
     }
 }
@@ -1,39 +1,136 @@
+use crate::method_macro::rpc_mode;
+use syn::parse::{Parse, ParseStream};
+use syn::punctuated::Punctuated;
+use syn::{Expr, Ident, LitStr, Path, Token};
+
 pub struct PropertyAttrArgs {
-    pub default: syn::Lit,
+    pub path: Option<String>,
+    pub default: Option<Expr>,
+    pub hint: Option<Expr>,
+    pub usage: Option<Expr>,
+    pub get: Option<Path>,
+    pub set: Option<Path>,
+    pub rset_mode: Option<Ident>,
+    pub no_editor: bool,
+}
+
+/// A single argument of the `#[property(...)]` attribute: either `name = value` or a flag.
+pub struct PropertyAttrArg {
+    name: Ident,
+    value: Option<Expr>,
+}
+
+impl Parse for PropertyAttrArg {
+    fn parse(input: ParseStream) -> syn::Result<Self> {
+        let name = input.parse::<Ident>()?;
+        let value = if input.peek(Token![=]) {
+            input.parse::<Token![=]>()?;
+            Some(input.parse()?)
+        } else {
+            None
+        };
+        Ok(PropertyAttrArg { name, value })
+    }
+}
+
+/// Parses the arguments of a `#[property(...)]` attribute.
+pub fn parse_property_args(attr: &syn::Attribute) -> syn::Result<Vec<PropertyAttrArg>> {
+    // `#[property]` without arguments is allowed.
+    if attr.tokens.is_empty() {
+        return Ok(Vec::new());
+    }
+
+    let args = attr.parse_args_with(Punctuated::<PropertyAttrArg, Token![,]>::parse_terminated)?;
+    Ok(args.into_iter().collect())
 }
 
 #[derive(Default)]
 pub struct PropertyAttrArgsBuilder {
-    default: Option<syn::Lit>,
+    path: Option<String>,
+    default: Option<Expr>,
+    hint: Option<Expr>,
+    usage: Option<Expr>,
+    get: Option<Path>,
+    set: Option<Path>,
+    rset_mode: Option<Ident>,
+    no_editor: Option<Ident>,
 }
 
-impl<'a> Extend<&'a syn::MetaNameValue> for PropertyAttrArgsBuilder {
-    fn extend<I>(&mut self, iter: I)
-    where
-        I: IntoIterator<Item = &'a syn::MetaNameValue>,
-    {
-        for pair in iter.into_iter() {
-            let name = pair
+impl PropertyAttrArgsBuilder {
+    pub fn add(&mut self, arg: PropertyAttrArg) -> syn::Result<()> {
+        let name = arg.name;
+        let value = match arg.value {
+            Some(value) => value,
+            None if name == "no_editor" => {
+                self.no_editor = Some(name);
+                return Ok(());
+            }
+            None => return Err(syn::Error::new_spanned(name, "unexpected argument")),
+        };
+
+        let duplicate = match name.to_string().as_str() {
+            "path" => self
                 .path
-                .get_ident()
-                .expect("should be single identifier")
-                .to_string();
-            match name.as_str() {
-                "default" => {
-                    if let Some(old) = self.default.replace(pair.lit.clone()) {
-                        panic!("there is already a default value set: {:?}", old);
-                    }
-                }
-                _ => panic!("unexpected argument: {}", &name),
+                .replace(string_value(&name, &value)?.value())
+                .is_some(),
+            "default" => self.default.replace(value).is_some(),
+            "hint" => self.hint.replace(value).is_some(),
+            "usage" => self.usage.replace(value).is_some(),
+            "get" => self.get.replace(path_value(&name, &value)?).is_some(),
+            "set" => self.set.replace(path_value(&name, &value)?).is_some(),
+            "rset" => {
+                let mode = rpc_mode(&string_value(&name, &value)?)?;
+                self.rset_mode.replace(mode).is_some()
             }
+            _ => return Err(syn::Error::new_spanned(name, "unexpected argument")),
+        };
+
+        if duplicate {
+            return Err(syn::Error::new_spanned(
+                &name,
+                format!("`{}` is already set", name),
+            ));
         }
+
+        Ok(())
     }
-}
 
-impl PropertyAttrArgsBuilder {
-    pub fn done(self) -> PropertyAttrArgs {
-        PropertyAttrArgs {
-            default: self.default.expect("`default` value is required"),
+    pub fn done(self) -> syn::Result<PropertyAttrArgs> {
+        if let (Some(no_editor), Some(_)) = (&self.no_editor, &self.usage) {
+            return Err(syn::Error::new_spanned(
+                no_editor,
+                "`no_editor` cannot be combined with `usage`",
+            ));
         }
+
+        Ok(PropertyAttrArgs {
+            path: self.path,
+            default: self.default,
+            hint: self.hint,
+            usage: self.usage,
+            get: self.get,
+            set: self.set,
+            rset_mode: self.rset_mode,
+            no_editor: self.no_editor.is_some(),
+        })
     }
 }
+
+pub fn string_value(name: &Ident, value: &Expr) -> syn::Result<LitStr> {
+    match value {
+        Expr::Lit(syn::ExprLit {
+            lit: syn::Lit::Str(lit),
+            ..
+        }) => Ok(lit.clone()),
+        _ => Err(syn::Error::new_spanned(
+            value,
+            format!("`{}` should be a string literal", name),
+        )),
+    }
+}
+
+pub fn path_value(name: &Ident, value: &Expr) -> syn::Result<Path> {
+    let lit = string_value(name, value)?;
+    lit.parse::<Path>()
+        .map_err(|_| syn::Error::new_spanned(&lit, format!("`{}` should name a function", name)))
+}
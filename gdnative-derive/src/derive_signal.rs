@@ -0,0 +1,181 @@
+use proc_macro::TokenStream;
+use proc_macro2::{Span, TokenStream as TokenStream2};
+use syn::{Data, DeriveInput, Fields, Ident, Lit, LitStr, Meta, MetaNameValue, NestedMeta};
+
+pub(crate) fn derive_signal(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    match impl_signal(&input) {
+        Ok(output) => output.into(),
+        Err(err) => err.to_compile_error().into(),
+    }
+}
+
+fn impl_signal(input: &DeriveInput) -> syn::Result<TokenStream2> {
+    let ident = &input.ident;
+
+    let mut name = None;
+    for pair in signal_args(&input.attrs)? {
+        if !pair.path.is_ident("name") {
+            return Err(syn::Error::new_spanned(pair.path, "unexpected argument"));
+        }
+        match &pair.lit {
+            Lit::Str(lit) => {
+                if name.replace(lit.clone()).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        pair,
+                        "there is already a signal name set",
+                    ));
+                }
+            }
+            lit => {
+                return Err(syn::Error::new_spanned(
+                    lit,
+                    "signal name should be a string literal",
+                ))
+            }
+        }
+    }
+    let name =
+        name.unwrap_or_else(|| LitStr::new(&to_snake_case(&ident.to_string()), Span::call_site()));
+
+    let struct_data = match &input.data {
+        Data::Struct(data) => data,
+        Data::Enum(data) => {
+            return Err(syn::Error::new(
+                data.enum_token.span,
+                "Signal derive macro only works on structs",
+            ))
+        }
+        Data::Union(data) => {
+            return Err(syn::Error::new(
+                data.union_token.span,
+                "Signal derive macro only works on structs",
+            ))
+        }
+    };
+
+    let fields = match &struct_data.fields {
+        Fields::Named(fields) => fields.named.iter().collect::<Vec<_>>(),
+        Fields::Unnamed(fields) => fields.unnamed.iter().collect::<Vec<_>>(),
+        Fields::Unit => Vec::new(),
+    };
+
+    let types = fields.iter().map(|field| &field.ty).collect::<Vec<_>>();
+
+    let mut arguments = Vec::new();
+    for (n, field) in fields.iter().enumerate() {
+        let arg_name = field
+            .ident
+            .as_ref()
+            .map(|ident| ident.to_string())
+            .unwrap_or_else(|| format!("arg{}", n));
+
+        let mut default = None;
+        for pair in signal_args(&field.attrs)? {
+            if !pair.path.is_ident("default") {
+                return Err(syn::Error::new_spanned(pair.path, "unexpected argument"));
+            }
+            if default.replace(pair.lit.clone()).is_some() {
+                return Err(syn::Error::new_spanned(
+                    pair,
+                    "there is already a default value set",
+                ));
+            }
+        }
+
+        // Without a default value, the argument is registered as untyped.
+        let default = match default {
+            Some(lit) => quote!(gdnative::ToVariant::to_variant(&#lit)),
+            None => quote!(gdnative::Variant::new()),
+        };
+
+        arguments.push(quote!(
+            gdnative::init::SignalArgument {
+                name: #arg_name,
+                default: #default,
+                hint: gdnative::init::PropertyHint::None,
+                usage: gdnative::init::PropertyUsage::DEFAULT,
+            }
+        ));
+    }
+
+    let bindings = (0..fields.len())
+        .map(|n| Ident::new(&format!("__arg_{}", n), Span::call_site()))
+        .collect::<Vec<_>>();
+
+    let pattern = match &struct_data.fields {
+        Fields::Named(_) => {
+            let names = fields.iter().map(|field| &field.ident);
+            quote!({ #(#names: #bindings),* })
+        }
+        Fields::Unnamed(_) => quote!(( #(#bindings),* )),
+        Fields::Unit => quote!(),
+    };
+
+    let (impl_generics, ty_generics, where_clause) = input.generics.split_for_impl();
+
+    let output = quote!(
+        impl #impl_generics gdnative::signal::Signal for #ident #ty_generics #where_clause {
+            const NAME: &'static str = #name;
+
+            type Args = (#(#types,)*);
+
+            fn arguments() -> Vec<gdnative::init::SignalArgument<'static>> {
+                vec![#(#arguments),*]
+            }
+
+            fn into_args(self) -> Vec<gdnative::Variant> {
+                let #ident #pattern = self;
+                vec![#(gdnative::ToVariant::to_variant(&#bindings)),*]
+            }
+        }
+    );
+
+    Ok(output)
+}
+
+/// Collects the `name = value` pairs of all `#[signal(...)]` attributes.
+fn signal_args(attrs: &[syn::Attribute]) -> syn::Result<Vec<MetaNameValue>> {
+    let mut pairs = Vec::new();
+    for attr in attrs.iter().filter(|attr| attr.path.is_ident("signal")) {
+        let list = match attr.parse_meta()? {
+            Meta::List(list) => list,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected arguments in parentheses: `#[signal(...)]`",
+                ))
+            }
+        };
+        for arg in list.nested {
+            match arg {
+                NestedMeta::Meta(Meta::NameValue(pair)) => pairs.push(pair),
+                arg => return Err(syn::Error::new_spanned(arg, "expected `name = value`")),
+            }
+        }
+    }
+    Ok(pairs)
+}
+
+/// Converts a type name like `TickWithData` to `tick_with_data`. Runs of capitals are kept as
+/// one word, so `HTTPRequestDone` becomes `http_request_done`.
+fn to_snake_case(name: &str) -> String {
+    let chars = name.chars().collect::<Vec<_>>();
+    let mut snake = String::with_capacity(name.len() + 4);
+    for (i, &c) in chars.iter().enumerate() {
+        if c.is_uppercase() {
+            // A capital starts a word unless it continues a run of capitals. The last capital of
+            // a run starts the next word if it is followed by a lowercase letter.
+            let starts_word = i > 0
+                && (!chars[i - 1].is_uppercase()
+                    || (i + 1 < chars.len() && chars[i + 1].is_lowercase()));
+            if starts_word {
+                snake.push('_');
+            }
+            snake.extend(c.to_lowercase());
+        } else {
+            snake.push(c);
+        }
+    }
+    snake
+}
@@ -9,11 +9,34 @@ use proc_macro::TokenStream;
 
 mod derive_conv_variant;
 mod derive_macro;
+mod derive_signal;
 mod method_macro;
 
+/// Registers the methods of an impl block that are marked with `#[export]`.
+///
+/// The `#[export]` attribute accepts the following optional arguments:
+///
+/// - `name = "..."`: the name the method is registered under, instead of its Rust name. It can
+///   be given several times to register the method under each of the names.
+/// - `rpc = "..."`: the RPC mode of the method, such as `"remote"` or `"puppetsync"`.
+/// - `tool`: the method is only registered when the library is loaded by the editor.
+///
+/// Trailing parameters of an exported method can be marked with `#[opt]`, which uses
+/// `Default::default()` when the argument is omitted, or with `#[default(expr)]`. A last parameter
+/// of type `&[Variant]` marked with `#[varargs]` receives all remaining arguments.
 #[proc_macro_attribute]
-pub fn methods(meta: TokenStream, input: TokenStream) -> TokenStream {
-    let (impl_block, export) = method_macro::parse_method_export(meta, input);
+pub fn methods(_meta: TokenStream, input: TokenStream) -> TokenStream {
+    let impl_block = parse_macro_input!(input as syn::ItemImpl);
+    let (impl_block, export) = method_macro::impl_gdnative_expose(impl_block);
+
+    // The impl block is still emitted, so that errors in exported methods do not cascade.
+    let export = match export {
+        Ok(export) => export,
+        Err(err) => {
+            let err = err.to_compile_error();
+            return quote!(#impl_block #err).into();
+        }
+    };
 
     let output = {
         let class_name = export.class_ty;
@@ -22,18 +45,36 @@ pub fn methods(meta: TokenStream, input: TokenStream) -> TokenStream {
             .methods
             .into_iter()
             .map(|m| {
-                let name = m.ident.clone().to_string();
+                let sig = m.sig;
+                let mut names = m.args.names;
+                if names.is_empty() {
+                    names.push(sig.ident.to_string());
+                }
+                let rpc_mode = m
+                    .args
+                    .rpc_mode
+                    .unwrap_or_else(|| syn::Ident::new("Disabled", proc_macro2::Span::call_site()));
 
-                quote!(
+                let register = quote!(
                     {
                         let method = gdnative::godot_wrap_method!(
                             #class_name,
-                            #m
+                            #sig
                         );
 
-                        builder.add_method(#name, method);
+                        #(builder.add_method_with_rpc_mode(#names, method, gdnative::init::RpcMode::#rpc_mode);)*
                     }
-                )
+                );
+
+                if m.args.tool {
+                    quote!(
+                        if gdnative::init::is_editor_hint() {
+                            #register
+                        }
+                    )
+                } else {
+                    register
+                }
             })
             .collect::<Vec<_>>();
 
@@ -57,12 +98,43 @@ pub fn methods(meta: TokenStream, input: TokenStream) -> TokenStream {
     TokenStream::from(output)
 }
 
+/// Derives `NativeClass` for a struct.
+///
+/// Fields marked with `#[property]` are exported as properties. The attribute accepts the
+/// following optional arguments:
+///
+/// - `path = "group/name"`: the property path, `base/<field>` by default.
+/// - `default = <expr>`: the default value, `Default::default()` if omitted.
+/// - `hint = <expr>`: a `PropertyHint`, `PropertyHint::None` if omitted.
+/// - `usage = <expr>`: the `PropertyUsage` flags, `PropertyUsage::DEFAULT` if omitted.
+/// - `no_editor`: shorthand for `usage = PropertyUsage::NOEDITOR`.
+/// - `rset = "remote"`: the `RpcMode` for setting the property over the network, using the
+///   same names as `#[export(rpc = ...)]`.
+/// - `get = "method"`, `set = "method"`: accessors with the signatures `fn(&self) -> T` and
+///   `fn(&mut self, T)`, used instead of reading and writing the field directly.
+///
+/// `PropertyHint` and `PropertyUsage` are in scope within `hint` and `usage`.
+///
+/// ```ignore
+/// #[derive(NativeClass)]
+/// #[inherit(Node)]
+/// struct Level {
+///     #[property(path = "level/background", hint = PropertyHint::File { filters: &["*.png"] })]
+///     background: GodotString,
+///     #[property(default = 3, get = "lives", set = "set_lives", no_editor)]
+///     lives: i64,
+/// }
+/// ```
 #[proc_macro_derive(
     NativeClass,
-    attributes(inherit, export, user_data, property, register_with)
+    attributes(inherit, export, user_data, property, register_with, signals)
 )]
 pub fn derive_native_class(input: TokenStream) -> TokenStream {
-    let data = derive_macro::parse_derive_input(input.clone());
+    let input = parse_macro_input!(input as syn::DeriveInput);
+    let data = match derive_macro::parse_derive_input(&input) {
+        Ok(data) => data,
+        Err(err) => return err.to_compile_error().into(),
+    };
 
     // generate NativeClass impl
     let trait_impl = {
@@ -73,18 +145,68 @@ pub fn derive_native_class(input: TokenStream) -> TokenStream {
             .register_callback
             .map(|function_path| quote!(#function_path(builder);))
             .unwrap_or(quote!({}));
+        let signals = data
+            .signals
+            .iter()
+            .map(|signal| quote!(builder.register_signal::<#signal>();));
         let properties = data.properties.iter().map(|(ident, config)| {
-            let default_value = &config.default;
-            let label = format!("base/{}", ident);
+            let path = config
+                .path
+                .clone()
+                .unwrap_or_else(|| format!("base/{}", ident));
+            let default_value = config
+                .default
+                .as_ref()
+                .map(|default| quote!(#default))
+                .unwrap_or_else(|| quote!(Default::default()));
+            let hint = config
+                .hint
+                .as_ref()
+                .map(|hint| quote!(#hint))
+                .unwrap_or_else(|| quote!(PropertyHint::None));
+            let usage = match (&config.usage, config.no_editor) {
+                (Some(usage), _) => quote!(#usage),
+                (None, true) => quote!(PropertyUsage::NOEDITOR),
+                (None, false) => quote!(PropertyUsage::DEFAULT),
+            };
+            let rset_mode = config
+                .rset_mode
+                .clone()
+                .unwrap_or_else(|| syn::Ident::new("Disabled", proc_macro2::Span::call_site()));
+            // Accessors given by name alone are methods of the class.
+            let accessor = |path: &syn::Path| {
+                if path.segments.len() == 1 {
+                    quote!(#name::#path)
+                } else {
+                    quote!(#path)
+                }
+            };
+            let getter = match &config.get {
+                Some(get) => {
+                    let get = accessor(get);
+                    quote!(|this: &#name| #get(this))
+                }
+                None => quote!(|this: &#name| this.#ident.clone()),
+            };
+            let setter = match &config.set {
+                Some(set) => {
+                    let set = accessor(set);
+                    quote!(|this: &mut #name, v| #set(this, v))
+                }
+                None => quote!(|this: &mut #name, v| this.#ident = v),
+            };
             quote!({
-                builder.add_property(gdnative::init::Property{
-                    name: #label,
-                    getter: |this: &#name| this.#ident,
-                    setter: |this: &mut #name, v| this.#ident = v,
+                #[allow(unused_imports)]
+                use gdnative::init::{PropertyHint, PropertyUsage};
+
+                builder.add_property_with_rset(gdnative::init::Property{
+                    name: #path,
+                    getter: #getter,
+                    setter: #setter,
                     default: #default_value,
-                    usage: gdnative::init::PropertyUsage::DEFAULT,
-                    hint: gdnative::init::PropertyHint::None
-                });
+                    usage: #usage,
+                    hint: #hint,
+                }, gdnative::init::RpcMode::#rset_mode);
             })
         });
 
@@ -106,6 +228,7 @@ pub fn derive_native_class(input: TokenStream) -> TokenStream {
 
                 fn register_properties(builder: &gdnative::init::ClassBuilder<Self>) {
                     #(#properties)*;
+                    #(#signals)*
                     #register_callback
                 }
             }
@@ -116,12 +239,62 @@ pub fn derive_native_class(input: TokenStream) -> TokenStream {
     trait_impl.into()
 }
 
-#[proc_macro_derive(ToVariant)]
+/// Implements `gdnative::signal::Signal` for a struct, whose fields are the arguments of the
+/// signal.
+///
+/// The signal is named after the struct in snake_case: `TickWithData` becomes
+/// `tick_with_data`. Runs of capitals are kept together as one word, so `HTTPDone` becomes
+/// `http_done` and `LoadedIO` becomes `loaded_io`. This is the name to use in GDScript, e.g.
+/// with `connect("http_done", ...)`.
+///
+/// Arguments are named after the fields, or `arg0`, `arg1`... for tuple structs. The signal
+/// can be customized with `#[signal(...)]` attributes:
+///
+/// - `#[signal(name = "...")]` on the struct: the name of the signal, instead of the
+///   snake_case name of the struct.
+/// - `#[signal(default = value)]` on a field: the default value of the argument, given as a
+///   literal, which is registered with the signal.
+///
+/// Signals are registered by listing them in `#[signals(...)]` on a `NativeClass`, and are
+/// connected with `godot_connect!`.
+#[proc_macro_derive(Signal, attributes(signal))]
+pub fn derive_signal(input: TokenStream) -> TokenStream {
+    derive_signal::derive_signal(input)
+}
+
+/// Implements `ToVariant` for a struct or an enum.
+///
+/// Structs with named fields become dictionaries, tuple structs become arrays, and newtypes
+/// convert as their only field. Enums are externally tagged by default: a variant becomes a
+/// dictionary with a single entry, mapping its name to its contents.
+///
+/// The conversion can be customized with `#[variant(...)]` attributes, which are shared with
+/// `FromVariant`. Fields accept the following arguments:
+///
+/// - `rename = "..."`: the dictionary key of a named field, instead of its Rust name.
+/// - `skip`: the field is left out, and set to `Default::default()` by `FromVariant`.
+/// - `default` or `default = "path"`: `FromVariant` uses `Default::default()`, or calls the
+///   given function, when the key of the field is missing. With `skip`, the value of the field.
+/// - `to_variant_with = "path"`: converts the field with a `fn(&T) -> Variant`.
+/// - `from_variant_with = "path"`: converts the field with a
+///   `fn(&Variant) -> Result<T, FromVariantError>`.
+/// - `with = "module"`: uses `module::to_variant` and `module::from_variant`.
+///
+/// Enum variants accept `rename = "..."`. Enums themselves accept the following arguments:
+///
+/// - `repr = "int"`: for enums with only unit variants, which convert to their discriminant.
+/// - `tag = "..."`: internally tagged, as a dictionary with the name of the variant under the
+///   `tag` key, next to the fields of struct variants. Tuple variants are not supported.
+/// - `tag = "...", content = "..."`: adjacently tagged, as a dictionary with the name of the
+///   variant under the `tag` key and its contents under the `content` key.
+#[proc_macro_derive(ToVariant, attributes(variant))]
 pub fn derive_to_variant(input: TokenStream) -> TokenStream {
     derive_conv_variant::derive_to_variant(input)
 }
 
-#[proc_macro_derive(FromVariant)]
+/// Implements `FromVariant` for a struct or an enum, in the representation of
+/// [`ToVariant`](derive.ToVariant.html).
+#[proc_macro_derive(FromVariant, attributes(variant))]
 pub fn derive_from_variant(input: TokenStream) -> TokenStream {
     derive_conv_variant::derive_from_variant(input)
 }
@@ -1,34 +1,280 @@
-use syn::{FnArg, ImplItem, ItemImpl, Pat, PatIdent, Signature, Type};
+use syn::{
+    Expr, FnArg, Ident, ImplItem, ItemImpl, Lit, LitStr, Meta, MetaList, NestedMeta, Pat, PatIdent,
+    Signature, Type,
+};
 
-use proc_macro::TokenStream;
+use proc_macro2::Span;
 use std::boxed::Box;
-use syn::export::Span;
 
 pub(crate) struct ClassMethodExport {
     pub(crate) class_ty: Box<Type>,
-    pub(crate) methods: Vec<Signature>,
+    pub(crate) methods: Vec<ExportMethod>,
 }
 
-/// Parse the input.
-///
-/// Returns the TokenStream of the impl block together with a description of methods to export.
-pub(crate) fn parse_method_export(
-    _meta: TokenStream,
-    input: TokenStream,
-) -> (ItemImpl, ClassMethodExport) {
-    let ast = match syn::parse_macro_input::parse::<ItemImpl>(input) {
-        Ok(impl_block) => impl_block,
-        Err(err) => {
-            // if the impl block is ill-formed there is no point in error handling.
-            panic!("{}", err);
+pub(crate) struct ExportMethod {
+    pub(crate) sig: Signature,
+    pub(crate) args: ExportArgs,
+}
+
+/// Arguments of the `#[export]` attribute.
+#[derive(Default)]
+pub(crate) struct ExportArgs {
+    /// Names to register the method under. The Rust name is used if there is none.
+    pub(crate) names: Vec<String>,
+    /// Name of the `RpcMode` variant to register the method with.
+    pub(crate) rpc_mode: Option<Ident>,
+    /// Whether the method is only registered in the editor.
+    pub(crate) tool: bool,
+}
+
+impl ExportArgs {
+    fn parse(attr: &syn::Attribute) -> syn::Result<Self> {
+        let mut args = ExportArgs::default();
+
+        // `#[export]` without arguments is allowed.
+        if attr.tokens.is_empty() {
+            return Ok(args);
+        }
+
+        let nested = match attr.parse_meta()? {
+            Meta::List(MetaList { nested, .. }) => nested,
+            meta => {
+                return Err(syn::Error::new_spanned(
+                    meta,
+                    "expected arguments in parentheses: `#[export(...)]`",
+                ))
+            }
+        };
+
+        for arg in nested {
+            let pair = match arg {
+                NestedMeta::Meta(Meta::NameValue(pair)) => pair,
+                NestedMeta::Meta(Meta::Path(ref path)) if path.is_ident("tool") => {
+                    args.tool = true;
+                    continue;
+                }
+                _ => return Err(syn::Error::new_spanned(arg, "unexpected argument")),
+            };
+
+            if pair.path.is_ident("name") {
+                let lit = string_lit(&pair.lit, "name")?;
+                let name = lit.value();
+                if args.names.contains(&name) {
+                    return Err(syn::Error::new_spanned(
+                        lit,
+                        format!("the method is already exported as {:?}", name),
+                    ));
+                }
+                args.names.push(name);
+            } else if pair.path.is_ident("rpc") {
+                let mode = rpc_mode(string_lit(&pair.lit, "rpc")?)?;
+                if args.rpc_mode.replace(mode).is_some() {
+                    return Err(syn::Error::new_spanned(
+                        pair,
+                        "there is already a rpc mode set",
+                    ));
+                }
+            } else {
+                return Err(syn::Error::new_spanned(pair.path, "unexpected argument"));
+            }
+        }
+
+        Ok(args)
+    }
+}
+
+fn string_lit<'a>(lit: &'a Lit, name: &str) -> syn::Result<&'a LitStr> {
+    match lit {
+        Lit::Str(lit) => Ok(lit),
+        lit => Err(syn::Error::new_spanned(
+            lit,
+            format!("`{}` should be a string literal", name),
+        )),
+    }
+}
+
+/// Maps the names used by GDScript keywords to `RpcMode` variants.
+pub(crate) fn rpc_mode(lit: &LitStr) -> syn::Result<Ident> {
+    let variant = match lit.value().as_str() {
+        "disabled" => "Disabled",
+        "remote" => "Remote",
+        "remotesync" | "sync" => "RemoteSync",
+        "master" => "Master",
+        "mastersync" => "MasterSync",
+        "puppet" | "slave" => "Puppet",
+        "puppetsync" => "PuppetSync",
+        _ => {
+            return Err(syn::Error::new_spanned(
+                lit,
+                "unknown rpc mode, expected one of \"disabled\", \"remote\", \"remotesync\", \
+                 \"master\", \"mastersync\", \"puppet\" or \"puppetsync\"",
+            ))
         }
     };
+    Ok(Ident::new(variant, Span::call_site()))
+}
+
+/// Removes the parameter attributes of an exported method from `sig`, and returns the signature
+/// to pass to `godot_wrap_method!`, where `#[opt]`, `#[default(expr)]` and `#[varargs]` are
+/// written as `#[opt]`, `#[opt = expr]` and `#[varargs]`.
+///
+/// The attributes are removed from every parameter even if some of them are invalid, so that
+/// the method can still be emitted without errors about unknown attributes.
+fn export_signature(sig: &mut Signature) -> syn::Result<Signature> {
+    let mut export = sig.clone();
+    let mut optional = false;
+    let mut varargs = false;
+    let mut errors = None;
+
+    for (arg, export_arg) in sig.inputs.iter_mut().zip(export.inputs.iter_mut()) {
+        let (arg, export_arg) = match (arg, export_arg) {
+            (FnArg::Typed(arg), FnArg::Typed(export_arg)) => (arg, export_arg),
+            _ => continue,
+        };
+
+        if varargs {
+            push_error(
+                &mut errors,
+                syn::Error::new_spanned(&arg, "the `#[varargs]` parameter must be the last one"),
+            );
+        }
+
+        let mut attrs = Vec::new();
+        let mut kind = Vec::new();
+        for attr in arg.attrs.drain(..) {
+            if attr.path.is_ident("opt") || attr.path.is_ident("varargs") {
+                if attr.tokens.is_empty() {
+                    kind.push(attr);
+                } else {
+                    let err = syn::Error::new_spanned(attr.tokens, "unexpected arguments");
+                    push_error(&mut errors, err);
+                }
+            } else if attr.path.is_ident("default") {
+                match attr.parse_args::<Expr>() {
+                    Ok(default) => kind.push(parse_quote!(#[opt = #default])),
+                    Err(err) => push_error(&mut errors, err),
+                }
+            } else {
+                attrs.push(attr);
+            }
+        }
+        arg.attrs = attrs;
 
-    impl_gdnative_expose(ast)
+        if kind.len() > 1 {
+            push_error(
+                &mut errors,
+                syn::Error::new_spanned(
+                    &kind[1],
+                    "only one of `#[opt]`, `#[default]` and `#[varargs]` is allowed per parameter",
+                ),
+            );
+        }
+        match kind.first() {
+            Some(attr) if attr.path.is_ident("varargs") => varargs = true,
+            Some(_) => optional = true,
+            None if optional => push_error(
+                &mut errors,
+                syn::Error::new_spanned(&arg, "required parameters cannot follow optional ones"),
+            ),
+            None => {}
+        }
+        export_arg.attrs = kind;
+    }
+
+    match errors {
+        Some(errors) => Err(errors),
+        None => Ok(export),
+    }
+}
+
+/// Checks that an exported method can be wrapped by `godot_wrap_method!`: it takes `&self` or
+/// `&mut self` and the owner, and has no generic parameters.
+fn check_signature(sig: &Signature) -> syn::Result<()> {
+    let generics = &sig.generics;
+    if let Some(param) = generics.type_params().next() {
+        return Err(syn::Error::new_spanned(
+            param,
+            "type parameters are not allowed in exported methods",
+        ));
+    }
+    if let Some(param) = generics.lifetimes().next() {
+        return Err(syn::Error::new_spanned(
+            param,
+            "lifetime parameters are not allowed in exported methods",
+        ));
+    }
+    if let Some(param) = generics.const_params().next() {
+        return Err(syn::Error::new_spanned(
+            param,
+            "const parameters are not allowed in exported methods",
+        ));
+    }
+
+    let mut inputs = sig.inputs.iter();
+    match inputs.next() {
+        Some(FnArg::Receiver(receiver)) if receiver.reference.is_some() => {}
+        Some(arg) => {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "exported methods must take `&self` or `&mut self`",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new(
+                sig.paren_token.span,
+                "exported methods must take `&self` or `&mut self`",
+            ))
+        }
+    }
+    match inputs.next() {
+        Some(FnArg::Typed(owner)) if owner.attrs.is_empty() => {}
+        Some(arg) => {
+            return Err(syn::Error::new_spanned(
+                arg,
+                "the owner cannot be optional: the argument after `self` must be the owner",
+            ))
+        }
+        None => {
+            return Err(syn::Error::new(
+                sig.paren_token.span,
+                "missing owner argument: exported methods must take the owner after `self`, \
+                 as in `fn method(&self, owner: Node)`",
+            ))
+        }
+    }
+
+    for arg in inputs {
+        if let FnArg::Typed(arg) = arg {
+            match &*arg.pat {
+                Pat::Ident(_) | Pat::Wild(_) => {}
+                pat => {
+                    return Err(syn::Error::new_spanned(
+                        pat,
+                        "parameters of exported methods must be identifiers or `_`",
+                    ))
+                }
+            }
+        }
+    }
+
+    Ok(())
+}
+
+/// Collects several errors into one, so that they are all reported.
+pub(crate) fn push_error(errors: &mut Option<syn::Error>, err: syn::Error) {
+    match errors {
+        Some(errors) => errors.combine(err),
+        None => *errors = Some(err),
+    }
 }
 
 /// Extract the data to export from the impl block.
-fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
+///
+/// Returns the impl block without the attributes of this macro, which should be emitted even if
+/// the exported methods are invalid, together with a description of methods to export.
+pub(crate) fn impl_gdnative_expose(
+    ast: ItemImpl,
+) -> (ItemImpl, Result<ClassMethodExport, syn::Error>) {
     // the ast input is used for inspecting.
     // this clone is used to remove all attributes so that the resulting
     // impl block actually compiles again.
@@ -43,7 +289,8 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
         methods: vec![],
     };
 
-    let mut methods_to_export = Vec::<Signature>::new();
+    let mut methods_to_export = Vec::<ExportMethod>::new();
+    let mut errors = None;
 
     // extract all methods that have the #[export] attribute.
     // add all items back to the impl block again.
@@ -67,10 +314,23 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
                 });
 
                 if let Some(idx) = attribute_pos {
-                    // TODO renaming? rpc modes?
-                    let _attr = method.attrs.remove(idx);
+                    let attr = method.attrs.remove(idx);
 
-                    methods_to_export.push(method.sig.clone());
+                    // The signature is checked before the parameter attributes are removed, which
+                    // happens even if it is invalid.
+                    let checked = check_signature(&method.sig);
+                    let sig = export_signature(&mut method.sig);
+                    let checked = checked.and_then(|()| {
+                        Ok(ExportMethod {
+                            sig: sig?,
+                            args: ExportArgs::parse(&attr)?,
+                        })
+                    });
+
+                    match checked {
+                        Ok(export_method) => methods_to_export.push(export_method),
+                        Err(err) => push_error(&mut errors, err),
+                    }
                 }
 
                 ImplItem::Method(method)
@@ -81,25 +341,17 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
         result.items.push(item);
     }
 
-    // check if the export methods have the proper "shape", the write them
-    // into the list of things to export.
-    {
-        for mut method in methods_to_export {
-            let generics = &method.generics;
-
-            if generics.type_params().count() > 0 {
-                eprintln!("type parameters not allowed in exported functions");
-                continue;
-            }
-            if generics.lifetimes().count() > 0 {
-                eprintln!("lifetime parameters not allowed in exported functions");
-                continue;
-            }
-            if generics.const_params().count() > 0 {
-                eprintln!("const parameters not allowed in exported functions");
-                continue;
-            }
+    if let Some(errors) = errors {
+        return (result, Err(errors));
+    }
 
+    // write the exported methods into the list of things to export.
+    {
+        for ExportMethod {
+            sig: mut method,
+            args,
+        } in methods_to_export
+        {
             // remove "mut" from arguments.
             // give every wildcard a (hopefully) unique name.
             method
@@ -132,9 +384,9 @@ fn impl_gdnative_expose(ast: ItemImpl) -> (ItemImpl, ClassMethodExport) {
             // exported binding is fine.
             method.unsafety = None;
 
-            export.methods.push(method);
+            export.methods.push(ExportMethod { sig: method, args });
         }
     }
 
-    (result, export)
+    (result, Ok(export))
 }
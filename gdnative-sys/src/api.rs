@@ -98,6 +98,53 @@ impl GodotApi {
             )*
         }
     }
+
+    /// Builds an API table from a lookup function instead of the structs passed by the engine.
+    ///
+    /// `lookup` is called once for every function in the table with the function's name, e.g.
+    /// `"godot_string_new"`, and should return a pointer to a function with the matching
+    /// signature. Functions for which `lookup` returns `None` are replaced by a stub that
+    /// aborts the process with the name of the missing function when called.
+    ///
+    /// This is intended for stand-in implementations of the API, such as the mock used to
+    /// test the bindings without a running engine.
+    pub unsafe fn from_lookup<F>(mut lookup: F) -> GodotApi
+    where
+        F: FnMut(&'static str) -> Option<*const std::os::raw::c_void>,
+    {
+        GodotApi {
+            $(
+                $(
+                    $core_name: match lookup(stringify!($core_name)) {
+                        Some(ptr) => std::mem::transmute::<*const std::os::raw::c_void, $core_ty>(ptr),
+                        None => {
+                            extern "C" fn missing() -> ! {
+                                eprintln!(concat!("Missing function: ", stringify!($core_name)));
+                                std::process::abort()
+                            }
+                            let stub: extern "C" fn() -> ! = missing;
+                            std::mem::transmute::<extern "C" fn() -> !, $core_ty>(stub)
+                        }
+                    },
+                )*
+            )*
+            $(
+                $(
+                    $ext_name: match lookup(stringify!($ext_name)) {
+                        Some(ptr) => std::mem::transmute::<*const std::os::raw::c_void, $ext_ty>(ptr),
+                        None => {
+                            extern "C" fn missing() -> ! {
+                                eprintln!(concat!("Missing function: ", stringify!($ext_name)));
+                                std::process::abort()
+                            }
+                            let stub: extern "C" fn() -> ! = missing;
+                            std::mem::transmute::<extern "C" fn() -> !, $ext_ty>(stub)
+                        }
+                    },
+                )*
+            )*
+        }
+    }
 }
     )
 }
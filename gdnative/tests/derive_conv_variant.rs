@@ -0,0 +1,280 @@
+use gdnative::mock;
+use gdnative::*;
+
+#[derive(Debug, PartialEq, ToVariant, FromVariant)]
+struct Renamed {
+    #[variant(rename = "Name")]
+    name: String,
+    #[variant(skip)]
+    cache: Vec<u8>,
+    #[variant(default)]
+    level: i64,
+    #[variant(default = "default_speed")]
+    speed: f64,
+    #[variant(with = "as_string")]
+    id: u32,
+    #[variant(to_variant_with = "doubled", from_variant_with = "halved")]
+    half: i64,
+}
+
+fn default_speed() -> f64 {
+    1.5
+}
+
+fn doubled(value: &i64) -> Variant {
+    Variant::from_i64(value * 2)
+}
+
+fn halved(variant: &Variant) -> Result<i64, FromVariantError> {
+    i64::from_variant(variant).map(|value| value / 2)
+}
+
+mod as_string {
+    use gdnative::*;
+
+    pub fn to_variant(id: &u32) -> Variant {
+        Variant::from_str(id.to_string())
+    }
+
+    pub fn from_variant(variant: &Variant) -> Result<u32, FromVariantError> {
+        String::from_variant(variant)?
+            .parse()
+            .map_err(FromVariantError::custom)
+    }
+}
+
+#[derive(Debug, PartialEq, ToVariant, FromVariant)]
+struct Tuple(i64, #[variant(skip)] bool, String);
+
+#[derive(Copy, Clone, Debug, PartialEq, ToVariant, FromVariant)]
+#[variant(repr = "int")]
+enum Direction {
+    Left = 1,
+    Right,
+    Up = 10,
+}
+
+#[derive(Debug, PartialEq, ToVariant, FromVariant)]
+#[variant(tag = "type")]
+enum Internal {
+    Idle,
+    #[variant(rename = "move")]
+    Move {
+        speed: f64,
+    },
+}
+
+#[derive(Debug, PartialEq, ToVariant, FromVariant)]
+#[variant(tag = "t", content = "c")]
+enum Adjacent {
+    Idle,
+    Jump(f64),
+    Pair(i64, i64),
+    Move { speed: f64 },
+}
+
+#[derive(Debug, PartialEq, ToVariant, FromVariant)]
+enum External {
+    #[variant(rename = "jump")]
+    Jump(f64),
+}
+
+fn dict(variant: &Variant) -> Dictionary {
+    variant.try_to_dictionary().expect("should be a dictionary")
+}
+
+#[test]
+fn struct_field_attributes() {
+    mock::install();
+
+    let value = Renamed {
+        name: "foo".into(),
+        cache: vec![1, 2],
+        level: 3,
+        speed: 2.0,
+        id: 42,
+        half: 5,
+    };
+    let variant = value.to_variant();
+    let d = dict(&variant);
+    assert_eq!(5, d.len());
+    assert_eq!("foo", d.get(&"Name".into()).to_string());
+    assert!(!d.contains(&"name".into()));
+    assert!(!d.contains(&"cache".into()));
+    assert_eq!("42", d.get(&"id".into()).to_string());
+    assert_eq!(Some(10), d.get(&"half".into()).try_to_i64());
+
+    let back = Renamed::from_variant(&variant).unwrap();
+    assert_eq!(
+        Renamed {
+            cache: vec![],
+            ..value
+        },
+        back
+    );
+
+    let mut d = Dictionary::new();
+    d.set(&"Name".into(), &"bar".into());
+    d.set(&"id".into(), &"7".into());
+    d.set(&"half".into(), &Variant::from_i64(4));
+    let back = Renamed::from_variant(&d.to_variant()).unwrap();
+    assert_eq!(0, back.level);
+    assert_eq!(1.5, back.speed);
+    assert_eq!(7, back.id);
+    assert_eq!(2, back.half);
+
+    d.set(&"id".into(), &"seven".into());
+    assert!(Renamed::from_variant(&d.to_variant()).is_err());
+}
+
+#[test]
+fn skipped_tuple_field() {
+    mock::install();
+
+    let variant = Tuple(1, true, "a".into()).to_variant();
+    assert_eq!(2, variant.try_to_array().unwrap().len());
+    assert_eq!(
+        Ok(Tuple(1, false, "a".into())),
+        Tuple::from_variant(&variant)
+    );
+}
+
+#[test]
+fn int_repr() {
+    mock::install();
+
+    assert_eq!(Some(1), Direction::Left.to_variant().try_to_i64());
+    assert_eq!(Some(2), Direction::Right.to_variant().try_to_i64());
+    assert_eq!(
+        Ok(Direction::Up),
+        Direction::from_variant(&Variant::from_i64(10))
+    );
+    assert_eq!(
+        Err(FromVariantError::UnknownEnumVariant {
+            variant: "3".into(),
+            expected: &["Left", "Right", "Up"],
+        }),
+        Direction::from_variant(&Variant::from_i64(3))
+    );
+}
+
+#[test]
+fn internally_tagged() {
+    mock::install();
+
+    let variant = Internal::Move { speed: 2.0 }.to_variant();
+    let d = dict(&variant);
+    assert_eq!("move", d.get(&"type".into()).to_string());
+    assert_eq!(Some(2.0), d.get(&"speed".into()).try_to_f64());
+    assert_eq!(
+        Ok(Internal::Move { speed: 2.0 }),
+        Internal::from_variant(&variant)
+    );
+
+    let variant = Internal::Idle.to_variant();
+    assert_eq!(1, dict(&variant).len());
+    assert_eq!(Ok(Internal::Idle), Internal::from_variant(&variant));
+
+    let mut d = Dictionary::new();
+    d.set(&"speed".into(), &Variant::from_f64(1.0));
+    assert!(Internal::from_variant(&d.to_variant()).is_err());
+}
+
+#[test]
+fn adjacently_tagged() {
+    mock::install();
+
+    let values = vec![
+        Adjacent::Idle,
+        Adjacent::Jump(0.5),
+        Adjacent::Pair(1, 2),
+        Adjacent::Move { speed: 3.0 },
+    ];
+    for value in values {
+        let variant = value.to_variant();
+        assert_eq!(Ok(&value), Adjacent::from_variant(&variant).as_ref());
+    }
+
+    let d = dict(&Adjacent::Jump(0.5).to_variant());
+    assert_eq!("Jump", d.get(&"t".into()).to_string());
+    assert_eq!(Some(0.5), d.get(&"c".into()).try_to_f64());
+    assert!(!dict(&Adjacent::Idle.to_variant()).contains(&"c".into()));
+}
+
+#[test]
+fn renamed_variant() {
+    mock::install();
+
+    let variant = External::Jump(1.0).to_variant();
+    assert_eq!(Some(1.0), dict(&variant).get(&"jump".into()).try_to_f64());
+    assert_eq!(Ok(External::Jump(1.0)), External::from_variant(&variant));
+
+    let mut d = Dictionary::new();
+    d.set(&"jump".into(), &"high".into());
+    match External::from_variant(&d.to_variant()) {
+        Err(FromVariantError::InvalidEnumVariant { variant, .. }) => assert_eq!("jump", variant),
+        other => panic!("unexpected result: {:?}", other),
+    }
+}
+
+#[derive(Debug, PartialEq, ToVariant, FromVariant)]
+struct Item {
+    item_id: i64,
+}
+
+#[derive(Debug, PartialEq, ToVariant, FromVariant)]
+struct Inventory {
+    items: Vec<Item>,
+    equipped: Option<External>,
+}
+
+#[test]
+fn error_paths() {
+    mock::install();
+
+    let mut item = Dictionary::new();
+    item.set(&"item_id".into(), &"sword".into());
+    let mut d = Dictionary::new();
+    d.set(
+        &"items".into(),
+        &vec![Item { item_id: 1 }.to_variant(), item.to_variant()].to_variant(),
+    );
+
+    let err = Inventory::from_variant(&d.to_variant()).unwrap_err();
+    assert_eq!(
+        vec![
+            FromVariantPathSegment::Field("items"),
+            FromVariantPathSegment::Item(1),
+            FromVariantPathSegment::Field("item_id"),
+        ],
+        err.path()
+    );
+    assert_eq!(
+        &FromVariantError::InvalidVariantType {
+            variant_type: VariantType::GodotString,
+            expected: VariantType::I64,
+        },
+        err.leaf()
+    );
+    assert_eq!(
+        ".items[1].item_id: expected I64, got GodotString",
+        err.to_string()
+    );
+
+    let mut jump = Dictionary::new();
+    jump.set(&"jump".into(), &"high".into());
+    d.set(&"items".into(), &VariantArray::new().to_variant());
+    d.set(&"equipped".into(), &jump.to_variant());
+    let err = Inventory::from_variant(&d.to_variant()).unwrap_err();
+    assert_eq!(
+        ".equipped::jump: expected F64, got GodotString",
+        err.to_string()
+    );
+
+    let err = Inventory::from_variant(&Variant::from_i64(1)).unwrap_err();
+    assert!(err.path().is_empty());
+    assert_eq!(
+        "invalid struct representation: expected Dictionary, got I64",
+        err.to_string()
+    );
+}
@@ -0,0 +1,95 @@
+use gdnative::init::{PropertyHint, PropertyUsage};
+use gdnative::mock::{self, MockInstance};
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Properties {
+    #[property(default = 10)]
+    plain: i64,
+    #[property(path = "level/background", hint = PropertyHint::File { filters: &["*.png"] })]
+    background: String,
+    #[property(default = 2, get = "get_doubled", set = "set_doubled", no_editor)]
+    doubled: i64,
+    #[property(usage = PropertyUsage::STORAGE | PropertyUsage::CHECKABLE)]
+    checked: bool,
+}
+
+#[methods]
+impl Properties {
+    fn _init(_owner: Reference) -> Self {
+        Properties {
+            plain: 10,
+            background: String::new(),
+            doubled: 1,
+            checked: false,
+        }
+    }
+
+    fn get_doubled(&self) -> i64 {
+        self.doubled * 2
+    }
+
+    fn set_doubled(&mut self, value: i64) {
+        self.doubled = value / 2;
+    }
+}
+
+#[test]
+fn property_registration() {
+    mock::init_nativescript(|handle| handle.add_class::<Properties>());
+    let info = mock::class_info("Properties").unwrap();
+
+    let paths = info
+        .properties
+        .iter()
+        .map(|p| p.path.as_str())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        vec![
+            "base/plain",
+            "level/background",
+            "base/doubled",
+            "base/checked"
+        ],
+        paths
+    );
+
+    let plain = info.property("base/plain").unwrap();
+    assert_eq!(Some(10), plain.default.try_to_i64());
+    assert_eq!(PropertyUsage::DEFAULT, plain.usage);
+
+    let background = info.property("level/background").unwrap();
+    assert_eq!(
+        PropertyHint::File { filters: &[] }.to_sys(),
+        background.hint
+    );
+    assert_eq!("*.png", background.hint_string);
+    assert_eq!(Some(String::new()), background.default.try_to_string());
+
+    let doubled = info.property("base/doubled").unwrap();
+    assert_eq!(PropertyUsage::NOEDITOR, doubled.usage);
+
+    let checked = info.property("base/checked").unwrap();
+    assert_eq!(
+        PropertyUsage::STORAGE | PropertyUsage::CHECKABLE,
+        checked.usage
+    );
+    assert_eq!(Some(false), checked.default.try_to_bool());
+}
+
+#[test]
+fn property_accessors() {
+    mock::init_nativescript(|handle| handle.add_class::<Properties>());
+    let instance = MockInstance::new("Properties").unwrap();
+
+    assert_eq!(Some(2), instance.get("base/doubled").unwrap().try_to_i64());
+    assert!(instance.set("base/doubled", &Variant::from_i64(8)));
+    assert_eq!(Some(8), instance.get("base/doubled").unwrap().try_to_i64());
+
+    assert!(instance.set("level/background", &Variant::from_str("res://sky.png")));
+    assert_eq!(
+        Some("res://sky.png".to_string()),
+        instance.get("level/background").unwrap().try_to_string()
+    );
+}
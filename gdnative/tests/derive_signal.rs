@@ -0,0 +1,28 @@
+// The signals are never emitted, only their names are checked.
+#![allow(dead_code)]
+
+use gdnative::signal::Signal;
+use gdnative::*;
+
+#[derive(Signal)]
+struct TickWithData {
+    data: i64,
+}
+
+#[derive(Signal)]
+struct HTTPRequestDone;
+
+#[derive(Signal)]
+struct LoadedIO;
+
+#[derive(Signal)]
+#[signal(name = "custom")]
+struct Renamed;
+
+#[test]
+fn default_names() {
+    assert_eq!("tick_with_data", TickWithData::NAME);
+    assert_eq!("http_request_done", HTTPRequestDone::NAME);
+    assert_eq!("loaded_io", LoadedIO::NAME);
+    assert_eq!("custom", Renamed::NAME);
+}
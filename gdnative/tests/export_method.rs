@@ -0,0 +1,303 @@
+use gdnative::mock::{self, MockInstance};
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Networked {
+    #[property(rset = "puppet")]
+    position: i64,
+}
+
+#[methods]
+impl Networked {
+    fn _init(_owner: Reference) -> Self {
+        Networked { position: 0 }
+    }
+
+    #[export]
+    fn local(&self, _owner: Reference) -> i64 {
+        self.position
+    }
+
+    #[export(rpc = "remote")]
+    fn remote(&self, _owner: Reference) {}
+
+    #[export(rpc = "remotesync")]
+    fn remote_sync(&self, _owner: Reference) {}
+
+    #[export(rpc = "master")]
+    fn master(&self, _owner: Reference) {}
+
+    #[export(rpc = "mastersync")]
+    fn master_sync(&self, _owner: Reference) {}
+
+    #[export(rpc = "puppet")]
+    fn puppet(&self, _owner: Reference) {}
+
+    #[export(rpc = "puppetsync")]
+    fn puppet_sync(&self, _owner: Reference) {}
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Optional;
+
+#[methods]
+impl Optional {
+    fn _init(_owner: Reference) -> Self {
+        Optional
+    }
+
+    #[export]
+    fn sum(&self, _owner: Reference, a: i64, #[opt] b: i64, #[default(100)] c: i64) -> i64 {
+        a + b + c
+    }
+
+    #[export]
+    fn count(&self, _owner: Reference, first: String, #[varargs] rest: &[Variant]) -> i64 {
+        first.len() as i64 + rest.len() as i64
+    }
+}
+
+#[test]
+fn optional_arguments() {
+    mock::init_nativescript(|handle| handle.add_class::<Optional>());
+    let instance = MockInstance::new("Optional").unwrap();
+
+    let sum = |args: &[i64]| {
+        let args = args
+            .iter()
+            .map(|&n| Variant::from_i64(n))
+            .collect::<Vec<_>>();
+        instance.call("sum", &args).unwrap().try_to_i64()
+    };
+    assert_eq!(Some(101), sum(&[1]));
+    assert_eq!(Some(103), sum(&[1, 2]));
+    assert_eq!(Some(6), sum(&[1, 2, 3]));
+
+    // Too few or too many arguments are rejected.
+    assert!(instance.call("sum", &[]).unwrap().is_nil());
+    assert!(sum(&[1, 2, 3, 4]).is_none());
+}
+
+#[test]
+fn varargs() {
+    mock::init_nativescript(|handle| handle.add_class::<Optional>());
+    let instance = MockInstance::new("Optional").unwrap();
+
+    let first = Variant::from_str("abc");
+    let count = instance.call("count", &[first.clone()]).unwrap();
+    assert_eq!(Some(3), count.try_to_i64());
+
+    let args = [first, Variant::from_i64(1), Variant::new()];
+    let count = instance.call("count", &args).unwrap();
+    assert_eq!(Some(5), count.try_to_i64());
+
+    assert!(instance.call("count", &[]).unwrap().is_nil());
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Renamed;
+
+#[methods]
+impl Renamed {
+    fn _init(_owner: Reference) -> Self {
+        Renamed
+    }
+
+    #[export(name = "getValue")]
+    fn get_value(&self, _owner: Reference) -> i64 {
+        42
+    }
+
+    #[export(name = "size", name = "length", rpc = "remote")]
+    fn len(&self, _owner: Reference) -> i64 {
+        3
+    }
+
+    #[export(tool)]
+    fn refresh(&self, _owner: Reference) {}
+}
+
+#[test]
+fn renamed_methods() {
+    mock::init_nativescript(|handle| handle.add_class::<Renamed>());
+    let info = mock::class_info("Renamed").unwrap();
+
+    assert!(info.method("get_value").is_none());
+    assert!(info.method("len").is_none());
+    assert_eq!(
+        init::RpcMode::Remote.to_sys(),
+        info.method("length").unwrap().rpc_mode
+    );
+
+    let instance = MockInstance::new("Renamed").unwrap();
+    let value = instance.call("getValue", &[]).unwrap();
+    assert_eq!(Some(42), value.try_to_i64());
+    assert_eq!(Some(3), instance.call("size", &[]).unwrap().try_to_i64());
+    assert_eq!(Some(3), instance.call("length", &[]).unwrap().try_to_i64());
+}
+
+#[test]
+fn tool_methods() {
+    mock::set_editor_hint(false);
+    mock::init_nativescript(|handle| handle.add_tool_class::<Renamed>());
+    assert!(mock::class_info("Renamed")
+        .unwrap()
+        .method("refresh")
+        .is_none());
+
+    mock::set_editor_hint(true);
+    mock::init_nativescript(|handle| handle.add_tool_class::<Renamed>());
+    assert!(mock::class_info("Renamed")
+        .unwrap()
+        .method("refresh")
+        .is_some());
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Fallible;
+
+#[methods]
+impl Fallible {
+    fn _init(_owner: Reference) -> Self {
+        Fallible
+    }
+
+    #[export]
+    fn parse(&self, _owner: Reference, text: String) -> Result<i64, std::num::ParseIntError> {
+        text.parse()
+    }
+
+    #[export]
+    fn read(&self, _owner: Reference, path: String) -> std::io::Result<String> {
+        std::fs::read_to_string(path)
+    }
+
+    #[export]
+    fn check(&self, _owner: Reference, value: i64) -> Result<i64, Vec<i64>> {
+        if value >= 0 {
+            Ok(value)
+        } else {
+            Err(vec![value])
+        }
+    }
+
+    #[export]
+    fn fail(&self, _owner: Reference) {
+        panic!("failed on purpose");
+    }
+
+    #[export]
+    fn sum(&self, _owner: Reference, numbers: Vec<i64>) -> i64 {
+        numbers.iter().sum()
+    }
+}
+
+#[test]
+fn result_errors() {
+    mock::init_nativescript(|handle| handle.add_class::<Fallible>());
+    let instance = MockInstance::new("Fallible").unwrap();
+    mock::take_messages();
+
+    let ok = instance.call("parse", &[Variant::from_str("42")]).unwrap();
+    assert_eq!(Some(42), ok.try_to_i64());
+    assert!(mock::take_messages().is_empty());
+
+    let err = instance.call("parse", &[Variant::from_str("x")]).unwrap();
+    assert!(err.is_nil());
+    let messages = mock::take_messages();
+    assert_eq!(1, messages.len());
+    assert_eq!(mock::MessageLevel::Error, messages[0].level);
+    assert!(messages[0]
+        .text
+        .starts_with("Fallible::parse returned an error: invalid digit found in string"));
+
+    // Aliases of `Result` are detected by their type.
+    let missing = Variant::from_str("/nonexistent/gdnative-test");
+    assert!(instance.call("read", &[missing]).unwrap().is_nil());
+    let messages = mock::take_messages();
+    assert_eq!(1, messages.len());
+    assert!(messages[0]
+        .text
+        .starts_with("Fallible::read returned an error: "));
+
+    // Errors that only implement `ToVariant` are converted like any other value.
+    let err = instance.call("check", &[Variant::from_i64(-1)]).unwrap();
+    let dict = err.try_to_dictionary().expect("should be a dictionary");
+    assert!(dict.contains(&"Err".into()));
+    assert!(mock::take_messages().is_empty());
+}
+
+#[test]
+fn panic_messages() {
+    mock::init_nativescript(|handle| handle.add_class::<Fallible>());
+    let instance = MockInstance::new("Fallible").unwrap();
+    mock::take_messages();
+
+    assert!(instance.call("fail", &[]).unwrap().is_nil());
+    let messages = mock::take_messages();
+    assert_eq!(1, messages.len());
+    let text = &messages[0].text;
+    assert!(
+        text.starts_with("Fallible::fail panicked: 'failed on purpose' at "),
+        "{}",
+        text
+    );
+    assert!(text.contains("export_method.rs"), "{}", text);
+}
+
+#[test]
+fn rpc_modes() {
+    mock::init_nativescript(|handle| handle.add_class::<Networked>());
+    let info = mock::class_info("Networked").unwrap();
+
+    let modes = [
+        ("local", init::RpcMode::Disabled),
+        ("remote", init::RpcMode::Remote),
+        ("remote_sync", init::RpcMode::RemoteSync),
+        ("master", init::RpcMode::Master),
+        ("master_sync", init::RpcMode::MasterSync),
+        ("puppet", init::RpcMode::Puppet),
+        ("puppet_sync", init::RpcMode::PuppetSync),
+    ];
+    for &(method, mode) in modes.iter() {
+        assert_eq!(
+            mode.to_sys(),
+            info.method(method).unwrap().rpc_mode,
+            "{}",
+            method
+        );
+    }
+
+    let position = info.property("base/position").unwrap();
+    assert_eq!(init::RpcMode::Puppet.to_sys(), position.rset_mode);
+
+    let instance = MockInstance::new("Networked").unwrap();
+    assert_eq!(Some(0), instance.call("local", &[]).unwrap().try_to_i64());
+}
+
+#[test]
+fn argument_errors() {
+    mock::init_nativescript(|handle| handle.add_class::<Fallible>());
+    let instance = MockInstance::new("Fallible").unwrap();
+    mock::take_messages();
+
+    let numbers = (1, String::from("two")).to_variant();
+    assert!(instance.call("sum", &[numbers]).unwrap().is_nil());
+    let messages = mock::take_messages();
+    assert_eq!(1, messages.len());
+    let text = &messages[0].text;
+    assert!(
+        text.starts_with("Cannot convert argument #1 (numbers: "),
+        "{}",
+        text
+    );
+    assert!(
+        text.contains("): [1]: expected I64, got GodotString"),
+        "{}",
+        text
+    );
+}
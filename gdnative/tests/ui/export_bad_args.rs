@@ -0,0 +1,23 @@
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Foo;
+
+#[methods]
+impl Foo {
+    fn _init(_owner: Reference) -> Self {
+        Foo
+    }
+
+    #[export(rpc = "everyone")]
+    fn bad_rpc(&self, _owner: Reference) {}
+
+    #[export(rename = "foo")]
+    fn unknown(&self, _owner: Reference) {}
+
+    #[export]
+    fn required_after_optional(&self, _owner: Reference, #[opt] _a: i64, _b: i64) {}
+}
+
+fn main() {}
@@ -0,0 +1,23 @@
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Foo;
+
+#[methods]
+impl Foo {
+    fn _init(_owner: Reference) -> Self {
+        Foo
+    }
+
+    #[export]
+    fn generic<T: ToVariant>(&self, _owner: Reference, _value: T) {}
+
+    #[export]
+    fn borrowed<'a>(&self, _owner: Reference, _value: &'a str) {}
+
+    #[export]
+    fn generic_optional<T: Default>(&self, _owner: Reference, #[opt] _value: T) {}
+}
+
+fn main() {}
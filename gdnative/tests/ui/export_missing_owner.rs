@@ -0,0 +1,20 @@
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Foo;
+
+#[methods]
+impl Foo {
+    fn _init(_owner: Reference) -> Self {
+        Foo
+    }
+
+    #[export]
+    fn no_owner(&self) {}
+
+    #[export]
+    fn by_value(self, _owner: Reference) {}
+}
+
+fn main() {}
@@ -0,0 +1,9 @@
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+enum NotAStruct {
+    A,
+}
+
+fn main() {}
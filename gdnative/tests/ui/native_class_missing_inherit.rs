@@ -0,0 +1,12 @@
+use gdnative::*;
+
+#[derive(NativeClass)]
+struct NoBase;
+
+impl NoBase {
+    fn _init(_owner: Reference) -> Self {
+        NoBase
+    }
+}
+
+fn main() {}
@@ -0,0 +1,18 @@
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Foo {
+    #[property(path = 42)]
+    bar: i64,
+    #[property(rset = "everyone")]
+    baz: i64,
+}
+
+impl Foo {
+    fn _init(_owner: Reference) -> Self {
+        Foo { bar: 1, baz: 2 }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,16 @@
+use gdnative::*;
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+struct Foo {
+    #[property(default = 1, color = 2)]
+    bar: i64,
+}
+
+impl Foo {
+    fn _init(_owner: Reference) -> Self {
+        Foo { bar: 1 }
+    }
+}
+
+fn main() {}
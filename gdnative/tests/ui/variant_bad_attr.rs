@@ -0,0 +1,22 @@
+use gdnative::*;
+
+#[derive(ToVariant, FromVariant)]
+#[variant(repr = "int")]
+enum Direction {
+    Left,
+    Custom(i64),
+}
+
+#[derive(ToVariant, FromVariant)]
+struct Foo {
+    #[variant(rename = "Bar", color = "red")]
+    bar: i64,
+}
+
+#[derive(ToVariant, FromVariant)]
+#[variant(tag = "type")]
+struct Tagged {
+    bar: i64,
+}
+
+fn main() {}
@@ -9,6 +9,8 @@ pub extern "C" fn run_tests(
 ) -> gdnative::sys::godot_variant {
     let mut status = true;
     status &= gdnative::test_string();
+    status &= gdnative::test_string_name();
+    status &= gdnative::test_node_path();
 
     status &= gdnative::test_dictionary();
     // status &= gdnative::test_dictionary_clone_clear();
@@ -16,6 +18,9 @@ pub extern "C" fn run_tests(
     status &= gdnative::test_array();
     // status &= gdnative::test_array_clone_clear();
 
+    status &= gdnative::test_typed_array();
+    status &= gdnative::test_typed_dictionary();
+
     status &= gdnative::test_variant_nil();
     status &= gdnative::test_variant_i64();
     status &= gdnative::test_variant_bool();
@@ -28,6 +33,11 @@ pub extern "C" fn run_tests(
     status &= gdnative::test_variant_result();
     status &= gdnative::test_to_variant_iter();
     status &= gdnative::test_variant_tuple();
+    status &= gdnative::test_variant_std_types();
+    status &= gdnative::test_variant_evaluate();
+    status &= gdnative::test_variant_ops();
+    status &= gdnative::marshal::test_marshal_value_round_trip();
+    status &= gdnative::marshal::test_marshal_from_variant();
 
     status &= gdnative::test_byte_array_access();
     status &= gdnative::test_int32_array_access();
@@ -36,6 +46,7 @@ pub extern "C" fn run_tests(
     status &= gdnative::test_string_array_access();
     status &= gdnative::test_vector2_array_access();
     status &= gdnative::test_vector3_array_access();
+    status &= gdnative::test_pool_array_api();
 
     status &= test_constructor();
     status &= test_underscore_method_binding();
@@ -45,6 +56,9 @@ pub extern "C" fn run_tests(
     status &= test_owner_free_ub();
 
     status &= test_variant_call_args();
+    status &= test_typed_signals();
+    status &= test_node_lookup();
+    status &= test_array_sort_custom();
 
     gdnative::Variant::from_bool(status).forget()
 }
@@ -131,6 +145,11 @@ impl Foo {
         }
     }
 
+    #[export]
+    fn greater(&self, _owner: Reference, a: i64, b: i64) -> bool {
+        a > b
+    }
+
     #[export]
     fn choose_variant(&self, _owner: Reference, a: i32, what: Variant, b: f64) -> Variant {
         let what = what.try_to_string().expect("should be string");
@@ -151,10 +170,7 @@ fn test_rust_class_construction() -> bool {
         assert_eq!(Ok(42), foo.map(|foo, owner| { foo.answer(owner) }));
 
         let mut base = foo.into_base();
-        assert_eq!(
-            Some(42),
-            unsafe { base.call("answer".into(), &[]) }.try_to_i64()
-        );
+        assert_eq!(Some(42), unsafe { base.call("answer", &[]) }.try_to_i64());
 
         let foo = Instance::<Foo>::try_from_base(base).expect("should be able to downcast");
         assert_eq!(Ok(42), foo.map(|foo, owner| { foo.answer(owner) }));
@@ -233,7 +249,7 @@ fn test_owner_free_ub() -> bool {
             let mut base = bar.into_base();
             assert_eq!(
                 Some(true),
-                base.call("set_script_is_not_ub".into(), &[]).try_to_bool()
+                base.call("set_script_is_not_ub", &[]).try_to_bool()
             );
             base.free();
         }
@@ -244,9 +260,7 @@ fn test_owner_free_ub() -> bool {
                 .expect("lock should not fail");
             assert_eq!(
                 Some(true),
-                bar.into_base()
-                    .call("free_is_not_ub".into(), &[])
-                    .try_to_bool()
+                bar.into_base().call("free_is_not_ub", &[]).try_to_bool()
             );
         }
 
@@ -439,11 +453,161 @@ fn test_variant_call_args() -> bool {
     ok
 }
 
+#[derive(Signal)]
+#[signal(name = "counted")]
+struct Counted {
+    #[signal(default = 1)]
+    amount: i64,
+    label: GodotString,
+}
+
+#[derive(NativeClass)]
+#[inherit(Reference)]
+#[signals(Counted)]
+struct TypedSignals {
+    total: i64,
+}
+
+#[methods]
+impl TypedSignals {
+    fn _init(_owner: Reference) -> Self {
+        TypedSignals { total: 0 }
+    }
+
+    #[export]
+    fn on_counted(&mut self, _owner: Reference, amount: i64, _label: GodotString) {
+        self.total += amount;
+    }
+
+    #[export(name = "onCountedTwice")]
+    fn on_counted_twice(&mut self, _owner: Reference, amount: i64, _label: GodotString) {
+        self.total += amount * 2;
+    }
+}
+
+fn test_typed_signals() -> bool {
+    println!(" -- test_typed_signals");
+
+    let ok = std::panic::catch_unwind(|| {
+        use gdnative::signal::Signal;
+
+        assert_eq!("counted", Counted::NAME);
+
+        let receiver = Instance::<TypedSignals>::new();
+        let target = receiver.base().new_ref();
+        let mut emitter = Instance::<TypedSignals>::new().into_base();
+
+        unsafe {
+            godot_connect!(emitter, Counted => target, TypedSignals::on_counted)
+                .expect("should be able to connect");
+            godot_connect!(
+                emitter,
+                Counted => target,
+                TypedSignals::on_counted_twice,
+                name = "onCountedTwice"
+            )
+            .expect("should be able to connect to a renamed method");
+
+            Counted {
+                amount: 3,
+                label: "three".into(),
+            }
+            .emit(&mut emitter);
+        }
+
+        assert_eq!(Ok(9), receiver.map(|receiver, _| receiver.total));
+
+        unsafe {
+            godot_disconnect!(
+                emitter,
+                Counted => target,
+                TypedSignals::on_counted_twice,
+                name = "onCountedTwice"
+            );
+            assert!(!emitter.to_object().is_connected(
+                Counted::NAME,
+                Some(target.to_object()),
+                "onCountedTwice"
+            ));
+        }
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_typed_signals failed");
+    }
+
+    ok
+}
+
+#[derive(NativeClass)]
+#[inherit(Node)]
+struct NodeLookup;
+
+#[methods]
+impl NodeLookup {
+    fn _init(_owner: Node) -> Self {
+        NodeLookup
+    }
+}
+
+fn test_node_lookup() -> bool {
+    println!(" -- test_node_lookup");
+
+    let ok = std::panic::catch_unwind(|| unsafe {
+        let mut parent = FreeOnDrop::new(Node::new());
+        let mut child = Instance::<NodeLookup>::new().into_base();
+        child.set_name("Child".into());
+        parent.add_child(Some(child), false);
+
+        assert!(parent.get_node_as::<Node>("Child").is_some());
+        assert!(parent.get_node_as::<Spatial>("Child").is_none());
+        assert!(parent.get_instance_as::<NodeLookup>("Child").is_some());
+        assert!(parent.get_instance_as::<NodeLookup>(".").is_none());
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_node_lookup failed");
+    }
+
+    ok
+}
+
+fn test_array_sort_custom() -> bool {
+    println!(" -- test_array_sort_custom");
+
+    let ok = std::panic::catch_unwind(|| {
+        let foo = Instance::<Foo>::new().into_base();
+
+        let mut array = VariantArray::new();
+        for i in &[3, 1, 5, 3] {
+            array.push(&Variant::from_i64(*i));
+        }
+
+        array.sort_custom(&foo, "greater");
+        let sorted: Vec<_> = array.iter().map(|v| v.try_to_i64().unwrap()).collect();
+        assert_eq!(vec![5, 3, 3, 1], sorted);
+
+        let four = Variant::from_i64(4);
+        assert_eq!(1, array.bsearch_custom(&four, &foo, "greater", true));
+    })
+    .is_ok();
+
+    if !ok {
+        godot_error!("   !! Test test_array_sort_custom failed");
+    }
+
+    ok
+}
+
 fn init(handle: init::InitHandle) {
     handle.add_class::<Foo>();
     handle.add_class::<Bar>();
     handle.add_class::<RegisterSignal>();
     handle.add_class::<VariantCallArgs>();
+    handle.add_class::<TypedSignals>();
+    handle.add_class::<NodeLookup>();
 }
 
 godot_gdnative_init!();